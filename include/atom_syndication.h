@@ -0,0 +1,83 @@
+/*
+ * C interface of the atom_syndication crate, built with the `ffi` feature.
+ *
+ * Feeds are returned as opaque pointers which must be released with atom_feed_free. Strings
+ * returned by these functions are allocated by the library and must be released with
+ * atom_string_free. Entries are borrowed from their feed and must not be used after the feed
+ * has been freed.
+ */
+
+#ifndef ATOM_SYNDICATION_H
+#define ATOM_SYNDICATION_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An opaque handle to a feed. */
+typedef struct AtomFeed AtomFeed;
+
+/* An opaque handle to an entry. */
+typedef struct AtomEntry AtomEntry;
+
+/*
+ * Parse a feed from a NUL-terminated UTF-8 string.
+ *
+ * Returns NULL if the feed could not be parsed. In that case, if error is not NULL, it is set to
+ * a description of the error which must be released with atom_string_free.
+ */
+AtomFeed *atom_feed_parse(const char *xml, char **error);
+
+/* Release a feed returned by atom_feed_parse. */
+void atom_feed_free(AtomFeed *feed);
+
+/* Release a string returned by this library. */
+void atom_string_free(char *s);
+
+/* Serialize a feed to XML. */
+char *atom_feed_to_string(const AtomFeed *feed);
+
+/* Return the title of a feed. */
+char *atom_feed_title(const AtomFeed *feed);
+
+/* Return the id of a feed. */
+char *atom_feed_id(const AtomFeed *feed);
+
+/* Return the last updated date of a feed as an RFC 3339 string. */
+char *atom_feed_updated(const AtomFeed *feed);
+
+/* Return the number of entries in a feed. */
+size_t atom_feed_entry_count(const AtomFeed *feed);
+
+/*
+ * Return the entry at index in a feed, or NULL if the index is out of bounds.
+ *
+ * The entry is borrowed from the feed and is valid until the feed is freed.
+ */
+const AtomEntry *atom_feed_entry(const AtomFeed *feed, size_t index);
+
+/* Return the title of an entry. */
+char *atom_entry_title(const AtomEntry *entry);
+
+/* Return the id of an entry. */
+char *atom_entry_id(const AtomEntry *entry);
+
+/* Return the last updated date of an entry as an RFC 3339 string. */
+char *atom_entry_updated(const AtomEntry *entry);
+
+/* Return the summary of an entry, or NULL if it has none. */
+char *atom_entry_summary(const AtomEntry *entry);
+
+/* Return the content of an entry, or NULL if it has none. */
+char *atom_entry_content(const AtomEntry *entry);
+
+/* Return the URI of the alternate link of an entry, or NULL if it has none. */
+char *atom_entry_link(const AtomEntry *entry);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ATOM_SYNDICATION_H */
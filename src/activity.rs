@@ -0,0 +1,211 @@
+//! Export to Activity Streams 2.0, the vocabulary of ActivityPub.
+//!
+//! Entries become `Article` objects, or `Note` objects when they have no title, and a feed
+//! becomes an `OrderedCollection` of its entries that can be served as an outbox.
+
+use serde_json::{json, Map, Value};
+
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::person::Person;
+use crate::util::{default_fixed_datetime, FixedDateTime};
+
+const CONTEXT: &str = "https://www.w3.org/ns/activitystreams";
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn date(date: &FixedDateTime) -> Option<Value> {
+    if *date == default_fixed_datetime() {
+        None
+    } else {
+        Some(Value::String(date.to_rfc3339()))
+    }
+}
+
+fn actor(person: &Person) -> Value {
+    let mut actor = Map::new();
+    actor.insert("type".to_string(), json!("Person"));
+    actor.insert("name".to_string(), json!(person.name()));
+    if let Some(uri) = person.uri() {
+        actor.insert("url".to_string(), json!(uri));
+    }
+    Value::Object(actor)
+}
+
+/// Return the HTML content of an entry, which is the escaped text for text content.
+fn content(entry: &Entry) -> Option<String> {
+    let content = entry.content()?;
+    let value = content.value()?;
+    match content.content_type() {
+        None | Some("text") => Some(escape_html(value)),
+        Some("html") | Some("xhtml") => Some(value.to_string()),
+        Some(_) => None,
+    }
+}
+
+fn object(entry: &Entry, authors: &[Person]) -> Map<String, Value> {
+    let mut object = Map::new();
+    if entry.title().is_empty() {
+        object.insert("type".to_string(), json!("Note"));
+    } else {
+        object.insert("type".to_string(), json!("Article"));
+    }
+    object.insert("id".to_string(), json!(entry.id()));
+    if !entry.title().is_empty() {
+        object.insert("name".to_string(), json!(entry.title()));
+    }
+    if let Some(summary) = entry.summary() {
+        object.insert("summary".to_string(), json!(summary));
+    }
+    if let Some(content) = content(entry) {
+        object.insert("content".to_string(), json!(content));
+        object.insert("mediaType".to_string(), json!("text/html"));
+    }
+    if let Some(link) = entry.links().iter().find(|link| link.rel() == "alternate") {
+        object.insert("url".to_string(), json!(link.href()));
+    }
+    let published = entry.published().unwrap_or_else(|| entry.updated());
+    if let Some(published) = date(published) {
+        object.insert("published".to_string(), published);
+    }
+    if let Some(updated) = date(entry.updated()) {
+        object.insert("updated".to_string(), updated);
+    }
+
+    let authors = if entry.authors().is_empty() {
+        authors
+    } else {
+        entry.authors()
+    };
+    if !authors.is_empty() {
+        let actors = authors.iter().map(actor).collect::<Vec<_>>();
+        object.insert("attributedTo".to_string(), Value::Array(actors));
+    }
+
+    if !entry.categories().is_empty() {
+        let tags = entry
+            .categories()
+            .iter()
+            .map(|category| {
+                json!({
+                    "type": "Object",
+                    "name": category.label().unwrap_or_else(|| category.term()),
+                })
+            })
+            .collect::<Vec<_>>();
+        object.insert("tag".to_string(), Value::Array(tags));
+    }
+
+    let attachments = entry
+        .enclosures()
+        .map(|link| {
+            let mut attachment = Map::new();
+            attachment.insert("type".to_string(), json!("Document"));
+            attachment.insert("url".to_string(), json!(link.href()));
+            if let Some(mime_type) = link.mime_type() {
+                attachment.insert("mediaType".to_string(), json!(mime_type));
+            }
+            if let Some(title) = link.title() {
+                attachment.insert("name".to_string(), json!(title));
+            }
+            Value::Object(attachment)
+        })
+        .collect::<Vec<_>>();
+    if !attachments.is_empty() {
+        object.insert("attachment".to_string(), Value::Array(attachments));
+    }
+
+    object
+}
+
+impl Entry {
+    /// Convert this entry to an Activity Streams 2.0 object.
+    ///
+    /// An entry with a title becomes an `Article` and an entry without one becomes a `Note`.
+    /// Text content is escaped to HTML, while content of other media types and out of line
+    /// content are left out. Enclosures become attachments and categories become tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_id("https://example.com/notes/1");
+    /// entry.set_content(Content::text("Fish & chips"));
+    ///
+    /// let activity = entry.to_activity();
+    /// assert_eq!(activity["@context"], "https://www.w3.org/ns/activitystreams");
+    /// assert_eq!(activity["type"], "Note");
+    /// assert_eq!(activity["content"], "Fish &amp; chips");
+    /// ```
+    pub fn to_activity(&self) -> Value {
+        let mut object = Map::new();
+        object.insert("@context".to_string(), json!(CONTEXT));
+        object.extend(self::object(self, &[]));
+        Value::Object(object)
+    }
+}
+
+impl Feed {
+    /// Convert this feed to an Activity Streams 2.0 `OrderedCollection` of its entries.
+    ///
+    /// The entries keep the order of the feed and those without authors are attributed to the
+    /// authors of the feed. The id of the collection is the `self` link of the feed, or its id
+    /// if it has none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Blog");
+    /// feed.set_self_link("https://example.com/outbox");
+    /// feed.set_entries(vec![Entry::default().with_title("First Post")]);
+    ///
+    /// let collection = feed.to_activity_collection();
+    /// assert_eq!(collection["type"], "OrderedCollection");
+    /// assert_eq!(collection["id"], "https://example.com/outbox");
+    /// assert_eq!(collection["totalItems"], 1);
+    /// assert_eq!(collection["orderedItems"][0]["type"], "Article");
+    /// ```
+    pub fn to_activity_collection(&self) -> Value {
+        let mut collection = Map::new();
+        collection.insert("@context".to_string(), json!(CONTEXT));
+        collection.insert("type".to_string(), json!("OrderedCollection"));
+        collection.insert(
+            "id".to_string(),
+            json!(self.self_link().unwrap_or_else(|| self.id())),
+        );
+        if !self.title().is_empty() {
+            collection.insert("name".to_string(), json!(self.title()));
+        }
+        if let Some(subtitle) = self.subtitle() {
+            collection.insert("summary".to_string(), json!(subtitle));
+        }
+        if let Some(updated) = date(self.updated()) {
+            collection.insert("updated".to_string(), updated);
+        }
+        collection.insert("totalItems".to_string(), json!(self.entries().len()));
+        let items = self
+            .entries()
+            .iter()
+            .map(|entry| Value::Object(object(entry, self.authors())))
+            .collect::<Vec<_>>();
+        collection.insert("orderedItems".to_string(), Value::Array(items));
+        Value::Object(collection)
+    }
+}
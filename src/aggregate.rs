@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::source::Source;
+
+impl Feed {
+    /// Combine the entries of several feeds into a single feed, as done by a "planet" aggregator.
+    ///
+    /// The entries are ordered with the most recently updated first, and entries updated at the
+    /// same time keep their order in `feeds`. Each entry is given a `source` element created from
+    /// the metadata of the feed it was taken from, unless it already has one, so its provenance
+    /// is preserved. The namespaces of all the feeds are declared. A prefix that an earlier feed
+    /// binds to another namespace is renamed with a numeric suffix, and a namespace that an
+    /// earlier feed binds to another prefix keeps that prefix, with the extensions of the entries
+    /// renamed to match.
+    ///
+    /// The updated date of the result is the latest of the feeds, and its other metadata is left
+    /// empty to be filled in by the aggregator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use atom_syndication::{Entry, Feed, FixedDateTime};
+    ///
+    /// let mut first = Feed::default();
+    /// first.set_title("First");
+    /// first.set_entries(vec![Entry::default()
+    ///     .with_id("urn:first:1")
+    ///     .with_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap())]);
+    ///
+    /// let mut second = Feed::default();
+    /// second.set_title("Second");
+    /// second.set_entries(vec![Entry::default()
+    ///     .with_id("urn:second:1")
+    ///     .with_updated(FixedDateTime::from_str("2017-06-04T15:15:44-05:00").unwrap())]);
+    ///
+    /// let planet = Feed::aggregate(&[first, second]);
+    /// let entry = &planet.entries()[0];
+    /// assert_eq!(entry.id(), "urn:second:1");
+    /// assert_eq!(entry.source().unwrap().title(), "Second");
+    /// ```
+    pub fn aggregate(feeds: &[Feed]) -> Feed {
+        let mut aggregate = Feed::default();
+        let mut entries = Vec::new();
+
+        for feed in feeds {
+            if feed.updated() > aggregate.updated() {
+                aggregate.set_updated(*feed.updated());
+            }
+
+            let renames = merge_namespaces(&mut aggregate, feed);
+            let source = Source::from(feed);
+            entries.extend(feed.entries().iter().map(|entry| {
+                let mut entry = entry.clone();
+                if entry.source().is_none() {
+                    entry.set_source(source.clone());
+                }
+                if !renames.is_empty() {
+                    entry.rename_prefixes(&renames);
+                }
+                entry
+            }));
+        }
+
+        if let Some(updated) = entries.iter().map(Entry::updated).max() {
+            if updated > aggregate.updated() {
+                aggregate.set_updated(*updated);
+            }
+        }
+
+        entries.sort_by(|a, b| b.updated().cmp(a.updated()));
+        aggregate.set_entries(entries);
+        aggregate
+    }
+}
+
+/// Declare the namespaces of a feed on the aggregate. Returns the prefixes of the feed that must
+/// be renamed to refer to the same namespaces in the aggregate.
+fn merge_namespaces(aggregate: &mut Feed, feed: &Feed) -> HashMap<String, String> {
+    let mut renames = HashMap::new();
+
+    for (prefix, uri) in feed.namespaces() {
+        let bound = aggregate
+            .namespaces()
+            .iter()
+            .find(|&(_, u)| u == uri)
+            .map(|(p, _)| p.clone());
+        if let Some(bound) = bound {
+            if bound != *prefix {
+                renames.insert(prefix.clone(), bound);
+            }
+            continue;
+        }
+
+        let mut renamed = prefix.clone();
+        let mut suffix = 1;
+        while aggregate.namespaces().contains_key(&renamed)
+            || (renamed != *prefix && feed.namespaces().contains_key(&renamed))
+        {
+            renamed = format!("{}{}", prefix, suffix);
+            suffix += 1;
+        }
+
+        if renamed != *prefix {
+            renames.insert(prefix.clone(), renamed.clone());
+        }
+        aggregate.namespaces_mut().insert(renamed, uri.clone());
+    }
+
+    renames
+}
@@ -0,0 +1,184 @@
+use crate::entry::Entry;
+use crate::extension::Extension;
+use crate::feed::Feed;
+
+/// The namespace of the feed history elements defined by RFC 5005.
+const HISTORY_NAMESPACE: &str = "http://purl.org/syndication/history/1.0";
+
+/// A feed split into a current document and a chain of archive documents, as described by
+/// RFC 5005.
+///
+/// Archive documents hold the oldest entries in pages of a fixed size and never change once
+/// full, so they can be cached indefinitely. The current document holds the newest entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedArchive {
+    current: Feed,
+    archives: Vec<(String, Feed)>,
+}
+
+impl FeedArchive {
+    /// Return the current document, which links to the newest archive document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default(); 3]);
+    ///
+    /// let archive = feed.archive(2, "http://example.com/archive/{page}.xml");
+    /// assert_eq!(archive.current().entries().len(), 1);
+    /// ```
+    pub fn current(&self) -> &Feed {
+        &self.current
+    }
+
+    /// Return the archive documents along with their URLs, oldest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default(); 3]);
+    ///
+    /// let archive = feed.archive(2, "http://example.com/archive/{page}.xml");
+    /// let (url, page) = &archive.archives()[0];
+    /// assert_eq!(url, "http://example.com/archive/1.xml");
+    /// assert_eq!(page.entries().len(), 2);
+    /// ```
+    pub fn archives(&self) -> &[(String, Feed)] {
+        &self.archives
+    }
+
+    /// Consume this archive and return the current document and the archive documents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let archive = Feed::default().archive(10, "http://example.com/archive/{page}.xml");
+    /// let (current, archives) = archive.into_parts();
+    /// assert!(archives.is_empty());
+    /// ```
+    pub fn into_parts(self) -> (Feed, Vec<(String, Feed)>) {
+        (self.current, self.archives)
+    }
+}
+
+impl Feed {
+    /// Split this feed into a current document and archive documents as described by RFC 5005.
+    ///
+    /// The entries are ordered by their updated date and the oldest are placed in archive
+    /// documents of `page_size` entries each, leaving between one and `page_size` of the newest
+    /// entries in the current document. The URL of each archive document is `url_template` with
+    /// `{page}` replaced by its page number, starting from 1 for the oldest.
+    ///
+    /// Each archive document is marked with `fh:archive` and links to itself and to the previous
+    /// and next archive documents. The current document links to the newest archive document with
+    /// `prev-archive`. If this feed has a `self` link, the archive documents link to it as
+    /// `current`. Within each document the entries keep their order in this feed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_self_link("http://example.com/feed.xml");
+    /// feed.set_entries(vec![Entry::default(); 5]);
+    ///
+    /// let archive = feed.archive(2, "http://example.com/archive/{page}.xml");
+    /// assert_eq!(archive.archives().len(), 2);
+    ///
+    /// let prev = archive.current().links().iter().find(|link| link.rel() == "prev-archive");
+    /// assert_eq!(prev.unwrap().href(), "http://example.com/archive/2.xml");
+    /// ```
+    pub fn archive(&self, page_size: usize, url_template: &str) -> FeedArchive {
+        assert!(page_size > 0, "page size must be greater than zero");
+
+        let mut positions = (0..self.entries().len()).collect::<Vec<_>>();
+        positions.sort_by_key(|&position| self.entries()[position].updated());
+
+        let archived = match positions.len() {
+            0 => 0,
+            len => (len - 1) / page_size * page_size,
+        };
+        let urls = (1..=archived / page_size)
+            .map(|page| url_template.replace("{page}", &page.to_string()))
+            .collect::<Vec<_>>();
+        let current_link = self.self_link().map(str::to_string);
+
+        let mut archives = Vec::with_capacity(urls.len());
+
+        for (page, chunk) in positions[..archived].chunks(page_size).enumerate() {
+            let mut feed = self.page(chunk);
+            let url = urls[page].clone();
+
+            feed.namespaces_mut()
+                .insert("fh".to_string(), HISTORY_NAMESPACE.to_string());
+            feed.extensions_mut()
+                .entry("fh".to_string())
+                .or_default()
+                .insert("archive".to_string(), vec![archive_marker()]);
+
+            let updated = feed.entries().iter().map(Entry::updated).max().cloned();
+            if let Some(updated) = updated {
+                feed.set_updated(updated);
+            }
+
+            feed.set_self_link(url.clone());
+
+            if let Some(ref current) = current_link {
+                feed.set_link_href("current", current.clone());
+            }
+
+            if page > 0 {
+                feed.set_link_href("prev-archive", urls[page - 1].clone());
+            }
+
+            if let Some(next) = urls.get(page + 1) {
+                feed.set_link_href("next-archive", next.clone());
+            }
+
+            archives.push((url, feed));
+        }
+
+        let mut current = self.page(&positions[archived..]);
+
+        if let Some(prev) = urls.last() {
+            current.set_link_href("prev-archive", prev.clone());
+        }
+
+        FeedArchive { current, archives }
+    }
+
+    /// Return a copy of this feed containing only the entries at the given positions.
+    fn page(&self, positions: &[usize]) -> Feed {
+        let mut positions = positions.to_vec();
+        positions.sort_unstable();
+
+        let mut feed = self.clone_without_entries();
+        feed.set_entries(
+            positions
+                .into_iter()
+                .map(|position| self.entries()[position].clone())
+                .collect::<Vec<_>>(),
+        );
+        feed
+    }
+}
+
+/// Return the `fh:archive` element that marks an archive document.
+fn archive_marker() -> Extension {
+    let mut extension = Extension::default();
+    extension.set_name("fh:archive");
+    extension
+}
@@ -0,0 +1,195 @@
+//! Validate an Atom feed read from a file, a URL or standard input.
+//!
+//! ```text
+//! atom-validate [--json] [--strict] [FILE | URL | -]
+//! ```
+//!
+//! The exit status is 0 when the feed is valid, 1 when it is invalid or cannot be parsed, 2 when
+//! it cannot be read and 64 when the arguments are wrong. With `--strict`, warnings make the feed
+//! invalid too. Malformed dates are reported as warnings rather than stopping the feed from being
+//! validated.
+
+extern crate atom_syndication as atom;
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+
+use crate::atom::{Feed, ReadConfig, Severity, ValidationIssue};
+
+const USAGE: &str = "usage: atom-validate [--json] [--strict] [FILE | URL | -]";
+
+const EXIT_INVALID: i32 = 1;
+const EXIT_UNREADABLE: i32 = 2;
+const EXIT_USAGE: i32 = 64;
+
+struct Options {
+    json: bool,
+    strict: bool,
+    input: String,
+}
+
+/// Parse the command line arguments, returning `None` if help was requested.
+fn parse_args<I: Iterator<Item = String>>(args: I) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        json: false,
+        strict: false,
+        input: "-".to_string(),
+    };
+    let mut input = None;
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--strict" => options.strict = true,
+            "-h" | "--help" => return Ok(None),
+            "-" => input = Some(arg),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+            _ if input.is_some() => return Err("only one input can be validated".to_string()),
+            _ => input = Some(arg),
+        }
+    }
+
+    if let Some(input) = input {
+        options.input = input;
+    }
+    Ok(Some(options))
+}
+
+fn read_input(input: &str) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    if input == "-" {
+        io::stdin().read_to_end(&mut bytes)?;
+    } else if input.starts_with("http://") || input.starts_with("https://") {
+        let response = ureq::get(input)
+            .call()
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+        response.into_reader().read_to_end(&mut bytes)?;
+    } else {
+        bytes = fs::read(input)?;
+    }
+    Ok(bytes)
+}
+
+fn json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn print_json(input: &str, valid: bool, error: Option<&str>, issues: &[ValidationIssue]) {
+    let mut out = String::from("{\"input\":");
+    json_string(&mut out, input);
+    write!(out, ",\"valid\":{}", valid).unwrap();
+    if let Some(error) = error {
+        out.push_str(",\"error\":");
+        json_string(&mut out, error);
+    }
+    out.push_str(",\"issues\":[");
+    for (i, issue) in issues.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str("{\"severity\":");
+        json_string(&mut out, &issue.severity().to_string());
+        out.push_str(",\"location\":");
+        json_string(&mut out, issue.location());
+        out.push_str(",\"message\":");
+        json_string(&mut out, issue.message());
+        out.push('}');
+    }
+    out.push_str("]}");
+    println!("{}", out);
+}
+
+fn run(options: &Options) -> i32 {
+    let bytes = match read_input(&options.input) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            eprintln!("atom-validate: {}: {}", options.input, err);
+            return EXIT_UNREADABLE;
+        }
+    };
+
+    let config = ReadConfig::new().lenient_dates(true);
+    let (feed, stats) = match Feed::read_with_stats(&bytes[..], &config) {
+        Ok(result) => result,
+        Err(err) => {
+            let error = err.to_string();
+            if options.json {
+                print_json(&options.input, false, Some(&error), &[]);
+            } else {
+                println!("{}: error: {}", options.input, error);
+            }
+            return EXIT_INVALID;
+        }
+    };
+
+    let report = feed.validate();
+    let issues = stats
+        .issues()
+        .iter()
+        .chain(report.issues())
+        .cloned()
+        .collect::<Vec<_>>();
+    let count = |severity| {
+        issues
+            .iter()
+            .filter(|issue| issue.severity() == severity)
+            .count()
+    };
+    let errors = count(Severity::Error);
+    let warnings = count(Severity::Warning);
+    let valid = errors == 0 && (!options.strict || warnings == 0);
+
+    if options.json {
+        print_json(&options.input, valid, None, &issues);
+    } else {
+        for issue in &issues {
+            println!("{}: {}", options.input, issue);
+        }
+        println!(
+            "{}: {} error{}, {} warning{}",
+            options.input,
+            errors,
+            if errors == 1 { "" } else { "s" },
+            warnings,
+            if warnings == 1 { "" } else { "s" },
+        );
+    }
+
+    if valid {
+        0
+    } else {
+        EXIT_INVALID
+    }
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(message) => {
+            eprintln!("atom-validate: {}", message);
+            eprintln!("{}", USAGE);
+            process::exit(EXIT_USAGE);
+        }
+    };
+
+    process::exit(run(&options));
+}
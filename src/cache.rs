@@ -0,0 +1,417 @@
+//! Caching of fetched feeds along with their HTTP validators.
+//!
+//! A [`FeedCache`] remembers the last version of each feed it has fetched, keyed by URL, along
+//! with the `ETag` and `Last-Modified` headers it was served with. Refreshing a feed skips the
+//! request while the cached version is fresh, makes a conditional request otherwise, and reports
+//! which entries changed. The cache itself does not speak HTTP: the request is made by a
+//! function given to [`FeedCache::refresh`], or to [`FeedCache::refresh_at`] on targets without
+//! a system clock such as `wasm32-unknown-unknown`.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use atom_syndication::cache::{FeedCache, Fetched, MemoryStorage, Refresh};
+//!
+//! let url = "http://example.com/feed.xml";
+//! let mut cache = FeedCache::new(MemoryStorage::new()).max_age(Duration::from_secs(0));
+//!
+//! let refresh = cache
+//!     .refresh(url, |validators| {
+//!         assert_eq!(validators.etag(), None);
+//!         Ok(Fetched::Modified {
+//!             body: b"<feed><entry><id>urn:entry:1</id></entry></feed>".to_vec(),
+//!             etag: Some("\"v1\"".to_string()),
+//!             last_modified: None,
+//!         })
+//!     })
+//!     .unwrap();
+//! match refresh {
+//!     Refresh::Updated(delta) => assert_eq!(delta.added().len(), 1),
+//!     _ => unreachable!(),
+//! }
+//!
+//! let refresh = cache
+//!     .refresh(url, |validators| {
+//!         assert_eq!(validators.etag(), Some("\"v1\""));
+//!         Ok(Fetched::NotModified)
+//!     })
+//!     .unwrap();
+//! assert_eq!(refresh, Refresh::NotModified);
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::time::{Duration, SystemTime};
+
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::feed::Feed;
+
+/// The HTTP validators a feed was served with, to be sent back in a conditional request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Validators {
+    /// Create validators from the values of the `ETag` and `Last-Modified` response headers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::cache::Validators;
+    ///
+    /// let validators = Validators::new(Some("\"v1\"".to_string()), None);
+    /// assert_eq!(validators.etag(), Some("\"v1\""));
+    /// ```
+    pub fn new(etag: Option<String>, last_modified: Option<String>) -> Self {
+        Validators {
+            etag,
+            last_modified,
+        }
+    }
+
+    /// Return the entity tag, to be sent in an `If-None-Match` request header.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Return the modification date, to be sent in an `If-Modified-Since` request header.
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+}
+
+/// A feed stored in a cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedFeed {
+    feed: Feed,
+    validators: Validators,
+    fetched: SystemTime,
+}
+
+impl CachedFeed {
+    /// Create a cached feed that was fetched at the given time with the given validators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::SystemTime;
+    ///
+    /// use atom_syndication::cache::{CachedFeed, Validators};
+    /// use atom_syndication::Feed;
+    ///
+    /// let cached = CachedFeed::new(Feed::default(), Validators::default(), SystemTime::now());
+    /// ```
+    pub fn new(feed: Feed, validators: Validators, fetched: SystemTime) -> Self {
+        CachedFeed {
+            feed,
+            validators,
+            fetched,
+        }
+    }
+
+    /// Return the feed.
+    pub fn feed(&self) -> &Feed {
+        &self.feed
+    }
+
+    /// Return the validators the feed was served with.
+    pub fn validators(&self) -> &Validators {
+        &self.validators
+    }
+
+    /// Return when the feed was last fetched or confirmed to be unmodified.
+    pub fn fetched(&self) -> SystemTime {
+        self.fetched
+    }
+}
+
+/// Storage for the feeds of a `FeedCache`, keyed by URL.
+///
+/// Implement this trait to keep cached feeds in a database or on disk.
+pub trait CacheStorage {
+    /// Return the feed stored for the URL, if any.
+    fn load(&self, url: &str) -> Option<CachedFeed>;
+
+    /// Store a feed for the URL, replacing any feed stored before.
+    fn store(&mut self, url: &str, cached: CachedFeed);
+
+    /// Remove the feed stored for the URL.
+    fn remove(&mut self, url: &str);
+}
+
+/// Storage that keeps cached feeds in memory.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage {
+    feeds: HashMap<String, CachedFeed>,
+}
+
+impl MemoryStorage {
+    /// Create empty storage.
+    pub fn new() -> Self {
+        MemoryStorage::default()
+    }
+}
+
+impl CacheStorage for MemoryStorage {
+    fn load(&self, url: &str) -> Option<CachedFeed> {
+        self.feeds.get(url).cloned()
+    }
+
+    fn store(&mut self, url: &str, cached: CachedFeed) {
+        self.feeds.insert(url.to_string(), cached);
+    }
+
+    fn remove(&mut self, url: &str) {
+        self.feeds.remove(url);
+    }
+}
+
+/// The result of a conditional request for a feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The server responded with `304 Not Modified`.
+    NotModified,
+    /// The server sent the feed.
+    Modified {
+        /// The body of the response.
+        body: Vec<u8>,
+        /// The value of the `ETag` response header, if any.
+        etag: Option<String>,
+        /// The value of the `Last-Modified` response header, if any.
+        last_modified: Option<String>,
+    },
+}
+
+/// The entries that changed between two versions of a feed, matched by id.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedDelta {
+    added: Vec<Entry>,
+    updated: Vec<Entry>,
+    removed: Vec<String>,
+}
+
+impl FeedDelta {
+    /// Compare two versions of a feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::cache::FeedDelta;
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut old = Feed::default();
+    /// old.set_entries(vec![Entry::default().with_id("urn:entry:1")]);
+    ///
+    /// let mut new = Feed::default();
+    /// new.set_entries(vec![Entry::default().with_id("urn:entry:2")]);
+    ///
+    /// let delta = FeedDelta::between(&old, &new);
+    /// assert_eq!(delta.added()[0].id(), "urn:entry:2");
+    /// assert_eq!(delta.removed(), ["urn:entry:1"]);
+    /// ```
+    pub fn between(old: &Feed, new: &Feed) -> Self {
+        let previous = old
+            .entries()
+            .iter()
+            .map(|entry| (entry.id(), entry))
+            .collect::<HashMap<_, _>>();
+        let current = new.entries().iter().map(Entry::id).collect::<HashSet<_>>();
+
+        let mut delta = FeedDelta::default();
+        for entry in new.entries() {
+            match previous.get(entry.id()) {
+                None => delta.added.push(entry.clone()),
+                Some(&previous) if previous != entry => delta.updated.push(entry.clone()),
+                Some(_) => {}
+            }
+        }
+
+        delta.removed = old
+            .entries()
+            .iter()
+            .map(Entry::id)
+            .filter(|id| !current.contains(id))
+            .map(str::to_string)
+            .collect();
+
+        delta
+    }
+
+    /// Return the entries whose ids were not in the previous version, in document order.
+    pub fn added(&self) -> &[Entry] {
+        self.added.as_slice()
+    }
+
+    /// Return the entries that changed since the previous version, in document order.
+    pub fn updated(&self) -> &[Entry] {
+        self.updated.as_slice()
+    }
+
+    /// Return the ids of the entries that are no longer in the feed.
+    pub fn removed(&self) -> &[String] {
+        self.removed.as_slice()
+    }
+
+    /// Return whether no entries changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The outcome of refreshing a feed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Refresh {
+    /// The cached feed is still fresh, so no request was made.
+    Fresh,
+    /// The server confirmed that the cached feed has not been modified.
+    NotModified,
+    /// A new version of the feed was fetched. The delta is relative to the cached version, so
+    /// every entry is added the first time a feed is fetched.
+    Updated(FeedDelta),
+}
+
+/// A cache of fetched feeds.
+#[derive(Debug, Clone)]
+pub struct FeedCache<S> {
+    storage: S,
+    max_age: Duration,
+}
+
+impl<S: CacheStorage> FeedCache<S> {
+    /// Create a cache that keeps feeds in the given storage.
+    ///
+    /// Cached feeds are considered fresh for five minutes by default.
+    pub fn new(storage: S) -> Self {
+        FeedCache {
+            storage,
+            max_age: Duration::from_secs(5 * 60),
+        }
+    }
+
+    /// Set how long a cached feed is used without asking the server and return the cache.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Return the feed cached for the URL, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::cache::{FeedCache, MemoryStorage};
+    ///
+    /// let cache = FeedCache::new(MemoryStorage::new());
+    /// assert!(cache.get("http://example.com/feed.xml").is_none());
+    /// ```
+    pub fn get(&self, url: &str) -> Option<CachedFeed> {
+        self.storage.load(url)
+    }
+
+    /// Refresh the feed at the URL.
+    ///
+    /// If the cached feed is older than the maximum age, `fetch` is called with the validators
+    /// of the cached feed to make a conditional request. A new version of the feed replaces the
+    /// cached one. If reading the new version fails, the cached feed is kept.
+    ///
+    /// The age of the cached feed is measured from the current time. This is not available on
+    /// `wasm32-unknown-unknown`, where [`refresh_at`](#method.refresh_at) is used instead.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn refresh<F>(&mut self, url: &str, fetch: F) -> Result<Refresh, Error>
+    where
+        F: FnOnce(&Validators) -> Result<Fetched, Error>,
+    {
+        self.refresh_at(url, SystemTime::now(), fetch)
+    }
+
+    /// Refresh the feed at the URL as of the given time.
+    ///
+    /// This is [`refresh`](#method.refresh) with the current time given by the caller, for
+    /// targets without a system clock and for tests. The time is recorded as the time the feed
+    /// was fetched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// use atom_syndication::cache::{FeedCache, Fetched, MemoryStorage, Refresh};
+    ///
+    /// let url = "http://example.com/feed.xml";
+    /// let mut cache = FeedCache::new(MemoryStorage::new()).max_age(Duration::from_secs(60));
+    /// let fetch = |_: &_| {
+    ///     Ok(Fetched::Modified {
+    ///         body: b"<feed></feed>".to_vec(),
+    ///         etag: None,
+    ///         last_modified: None,
+    ///     })
+    /// };
+    ///
+    /// let now = SystemTime::UNIX_EPOCH;
+    /// assert!(matches!(cache.refresh_at(url, now, fetch), Ok(Refresh::Updated(_))));
+    /// assert_eq!(cache.refresh_at(url, now + Duration::from_secs(30), fetch).unwrap(), Refresh::Fresh);
+    /// ```
+    pub fn refresh_at<F>(&mut self, url: &str, now: SystemTime, fetch: F) -> Result<Refresh, Error>
+    where
+        F: FnOnce(&Validators) -> Result<Fetched, Error>,
+    {
+        let cached = self.storage.load(url);
+
+        if let Some(ref cached) = cached {
+            let age = now.duration_since(cached.fetched).unwrap_or_default();
+            if age < self.max_age {
+                return Ok(Refresh::Fresh);
+            }
+        }
+
+        let validators = cached
+            .as_ref()
+            .map(|cached| cached.validators.clone())
+            .unwrap_or_default();
+
+        match fetch(&validators)? {
+            Fetched::NotModified => match cached {
+                Some(mut cached) => {
+                    cached.fetched = now;
+                    self.storage.store(url, cached);
+                    Ok(Refresh::NotModified)
+                }
+                None => Err(Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "not modified response for a feed that is not cached",
+                ))),
+            },
+            Fetched::Modified {
+                body,
+                etag,
+                last_modified,
+            } => {
+                let feed = Feed::read_from(&body[..])?;
+                let delta = match cached {
+                    Some(ref cached) => FeedDelta::between(&cached.feed, &feed),
+                    None => FeedDelta::between(&Feed::default(), &feed),
+                };
+
+                let validators = Validators::new(etag, last_modified);
+                self.storage
+                    .store(url, CachedFeed::new(feed, validators, now));
+                Ok(Refresh::Updated(delta))
+            }
+        }
+    }
+
+    /// Remove the feed cached for the URL.
+    pub fn invalidate(&mut self, url: &str) {
+        self.storage.remove(url);
+    }
+
+    /// Consume the cache and return its storage.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::{BufRead, Write};
 
 use quick_xml::events::attributes::Attributes;
@@ -7,23 +8,49 @@ use quick_xml::Reader;
 use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
+use crate::fromxml::{FromXml, ReadContext};
+use crate::intern::Text;
+use crate::toxml::{ToXml, WriteContext};
+use crate::util::IntoOption;
 
 /// Represents a category in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq, Builder)]
-#[builder(setter(into), default)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct Category {
     /// Identifies the category.
-    term: String,
+    term: Text<String>,
     /// Identifies the categorization scheme via a URI.
-    scheme: Option<String>,
+    scheme: Text<Option<String>>,
     /// A human-readable label for display.
-    label: Option<String>,
+    label: Text<Option<String>>,
+    /// Additional attributes of the element that are not otherwise represented.
+    attrs: BTreeMap<String, String>,
 }
 
 impl Category {
+    /// Create a category with the given term.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let category = Category::new("technology")
+    ///     .with_scheme("http://example.com/scheme")
+    ///     .with_label("Technology");
+    /// assert_eq!(category.term(), "technology");
+    /// assert_eq!(category.label(), Some("Technology"));
+    /// ```
+    pub fn new<V>(term: V) -> Self
+    where
+        V: Into<String>,
+    {
+        Category {
+            term: Text::Owned(term.into()),
+            ..Default::default()
+        }
+    }
+
     /// Return the term that identifies this category.
     ///
     /// # Examples
@@ -39,6 +66,21 @@ impl Category {
         self.term.as_str()
     }
 
+    /// Return a mutable reference to the term that identifies this category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.term_mut().push_str("technology");
+    /// assert_eq!(category.term(), "technology");
+    /// ```
+    pub fn term_mut(&mut self) -> &mut String {
+        self.term.to_mut()
+    }
+
     /// Set the term that identifies this category.
     ///
     /// # Examples
@@ -53,7 +95,24 @@ impl Category {
     where
         V: Into<String>,
     {
-        self.term = term.into();
+        self.term = Text::Owned(term.into());
+    }
+
+    /// Set the term that identifies this category and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let category = Category::default().with_term("technology");
+    /// ```
+    pub fn with_term<V>(mut self, term: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_term(term);
+        self
     }
 
     /// Return the categorization scheme URI.
@@ -64,11 +123,26 @@ impl Category {
     /// use atom_syndication::Category;
     ///
     /// let mut category = Category::default();
-    /// category.set_scheme("http://example.com/scheme".to_string());
+    /// category.set_scheme("http://example.com/scheme");
     /// assert_eq!(category.scheme(), Some("http://example.com/scheme"));
     /// ```
     pub fn scheme(&self) -> Option<&str> {
-        self.scheme.as_ref().map(String::as_str)
+        self.scheme.as_deref()
+    }
+
+    /// Return a mutable reference to the categorization scheme URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// *category.scheme_mut() = Some("http://example.com/scheme".to_string());
+    /// assert_eq!(category.scheme(), Some("http://example.com/scheme"));
+    /// ```
+    pub fn scheme_mut(&mut self) -> &mut Option<String> {
+        self.scheme.to_mut()
     }
 
     /// Set the categorization scheme URI.
@@ -79,13 +153,30 @@ impl Category {
     /// use atom_syndication::Category;
     ///
     /// let mut category = Category::default();
-    /// category.set_scheme("http://example.com/scheme".to_string());
+    /// category.set_scheme("http://example.com/scheme");
     /// ```
     pub fn set_scheme<V>(&mut self, scheme: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
+    {
+        self.scheme = Text::Owned(scheme.into_option());
+    }
+
+    /// Set the categorization scheme URI and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let category = Category::default().with_scheme("http://example.com/scheme");
+    /// ```
+    pub fn with_scheme<V>(mut self, scheme: V) -> Self
+    where
+        V: IntoOption<String>,
     {
-        self.scheme = scheme.into();
+        self.set_scheme(scheme);
+        self
     }
 
     /// Return the label for this category.
@@ -96,12 +187,26 @@ impl Category {
     /// use atom_syndication::Category;
     ///
     /// let mut category = Category::default();
-    /// category.set_scheme("Technology".to_string());
-    /// assert_eq!(category.scheme(), Some("Technology"));
+    /// category.set_label("Technology");
+    /// assert_eq!(category.label(), Some("Technology"));
     /// ```
-
     pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(String::as_str)
+        self.label.as_deref()
+    }
+
+    /// Return a mutable reference to the label for this category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// *category.label_mut() = Some("Technology".to_string());
+    /// assert_eq!(category.label(), Some("Technology"));
+    /// ```
+    pub fn label_mut(&mut self) -> &mut Option<String> {
+        self.label.to_mut()
     }
 
     /// Set the label for this category.
@@ -112,27 +217,188 @@ impl Category {
     /// use atom_syndication::Category;
     ///
     /// let mut category = Category::default();
-    /// category.set_scheme("Technology".to_string());
+    /// category.set_label("Technology");
     /// ```
     pub fn set_label<V>(&mut self, label: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.label = label.into();
+        self.label = Text::Owned(label.into_option());
+    }
+
+    /// Set the label for this category and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let category = Category::default().with_label("Technology");
+    /// ```
+    pub fn with_label<V>(mut self, label: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_label(label);
+        self
+    }
+
+    /// Return the additional attributes of this category.
+    ///
+    /// These are attributes of the element that are not otherwise represented, such as `xml:lang`
+    /// or vendor-specific attributes. They are written back out when serializing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Category;
+    ///
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("xml:lang".to_string(), "en".to_string());
+    ///
+    /// let mut category = Category::default();
+    /// category.set_attrs(attrs);
+    /// assert_eq!(category.attrs().get("xml:lang").map(String::as_str), Some("en"));
+    /// ```
+    pub fn attrs(&self) -> &BTreeMap<String, String> {
+        &self.attrs
+    }
+
+    /// Return a mutable reference to the additional attributes of this category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.attrs_mut()
+    ///     .insert("xml:lang".to_string(), "en".to_string());
+    /// assert_eq!(category.attrs().len(), 1);
+    /// ```
+    pub fn attrs_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.attrs
+    }
+
+    /// Set the additional attributes of this category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.set_attrs(BTreeMap::new());
+    /// ```
+    pub fn set_attrs<V>(&mut self, attrs: V)
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.attrs = attrs.into();
+    }
+
+    /// Set the additional attributes of this category and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Category;
+    ///
+    /// let category = Category::default().with_attrs(BTreeMap::new());
+    /// ```
+    pub fn with_attrs<V>(mut self, attrs: V) -> Self
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.set_attrs(attrs);
+        self
+    }
+}
+
+/// Builder for [`Category`](struct.Category.html).
+///
+/// The text of a category is stored so that it can be shared, which the derived builders of the
+/// other types cannot express, so this builder is written out with the same methods.
+#[derive(Clone, Default)]
+pub struct CategoryBuilder {
+    term: Option<String>,
+    scheme: Option<Option<String>>,
+    label: Option<Option<String>>,
+    attrs: Option<BTreeMap<String, String>>,
+}
+
+impl CategoryBuilder {
+    /// Identifies the category.
+    pub fn term<VALUE: Into<String>>(&mut self, value: VALUE) -> &mut Self {
+        self.term = Some(value.into());
+        self
+    }
+
+    /// Identifies the categorization scheme via a URI.
+    pub fn scheme<VALUE: Into<Option<String>>>(&mut self, value: VALUE) -> &mut Self {
+        self.scheme = Some(value.into());
+        self
+    }
+
+    /// A human-readable label for display.
+    pub fn label<VALUE: Into<Option<String>>>(&mut self, value: VALUE) -> &mut Self {
+        self.label = Some(value.into());
+        self
+    }
+
+    /// Additional attributes of the element that are not otherwise represented.
+    pub fn attrs<VALUE: Into<BTreeMap<String, String>>>(&mut self, value: VALUE) -> &mut Self {
+        self.attrs = Some(value.into());
+        self
+    }
+
+    /// Builds a new `Category`.
+    ///
+    /// # Errors
+    ///
+    /// Fields that have not been set are left at their default values, so this does not fail.
+    pub fn build(&self) -> Result<Category, String> {
+        Ok(Category {
+            term: Text::Owned(self.term.clone().unwrap_or_default()),
+            scheme: Text::Owned(self.scheme.clone().unwrap_or_default()),
+            label: Text::Owned(self.label.clone().unwrap_or_default()),
+            attrs: self.attrs.clone().unwrap_or_default(),
+        })
     }
 }
 
 impl FromXml for Category {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, mut atts: Attributes) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        mut atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
         let mut category = Category::default();
 
         for attr in atts.with_checks(false) {
             if let Ok(att) = attr {
                 match att.key {
-                    b"term" => category.term = att.unescape_and_decode_value(reader)?,
-                    b"scheme" => category.scheme = Some(att.unescape_and_decode_value(reader)?),
-                    b"label" => category.label = Some(att.unescape_and_decode_value(reader)?),
-                    _ => {}
+                    b"term" => {
+                        let term = att.unescape_and_decode_value(reader)?;
+                        category.term = ctx.intern(term);
+                    }
+                    b"scheme" => {
+                        let scheme = Some(att.unescape_and_decode_value(reader)?);
+                        category.scheme = ctx.intern(scheme);
+                    }
+                    b"label" => {
+                        let label = Some(att.unescape_and_decode_value(reader)?);
+                        category.label = ctx.intern(label);
+                    }
+                    key => {
+                        let key = reader.decode(key).into_owned();
+                        category
+                            .attrs
+                            .insert(key, att.unescape_and_decode_value(reader)?);
+                    }
                 }
             }
         }
@@ -144,19 +410,25 @@ impl FromXml for Category {
 }
 
 impl ToXml for Category {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        _ctx: &WriteContext,
+    ) -> Result<(), XmlError> {
         let name = b"category";
         let mut element = BytesStart::borrowed(name, name.len());
-        element.push_attribute(("term", &*self.term));
+        element.push_attribute(("term", self.term()));
 
-        if let Some(ref scheme) = self.scheme {
-            element.push_attribute(("scheme", &**scheme));
+        if let Some(scheme) = self.scheme() {
+            element.push_attribute(("scheme", scheme));
         }
 
-        if let Some(ref label) = self.label {
-            element.push_attribute(("label", &**label));
+        if let Some(label) = self.label() {
+            element.push_attribute(("label", label));
         }
 
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+
         writer.write_event(Event::Empty(element))?;
 
         Ok(())
@@ -0,0 +1,203 @@
+use std::borrow::Cow;
+
+use encoding_rs::{Encoding, UTF_8};
+use http::header::CONTENT_TYPE;
+use http::HeaderMap;
+
+use crate::config::ReadConfig;
+use crate::entry::read_entry_document;
+use crate::error::Error;
+use crate::feed::Feed;
+use crate::fromxml::ReadContext;
+use crate::stats::{ParseStats, Timer};
+use crate::util::{declared_encoding, normalize_encoding, strip_encoding_declaration};
+use crate::validate::{Severity, ValidationIssue};
+
+/// The parts of a `Content-Type` header that affect how a feed is read.
+#[derive(Debug, Default)]
+struct MediaType {
+    essence: String,
+    charset: Option<String>,
+    kind: Option<String>,
+}
+
+impl MediaType {
+    fn parse(value: &str) -> Self {
+        let mut parts = value.split(';');
+        let mut media_type = MediaType {
+            essence: parts.next().unwrap_or_default().trim().to_ascii_lowercase(),
+            ..Default::default()
+        };
+
+        for param in parts {
+            let mut pair = param.splitn(2, '=');
+            let name = pair.next().unwrap_or_default().trim().to_ascii_lowercase();
+            let value = pair.next().unwrap_or_default().trim().trim_matches('"');
+            match name.as_str() {
+                "charset" => media_type.charset = Some(value.to_string()),
+                "type" => media_type.kind = Some(value.to_ascii_lowercase()),
+                _ => {}
+            }
+        }
+
+        media_type
+    }
+
+    fn is_xml(&self) -> bool {
+        self.essence == "application/xml"
+            || self.essence == "text/xml"
+            || self.essence.ends_with("+xml")
+    }
+}
+
+/// Return the encoding given by the byte order mark at the start of a document, if any.
+fn bom_encoding(body: &[u8]) -> Option<&'static Encoding> {
+    Encoding::for_bom(body).map(|(encoding, _)| encoding)
+}
+
+/// Decode a document with the encoding given by the `Content-Type` header, returning it as
+/// UTF-8 without an encoding declaration so that the declaration is not applied again.
+fn decode_with_charset<'a>(
+    body: &'a [u8],
+    charset: &str,
+    issues: &mut Vec<ValidationIssue>,
+) -> Cow<'a, [u8]> {
+    let encoding = match Encoding::for_label(charset.as_bytes()) {
+        Some(encoding) => encoding,
+        None => {
+            issues.push(ValidationIssue::new(
+                Severity::Warning,
+                "/",
+                format!("unknown charset {} in the Content-Type header", charset),
+            ));
+            return Cow::Borrowed(body);
+        }
+    };
+
+    if let Some(declared) = declared_encoding(body) {
+        if Encoding::for_label(declared) != Some(encoding) {
+            issues.push(ValidationIssue::new(
+                Severity::Warning,
+                "/",
+                format!(
+                    "the Content-Type charset {} does not match the encoding {} declared by \
+                     the document, so the charset is used",
+                    charset,
+                    String::from_utf8_lossy(declared)
+                ),
+            ));
+        }
+    }
+
+    if encoding != UTF_8 {
+        let (decoded, _) = encoding.decode_without_bom_handling(body);
+        return Cow::Owned(strip_encoding_declaration(&decoded).into_bytes());
+    }
+
+    match declared_encoding(body) {
+        Some(declared) if Encoding::for_label(declared) != Some(UTF_8) => {
+            // only the declaration is replaced, so invalid UTF-8 is still reported while reading
+            let end = body.windows(2).position(|pair| pair == b"?>").unwrap_or(0) + 2;
+            let decl = String::from_utf8_lossy(&body[..end]);
+            let mut xml = strip_encoding_declaration(&decl).into_bytes();
+            xml.extend_from_slice(&body[end..]);
+            Cow::Owned(xml)
+        }
+        _ => Cow::Borrowed(body),
+    }
+}
+
+impl Feed {
+    /// Attempt to read an Atom feed from the headers and body of an HTTP response.
+    ///
+    /// The encoding of the body is chosen as RFC 7303 describes: a byte order mark takes
+    /// precedence over the `charset` parameter of the `Content-Type` header, which takes
+    /// precedence over the XML declaration. A `Content-Type` of
+    /// `application/atom+xml;type=entry` is read as an entry document, which is returned as the
+    /// only entry of an otherwise empty feed.
+    ///
+    /// A `Content-Type` that is not an XML media type, a charset that does not match the
+    /// encoding declared by the document and an unknown charset are reported as warnings in
+    /// `ParseStats::issues`, along with the problems found while reading.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    /// use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/atom+xml; charset=iso-8859-1"));
+    ///
+    /// let body = b"<?xml version=\"1.0\" encoding=\"utf-8\"?><feed><title>Caf\xe9</title></feed>";
+    /// let (feed, stats) = Feed::from_http_parts(&headers, body).unwrap();
+    /// assert_eq!(feed.title(), "Café");
+    /// assert_eq!(stats.issues().len(), 1);
+    /// ```
+    pub fn from_http_parts(headers: &HeaderMap, body: &[u8]) -> Result<(Feed, ParseStats), Error> {
+        let start = Timer::start();
+        let mut issues = Vec::new();
+
+        let media_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(MediaType::parse);
+
+        if let Some(ref media_type) = media_type {
+            if !media_type.is_xml() {
+                issues.push(ValidationIssue::new(
+                    Severity::Warning,
+                    "/",
+                    format!(
+                        "the Content-Type {} is not an XML media type",
+                        media_type.essence
+                    ),
+                ));
+            }
+        }
+
+        let charset = media_type
+            .as_ref()
+            .and_then(|media_type| media_type.charset.as_deref());
+        let xml = match charset {
+            Some(charset) if bom_encoding(body).is_none() => {
+                decode_with_charset(body, charset, &mut issues)
+            }
+            _ => Cow::Borrowed(body),
+        };
+
+        let entry_document = match media_type {
+            Some(MediaType {
+                kind: Some(ref kind),
+                ..
+            }) => kind == "entry",
+            _ => false,
+        };
+
+        let (feed, mut stats) = if entry_document {
+            let mut ctx = ReadContext::new(ReadConfig::default());
+            let entry = read_entry_document(&xml, &mut ctx)?;
+            let feed = Feed::default()
+                .with_updated(*entry.updated())
+                .with_entries(vec![entry]);
+            let stats = ParseStats {
+                bytes: body.len(),
+                entries: 1,
+                skipped_elements: ctx.skipped,
+                duration: start.elapsed(),
+                issues: ctx.issues,
+            };
+            (feed, stats)
+        } else {
+            let xml = normalize_encoding(&xml);
+            let (feed, mut stats) = Feed::read_with_stats(&*xml, &ReadConfig::default())?;
+            stats.bytes = body.len();
+            stats.duration = start.elapsed();
+            (feed, stats)
+        };
+
+        issues.append(&mut stats.issues);
+        stats.issues = issues;
+        Ok((feed, stats))
+    }
+}
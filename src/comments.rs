@@ -0,0 +1,290 @@
+//! Support for the comment extensions used by blogs: the number of comments on an entry from
+//! the [Slash](http://web.resource.org/rss/1.0/modules/slash/) module, the URL of the feed of
+//! its comments from the [Well-Formed Web](http://wellformedweb.org/news/wfw_namespace_elements/)
+//! namespace, and the endpoints of the [Pingback](http://www.hixie.ch/specs/pingback/pingback)
+//! and [TrackBack](http://archive.cweiske.de/trackback/trackback-1.2.html) protocols that other
+//! blogs use to notify an entry that they link to it.
+//!
+//! The elements are looked up with the conventional `slash`, `wfw` and `trackback` prefixes. A
+//! feed that contains them must declare those prefixes.
+//!
+//! # Examples
+//!
+//! ```
+//! use atom_syndication::comments::{SLASH_NAMESPACE, WFW_NAMESPACE};
+//! use atom_syndication::{Entry, Feed};
+//!
+//! let mut entry = Entry::default();
+//! entry.set_comment_count(Some(12));
+//! entry.set_comment_feed("http://example.com/article/1/comments.xml");
+//!
+//! let mut feed = Feed::default();
+//! feed.namespaces_mut()
+//!     .insert("slash".to_string(), SLASH_NAMESPACE.to_string());
+//! feed.namespaces_mut()
+//!     .insert("wfw".to_string(), WFW_NAMESPACE.to_string());
+//! feed.set_entries(vec![entry]);
+//!
+//! let feed = feed.to_string().parse::<Feed>().unwrap();
+//! let entry = &feed.entries()[0];
+//! assert_eq!(entry.comment_count().unwrap(), Some(12));
+//! assert_eq!(entry.comment_feed(), Some("http://example.com/article/1/comments.xml"));
+//! ```
+
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::extension::{EntryExt, Extension};
+use crate::feed::Feed;
+use crate::link::Link;
+use crate::util::IntoOption;
+
+/// The namespace of the Slash module.
+pub const SLASH_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/slash/";
+/// The namespace of the Well-Formed Web comment elements.
+pub const WFW_NAMESPACE: &str = "http://wellformedweb.org/CommentAPI/";
+/// The namespace of the TrackBack module.
+pub const TRACKBACK_NAMESPACE: &str = "http://madskills.com/public/xml/rss/module/trackback/";
+
+/// The relation of a link to a Pingback server.
+pub const PINGBACK: &str = "pingback";
+
+/// Replace the link with the given relation, removing it if there is no URL.
+fn set_link(links: &mut Vec<Link>, rel: &str, href: Option<String>) {
+    match href {
+        Some(href) => match links.iter_mut().find(|link| link.rel() == rel) {
+            Some(link) => link.set_href(href),
+            None => links.push(Link::default().with_rel(rel).with_href(href)),
+        },
+        None => links.retain(|link| link.rel() != rel),
+    }
+}
+
+fn link_href<'a>(links: &'a [Link], rel: &str) -> Option<&'a str> {
+    links.iter().find(|link| link.rel() == rel).map(Link::href)
+}
+
+impl Entry {
+    /// Return the number of comments on this entry, from `slash:comments`.
+    ///
+    /// Returns an error if the element is not a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed xmlns:slash="http://purl.org/rss/1.0/modules/slash/">
+    ///     <entry><slash:comments>12</slash:comments></entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.entries()[0].comment_count().unwrap(), Some(12));
+    /// ```
+    pub fn comment_count(&self) -> Result<Option<u64>, Error> {
+        let value = self.extension::<String>("slash", "comments")?;
+        value
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| Error::InvalidExtension(value))
+            })
+            .transpose()
+    }
+
+    /// Set the number of comments on this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_comment_count(Some(12));
+    /// assert_eq!(entry.comment_count().unwrap(), Some(12));
+    ///
+    /// entry.set_comment_count(None);
+    /// assert_eq!(entry.comment_count().unwrap(), None);
+    /// ```
+    pub fn set_comment_count(&mut self, count: Option<u64>) {
+        match count {
+            Some(count) => self.set_extension("slash", "comments", &count),
+            None => self.remove_comment_extension("slash", "comments"),
+        }
+    }
+
+    /// Return the URL of the feed of comments on this entry, from `wfw:commentRss`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed xmlns:wfw="http://wellformedweb.org/CommentAPI/">
+    ///     <entry><wfw:commentRss>http://example.com/comments.xml</wfw:commentRss></entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.entries()[0].comment_feed(), Some("http://example.com/comments.xml"));
+    /// ```
+    pub fn comment_feed(&self) -> Option<&str> {
+        self.extensions()
+            .get("wfw")
+            .and_then(|map| map.get("commentRss"))
+            .and_then(|items| items.first())
+            .and_then(Extension::value)
+    }
+
+    /// Set the URL of the feed of comments on this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_comment_feed("http://example.com/comments.xml");
+    /// assert_eq!(entry.comment_feed(), Some("http://example.com/comments.xml"));
+    /// ```
+    pub fn set_comment_feed<V>(&mut self, url: V)
+    where
+        V: IntoOption<String>,
+    {
+        match url.into_option() {
+            Some(url) => self.set_extension("wfw", "commentRss", &url),
+            None => self.remove_comment_extension("wfw", "commentRss"),
+        }
+    }
+
+    /// Return the URL of the Pingback server for this entry, from its `rel="pingback"` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let link = Link::default()
+    ///     .with_rel("pingback")
+    ///     .with_href("http://example.com/xmlrpc.php");
+    /// let entry = Entry::default().with_links(vec![link]);
+    /// assert_eq!(entry.pingback(), Some("http://example.com/xmlrpc.php"));
+    /// ```
+    pub fn pingback(&self) -> Option<&str> {
+        link_href(self.links(), PINGBACK)
+    }
+
+    /// Set the URL of the Pingback server for this entry, replacing any `rel="pingback"` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_pingback("http://example.com/xmlrpc.php");
+    /// assert_eq!(entry.links()[0].rel(), "pingback");
+    ///
+    /// entry.set_pingback(None);
+    /// assert!(entry.links().is_empty());
+    /// ```
+    pub fn set_pingback<V>(&mut self, url: V)
+    where
+        V: IntoOption<String>,
+    {
+        set_link(self.links_mut(), PINGBACK, url.into_option());
+    }
+
+    /// Return the TrackBack ping URL of this entry, from `trackback:ping`.
+    ///
+    /// The URL is read from the text of the element or, as in RSS 1.0 feeds, from its
+    /// `rdf:resource` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed xmlns:trackback="http://madskills.com/public/xml/rss/module/trackback/">
+    ///     <entry><trackback:ping>http://example.com/trackback/1</trackback:ping></entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.entries()[0].trackback_ping(), Some("http://example.com/trackback/1"));
+    /// ```
+    pub fn trackback_ping(&self) -> Option<&str> {
+        let ping = self
+            .extensions()
+            .get("trackback")
+            .and_then(|map| map.get("ping"))
+            .and_then(|items| items.first())?;
+
+        ping.value()
+            .filter(|value| !value.is_empty())
+            .or_else(|| ping.attrs().get("rdf:resource").map(String::as_str))
+    }
+
+    /// Set the TrackBack ping URL of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_trackback_ping("http://example.com/trackback/1");
+    /// assert_eq!(entry.trackback_ping(), Some("http://example.com/trackback/1"));
+    /// ```
+    pub fn set_trackback_ping<V>(&mut self, url: V)
+    where
+        V: IntoOption<String>,
+    {
+        match url.into_option() {
+            Some(url) => self.set_extension("trackback", "ping", &url),
+            None => self.remove_comment_extension("trackback", "ping"),
+        }
+    }
+
+    fn remove_comment_extension(&mut self, prefix: &str, name: &str) {
+        if let Some(map) = self.extensions_mut().get_mut(prefix) {
+            map.remove(name);
+            if map.is_empty() {
+                self.extensions_mut().remove(prefix);
+            }
+        }
+    }
+}
+
+impl Feed {
+    /// Return the URL of the Pingback server for the entries of this feed, from its
+    /// `rel="pingback"` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_pingback("http://example.com/xmlrpc.php");
+    /// assert_eq!(feed.pingback(), Some("http://example.com/xmlrpc.php"));
+    /// ```
+    pub fn pingback(&self) -> Option<&str> {
+        link_href(self.links(), PINGBACK)
+    }
+
+    /// Set the URL of the Pingback server for the entries of this feed, replacing any
+    /// `rel="pingback"` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_pingback("http://example.com/xmlrpc.php");
+    /// feed.set_pingback(None);
+    /// assert_eq!(feed.pingback(), None);
+    /// ```
+    pub fn set_pingback<V>(&mut self, url: V)
+    where
+        V: IntoOption<String>,
+    {
+        set_link(self.links_mut(), PINGBACK, url.into_option());
+    }
+}
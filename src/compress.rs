@@ -0,0 +1,115 @@
+use std::io::{self, BufRead, BufReader, Write};
+
+use crate::error::Error;
+use crate::feed::Feed;
+
+/// A compression format that input can be detected in.
+pub(crate) enum Compression {
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+/// Read enough of the input to detect its compression format and encoding, even if the reader
+/// returns it in small pieces.
+pub(crate) fn peek<B: BufRead>(reader: &mut B) -> io::Result<Vec<u8>> {
+    let mut start = Vec::new();
+
+    while start.len() < 64 {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+
+        let len = buf.len().min(64 - start.len());
+        start.extend_from_slice(&buf[..len]);
+        reader.consume(len);
+    }
+
+    Ok(start)
+}
+
+/// Return the compression format of the input from its magic bytes, if it is compressed.
+pub(crate) fn detect(start: &[u8]) -> Option<Compression> {
+    #[cfg(feature = "gzip")]
+    {
+        if start.starts_with(&[0x1F, 0x8B]) {
+            return Some(Compression::Gzip);
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    {
+        if start.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            return Some(Compression::Zstd);
+        }
+    }
+
+    None
+}
+
+impl Compression {
+    /// Return a reader of the decompressed input.
+    pub(crate) fn decompress<'a, B: BufRead + 'a>(
+        &self,
+        reader: B,
+    ) -> Result<Box<dyn BufRead + 'a>, Error> {
+        Ok(match *self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                Box::new(BufReader::new(flate2::bufread::MultiGzDecoder::new(reader)))
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Box::new(BufReader::new(
+                zstd::stream::read::Decoder::with_buffer(reader)?,
+            )),
+        })
+    }
+}
+
+impl Feed {
+    /// Attempt to write this Atom feed to a writer, compressed with gzip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    ///
+    /// let compressed = feed.write_to_gz(Vec::new()).unwrap();
+    /// assert_eq!(&compressed[..2], b"\x1f\x8b");
+    ///
+    /// let feed = Feed::read_from(&compressed[..]).unwrap();
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// ```
+    #[cfg(feature = "gzip")]
+    pub fn write_to_gz<W: Write>(&self, writer: W) -> Result<W, Error> {
+        let encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+        Ok(self.write_to(encoder)?.finish()?)
+    }
+
+    /// Attempt to write this Atom feed to a writer, compressed with zstd at the given level.
+    ///
+    /// A level of 0 selects the default level of the zstd library.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    ///
+    /// let compressed = feed.write_to_zstd(Vec::new(), 0).unwrap();
+    /// let feed = Feed::read_from(&compressed[..]).unwrap();
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// ```
+    #[cfg(feature = "zstd")]
+    pub fn write_to_zstd<W: Write>(&self, writer: W, level: i32) -> Result<W, Error> {
+        let encoder = zstd::stream::write::Encoder::new(writer, level)?;
+        Ok(self.write_to(encoder)?.finish()?)
+    }
+}
@@ -0,0 +1,408 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::entry::Entry;
+use crate::policy::LinkPolicy;
+
+/// Options for reading an Atom feed.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Feed, ReadConfig};
+///
+/// let config = ReadConfig::new().fidelity(true);
+/// let feed = Feed::read_with_config("<feed></feed>".as_bytes(), &config).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReadConfig {
+    fidelity: bool,
+    max_depth: usize,
+    link_policy: Option<LinkPolicy>,
+    max_bytes: Option<u64>,
+    metadata_only: bool,
+    entry_offsets: bool,
+    fill_missing_updated: bool,
+    lenient_dates: bool,
+    html_entities: bool,
+    skip_broken_entries: bool,
+    lazy_content: bool,
+    intern: bool,
+}
+
+impl Default for ReadConfig {
+    fn default() -> Self {
+        ReadConfig {
+            fidelity: false,
+            max_depth: 128,
+            link_policy: None,
+            max_bytes: None,
+            metadata_only: false,
+            entry_offsets: false,
+            fill_missing_updated: false,
+            lenient_dates: false,
+            html_entities: false,
+            skip_broken_entries: false,
+            lazy_content: false,
+            intern: false,
+        }
+    }
+}
+
+impl ReadConfig {
+    /// Create a configuration with the default options.
+    pub fn new() -> Self {
+        ReadConfig::default()
+    }
+
+    /// Keep unknown child elements and the original order of child elements.
+    ///
+    /// When enabled, feeds and entries remember the elements they contain which are not
+    /// otherwise represented, along with the order in which all of their child elements
+    /// appeared. Writing the feed reproduces both, so that parsing, modifying and writing a feed
+    /// leaves the rest of the document alone. Disabled by default.
+    pub fn fidelity(mut self, fidelity: bool) -> Self {
+        self.fidelity = fidelity;
+        self
+    }
+
+    /// Return whether unknown child elements and the original order of child elements are kept.
+    pub fn is_fidelity(&self) -> bool {
+        self.fidelity
+    }
+
+    /// Set how deeply extension elements may be nested within each other.
+    ///
+    /// Reading a document that nests extension elements more deeply fails with
+    /// `Error::TooDeep` instead of exhausting the stack. Defaults to 128.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Return how deeply extension elements may be nested within each other.
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Set the maximum number of bytes to read.
+    ///
+    /// Reading a larger document fails with `Error::TooLarge` as soon as the limit is passed,
+    /// without reading the rest of it. Compressed input is limited both before and after it is
+    /// decompressed. There is no limit by default.
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Return the maximum number of bytes to read, if there is a limit.
+    pub fn get_max_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
+
+    /// Stop reading a feed at its first entry, keeping only the metadata that precedes it.
+    ///
+    /// See `Feed::read_metadata`. Disabled by default.
+    pub fn metadata_only(mut self, metadata_only: bool) -> Self {
+        self.metadata_only = metadata_only;
+        self
+    }
+
+    /// Return whether reading a feed stops at its first entry.
+    pub fn is_metadata_only(&self) -> bool {
+        self.metadata_only
+    }
+
+    /// Record the range of bytes each entry occupies in the document.
+    ///
+    /// The ranges are returned by `Entry::byte_range`, and let a feed that has changed be
+    /// re-read from the first entry that differs. UTF-16 documents cannot be read with this
+    /// enabled, since they are converted to UTF-8 first. Disabled by default.
+    pub fn entry_offsets(mut self, entry_offsets: bool) -> Self {
+        self.entry_offsets = entry_offsets;
+        self
+    }
+
+    /// Return whether the range of bytes each entry occupies is recorded.
+    pub fn is_entry_offsets(&self) -> bool {
+        self.entry_offsets
+    }
+
+    /// Give entries without an `updated` element the published time of the entry, or else the
+    /// `updated` time of the feed.
+    ///
+    /// See `Feed::fill_missing_updated`. Disabled by default.
+    pub fn fill_missing_updated(mut self, fill_missing_updated: bool) -> Self {
+        self.fill_missing_updated = fill_missing_updated;
+        self
+    }
+
+    /// Return whether entries without an `updated` element are given one.
+    pub fn is_fill_missing_updated(&self) -> bool {
+        self.fill_missing_updated
+    }
+
+    /// Read `updated` and `published` dates that are not formatted as described by RFC 3339 as
+    /// missing, instead of failing with `Error::InvalidDate`.
+    ///
+    /// Each malformed date is reported as a warning in `ParseStats::issues`. Disabled by
+    /// default.
+    pub fn lenient_dates(mut self, lenient_dates: bool) -> Self {
+        self.lenient_dates = lenient_dates;
+        self
+    }
+
+    /// Return whether malformed dates are read as missing instead of failing.
+    pub fn is_lenient_dates(&self) -> bool {
+        self.lenient_dates
+    }
+
+    /// Resolve the named character references of HTML, such as `&nbsp;` and `&copy;`, in text.
+    ///
+    /// Feeds often use these without declaring them, which is not valid XML. Text containing
+    /// one is otherwise kept in its escaped form. Text constructs and content of the `xhtml`
+    /// type are kept as markup and are not affected. Disabled by default.
+    pub fn html_entities(mut self, html_entities: bool) -> Self {
+        self.html_entities = html_entities;
+        self
+    }
+
+    /// Return whether the named character references of HTML are resolved in text.
+    pub fn is_html_entities(&self) -> bool {
+        self.html_entities
+    }
+
+    /// Tolerate the common mistakes of feed generators rather than failing or keeping text
+    /// escaped, by enabling both [`lenient_dates`](#method.lenient_dates) and
+    /// [`html_entities`](#method.html_entities).
+    pub fn lenient(self, lenient: bool) -> Self {
+        self.lenient_dates(lenient).html_entities(lenient)
+    }
+
+    /// Skip entries that cannot be read instead of failing to read the whole feed.
+    ///
+    /// The rest of a broken entry, such as one with a malformed date or attribute, is skipped up
+    /// to its end tag and reading continues with the next element. The error that stopped the
+    /// entry from being read is reported in `ParseStats::issues`, at the path of the element it
+    /// occurred in. Malformed XML and I/O errors still fail the whole feed, except with
+    /// `FeedParser`, which reads each entry on its own and so can skip entries with malformed
+    /// XML too. Disabled by default.
+    pub fn skip_broken_entries(mut self, skip_broken_entries: bool) -> Self {
+        self.skip_broken_entries = skip_broken_entries;
+        self
+    }
+
+    /// Return whether entries that cannot be read are skipped.
+    pub fn is_skip_broken_entries(&self) -> bool {
+        self.skip_broken_entries
+    }
+
+    /// Record where the content of each entry is in the document instead of decoding it.
+    ///
+    /// The value of such content is missing until it is decoded with `Content::load`, so
+    /// readers that only need the metadata of entries do not pay for decoding large inline
+    /// content. Content that refers to its value with `src` is read as usual. UTF-16 documents
+    /// cannot be read with this enabled, since they are converted to UTF-8 first. Disabled by
+    /// default.
+    pub fn lazy_content(mut self, lazy_content: bool) -> Self {
+        self.lazy_content = lazy_content;
+        self
+    }
+
+    /// Return whether the content of entries is left to be decoded on demand.
+    pub fn is_lazy_content(&self) -> bool {
+        self.lazy_content
+    }
+
+    /// Share the text of people and categories that is repeated across the document.
+    ///
+    /// The names, emails and URIs of authors and contributors and the terms, schemes and labels
+    /// of categories are kept once for the whole document, and each person and category with
+    /// the same text refers to that copy. This saves memory on archive feeds that repeat the same
+    /// people and categories across thousands of entries. Shared text is copied when it is
+    /// modified through the `_mut` accessors. Disabled by default.
+    pub fn intern(mut self, intern: bool) -> Self {
+        self.intern = intern;
+        self
+    }
+
+    /// Return whether repeated text of people and categories is shared.
+    pub fn is_intern(&self) -> bool {
+        self.intern
+    }
+
+    /// Remove the links and content `src` that the policy does not allow while reading.
+    ///
+    /// No policy is enforced by default.
+    pub fn link_policy(mut self, link_policy: LinkPolicy) -> Self {
+        self.link_policy = Some(link_policy);
+        self
+    }
+
+    /// Return the policy enforced on links while reading.
+    pub fn get_link_policy(&self) -> Option<&LinkPolicy> {
+        self.link_policy.as_ref()
+    }
+}
+
+/// Options for writing an Atom feed.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Feed, WriteConfig};
+///
+/// let config = WriteConfig::new().namespace("dc", "http://purl.org/dc/elements/1.1/");
+/// let xml = Feed::default().write_with_config(Vec::new(), &config).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WriteConfig {
+    namespaces: BTreeMap<String, String>,
+    indent: Option<(u8, usize)>,
+    declaration: bool,
+    fragment: bool,
+    cdata: CdataPolicy,
+    entry_order: EntryOrder,
+    wrap_xhtml: bool,
+}
+
+/// When to write human-readable text in CDATA sections.
+///
+/// The policy applies to titles, subtitles, rights, summaries and text or HTML content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CdataPolicy {
+    /// Never use CDATA sections. This is the default.
+    #[default]
+    Never,
+    /// Use a CDATA section when the text contains markup characters (`<` or `&`).
+    WhenNeeded,
+    /// Use a CDATA section for all non-empty text.
+    Always,
+}
+
+/// The order in which to write the entries of a feed.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum EntryOrder {
+    /// Keep the entries in the order they appear in the feed. This is the default.
+    #[default]
+    Document,
+    /// Write the most recently updated entries first.
+    NewestFirst,
+    /// Write the least recently updated entries first.
+    OldestFirst,
+    /// Write the entries sorted with the given comparison function. Entries that compare equal
+    /// keep the order they appear in the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{EntryOrder, Feed, WriteConfig};
+    ///
+    /// let xml = "<feed><entry><id>b</id></entry><entry><id>a</id></entry></feed>";
+    /// let feed = xml.parse::<Feed>().unwrap();
+    ///
+    /// let config = WriteConfig::new().entry_order(EntryOrder::Custom(|a, b| a.id().cmp(b.id())));
+    /// let xml = String::from_utf8(feed.write_with_config(Vec::new(), &config).unwrap()).unwrap();
+    /// assert!(xml.find("<id>a</id>") < xml.find("<id>b</id>"));
+    /// ```
+    Custom(fn(&Entry, &Entry) -> Ordering),
+}
+
+impl WriteConfig {
+    /// Create a configuration with the default options.
+    pub fn new() -> Self {
+        WriteConfig::default()
+    }
+
+    /// Register a preferred prefix for a namespace URI.
+    ///
+    /// The namespace is declared on the `feed` element, and extensions whose prefix is bound to
+    /// the same URI by the feed are written using this prefix instead. Prefixes registered here
+    /// take precedence over those declared by the feed.
+    pub fn namespace<P, U>(mut self, prefix: P, uri: U) -> Self
+    where
+        P: Into<String>,
+        U: Into<String>,
+    {
+        self.namespaces.insert(prefix.into(), uri.into());
+        self
+    }
+
+    /// Return the registered namespace prefixes mapped to their URIs.
+    pub fn namespaces(&self) -> &BTreeMap<String, String> {
+        &self.namespaces
+    }
+
+    /// Indent nested elements by `size` repetitions of `indent_char`, such as `b' '` or `b'\t'`.
+    ///
+    /// Feeds are written without any indentation by default.
+    pub fn indent(mut self, indent_char: u8, size: usize) -> Self {
+        self.indent = Some((indent_char, size));
+        self
+    }
+
+    /// Return the character and number of repetitions used to indent nested elements.
+    pub fn get_indent(&self) -> Option<(u8, usize)> {
+        self.indent
+    }
+
+    /// Write an XML declaration before the `feed` element. Disabled by default.
+    pub fn declaration(mut self, declaration: bool) -> Self {
+        self.declaration = declaration;
+        self
+    }
+
+    /// Return whether an XML declaration is written before the `feed` element.
+    pub fn is_declaration(&self) -> bool {
+        self.declaration
+    }
+
+    /// Write a fragment to be embedded in a larger XML document. Disabled by default.
+    ///
+    /// A fragment has no XML declaration, and its root `feed` or `entry` element does not declare
+    /// the Atom namespace or any extension namespaces. The enclosing document is expected to
+    /// declare them instead.
+    pub fn fragment(mut self, fragment: bool) -> Self {
+        self.fragment = fragment;
+        self
+    }
+
+    /// Return whether a fragment to be embedded in a larger XML document is written.
+    pub fn is_fragment(&self) -> bool {
+        self.fragment
+    }
+
+    /// Set when human-readable text is written in CDATA sections.
+    pub fn cdata(mut self, cdata: CdataPolicy) -> Self {
+        self.cdata = cdata;
+        self
+    }
+
+    /// Return when human-readable text is written in CDATA sections.
+    pub fn get_cdata(&self) -> CdataPolicy {
+        self.cdata
+    }
+
+    /// Set the order in which entries are written, regardless of their order in the feed.
+    pub fn entry_order(mut self, entry_order: EntryOrder) -> Self {
+        self.entry_order = entry_order;
+        self
+    }
+
+    /// Return the order in which entries are written.
+    pub fn get_entry_order(&self) -> EntryOrder {
+        self.entry_order
+    }
+
+    /// Wrap the value of XHTML content in an XHTML `div` element when it is not already contained
+    /// in a single `div`, as the specification requires. Disabled by default.
+    pub fn wrap_xhtml(mut self, wrap_xhtml: bool) -> Self {
+        self.wrap_xhtml = wrap_xhtml;
+        self
+    }
+
+    /// Return whether XHTML content that is not contained in a single `div` is wrapped in one.
+    pub fn is_wrap_xhtml(&self) -> bool {
+        self.wrap_xhtml
+    }
+}
@@ -1,4 +1,8 @@
-use std::io::{BufRead, Write};
+use std::collections::BTreeMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
 
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
@@ -6,14 +10,17 @@ use quick_xml::Error as XmlError;
 use quick_xml::Reader;
 use quick_xml::Writer;
 
+use crate::config::ReadConfig;
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
-use crate::util::atom_any_text;
+use crate::fromxml::{FromXml, ReadContext};
+use crate::toxml::{write_text, ToXml, WriteContext};
+use crate::util::{atom_xhtml, is_xhtml_div, IntoOption};
+
+const XHTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
 
 /// Represents the content of an Atom entry
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Builder)]
 #[builder(setter(into), default)]
 pub struct Content {
     /// The text value of the content.
@@ -22,9 +29,112 @@ pub struct Content {
     src: Option<String>,
     /// Either "text", "html", "xhtml", or the MIME type of the content.
     content_type: Option<String>,
+    /// Additional attributes of the element that are not otherwise represented.
+    attrs: BTreeMap<String, String>,
+    /// The range of bytes of the value in the document, if it has not been decoded yet.
+    #[builder(setter(skip))]
+    byte_range: Option<Range<usize>>,
 }
 
 impl Content {
+    /// Create plain text content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let content = Content::text("Entry content");
+    /// assert_eq!(content.content_type(), Some("text"));
+    /// assert_eq!(content.value(), Some("Entry content"));
+    /// ```
+    pub fn text<V>(value: V) -> Self
+    where
+        V: Into<String>,
+    {
+        Content {
+            value: Some(value.into()),
+            content_type: Some("text".to_string()),
+            ..Content::default()
+        }
+    }
+
+    /// Create HTML content from unescaped markup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let content = Content::html("<p>Entry content</p>");
+    /// assert_eq!(content.content_type(), Some("html"));
+    /// assert_eq!(content.value(), Some("<p>Entry content</p>"));
+    /// ```
+    pub fn html<V>(value: V) -> Self
+    where
+        V: Into<String>,
+    {
+        Content {
+            value: Some(value.into()),
+            content_type: Some("html".to_string()),
+            ..Content::default()
+        }
+    }
+
+    /// Create XHTML content from well-formed markup.
+    ///
+    /// XHTML content must be contained in a single XHTML `div` element. Markup that is not
+    /// already such a `div` is wrapped in one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let content = Content::xhtml("<p>Entry content</p>");
+    /// assert_eq!(content.content_type(), Some("xhtml"));
+    /// assert_eq!(
+    ///     content.value(),
+    ///     Some(r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>Entry content</p></div>"#)
+    /// );
+    /// ```
+    pub fn xhtml<V>(value: V) -> Self
+    where
+        V: Into<String>,
+    {
+        let mut content = Content {
+            value: Some(value.into()),
+            content_type: Some("xhtml".to_string()),
+            ..Content::default()
+        };
+        content.wrap_xhtml();
+        content
+    }
+
+    /// Create content that refers to a document of the given media type at `src`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let content = Content::out_of_line("http://example.com/image.png", "image/png");
+    /// assert_eq!(content.src(), Some("http://example.com/image.png"));
+    /// assert_eq!(content.content_type(), Some("image/png"));
+    /// assert_eq!(content.value(), None);
+    /// ```
+    pub fn out_of_line<S, M>(src: S, mime_type: M) -> Self
+    where
+        S: Into<String>,
+        M: Into<String>,
+    {
+        Content {
+            src: Some(src.into()),
+            content_type: Some(mime_type.into()),
+            ..Content::default()
+        }
+    }
+
     /// Return the text value of the content.
     ///
     /// If the `content_type` is neither `"text"`, `"html"`, or `"xhtml"` then the value should
@@ -36,13 +146,31 @@ impl Content {
     /// use atom_syndication::Content;
     ///
     /// let mut content = Content::default();
-    /// content.set_value("Example content".to_string());
+    /// content.set_value("Example content");
     /// assert_eq!(content.value(), Some("Example content"));
     /// ```
     pub fn value(&self) -> Option<&str> {
         self.value.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the text value of the content.
+    ///
+    /// If the `content_type` is neither `"text"`, `"html"`, or `"xhtml"` then the value should
+    /// be a base64 encoded document of the indicated MIME type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// *content.value_mut() = Some("Example content".to_string());
+    /// assert_eq!(content.value(), Some("Example content"));
+    /// ```
+    pub fn value_mut(&mut self) -> &mut Option<String> {
+        &mut self.value
+    }
+
     /// Set the text value of the content.
     ///
     /// # Examples
@@ -51,13 +179,30 @@ impl Content {
     /// use atom_syndication::Content;
     ///
     /// let mut content = Content::default();
-    /// content.set_value("Example content".to_string());
+    /// content.set_value("Example content");
     /// ```
     pub fn set_value<V>(&mut self, value: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.value = value.into();
+        self.value = value.into_option();
+    }
+
+    /// Set the text value of the content and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let content = Content::default().with_value("Example content");
+    /// ```
+    pub fn with_value<V>(mut self, value: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_value(value);
+        self
     }
 
     /// Return the URI where the content can be found.
@@ -68,13 +213,28 @@ impl Content {
     /// use atom_syndication::Content;
     ///
     /// let mut content = Content::default();
-    /// content.set_src("http://example.com/content.html".to_string());
+    /// content.set_src("http://example.com/content.html");
     /// assert_eq!(content.src(), Some("http://example.com/content.html"));
     /// ```
     pub fn src(&self) -> Option<&str> {
         self.src.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the URI where the content can be found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// *content.src_mut() = Some("http://example.com/content.html".to_string());
+    /// assert_eq!(content.src(), Some("http://example.com/content.html"));
+    /// ```
+    pub fn src_mut(&mut self) -> &mut Option<String> {
+        &mut self.src
+    }
+
     /// Set the URI where the content can be found.
     ///
     /// # Examples
@@ -83,13 +243,30 @@ impl Content {
     /// use atom_syndication::Content;
     ///
     /// let mut content = Content::default();
-    /// content.set_src("http://example.com/content.html".to_string());
+    /// content.set_src("http://example.com/content.html");
     /// ```
     pub fn set_src<V>(&mut self, src: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
+    {
+        self.src = src.into_option();
+    }
+
+    /// Set the URI where the content can be found and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let content = Content::default().with_src("http://example.com/content.html");
+    /// ```
+    pub fn with_src<V>(mut self, src: V) -> Self
+    where
+        V: IntoOption<String>,
     {
-        self.src = src.into();
+        self.set_src(src);
+        self
     }
 
     /// Return the type of the content.
@@ -102,13 +279,30 @@ impl Content {
     /// use atom_syndication::Content;
     ///
     /// let mut content = Content::default();
-    /// content.set_content_type("image/png".to_string());
+    /// content.set_content_type("image/png");
     /// assert_eq!(content.content_type(), Some("image/png"));
     /// ```
     pub fn content_type(&self) -> Option<&str> {
         self.content_type.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the type of the content.
+    ///
+    /// The type is either `"text"`, `"html"`, `"xhtml"`, or the MIME type of the content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// *content.content_type_mut() = Some("image/png".to_string());
+    /// assert_eq!(content.content_type(), Some("image/png"));
+    /// ```
+    pub fn content_type_mut(&mut self) -> &mut Option<String> {
+        &mut self.content_type
+    }
+
     /// Set the type of the content.
     ///
     /// # Examples
@@ -117,19 +311,364 @@ impl Content {
     /// use atom_syndication::Content;
     ///
     /// let mut content = Content::default();
-    /// content.set_content_type("image/png".to_string());
+    /// content.set_content_type("image/png");
     /// assert_eq!(content.content_type(), Some("image/png"));
     /// ```
     pub fn set_content_type<V>(&mut self, content_type: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
+    {
+        self.content_type = content_type.into_option();
+    }
+
+    /// Set the type of the content and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let content = Content::default().with_content_type("image/png");
+    /// assert_eq!(content.content_type(), Some("image/png"));
+    /// ```
+    pub fn with_content_type<V>(mut self, content_type: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_content_type(content_type);
+        self
+    }
+
+    /// Return the media type of the content.
+    ///
+    /// The `"text"`, `"html"` and `"xhtml"` types are mapped to `text/plain`, `text/html` and
+    /// `application/xhtml+xml` respectively. Returns `None` if the type is missing or is not a
+    /// valid MIME type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("html");
+    /// assert_eq!(content.mime(), Some(mime::TEXT_HTML));
+    /// ```
+    #[cfg(feature = "mime")]
+    pub fn mime(&self) -> Option<mime::Mime> {
+        match self.content_type.as_deref() {
+            Some("text") => Some(mime::TEXT_PLAIN),
+            Some("html") => Some(mime::TEXT_HTML),
+            Some("xhtml") => "application/xhtml+xml".parse().ok(),
+            Some(content_type) => content_type.parse().ok(),
+            None => None,
+        }
+    }
+
+    /// Set the type of the content to the given media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_mime(mime::IMAGE_PNG);
+    /// assert_eq!(content.content_type(), Some("image/png"));
+    /// ```
+    #[cfg(feature = "mime")]
+    pub fn set_mime(&mut self, mime: mime::Mime) {
+        self.content_type = Some(mime.to_string());
+    }
+
+    /// Return the value of the content as bytes, decoding it if it is base64 encoded.
+    ///
+    /// Content of a media type that is neither textual nor XML is base64 encoded, and is decoded
+    /// here. Any other content is returned as its UTF-8 bytes. Content without a value, such as
+    /// content referenced by `src`, returns no bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("application/octet-stream");
+    /// content.set_value("AAEC\n/w==");
+    /// assert_eq!(content.decoded_bytes().unwrap(), vec![0, 1, 2, 255]);
+    /// ```
+    pub fn decoded_bytes(&self) -> Result<Vec<u8>, Error> {
+        let value = match self.value {
+            Some(ref value) => value,
+            None => return Ok(Vec::new()),
+        };
+
+        if !self.is_base64() {
+            return Ok(value.as_bytes().to_vec());
+        }
+
+        let encoded = value
+            .bytes()
+            .filter(|b| !b.is_ascii_whitespace())
+            .collect::<Vec<_>>();
+        Ok(base64::decode(&encoded)?)
+    }
+
+    /// Set the type and value of the content from the given media type and bytes.
+    ///
+    /// The bytes are base64 encoded unless the media type is textual or XML, in which case they
+    /// are stored as text with any invalid UTF-8 sequences replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_decoded_bytes("image/png", &[0x89, b'P', b'N', b'G']);
+    /// assert_eq!(content.content_type(), Some("image/png"));
+    /// assert_eq!(content.value(), Some("iVBORw=="));
+    /// ```
+    pub fn set_decoded_bytes<M, B>(&mut self, mime_type: M, bytes: B)
+    where
+        M: Into<String>,
+        B: AsRef<[u8]>,
     {
-        self.content_type = content_type.into();
+        self.content_type = Some(mime_type.into());
+
+        let bytes = bytes.as_ref();
+        self.value = if self.is_base64() {
+            Some(base64::encode(bytes))
+        } else {
+            Some(String::from_utf8_lossy(bytes).into_owned())
+        };
+    }
+
+    /// Wrap the value of XHTML content in an XHTML `div` element if it is not already contained
+    /// in a single `div`, as the specification requires. Returns whether the value was wrapped.
+    ///
+    /// Content that is not XHTML is left unchanged. See also
+    /// [`WriteConfig::wrap_xhtml`](struct.WriteConfig.html#method.wrap_xhtml), which wraps the
+    /// content when it is written instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("xhtml");
+    /// content.set_value("<p>One</p><p>Two</p>");
+    ///
+    /// assert!(content.wrap_xhtml());
+    /// assert_eq!(
+    ///     content.value(),
+    ///     Some(r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>One</p><p>Two</p></div>"#)
+    /// );
+    /// assert!(!content.wrap_xhtml());
+    /// ```
+    pub fn wrap_xhtml(&mut self) -> bool {
+        let wrapped = match self.wrapped_xhtml() {
+            Some(wrapped) => wrapped,
+            None => return false,
+        };
+
+        self.value = Some(wrapped);
+        true
+    }
+
+    /// Return the range of bytes the value of this content occupies in the document it was read
+    /// from, if the value has not been decoded yet.
+    ///
+    /// The range covers the text or markup between the start and end tags of the `content`
+    /// element. It is only recorded when the feed is read with `ReadConfig::lazy_content`
+    /// enabled, and offsets count the bytes of the document as it was given, after it has been
+    /// decompressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let xml = r#"<feed><entry><content type="html">&lt;p&gt;Hi&lt;/p&gt;</content></entry></feed>"#;
+    /// let config = ReadConfig::new().lazy_content(true);
+    /// let feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    ///
+    /// let content = feed.entries()[0].content().unwrap();
+    /// assert_eq!(content.value(), None);
+    /// assert_eq!(&xml[content.byte_range().unwrap()], "&lt;p&gt;Hi&lt;/p&gt;");
+    /// ```
+    pub fn byte_range(&self) -> Option<Range<usize>> {
+        self.byte_range.clone()
+    }
+
+    /// Decode the value of this content from the document it was read from, if it was read with
+    /// `ReadConfig::lazy_content` enabled.
+    ///
+    /// `document` must be the same document, and `config` the configuration it was read with.
+    /// Content whose value has already been decoded is left unchanged. Content must be loaded
+    /// before it is written, otherwise writing fails with `Error::ContentNotLoaded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let xml = r#"<feed><entry><content type="html">&lt;p&gt;Hi&lt;/p&gt;</content></entry></feed>"#;
+    /// let config = ReadConfig::new().lazy_content(true);
+    /// let mut feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    ///
+    /// let content = feed.entries_mut()[0].content_mut().as_mut().unwrap();
+    /// content.load(xml.as_bytes(), &config).unwrap();
+    /// assert_eq!(content.value(), Some("<p>Hi</p>"));
+    /// assert_eq!(content.byte_range(), None);
+    /// ```
+    pub fn load(&mut self, document: &[u8], config: &ReadConfig) -> Result<(), Error> {
+        let body = match self.byte_range {
+            Some(ref range) => document.get(range.clone()).ok_or(Error::Eof)?,
+            None => return Ok(()),
+        };
+
+        let xml = [&b"<content>"[..], body, b"</content>"].concat();
+        let mut reader = Reader::from_reader(&xml[..]);
+        reader.expand_empty_elements(true);
+
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event(&mut buf)? {
+                Event::Start(_) => break,
+                Event::Eof => return Err(Error::Eof),
+                _ => buf.clear(),
+            }
+        }
+
+        self.value = match self.content_type.as_deref() {
+            Some("xhtml") => atom_xhtml(&mut reader)?,
+            _ => ReadContext::new(config.clone()).read_text(&mut reader)?,
+        };
+        self.byte_range = None;
+        Ok(())
+    }
+
+    /// Return whether this content only refers to its value with `src`.
+    pub(crate) fn is_out_of_line(&self) -> bool {
+        self.value.is_none() && self.src.is_some()
+    }
+
+    /// Return whether this is XHTML content whose value is not contained in a single `div`.
+    pub(crate) fn is_unwrapped_xhtml(&self) -> bool {
+        self.content_type.as_deref() == Some("xhtml")
+            && self.src.is_none()
+            && !is_xhtml_div(self.value.as_deref().unwrap_or_default())
+    }
+
+    /// Return the value of XHTML content wrapped in a `div`, or `None` if it needs no wrapping.
+    fn wrapped_xhtml(&self) -> Option<String> {
+        if !self.is_unwrapped_xhtml() {
+            return None;
+        }
+
+        let value = self.value.as_deref().unwrap_or_default();
+        Some(format!(
+            r#"<div xmlns="{}">{}</div>"#,
+            XHTML_NAMESPACE, value
+        ))
+    }
+
+    fn is_base64(&self) -> bool {
+        match self.content_type.as_deref() {
+            None | Some("text") | Some("html") | Some("xhtml") => false,
+            Some(content_type) => {
+                let content_type = content_type.to_ascii_lowercase();
+                let essence = content_type.split(';').next().unwrap_or_default().trim();
+                !(essence.starts_with("text/")
+                    || essence.ends_with("+xml")
+                    || essence.ends_with("/xml"))
+            }
+        }
+    }
+
+    /// Return the additional attributes of this content.
+    ///
+    /// These are attributes of the element that are not otherwise represented, such as `xml:lang`
+    /// or vendor-specific attributes. They are written back out when serializing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Content;
+    ///
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("xml:lang".to_string(), "en".to_string());
+    ///
+    /// let mut content = Content::default();
+    /// content.set_attrs(attrs);
+    /// assert_eq!(content.attrs().get("xml:lang").map(String::as_str), Some("en"));
+    /// ```
+    pub fn attrs(&self) -> &BTreeMap<String, String> {
+        &self.attrs
+    }
+
+    /// Return a mutable reference to the additional attributes of this content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.attrs_mut()
+    ///     .insert("xml:lang".to_string(), "en".to_string());
+    /// assert_eq!(content.attrs().len(), 1);
+    /// ```
+    pub fn attrs_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.attrs
+    }
+
+    /// Set the additional attributes of this content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_attrs(BTreeMap::new());
+    /// ```
+    pub fn set_attrs<V>(&mut self, attrs: V)
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.attrs = attrs.into();
+    }
+
+    /// Set the additional attributes of this content and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Content;
+    ///
+    /// let content = Content::default().with_attrs(BTreeMap::new());
+    /// ```
+    pub fn with_attrs<V>(mut self, attrs: V) -> Self
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.set_attrs(attrs);
+        self
     }
 }
 
 impl FromXml for Content {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, mut atts: Attributes) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        mut atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
         let mut content = Content::default();
 
         for attr in atts.with_checks(false) {
@@ -137,19 +676,73 @@ impl FromXml for Content {
                 match att.key {
                     b"type" => content.content_type = Some(att.unescape_and_decode_value(reader)?),
                     b"src" => content.src = Some(att.unescape_and_decode_value(reader)?),
-                    _ => {}
+                    key => {
+                        let key = reader.decode(key).into_owned();
+                        content
+                            .attrs
+                            .insert(key, att.unescape_and_decode_value(reader)?);
+                    }
                 }
             }
         }
 
-        content.value = atom_any_text(reader, atts)?;
+        if ctx.config.is_lazy_content() && content.src.is_none() {
+            content.byte_range = Some(skip_value(reader, ctx.offset)?);
+        } else {
+            content.value = ctx.read_any_text(reader, atts)?;
+        }
 
         Ok(content)
     }
 }
 
+/// Skip the value of an element whose start tag has just been read, returning the range of bytes
+/// between its start and end tags.
+fn skip_value<B: BufRead>(reader: &mut Reader<B>, offset: usize) -> Result<Range<usize>, Error> {
+    let start = reader.buffer_position();
+    let mut depth = 0;
+    let mut buf = Vec::new();
+
+    loop {
+        let position = reader.buffer_position();
+        match reader.read_event(&mut buf)? {
+            Event::Start(_) => depth += 1,
+            Event::End(_) if depth == 0 => return Ok(offset + start..offset + position),
+            Event::End(_) => depth -= 1,
+            Event::Eof => return Err(Error::Eof),
+            _ => {}
+        }
+
+        buf.clear();
+    }
+}
+
+/// The I/O error returned when writing content whose value has not been loaded.
+#[derive(Debug)]
+pub(crate) struct NotLoaded;
+
+impl fmt::Display for NotLoaded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("content must be loaded with Content::load before it is written")
+    }
+}
+
+impl StdError for NotLoaded {}
+
+/// Return whether the I/O error was returned when writing content that has not been loaded.
+pub(crate) fn is_not_loaded(err: &io::Error) -> bool {
+    matches!(err.get_ref(), Some(inner) if inner.is::<NotLoaded>())
+}
+
 impl ToXml for Content {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>, ctx: &WriteContext) -> Result<(), XmlError> {
+        if self.value.is_none() && self.byte_range.is_some() {
+            return Err(XmlError::Io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                NotLoaded,
+            )));
+        }
+
         let name = b"content";
         let mut element = BytesStart::borrowed(name, name.len());
 
@@ -165,10 +758,23 @@ impl ToXml for Content {
             element.push_attribute(("src", &**src));
         }
 
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+
         writer.write_event(Event::Start(element))?;
 
-        if let Some(ref value) = self.value {
-            writer.write_event(Event::Text(BytesText::from_escaped(value.as_bytes())))?;
+        let wrapped = if ctx.config.is_wrap_xhtml() {
+            self.wrapped_xhtml()
+        } else {
+            None
+        };
+
+        if let Some(value) = wrapped.as_deref().or(self.value.as_deref()) {
+            match self.content_type.as_deref() {
+                None | Some("text") | Some("html") => write_text(writer, value, ctx)?,
+                _ => {
+                    writer.write_event(Event::Text(BytesText::from_escaped(value.as_bytes())))?;
+                }
+            }
         }
 
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
@@ -0,0 +1,841 @@
+//! Enveloped XML signatures of feeds and entries.
+//!
+//! A signed document carries a `ds:Signature` element as the last child of its root element, as
+//! described by the [XML Signature](https://www.w3.org/TR/xmldsig-core1/) recommendation. The
+//! signature covers the whole document except itself, canonicalized with [exclusive XML
+//! canonicalization](https://www.w3.org/TR/xml-exc-c14n/) and digested with SHA-256.
+//!
+//! Computing and checking signature values is left to implementations of [`Signer`] and
+//! [`Verifier`], so any signature algorithm can be used. [`HmacSha256`] implements both for
+//! documents signed with a shared key.
+//!
+//! # Examples
+//!
+//! ```
+//! use atom_syndication::dsig::HmacSha256;
+//! use atom_syndication::Feed;
+//!
+//! let key = HmacSha256::new("secret").with_key_name("advisories");
+//!
+//! let mut feed = Feed::default();
+//! feed.set_title("Security Advisories");
+//! let xml = feed.write_signed(Vec::new(), &key).unwrap();
+//!
+//! let (feed, signature) = Feed::read_verified(&xml, &key).unwrap();
+//! assert_eq!(feed.title(), "Security Advisories");
+//! assert_eq!(signature.key_name(), Some("advisories"));
+//! ```
+
+use std::fmt;
+use std::io::{self, Write};
+use std::str;
+
+use hmac::{Hmac, Mac};
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::Reader;
+use sha2::{Digest, Sha256};
+
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::feed::Feed;
+
+/// The namespace of XML signatures.
+const DSIG_NAMESPACE: &str = "http://www.w3.org/2000/09/xmldsig#";
+
+/// The namespace bound to the `xml` prefix, which is never declared.
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Exclusive XML canonicalization without comments.
+const EXC_C14N: &str = "http://www.w3.org/2001/10/xml-exc-c14n#";
+
+/// The transform that removes the signature from the signed document.
+const ENVELOPED_SIGNATURE: &str = "http://www.w3.org/2000/09/xmldsig#enveloped-signature";
+
+/// The SHA-256 digest method.
+const SHA256: &str = "http://www.w3.org/2001/04/xmlenc#sha256";
+
+/// The HMAC-SHA256 signature method.
+pub const HMAC_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#hmac-sha256";
+
+/// The RSASSA-PKCS1-v1_5 with SHA-256 signature method.
+pub const RSA_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#rsa-sha256";
+
+/// The ECDSA with SHA-256 signature method.
+pub const ECDSA_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#ecdsa-sha256";
+
+/// Computes signature values with a private or shared key.
+pub trait Signer {
+    /// Return the URI of the signature method, such as `RSA_SHA256`.
+    fn algorithm(&self) -> &str;
+
+    /// Return the name of the key, which is recorded in the signature to identify the signer.
+    fn key_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Return the signature value of the canonical `SignedInfo` element.
+    fn sign(&self, data: &[u8]) -> io::Result<Vec<u8>>;
+}
+
+/// Checks signature values with a public or shared key.
+pub trait Verifier {
+    /// Return whether `signature` is a valid signature of the canonical `SignedInfo` element
+    /// made with the given signature method and the key with the given name.
+    fn verify(
+        &self,
+        algorithm: &str,
+        key_name: Option<&str>,
+        data: &[u8],
+        signature: &[u8],
+    ) -> bool;
+}
+
+/// Signs and verifies documents with HMAC-SHA256 and a shared key.
+#[derive(Clone)]
+pub struct HmacSha256 {
+    key: Vec<u8>,
+    key_name: Option<String>,
+}
+
+impl HmacSha256 {
+    /// Create a signer and verifier using the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::dsig::{HmacSha256, Signer};
+    ///
+    /// let key = HmacSha256::new("secret");
+    /// assert_eq!(key.sign(b"data").unwrap().len(), 32);
+    /// ```
+    pub fn new<K>(key: K) -> Self
+    where
+        K: Into<Vec<u8>>,
+    {
+        HmacSha256 {
+            key: key.into(),
+            key_name: None,
+        }
+    }
+
+    /// Set the name of the key and return the signer.
+    ///
+    /// The name is recorded in signatures, and signatures naming a different key are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::dsig::{HmacSha256, Signer};
+    ///
+    /// let key = HmacSha256::new("secret").with_key_name("advisories");
+    /// assert_eq!(key.key_name(), Some("advisories"));
+    /// ```
+    pub fn with_key_name<N>(mut self, key_name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.key_name = Some(key_name.into());
+        self
+    }
+
+    fn mac(&self, data: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts any key");
+        mac.update(data);
+        mac
+    }
+}
+
+impl fmt::Debug for HmacSha256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HmacSha256")
+            .field("key_name", &self.key_name)
+            .finish()
+    }
+}
+
+impl Signer for HmacSha256 {
+    fn algorithm(&self) -> &str {
+        HMAC_SHA256
+    }
+
+    fn key_name(&self) -> Option<&str> {
+        self.key_name.as_deref()
+    }
+
+    fn sign(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        Ok(self.mac(data).finalize().into_bytes().to_vec())
+    }
+}
+
+impl Verifier for HmacSha256 {
+    fn verify(
+        &self,
+        algorithm: &str,
+        key_name: Option<&str>,
+        data: &[u8],
+        signature: &[u8],
+    ) -> bool {
+        if algorithm != HMAC_SHA256 {
+            return false;
+        }
+        if self.key_name.is_some() && key_name != self.key_name.as_deref() {
+            return false;
+        }
+        self.mac(data).verify_slice(signature).is_ok()
+    }
+}
+
+/// A verified signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    algorithm: String,
+    key_name: Option<String>,
+}
+
+impl Signature {
+    /// Return the URI of the signature method.
+    pub fn algorithm(&self) -> &str {
+        self.algorithm.as_str()
+    }
+
+    /// Return the name of the key the document was signed with, which identifies the signer.
+    pub fn key_name(&self) -> Option<&str> {
+        self.key_name.as_deref()
+    }
+}
+
+/// Sign an XML document with an enveloped signature.
+///
+/// The signed document is returned in canonical form, with the signature as the last child of
+/// the root element. An enveloped signature already present in the document is replaced.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::dsig::{self, HmacSha256};
+///
+/// let key = HmacSha256::new("secret");
+/// let xml = dsig::sign(b"<entry><title>Entry Title</title></entry>", &key).unwrap();
+/// assert!(xml.starts_with(b"<entry><title>Entry Title</title><ds:Signature"));
+/// ```
+pub fn sign(document: &[u8], signer: &dyn Signer) -> Result<Vec<u8>, Error> {
+    let canonical = canonicalize(document, Subset::Document)?;
+    let end = canonical.root_end.ok_or(Error::Eof)?;
+    let digest = base64::encode(Sha256::digest(&canonical.xml));
+
+    let signature = |value: &str| {
+        let mut signature = format!(
+            concat!(
+                r#"<ds:Signature xmlns:ds="{}"><ds:SignedInfo>"#,
+                r#"<ds:CanonicalizationMethod Algorithm="{}"></ds:CanonicalizationMethod>"#,
+                r#"<ds:SignatureMethod Algorithm="{}"></ds:SignatureMethod>"#,
+                r#"<ds:Reference URI=""><ds:Transforms>"#,
+                r#"<ds:Transform Algorithm="{}"></ds:Transform>"#,
+                r#"<ds:Transform Algorithm="{}"></ds:Transform>"#,
+                r#"</ds:Transforms><ds:DigestMethod Algorithm="{}"></ds:DigestMethod>"#,
+                r#"<ds:DigestValue>{}</ds:DigestValue></ds:Reference></ds:SignedInfo>"#,
+                r#"<ds:SignatureValue>{}</ds:SignatureValue>"#,
+            ),
+            DSIG_NAMESPACE,
+            EXC_C14N,
+            escape_attribute(signer.algorithm()),
+            ENVELOPED_SIGNATURE,
+            EXC_C14N,
+            SHA256,
+            digest,
+            value,
+        );
+        if let Some(key_name) = signer.key_name() {
+            signature.push_str("<ds:KeyInfo><ds:KeyName>");
+            signature.push_str(&escape_text(key_name));
+            signature.push_str("</ds:KeyName></ds:KeyInfo>");
+        }
+        signature.push_str("</ds:Signature>");
+
+        let mut xml = Vec::with_capacity(canonical.xml.len() + signature.len());
+        xml.extend_from_slice(&canonical.xml[..end]);
+        xml.extend_from_slice(signature.as_bytes());
+        xml.extend_from_slice(&canonical.xml[end..]);
+        xml
+    };
+
+    let unsigned = signature("");
+    let signed_info = canonicalize(&unsigned, Subset::Signature("SignedInfo"))?;
+    let value = base64::encode(signer.sign(&signed_info.xml)?);
+    Ok(signature(&value))
+}
+
+/// Verify the enveloped signature of an XML document and return it.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::dsig::{self, HmacSha256};
+///
+/// let key = HmacSha256::new("secret");
+/// let xml = dsig::sign(b"<entry><title>Entry Title</title></entry>", &key).unwrap();
+/// assert!(dsig::verify(&xml, &key).is_ok());
+///
+/// let tampered = String::from_utf8(xml).unwrap().replace("Entry Title", "Other Title");
+/// assert!(dsig::verify(tampered.as_bytes(), &key).is_err());
+/// ```
+pub fn verify(document: &[u8], verifier: &dyn Verifier) -> Result<Signature, Error> {
+    verify_document(document, verifier).map(|(signature, _)| signature)
+}
+
+/// Verify the enveloped signature of a document, returning it along with the prefix of the
+/// signature element.
+fn verify_document(document: &[u8], verifier: &dyn Verifier) -> Result<(Signature, String), Error> {
+    let signature = canonicalize(document, Subset::Signature("Signature"))?;
+    if signature.xml.is_empty() {
+        return Err(invalid("missing signature"));
+    }
+    let parts = SignatureParts::parse(&signature.xml)?;
+
+    if parts.canonicalization != EXC_C14N {
+        return Err(invalid("unsupported canonicalization method"));
+    }
+    if parts.references != 1 || !parts.reference_uri.is_empty() {
+        return Err(invalid("signature must reference the whole document"));
+    }
+    if !parts
+        .transforms
+        .iter()
+        .all(|transform| transform == ENVELOPED_SIGNATURE || transform == EXC_C14N)
+    {
+        return Err(invalid("unsupported transform"));
+    }
+    if parts.digest_method != SHA256 {
+        return Err(invalid("unsupported digest method"));
+    }
+
+    let canonical = canonicalize(document, Subset::Document)?;
+    let digest = base64::decode(strip_whitespace(&parts.digest_value))?;
+    if Sha256::digest(&canonical.xml).as_slice() != digest.as_slice() {
+        return Err(invalid("digest does not match the document"));
+    }
+
+    let signed_info = canonicalize(document, Subset::Signature("SignedInfo"))?;
+    let value = base64::decode(strip_whitespace(&parts.signature_value))?;
+    let key_name = parts.key_name.as_deref();
+    if !verifier.verify(&parts.algorithm, key_name, &signed_info.xml, &value) {
+        return Err(invalid("signature value does not match"));
+    }
+
+    let signature = Signature {
+        algorithm: parts.algorithm,
+        key_name: parts.key_name,
+    };
+    Ok((signature, parts.prefix))
+}
+
+impl Feed {
+    /// Write this feed to a writer with an enveloped signature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::dsig::HmacSha256;
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = Feed::default().write_signed(Vec::new(), &HmacSha256::new("secret")).unwrap();
+    /// assert!(String::from_utf8(xml).unwrap().contains("<ds:SignatureValue>"));
+    /// ```
+    pub fn write_signed<W: Write>(&self, mut writer: W, signer: &dyn Signer) -> Result<W, Error> {
+        let xml = sign(&self.write_to(Vec::new())?, signer)?;
+        writer.write_all(&xml)?;
+        Ok(writer)
+    }
+
+    /// Verify the enveloped signature of a feed document and read the feed.
+    ///
+    /// The signature element is not included in the extensions of the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::dsig::HmacSha256;
+    /// use atom_syndication::Feed;
+    ///
+    /// let key = HmacSha256::new("secret");
+    /// let xml = Feed::default().write_signed(Vec::new(), &key).unwrap();
+    ///
+    /// let (feed, _) = Feed::read_verified(&xml, &key).unwrap();
+    /// assert!(feed.extensions().is_empty());
+    /// assert!(Feed::read_verified(&xml, &HmacSha256::new("other")).is_err());
+    /// ```
+    pub fn read_verified(
+        document: &[u8],
+        verifier: &dyn Verifier,
+    ) -> Result<(Feed, Signature), Error> {
+        let (signature, prefix) = verify_document(document, verifier)?;
+        let mut feed = Feed::read_from(document)?;
+
+        let extensions = feed.extensions_mut();
+        if let Some(elements) = extensions.get_mut(&prefix) {
+            elements.remove("Signature");
+            if elements.is_empty() {
+                extensions.remove(&prefix);
+            }
+        }
+
+        Ok((feed, signature))
+    }
+}
+
+impl Entry {
+    /// Write this entry as a standalone Atom entry document with an enveloped signature.
+    ///
+    /// The signature can be verified with [`verify`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::dsig::{self, HmacSha256};
+    /// use atom_syndication::Entry;
+    ///
+    /// let key = HmacSha256::new("secret");
+    /// let xml = Entry::default().write_signed(Vec::new(), &key).unwrap();
+    /// assert!(dsig::verify(&xml, &key).is_ok());
+    /// ```
+    pub fn write_signed<W: Write>(&self, mut writer: W, signer: &dyn Signer) -> Result<W, Error> {
+        let xml = sign(&self.write_to(Vec::new())?, signer)?;
+        writer.write_all(&xml)?;
+        Ok(writer)
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidSignature(message.to_string())
+}
+
+fn strip_whitespace(value: &str) -> String {
+    value.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// The values of a signature element needed to verify it.
+#[derive(Default)]
+struct SignatureParts {
+    prefix: String,
+    canonicalization: String,
+    algorithm: String,
+    references: usize,
+    reference_uri: String,
+    transforms: Vec<String>,
+    digest_method: String,
+    digest_value: String,
+    signature_value: String,
+    key_name: Option<String>,
+}
+
+impl SignatureParts {
+    /// Read the parts of a canonical signature element.
+    fn parse(xml: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::from_reader(xml);
+        let mut buf = Vec::new();
+        let mut parts = SignatureParts::default();
+        let mut current = Vec::new();
+
+        loop {
+            match reader.read_event(&mut buf)? {
+                Event::Start(ref element) => {
+                    let name = element.name();
+                    if parts.prefix.is_empty() {
+                        if let Some(colon) = name.iter().position(|&b| b == b':') {
+                            parts.prefix = str::from_utf8(&name[..colon])?.to_string();
+                        }
+                    }
+
+                    current = element.local_name().to_vec();
+                    match element.local_name() {
+                        b"CanonicalizationMethod" => {
+                            parts.canonicalization = algorithm(element)?;
+                        }
+                        b"SignatureMethod" => parts.algorithm = algorithm(element)?,
+                        b"Reference" => {
+                            parts.references += 1;
+                            parts.reference_uri = attribute(element, b"URI")?.unwrap_or_default();
+                        }
+                        b"Transform" => parts.transforms.push(algorithm(element)?),
+                        b"DigestMethod" => parts.digest_method = algorithm(element)?,
+                        _ => {}
+                    }
+                }
+                Event::Text(ref text) => {
+                    let text = text.unescaped()?;
+                    let text = str::from_utf8(&text)?;
+                    match current.as_slice() {
+                        b"DigestValue" => parts.digest_value.push_str(text),
+                        b"SignatureValue" => parts.signature_value.push_str(text),
+                        b"KeyName" => parts
+                            .key_name
+                            .get_or_insert_with(String::new)
+                            .push_str(text),
+                        _ => {}
+                    }
+                }
+                Event::End(_) => current.clear(),
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(parts)
+    }
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Result<Option<String>, Error> {
+    for attr in element.attributes() {
+        let attr = attr?;
+        if attr.key == name {
+            return Ok(Some(str::from_utf8(&attr.unescaped_value()?)?.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+fn algorithm(element: &BytesStart) -> Result<String, Error> {
+    Ok(attribute(element, b"Algorithm")?.unwrap_or_default())
+}
+
+/// The part of a document to canonicalize.
+#[derive(Debug, Clone, Copy)]
+enum Subset {
+    /// The whole document except its enveloped signature.
+    Document,
+    /// The first element with the given local name in the signature namespace that is part of
+    /// the enveloped signature.
+    Signature(&'static str),
+}
+
+/// The canonical form of part of a document.
+struct Canonical {
+    xml: Vec<u8>,
+    /// The offset of the end tag of the root element.
+    root_end: Option<usize>,
+}
+
+/// A namespace declared on an element at the given depth.
+struct Declaration {
+    depth: usize,
+    prefix: String,
+    uri: String,
+}
+
+fn lookup<'a>(declarations: &'a [Declaration], prefix: &str) -> Option<&'a str> {
+    declarations
+        .iter()
+        .rev()
+        .find(|declaration| declaration.prefix == prefix)
+        .map(|declaration| declaration.uri.as_str())
+}
+
+fn split_name(name: &str) -> (&str, &str) {
+    match name.find(':') {
+        Some(colon) => (&name[..colon], &name[colon + 1..]),
+        None => ("", name),
+    }
+}
+
+/// Canonicalize part of a document with exclusive XML canonicalization, omitting comments.
+fn canonicalize(document: &[u8], subset: Subset) -> Result<Canonical, Error> {
+    let mut reader = Reader::from_reader(document);
+    let mut buf = Vec::new();
+
+    let mut output = Vec::new();
+    let mut root_end = None;
+    let mut root_closed = false;
+
+    // the namespaces declared in the input and those rendered in the output
+    let mut declared = Vec::<Declaration>::new();
+    let mut rendered = Vec::<Declaration>::new();
+
+    let mut depth = 0;
+    // the depth of the enveloped signature, while inside it
+    let mut signature = None;
+    // the depth of the element being canonicalized in `Subset::Signature`, while inside it
+    let mut apex = None;
+    let mut found = false;
+
+    loop {
+        buf.clear();
+        let event = reader.read_event(&mut buf)?;
+        let visible = match subset {
+            Subset::Document => signature.is_none(),
+            Subset::Signature(_) => apex.is_some(),
+        };
+
+        match event {
+            Event::Start(ref element) | Event::Empty(ref element) => {
+                depth += 1;
+
+                let mut attrs = Vec::new();
+                for attr in element.attributes() {
+                    let attr = attr?;
+                    let key = str::from_utf8(attr.key)?.to_string();
+                    let value = attribute_value(&attr)?;
+                    if key == "xmlns" {
+                        declared.push(Declaration {
+                            depth,
+                            prefix: String::new(),
+                            uri: value,
+                        });
+                    } else if let Some(prefix) = key.strip_prefix("xmlns:") {
+                        declared.push(Declaration {
+                            depth,
+                            prefix: prefix.to_string(),
+                            uri: value,
+                        });
+                    } else {
+                        attrs.push((key, value));
+                    }
+                }
+
+                let name = str::from_utf8(element.name())?;
+                let (prefix, local) = split_name(name);
+                let in_dsig = lookup(&declared, prefix) == Some(DSIG_NAMESPACE);
+
+                if depth == 2 && signature.is_none() && in_dsig && local == "Signature" {
+                    signature = Some(depth);
+                }
+                if let Subset::Signature(target) = subset {
+                    if signature.is_some() && !found && in_dsig && local == target {
+                        apex = Some(depth);
+                        found = true;
+                    }
+                }
+
+                let visible = match subset {
+                    Subset::Document => signature.is_none(),
+                    Subset::Signature(_) => apex.is_some(),
+                };
+                if visible {
+                    write_start(&mut output, name, attrs, depth, &declared, &mut rendered)?;
+                }
+
+                if let Event::Empty(_) = event {
+                    if visible {
+                        if depth == 1 {
+                            root_end = Some(output.len());
+                        }
+                        write_end(&mut output, name);
+                    }
+                    close(
+                        depth,
+                        &mut declared,
+                        &mut rendered,
+                        &mut signature,
+                        &mut apex,
+                    );
+                    depth -= 1;
+                }
+            }
+            Event::End(ref element) => {
+                if visible {
+                    if depth == 1 {
+                        root_end = Some(output.len());
+                    }
+                    write_end(&mut output, str::from_utf8(element.name())?);
+                }
+                close(
+                    depth,
+                    &mut declared,
+                    &mut rendered,
+                    &mut signature,
+                    &mut apex,
+                );
+                depth -= 1;
+                if depth == 0 {
+                    root_closed = true;
+                }
+            }
+            Event::Text(ref text) if visible && depth > 0 => {
+                let text = BytesText::from_escaped(normalize_newlines(text.escaped()));
+                output
+                    .extend_from_slice(escape_text(str::from_utf8(&text.unescaped()?)?).as_bytes());
+            }
+            Event::CData(ref text) if visible && depth > 0 => {
+                let text = normalize_newlines(text.escaped());
+                output.extend_from_slice(escape_text(str::from_utf8(&text)?).as_bytes());
+            }
+            Event::PI(ref pi) if visible => {
+                if depth == 0 && root_closed {
+                    output.push(b'\n');
+                }
+                output.extend_from_slice(b"<?");
+                output.extend_from_slice(pi.escaped());
+                output.extend_from_slice(b"?>");
+                if depth == 0 && !root_closed {
+                    output.push(b'\n');
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(Canonical {
+        xml: output,
+        root_end,
+    })
+}
+
+/// Leave the element at the given depth.
+fn close(
+    depth: usize,
+    declared: &mut Vec<Declaration>,
+    rendered: &mut Vec<Declaration>,
+    signature: &mut Option<usize>,
+    apex: &mut Option<usize>,
+) {
+    declared.retain(|declaration| declaration.depth < depth);
+    rendered.retain(|declaration| declaration.depth < depth);
+    if *apex == Some(depth) {
+        *apex = None;
+    }
+    if *signature == Some(depth) {
+        *signature = None;
+    }
+}
+
+/// Write a start tag, declaring the namespaces it visibly uses that are not already in scope
+/// in the output.
+fn write_start(
+    output: &mut Vec<u8>,
+    name: &str,
+    mut attrs: Vec<(String, String)>,
+    depth: usize,
+    declared: &[Declaration],
+    rendered: &mut Vec<Declaration>,
+) -> Result<(), Error> {
+    let mut prefixes = vec![split_name(name).0];
+    for (key, _) in &attrs {
+        let (prefix, _) = split_name(key);
+        if !prefix.is_empty() && prefix != "xml" && !prefixes.contains(&prefix) {
+            prefixes.push(prefix);
+        }
+    }
+    prefixes.sort_unstable();
+
+    let mut declarations = Vec::new();
+    for prefix in prefixes {
+        let uri = lookup(declared, prefix);
+        let current = lookup(rendered, prefix);
+        // an unprefixed element outside the default namespace undeclares an inherited one
+        let needed = if prefix.is_empty() {
+            uri.unwrap_or("") != current.unwrap_or("")
+        } else {
+            uri.is_some() && uri != current
+        };
+        if needed {
+            declarations.push(Declaration {
+                depth,
+                prefix: prefix.to_string(),
+                uri: uri.unwrap_or("").to_string(),
+            });
+        }
+    }
+
+    attrs.sort_by_cached_key(|(key, _)| {
+        let (prefix, local) = split_name(key);
+        let uri = match prefix {
+            "" => "",
+            "xml" => XML_NAMESPACE,
+            prefix => lookup(declared, prefix).unwrap_or(""),
+        };
+        (uri.to_string(), local.to_string())
+    });
+
+    output.push(b'<');
+    output.extend_from_slice(name.as_bytes());
+    for declaration in &declarations {
+        if declaration.prefix.is_empty() {
+            output.extend_from_slice(b" xmlns=\"");
+        } else {
+            output.extend_from_slice(b" xmlns:");
+            output.extend_from_slice(declaration.prefix.as_bytes());
+            output.extend_from_slice(b"=\"");
+        }
+        output.extend_from_slice(escape_attribute(&declaration.uri).as_bytes());
+        output.push(b'"');
+    }
+    for (key, value) in &attrs {
+        output.push(b' ');
+        output.extend_from_slice(key.as_bytes());
+        output.extend_from_slice(b"=\"");
+        output.extend_from_slice(escape_attribute(value).as_bytes());
+        output.push(b'"');
+    }
+    output.push(b'>');
+
+    rendered.extend(declarations);
+    Ok(())
+}
+
+fn write_end(output: &mut Vec<u8>, name: &str) {
+    output.extend_from_slice(b"</");
+    output.extend_from_slice(name.as_bytes());
+    output.push(b'>');
+}
+
+/// Return the value of an attribute after normalizing its whitespace and expanding references.
+fn attribute_value(attr: &Attribute) -> Result<String, Error> {
+    let value = normalize_newlines(&attr.value)
+        .into_iter()
+        .map(|b| match b {
+            b'\t' | b'\n' => b' ',
+            b => b,
+        })
+        .collect::<Vec<_>>();
+    let value = BytesText::from_escaped(value).unescaped()?.into_owned();
+    String::from_utf8(value).map_err(|err| Error::Utf8(err.utf8_error()))
+}
+
+/// Replace the line endings `\r\n` and `\r` with `\n`.
+fn normalize_newlines(text: &[u8]) -> Vec<u8> {
+    let mut normalized = Vec::with_capacity(text.len());
+    let mut bytes = text.iter().peekable();
+    while let Some(&b) = bytes.next() {
+        if b == b'\r' {
+            if bytes.peek() == Some(&&b'\n') {
+                bytes.next();
+            }
+            normalized.push(b'\n');
+        } else {
+            normalized.push(b);
+        }
+    }
+    normalized
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\r' => escaped.push_str("&#xD;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' => escaped.push_str("&#x9;"),
+            '\n' => escaped.push_str("&#xA;"),
+            '\r' => escaped.push_str("&#xD;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
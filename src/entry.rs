@@ -1,26 +1,40 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::io::{BufRead, Write};
+use std::ops::Range;
+use std::time::Duration;
 
 use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
 use quick_xml::Error as XmlError;
 use quick_xml::Reader;
 use quick_xml::Writer;
 
 use crate::category::Category;
+use crate::config::{ReadConfig, WriteConfig};
 use crate::content::Content;
-use crate::error::Error;
-use crate::extension::util::{extension_name, parse_extension};
-use crate::extension::ExtensionMap;
-use crate::fromxml::FromXml;
+use crate::error::{DetectedFormat, Error};
+use crate::extension::util::{
+    extension_name, extension_names, find_extension, parse_extension, parse_extension_element,
+    rename_prefix, rename_prefixes,
+};
+use crate::extension::{Extension, ExtensionMap};
+use crate::feed::Feed;
+use crate::fromxml::{FromXml, ReadContext, ATOM_NAMESPACE};
 use crate::link::Link;
 use crate::person::Person;
 use crate::source::Source;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{atom_datetime, atom_text, default_fixed_datetime, FixedDateTime};
+use crate::toxml::{write_children, ToXml, WriteContext, WriterExt};
+use crate::util::{
+    absolutize_urls, collect_attrs, count_words, default_fixed_datetime, markup_to_text,
+    normalize_encoding, resolve_iri, to_utc, FixedDateTime, Fnv64, IntoOption,
+};
+use crate::writer::CountingWriter;
 
 /// Represents an entry in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq, Builder)]
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
 #[builder(setter(into), default)]
 pub struct Entry {
     /// A human-readable title for the entry.
@@ -49,9 +63,109 @@ pub struct Entry {
     content: Option<Content>,
     /// The extensions for this entry.
     extensions: ExtensionMap,
+    /// Additional attributes of the element that are not otherwise represented.
+    attrs: BTreeMap<String, String>,
+    /// Child elements that are not otherwise represented.
+    #[builder(setter(skip))]
+    unknown_elements: Vec<Extension>,
+    /// The names of the child elements in the order they were read.
+    #[builder(setter(skip))]
+    element_order: Vec<String>,
+    /// The range of bytes the entry occupied in the document it was read from.
+    #[builder(setter(skip))]
+    byte_range: Option<Range<usize>>,
 }
 
 impl Entry {
+    /// Create an entry with the given title, id and updated date.
+    ///
+    /// Every Atom entry must have these elements, so prefer this to `Default` when building an
+    /// entry to publish.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, FixedDateTime};
+    ///
+    /// let updated: FixedDateTime = "2017-06-03T15:15:44-05:00".parse().unwrap();
+    /// let entry = Entry::new("Entry Title", "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6", updated);
+    /// assert_eq!(entry.title(), "Entry Title");
+    /// assert_eq!(entry.updated(), &updated);
+    /// ```
+    pub fn new<T, I, U>(title: T, id: I, updated: U) -> Self
+    where
+        T: Into<String>,
+        I: Into<String>,
+        U: Into<FixedDateTime>,
+    {
+        Entry {
+            title: title.into(),
+            id: id.into(),
+            updated: updated.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Attempt to write this entry as a standalone Atom entry document.
+    ///
+    /// Like [`Feed::write_to`](struct.Feed.html#method.write_to), the output is buffered and
+    /// flushed once the entry has been written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_title("Entry Title");
+    ///
+    /// let xml = String::from_utf8(entry.write_to(Vec::new()).unwrap()).unwrap();
+    /// assert!(xml.starts_with(r#"<entry xmlns="http://www.w3.org/2005/Atom"><title>"#));
+    /// ```
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<W, Error> {
+        self.write_with_config(writer, &WriteConfig::default())
+    }
+
+    /// Attempt to write this entry to a writer using the given configuration.
+    ///
+    /// Unless the configuration asks for a fragment, the `entry` element declares the Atom
+    /// namespace and the namespaces registered with the configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, WriteConfig};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_title("Entry Title");
+    ///
+    /// let config = WriteConfig::new().fragment(true);
+    /// let xml = String::from_utf8(entry.write_with_config(Vec::new(), &config).unwrap()).unwrap();
+    /// assert!(xml.starts_with("<entry><title>Entry Title</title>"));
+    /// ```
+    pub fn write_with_config<W: Write>(&self, writer: W, config: &WriteConfig) -> Result<W, Error> {
+        let mut writer = match config.get_indent() {
+            Some((indent_char, size)) => {
+                Writer::new_with_indent(CountingWriter::buffered(writer), indent_char, size)
+            }
+            None => Writer::new(CountingWriter::buffered(writer)),
+        };
+        let ctx = WriteContext::new(config.clone());
+
+        if config.is_fragment() {
+            self.write_element(&mut writer, None, &ctx)?;
+        } else {
+            if config.is_declaration() {
+                let decl = BytesDecl::new(b"1.0", Some(b"utf-8"), None);
+                writer.write_event(Event::Decl(decl))?;
+            }
+
+            self.write_element(&mut writer, Some(config.namespaces()), &ctx)?;
+        }
+
+        CountingWriter::finish(writer.into_inner()).map(|(writer, _)| writer)
+    }
+
     /// Return the title of this entry.
     ///
     /// # Examples
@@ -67,6 +181,21 @@ impl Entry {
         self.title.as_str()
     }
 
+    /// Return a mutable reference to the title of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.title_mut().push_str("Entry Title");
+    /// assert_eq!(entry.title(), "Entry Title");
+    /// ```
+    pub fn title_mut(&mut self) -> &mut String {
+        &mut self.title
+    }
+
     /// Set the title of this entry.
     ///
     /// # Examples
@@ -84,6 +213,23 @@ impl Entry {
         self.title = title.into();
     }
 
+    /// Set the title of this entry and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let entry = Entry::default().with_title("Entry Title");
+    /// ```
+    pub fn with_title<V>(mut self, title: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_title(title);
+        self
+    }
+
     /// Return the unique URI of this entry.
     ///
     /// # Examples
@@ -99,6 +245,21 @@ impl Entry {
         self.id.as_str()
     }
 
+    /// Return a mutable reference to the unique URI of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.id_mut().push_str("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// assert_eq!(entry.id(), "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// ```
+    pub fn id_mut(&mut self) -> &mut String {
+        &mut self.id
+    }
+
     /// Set the unique URI of this entry.
     ///
     /// # Examples
@@ -116,6 +277,23 @@ impl Entry {
         self.id = id.into();
     }
 
+    /// Set the unique URI of this entry and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let entry = Entry::default().with_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// ```
+    pub fn with_id<V>(mut self, id: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_id(id);
+        self
+    }
+
     /// Return the last time that this entry was modified.
     ///
     /// # Examples
@@ -133,6 +311,23 @@ impl Entry {
         &self.updated
     }
 
+    /// Return a mutable reference to the last time that this entry was modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut entry = Entry::default();
+    /// *entry.updated_mut() = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    /// assert_eq!(entry.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn updated_mut(&mut self) -> &mut FixedDateTime {
+        &mut self.updated
+    }
+
     /// Set the last time that this entry was modified.
     ///
     /// # Examples
@@ -152,6 +347,25 @@ impl Entry {
         self.updated = updated.into();
     }
 
+    /// Set the last time that this entry was modified and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let entry = Entry::default().with_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// ```
+    pub fn with_updated<V>(mut self, updated: V) -> Self
+    where
+        V: Into<FixedDateTime>,
+    {
+        self.set_updated(updated);
+        self
+    }
+
     /// Return the authors of this entry.
     ///
     /// # Examples
@@ -167,6 +381,21 @@ impl Entry {
         self.authors.as_slice()
     }
 
+    /// Return a mutable reference to the authors of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.authors_mut().push(Person::default());
+    /// assert_eq!(entry.authors().len(), 1);
+    /// ```
+    pub fn authors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.authors
+    }
+
     /// Set the authors of this entry.
     ///
     /// # Examples
@@ -184,39 +413,53 @@ impl Entry {
         self.authors = authors.into();
     }
 
-    /// Return the categories this entry belongs to.
+    /// Set the authors of this entry and return it.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Category};
+    /// use atom_syndication::{Entry, Person};
     ///
-    /// let mut entry = Entry::default();
-    /// entry.set_categories(vec![Category::default()]);
-    /// assert_eq!(entry.categories().len(), 1);
+    /// let entry = Entry::default().with_authors(vec![Person::default()]);
     /// ```
-    pub fn categories(&self) -> &[Category] {
-        self.categories.as_slice()
+    pub fn with_authors<V>(mut self, authors: V) -> Self
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.set_authors(authors);
+        self
     }
 
-    /// Set the categories this entry belongs to.
+    /// Return the authors of this entry, applying the inheritance rules of the specification.
+    ///
+    /// An entry without authors inherits those of its source, if it has one, and otherwise
+    /// those of the feed that contains it. An empty slice means the entry has no author, which
+    /// the specification does not allow.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Category};
+    /// use atom_syndication::{Entry, Feed, Person};
     ///
-    /// let mut entry = Entry::default();
-    /// entry.set_categories(vec![Category::default()]);
+    /// let feed = Feed::default().with_authors(vec![Person::default().with_name("John Doe")]);
+    /// let entry = Entry::default();
+    /// assert_eq!(entry.effective_authors(&feed)[0].name(), "John Doe");
+    ///
+    /// let entry = Entry::default().with_authors(vec![Person::default().with_name("Jane Doe")]);
+    /// assert_eq!(entry.effective_authors(&feed)[0].name(), "Jane Doe");
     /// ```
-    pub fn set_categories<V>(&mut self, categories: V)
-    where
-        V: Into<Vec<Category>>,
-    {
-        self.categories = categories.into();
+    pub fn effective_authors<'a>(&'a self, feed: &'a Feed) -> &'a [Person] {
+        if !self.authors.is_empty() {
+            return self.authors();
+        }
+
+        match self.source {
+            Some(ref source) if !source.authors().is_empty() => source.authors(),
+            _ => feed.authors(),
+        }
     }
 
-    /// Return the contributors to this entry.
+    /// Add an author to this entry.
     ///
     /// # Examples
     ///
@@ -224,163 +467,418 @@ impl Entry {
     /// use atom_syndication::{Entry, Person};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_contributors(vec![Person::default()]);
-    /// assert_eq!(entry.contributors().len(), 1);
+    /// entry.push_author(Person::default());
+    /// assert_eq!(entry.authors().len(), 1);
     /// ```
-    pub fn contributors(&self) -> &[Person] {
-        self.contributors.as_slice()
+    pub fn push_author(&mut self, author: Person) {
+        self.authors.push(author);
     }
 
-    /// Set the contributors to this entry.
+    /// Return the categories this entry belongs to.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Person};
+    /// use atom_syndication::{Entry, Category};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_contributors(vec![Person::default()]);
+    /// entry.set_categories(vec![Category::default()]);
+    /// assert_eq!(entry.categories().len(), 1);
     /// ```
-    pub fn set_contributors<V>(&mut self, contributors: V)
-    where
-        V: Into<Vec<Person>>,
-    {
-        self.contributors = contributors.into();
+    pub fn categories(&self) -> &[Category] {
+        self.categories.as_slice()
     }
 
-    /// Return the links for this entry.
+    /// Return the terms of the categories this entry belongs to.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Link};
+    /// use atom_syndication::{Entry, Category};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_links(vec![Link::default()]);
-    /// assert_eq!(entry.links().len(), 1);
+    /// entry.set_categories(vec![Category::new("rust"), Category::new("atom")]);
+    /// assert_eq!(entry.category_terms().collect::<Vec<_>>(), vec!["rust", "atom"]);
     /// ```
-    pub fn links(&self) -> &[Link] {
-        self.links.as_slice()
+    pub fn category_terms(&self) -> impl Iterator<Item = &str> {
+        self.categories.iter().map(Category::term)
     }
 
-    /// Set the links for this entry.
+    /// Return a mutable reference to the categories this entry belongs to.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Link};
+    /// use atom_syndication::{Entry, Category};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_links(vec![Link::default()]);
+    /// entry.categories_mut().push(Category::default());
+    /// assert_eq!(entry.categories().len(), 1);
     /// ```
-    pub fn set_links<V>(&mut self, links: V)
-    where
-        V: Into<Vec<Link>>,
-    {
-        self.links = links.into();
+    pub fn categories_mut(&mut self) -> &mut Vec<Category> {
+        &mut self.categories
     }
 
-    /// Return the time that this entry was initially created or first made available.
+    /// Set the categories this entry belongs to.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Entry;
-    /// use atom_syndication::FixedDateTime;
-    /// use std::str::FromStr;
+    /// use atom_syndication::{Entry, Category};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_published(FixedDateTime::from_str("2017-06-01T15:15:44-05:00").unwrap());
-    /// assert_eq!(entry.published().map(|x|x.to_rfc3339()), Some("2017-06-01T15:15:44-05:00".to_string()));
+    /// entry.set_categories(vec![Category::default()]);
     /// ```
-    pub fn published(&self) -> Option<&FixedDateTime> {
-        self.published.as_ref()
+    pub fn set_categories<V>(&mut self, categories: V)
+    where
+        V: Into<Vec<Category>>,
+    {
+        self.categories = categories.into();
     }
 
-    /// Set the time that this entry was initially created or first made available.
+    /// Set the categories this entry belongs to and return it.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Entry;
-    /// use atom_syndication::FixedDateTime;
-    /// use std::str::FromStr;
+    /// use atom_syndication::{Entry, Category};
     ///
-    /// let mut entry = Entry::default();
-    /// entry.set_published(FixedDateTime::from_str("2017-06-01T15:15:44-05:00").unwrap());
+    /// let entry = Entry::default().with_categories(vec![Category::default()]);
     /// ```
-    pub fn set_published<V>(&mut self, published: V)
+    pub fn with_categories<V>(mut self, categories: V) -> Self
     where
-        V: Into<Option<FixedDateTime>>,
+        V: Into<Vec<Category>>,
     {
-        self.published = published.into();
+        self.set_categories(categories);
+        self
     }
 
-    /// Return the information about the rights held in and over this entry.
+    /// Add a category to this entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Entry;
+    /// use atom_syndication::{Category, Entry};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_rights("© 2017 John Doe".to_string());
-    /// assert_eq!(entry.rights(), Some("© 2017 John Doe"));
+    /// entry.push_category(Category::default());
+    /// assert_eq!(entry.categories().len(), 1);
     /// ```
-    pub fn rights(&self) -> Option<&str> {
-        self.rights.as_ref().map(String::as_str)
+    pub fn push_category(&mut self, category: Category) {
+        self.categories.push(category);
     }
 
-    /// Set the information about the rights held in and over this entry.
+    /// Return the contributors to this entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Entry;
+    /// use atom_syndication::{Entry, Person};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_rights("© 2017 John Doe".to_string());
+    /// entry.set_contributors(vec![Person::default()]);
+    /// assert_eq!(entry.contributors().len(), 1);
     /// ```
-    pub fn set_rights<V>(&mut self, rights: V)
-    where
-        V: Into<Option<String>>,
-    {
-        self.rights = rights.into();
+    pub fn contributors(&self) -> &[Person] {
+        self.contributors.as_slice()
     }
 
-    /// Return the source of this entry if it was copied from another feed.
+    /// Return a mutable reference to the contributors to this entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Source};
+    /// use atom_syndication::{Entry, Person};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_source(Source::default());
-    /// assert!(entry.source().is_some());
+    /// entry.contributors_mut().push(Person::default());
+    /// assert_eq!(entry.contributors().len(), 1);
     /// ```
-    pub fn source(&self) -> Option<&Source> {
-        self.source.as_ref()
+    pub fn contributors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.contributors
     }
 
-    /// Set the source of this entry if it was copied from another feed.
+    /// Set the contributors to this entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Source};
+    /// use atom_syndication::{Entry, Person};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_source(Source::default());
+    /// entry.set_contributors(vec![Person::default()]);
     /// ```
-    pub fn set_source<V>(&mut self, source: V)
+    pub fn set_contributors<V>(&mut self, contributors: V)
     where
-        V: Into<Option<Source>>,
+        V: Into<Vec<Person>>,
     {
-        self.source = source.into()
+        self.contributors = contributors.into();
     }
 
-    /// Return the summary of this entry.
+    /// Set the contributors to this entry and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let entry = Entry::default().with_contributors(vec![Person::default()]);
+    /// ```
+    pub fn with_contributors<V>(mut self, contributors: V) -> Self
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.set_contributors(contributors);
+        self
+    }
+
+    /// Add a contributor to this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.push_contributor(Person::default());
+    /// assert_eq!(entry.contributors().len(), 1);
+    /// ```
+    pub fn push_contributor(&mut self, contributor: Person) {
+        self.contributors.push(contributor);
+    }
+
+    /// Return the links for this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![Link::default()]);
+    /// assert_eq!(entry.links().len(), 1);
+    /// ```
+    pub fn links(&self) -> &[Link] {
+        self.links.as_slice()
+    }
+
+    /// Return a mutable reference to the links for this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.links_mut().push(Link::default());
+    /// assert_eq!(entry.links().len(), 1);
+    /// ```
+    pub fn links_mut(&mut self) -> &mut Vec<Link> {
+        &mut self.links
+    }
+
+    /// Set the links for this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![Link::default()]);
+    /// ```
+    pub fn set_links<V>(&mut self, links: V)
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.links = links.into();
+    }
+
+    /// Set the links for this entry and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let entry = Entry::default().with_links(vec![Link::default()]);
+    /// ```
+    pub fn with_links<V>(mut self, links: V) -> Self
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.set_links(links);
+        self
+    }
+
+    /// Add a link to this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.push_link(Link::default());
+    /// assert_eq!(entry.links().len(), 1);
+    /// ```
+    pub fn push_link(&mut self, link: Link) {
+        self.links.push(link);
+    }
+
+    /// Return the enclosure links for this entry.
+    ///
+    /// Enclosures are links with a relation type of `"enclosure"`, usually pointing to a
+    /// potentially large resource such as an audio or video file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![Link::default()]);
+    /// entry.push_enclosure("http://example.com/audio.mp3", "audio/mpeg", 1000);
+    ///
+    /// let enclosures = entry.enclosures().collect::<Vec<_>>();
+    /// assert_eq!(enclosures.len(), 1);
+    /// assert_eq!(enclosures[0].href(), "http://example.com/audio.mp3");
+    /// ```
+    pub fn enclosures(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(|link| link.rel() == "enclosure")
+    }
+
+    /// Add an enclosure link to this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.push_enclosure("http://example.com/audio.mp3", "audio/mpeg", 1000);
+    ///
+    /// let link = &entry.links()[0];
+    /// assert_eq!(link.rel(), "enclosure");
+    /// assert_eq!(link.mime_type(), Some("audio/mpeg"));
+    /// assert_eq!(link.length(), Some(1000));
+    /// ```
+    pub fn push_enclosure<H, M>(&mut self, href: H, mime_type: M, length: u64)
+    where
+        H: Into<String>,
+        M: Into<String>,
+    {
+        let mut link = Link::default();
+        link.set_href(href);
+        link.set_rel("enclosure");
+        link.set_mime_type(mime_type.into());
+        link.set_length(length);
+        self.links.push(link);
+    }
+
+    /// Return the time that this entry was initially created or first made available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_published(FixedDateTime::from_str("2017-06-01T15:15:44-05:00").unwrap());
+    /// assert_eq!(entry.published().map(|x|x.to_rfc3339()), Some("2017-06-01T15:15:44-05:00".to_string()));
+    /// ```
+    pub fn published(&self) -> Option<&FixedDateTime> {
+        self.published.as_ref()
+    }
+
+    /// Return a mutable reference to the time that this entry was initially created or first made available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut entry = Entry::default();
+    /// *entry.published_mut() = Some(FixedDateTime::from_str("2017-06-01T15:15:44-05:00").unwrap());
+    /// assert_eq!(entry.published().map(|x|x.to_rfc3339()), Some("2017-06-01T15:15:44-05:00".to_string()));
+    /// ```
+    pub fn published_mut(&mut self) -> &mut Option<FixedDateTime> {
+        &mut self.published
+    }
+
+    /// Set the time that this entry was initially created or first made available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_published(FixedDateTime::from_str("2017-06-01T15:15:44-05:00").unwrap());
+    /// ```
+    pub fn set_published<V>(&mut self, published: V)
+    where
+        V: Into<Option<FixedDateTime>>,
+    {
+        self.published = published.into();
+    }
+
+    /// Set the time that this entry was initially created or first made available and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let entry = Entry::default().with_published(FixedDateTime::from_str("2017-06-01T15:15:44-05:00").unwrap());
+    /// ```
+    pub fn with_published<V>(mut self, published: V) -> Self
+    where
+        V: Into<Option<FixedDateTime>>,
+    {
+        self.set_published(published);
+        self
+    }
+
+    /// Return the information about the rights held in and over this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_rights("© 2017 John Doe");
+    /// assert_eq!(entry.rights(), Some("© 2017 John Doe"));
+    /// ```
+    pub fn rights(&self) -> Option<&str> {
+        self.rights.as_ref().map(String::as_str)
+    }
+
+    /// Return a mutable reference to the information about the rights held in and over this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// *entry.rights_mut() = Some("© 2017 John Doe".to_string());
+    /// assert_eq!(entry.rights(), Some("© 2017 John Doe"));
+    /// ```
+    pub fn rights_mut(&mut self) -> &mut Option<String> {
+        &mut self.rights
+    }
+
+    /// Set the information about the rights held in and over this entry.
     ///
     /// # Examples
     ///
@@ -388,13 +886,126 @@ impl Entry {
     /// use atom_syndication::Entry;
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_summary("Entry summary.".to_string());
+    /// entry.set_rights("© 2017 John Doe");
+    /// ```
+    pub fn set_rights<V>(&mut self, rights: V)
+    where
+        V: IntoOption<String>,
+    {
+        self.rights = rights.into_option();
+    }
+
+    /// Set the information about the rights held in and over this entry and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let entry = Entry::default().with_rights("© 2017 John Doe");
+    /// ```
+    pub fn with_rights<V>(mut self, rights: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_rights(rights);
+        self
+    }
+
+    /// Return the source of this entry if it was copied from another feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Source};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_source(Source::default());
+    /// assert!(entry.source().is_some());
+    /// ```
+    pub fn source(&self) -> Option<&Source> {
+        self.source.as_ref()
+    }
+
+    /// Return a mutable reference to the source of this entry if it was copied from another feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Source};
+    ///
+    /// let mut entry = Entry::default();
+    /// *entry.source_mut() = Some(Source::default());
+    /// assert!(entry.source().is_some());
+    /// ```
+    pub fn source_mut(&mut self) -> &mut Option<Source> {
+        &mut self.source
+    }
+
+    /// Set the source of this entry if it was copied from another feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Source};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_source(Source::default());
+    /// ```
+    pub fn set_source<V>(&mut self, source: V)
+    where
+        V: Into<Option<Source>>,
+    {
+        self.source = source.into()
+    }
+
+    /// Set the source of this entry if it was copied from another feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Source};
+    ///
+    /// let entry = Entry::default().with_source(Source::default());
+    /// ```
+    pub fn with_source<V>(mut self, source: V) -> Self
+    where
+        V: Into<Option<Source>>,
+    {
+        self.set_source(source);
+        self
+    }
+
+    /// Return the summary of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_summary("Entry summary.");
     /// assert_eq!(entry.summary(), Some("Entry summary."));
     /// ```
     pub fn summary(&self) -> Option<&str> {
         self.summary.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the summary of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// *entry.summary_mut() = Some("Entry summary.".to_string());
+    /// assert_eq!(entry.summary(), Some("Entry summary."));
+    /// ```
+    pub fn summary_mut(&mut self) -> &mut Option<String> {
+        &mut self.summary
+    }
+
     /// Set the summary of this entry.
     ///
     /// # Examples
@@ -403,13 +1014,30 @@ impl Entry {
     /// use atom_syndication::Entry;
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_summary("Entry summary.".to_string());
+    /// entry.set_summary("Entry summary.");
     /// ```
     pub fn set_summary<V>(&mut self, summary: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
+    {
+        self.summary = summary.into_option();
+    }
+
+    /// Set the summary of this entry and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let entry = Entry::default().with_summary("Entry summary.");
+    /// ```
+    pub fn with_summary<V>(mut self, summary: V) -> Self
+    where
+        V: IntoOption<String>,
     {
-        self.summary = summary.into();
+        self.set_summary(summary);
+        self
     }
 
     /// Return the content of this entry.
@@ -423,26 +1051,206 @@ impl Entry {
     /// entry.set_content(Content::default());
     /// assert!(entry.content().is_some());
     /// ```
-    pub fn content(&self) -> Option<&Content> {
-        self.content.as_ref()
+    pub fn content(&self) -> Option<&Content> {
+        self.content.as_ref()
+    }
+
+    /// Return the best available body of this entry as plain text.
+    ///
+    /// This is the content if it is textual, with the markup removed from HTML and XHTML
+    /// content. If the content only references the body by its `src`, that URI is returned
+    /// instead. Otherwise, such as when the content is binary or missing, the summary is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_summary("Entry summary.");
+    /// assert_eq!(entry.body_text().as_deref(), Some("Entry summary."));
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("html");
+    /// content.set_value("<p>Fish &amp; chips</p>");
+    /// entry.set_content(content);
+    /// assert_eq!(entry.body_text().as_deref(), Some("Fish & chips"));
+    /// ```
+    pub fn body_text(&self) -> Option<Cow<'_, str>> {
+        if let Some(ref content) = self.content {
+            match (content.value(), content.src()) {
+                (Some(value), _) => {
+                    let is_markup = |t: &str| {
+                        t == "html"
+                            || t == "xhtml"
+                            || t == "text/html"
+                            || t.ends_with("/xml")
+                            || t.ends_with("+xml")
+                    };
+
+                    match content.content_type() {
+                        None | Some("text") => return Some(Cow::Borrowed(value)),
+                        Some(t) if is_markup(t) => return Some(Cow::Owned(markup_to_text(value))),
+                        Some(t) if t.starts_with("text/") => return Some(Cow::Borrowed(value)),
+                        _ => {}
+                    }
+                }
+                (None, Some(src)) => return Some(Cow::Borrowed(src)),
+                (None, None) => {}
+            }
+        }
+
+        self.summary.as_deref().map(Cow::Borrowed)
+    }
+
+    /// Return the number of words in the body of this entry.
+    ///
+    /// The body is the one returned by [`body_text`](#method.body_text), except that the summary
+    /// is counted when the content is only referenced by its `src`. Chinese, Japanese and Korean
+    /// characters, which are not separated by spaces, each count as a word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content(Content::html("<p>Fish &amp; chips &#8212; <em>again</em>.</p>"));
+    /// assert_eq!(entry.word_count(), 3);
+    /// ```
+    pub fn word_count(&self) -> usize {
+        let text = match self.content {
+            Some(ref content) if content.is_out_of_line() => {
+                self.summary.as_deref().map(Cow::Borrowed)
+            }
+            _ => self.body_text(),
+        };
+
+        text.map_or(0, |text| count_words(&text))
+    }
+
+    /// Return the time it takes to read the body of this entry at the given reading speed.
+    ///
+    /// The time is computed from [`word_count`](#method.word_count) and is not rounded. A speed
+    /// of 200 to 250 words per minute is typical.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content(Content::text("word ".repeat(500)));
+    /// assert_eq!(entry.reading_time(200), Duration::from_secs(150));
+    /// ```
+    pub fn reading_time(&self, words_per_minute: u32) -> Duration {
+        let words = self.word_count() as u64;
+        let words_per_minute = u64::from(words_per_minute.max(1));
+        Duration::from_millis(words * 60_000 / words_per_minute)
+    }
+
+    /// Rewrite the relative URLs in the `href` and `src` attributes of the HTML or XHTML content
+    /// of this entry to absolute URLs.
+    ///
+    /// The URLs are resolved against the `xml:base` of the content and of the entry, which are
+    /// themselves resolved against `base`. Other kinds of content are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content(Content::html(
+    ///     r#"<a href="../about">About</a> <img src="cover.jpg"> <a href="https://example.org/">"#,
+    /// ));
+    /// entry.absolutize_content_urls("http://example.com/posts/1/");
+    ///
+    /// assert_eq!(
+    ///     entry.content().unwrap().value(),
+    ///     Some(concat!(
+    ///         r#"<a href="http://example.com/posts/about">About</a> "#,
+    ///         r#"<img src="http://example.com/posts/1/cover.jpg"> "#,
+    ///         r#"<a href="https://example.org/">"#,
+    ///     ))
+    /// );
+    /// ```
+    pub fn absolutize_content_urls(&mut self, base: &str) {
+        let mut base = base.to_string();
+        if let Some(entry_base) = self.attrs.get("xml:base") {
+            base = resolve_iri(&base, entry_base);
+        }
+
+        let content = match self.content {
+            Some(ref mut content) => content,
+            None => return,
+        };
+        if !matches!(
+            content.content_type(),
+            Some("html") | Some("xhtml") | Some("text/html")
+        ) {
+            return;
+        }
+        if let Some(content_base) = content.attrs().get("xml:base") {
+            base = resolve_iri(&base, content_base);
+        }
+
+        if let Some(value) = content.value_mut() {
+            *value = absolutize_urls(value, &base);
+        }
+    }
+
+    /// Return a mutable reference to the content of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Content};
+    ///
+    /// let mut entry = Entry::default();
+    /// *entry.content_mut() = Some(Content::default());
+    /// assert!(entry.content().is_some());
+    /// ```
+    pub fn content_mut(&mut self) -> &mut Option<Content> {
+        &mut self.content
+    }
+
+    /// Set the content of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Content};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content(Content::default());
+    /// assert!(entry.content().is_some());
+    /// ```
+    pub fn set_content<V>(&mut self, content: V)
+    where
+        V: Into<Option<Content>>,
+    {
+        self.content = content.into();
     }
 
-    /// Set the content of this entry.
+    /// Set the content of this entry and return it.
     ///
     /// # Examples
     ///
     /// ```
     /// use atom_syndication::{Entry, Content};
     ///
-    /// let mut entry = Entry::default();
-    /// entry.set_content(Content::default());
+    /// let entry = Entry::default().with_content(Content::default());
     /// assert!(entry.content().is_some());
     /// ```
-    pub fn set_content<V>(&mut self, content: V)
+    pub fn with_content<V>(mut self, content: V) -> Self
     where
         V: Into<Option<Content>>,
     {
-        self.content = content.into();
+        self.set_content(content);
+        self
     }
 
     /// Return the extensions for this entry.
@@ -474,6 +1282,26 @@ impl Entry {
         &self.extensions
     }
 
+    /// Return a mutable reference to the extensions for this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.extensions_mut()
+    ///     .entry("ext".to_string())
+    ///     .or_insert_with(HashMap::new)
+    ///     .insert("ext:name".to_string(), vec![Extension::default()]);
+    /// assert!(entry.extensions().contains_key("ext"));
+    /// ```
+    pub fn extensions_mut(&mut self) -> &mut ExtensionMap {
+        &mut self.extensions
+    }
+
     /// Set the extensions for this entry.
     ///
     /// # Examples
@@ -491,57 +1319,451 @@ impl Entry {
     {
         self.extensions = extensions.into()
     }
+
+    /// Set the extensions for this entry and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::extension::ExtensionMap;
+    ///
+    /// let entry = Entry::default().with_extensions(ExtensionMap::default());
+    /// ```
+    pub fn with_extensions<V>(mut self, extensions: V) -> Self
+    where
+        V: Into<ExtensionMap>,
+    {
+        self.set_extensions(extensions);
+        self
+    }
+
+    /// Return the additional attributes of this entry.
+    ///
+    /// These are attributes of the element that are not otherwise represented, such as `xml:lang`
+    /// or vendor-specific attributes. They are written back out when serializing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("xml:lang".to_string(), "en".to_string());
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_attrs(attrs);
+    /// assert_eq!(entry.attrs().get("xml:lang").map(String::as_str), Some("en"));
+    /// ```
+    pub fn attrs(&self) -> &BTreeMap<String, String> {
+        &self.attrs
+    }
+
+    /// Return a mutable reference to the additional attributes of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.attrs_mut()
+    ///     .insert("xml:lang".to_string(), "en".to_string());
+    /// assert_eq!(entry.attrs().len(), 1);
+    /// ```
+    pub fn attrs_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.attrs
+    }
+
+    /// Set the additional attributes of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_attrs(BTreeMap::new());
+    /// ```
+    pub fn set_attrs<V>(&mut self, attrs: V)
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.attrs = attrs.into();
+    }
+
+    /// Set the additional attributes of this entry and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Entry;
+    ///
+    /// let entry = Entry::default().with_attrs(BTreeMap::new());
+    /// ```
+    pub fn with_attrs<V>(mut self, attrs: V) -> Self
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.set_attrs(attrs);
+        self
+    }
+
+    /// Return the language of this entry from its `xml:lang` attribute as a BCP 47 language tag.
+    ///
+    /// The case of the subtags is normalized. Returns `None` if the attribute is missing or is
+    /// not a well-formed language tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry
+    ///     .attrs_mut()
+    ///     .insert("xml:lang".to_string(), "EN-gb".to_string());
+    /// assert_eq!(entry.language_tag().unwrap().as_str(), "en-GB");
+    /// ```
+    #[cfg(feature = "language-tags")]
+    pub fn language_tag(&self) -> Option<language_tags::LanguageTag> {
+        self.attrs
+            .get("xml:lang")
+            .and_then(|lang| language_tags::LanguageTag::parse(lang).ok())
+    }
+
+    /// Return the child elements of this entry that are not otherwise represented.
+    ///
+    /// These are only collected when the feed is read with fidelity mode enabled and are written
+    /// back out when serializing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><entry><rank>1</rank></entry></feed>"#;
+    /// let config = ReadConfig::new().fidelity(true);
+    /// let feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    /// assert_eq!(feed.entries()[0].unknown_elements()[0].name(), "rank");
+    /// ```
+    pub fn unknown_elements(&self) -> &[Extension] {
+        self.unknown_elements.as_slice()
+    }
+
+    /// Return the range of bytes this entry occupied in the document it was read from.
+    ///
+    /// The range runs from the `<` of the `entry` start tag to the `>` of its end tag. It is only
+    /// recorded when the feed is read with `ReadConfig::entry_offsets` enabled. Offsets count
+    /// the bytes of the document as it was given, after it has been decompressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let xml = "<feed><title>Feed</title><entry><id>urn:entry:1</id></entry></feed>";
+    /// let config = ReadConfig::new().entry_offsets(true);
+    /// let feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    ///
+    /// let range = feed.entries()[0].byte_range().unwrap();
+    /// assert_eq!(&xml[range], "<entry><id>urn:entry:1</id></entry>");
+    /// ```
+    pub fn byte_range(&self) -> Option<Range<usize>> {
+        self.byte_range.clone()
+    }
+
+    pub(crate) fn set_byte_range(&mut self, byte_range: Range<usize>) {
+        self.byte_range = Some(byte_range);
+    }
+
+    /// Return a key that orders this entry chronologically.
+    ///
+    /// Entries are ordered by their `updated` time, then by their `published` time and finally by
+    /// their id. Timestamps are compared as instants, so entries with different UTC offsets are
+    /// ordered correctly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use std::str::FromStr;
+    /// use atom_syndication::{Entry, FixedDateTime};
+    ///
+    /// let older = Entry::default()
+    ///     .with_id("urn:older")
+    ///     .with_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// let newer = Entry::default()
+    ///     .with_id("urn:newer")
+    ///     .with_updated(FixedDateTime::from_str("2017-06-03T22:00:00+01:00").unwrap());
+    /// assert!(older.date_key() < newer.date_key());
+    ///
+    /// let mut entries = BTreeMap::new();
+    /// entries.insert(newer.date_key(), &newer);
+    /// entries.insert(older.date_key(), &older);
+    /// assert_eq!(entries.values().next().map(|e| e.id()), Some("urn:older"));
+    /// ```
+    pub fn date_key(&self) -> EntryDateKey<'_> {
+        EntryDateKey {
+            updated: self.updated,
+            published: self.published,
+            id: &self.id,
+        }
+    }
+
+    /// Return a hashable key identifying this revision of the entry.
+    ///
+    /// Two entries have the same identity when they share the same id and `updated` time, which
+    /// makes the key suitable for tracking entries that have already been seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use atom_syndication::Entry;
+    ///
+    /// let entry = Entry::default().with_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    ///
+    /// let mut seen = HashSet::new();
+    /// assert!(seen.insert(entry.identity()));
+    /// assert!(!seen.insert(entry.clone().with_title("Entry Title").identity()));
+    /// ```
+    pub fn identity(&self) -> EntryIdentity {
+        EntryIdentity {
+            id: self.id.clone(),
+            updated: self.updated,
+        }
+    }
+
+    /// Return a hash of the id, `updated` time, title and content of this entry.
+    ///
+    /// The fingerprint changes whenever one of these does, so comparing it with a stored
+    /// fingerprint tells whether an entry has actually changed. It is computed with 64-bit
+    /// FNV-1a, which is stable across platforms and versions of this crate, so fingerprints can
+    /// be persisted. The `updated` time is hashed as an instant, so writing it with a different
+    /// UTC offset does not change the fingerprint, and content without a type is hashed as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let entry = Entry::default()
+    ///     .with_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6")
+    ///     .with_title("Entry Title");
+    /// let edited = entry.clone().with_content(Content::text("Entry content"));
+    ///
+    /// assert_eq!(entry.fingerprint(), entry.clone().fingerprint());
+    /// assert_ne!(entry.fingerprint(), edited.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv64::new();
+        hasher.write_str(&self.id);
+        hasher.write(&self.updated.timestamp().to_le_bytes());
+        hasher.write(&self.updated.timestamp_subsec_nanos().to_le_bytes());
+        hasher.write_str(&self.title);
+
+        match self.content {
+            Some(ref content) => {
+                hasher.write(&[1]);
+                hasher.write_str(content.content_type().unwrap_or("text"));
+                hasher.write_option(content.value());
+                hasher.write_option(content.src());
+            }
+            None => hasher.write(&[0]),
+        }
+
+        hasher.finish()
+    }
+
+    /// Set the `updated` time of this entry to its published time if it is missing.
+    ///
+    /// An entry that was read without an `updated` element has the default time of
+    /// 1970-01-01T00:00:00Z, which is treated as missing. Returns whether the time was set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = "<feed><entry><published>2017-06-03T15:15:44-05:00</published></entry></feed>";
+    /// let mut feed = xml.parse::<Feed>().unwrap();
+    /// let entry = &mut feed.entries_mut()[0];
+    ///
+    /// assert!(entry.fill_missing_updated());
+    /// assert_eq!(entry.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn fill_missing_updated(&mut self) -> bool {
+        match self.published {
+            Some(published) if self.updated == default_fixed_datetime() => {
+                self.updated = published;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Convert the dates of this entry, and of its source, to UTC.
+    ///
+    /// The dates keep the instant they represent, and are written with a `+00:00` offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use atom_syndication::{Entry, FixedDateTime};
+    ///
+    /// let mut entry = Entry::default()
+    ///     .with_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// entry.normalize_dates_utc();
+    /// assert_eq!(entry.updated().to_rfc3339(), "2017-06-03T20:15:44+00:00");
+    /// ```
+    pub fn normalize_dates_utc(&mut self) {
+        self.updated = to_utc(&self.updated);
+        self.published = self.published.as_ref().map(to_utc);
+
+        if let Some(ref mut source) = self.source {
+            let updated = to_utc(source.updated());
+            source.set_updated(updated);
+        }
+    }
+}
+
+/// A key for ordering entries chronologically.
+///
+/// Returned by [`Entry::date_key`](struct.Entry.html#method.date_key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntryDateKey<'a> {
+    updated: FixedDateTime,
+    published: Option<FixedDateTime>,
+    id: &'a str,
+}
+
+impl<'a> EntryDateKey<'a> {
+    /// Return the last time the entry was modified.
+    pub fn updated(&self) -> &FixedDateTime {
+        &self.updated
+    }
+
+    /// Return the time the entry was first made available.
+    pub fn published(&self) -> Option<&FixedDateTime> {
+        self.published.as_ref()
+    }
+
+    /// Return the unique URI of the entry.
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+}
+
+/// A hashable key identifying a revision of an entry.
+///
+/// Returned by [`Entry::identity`](struct.Entry.html#method.identity).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntryIdentity {
+    id: String,
+    updated: FixedDateTime,
+}
+
+impl EntryIdentity {
+    /// Return the unique URI of the entry.
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    /// Return the last time the entry was modified.
+    pub fn updated(&self) -> &FixedDateTime {
+        &self.updated
+    }
 }
 
 impl FromXml for Entry {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
         let mut entry = Entry::default();
+        collect_attrs(reader, atts, &mut entry.attrs)?;
         let mut buf = Vec::new();
 
         loop {
             match reader.read_event(&mut buf)? {
-                Event::Start(element) => match element.name() {
-                    b"id" => entry.id = atom_text(reader)?.unwrap_or_default(),
-                    b"title" => entry.title = atom_text(reader)?.unwrap_or_default(),
-                    b"updated" => {
-                        entry.updated =
-                            atom_datetime(reader)?.unwrap_or_else(default_fixed_datetime)
-                    }
-                    b"author" => entry
-                        .authors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    b"category" => entry
-                        .categories
-                        .push(Category::from_xml(reader, element.attributes())?),
-                    b"contributor" => entry
-                        .contributors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    b"link" => entry
-                        .links
-                        .push(Link::from_xml(reader, element.attributes())?),
-                    b"published" => entry.published = atom_datetime(reader)?,
-                    b"rights" => entry.rights = atom_text(reader)?,
-                    b"source" => {
-                        entry.source = Some(Source::from_xml(reader, element.attributes())?)
-                    }
-                    b"summary" => entry.summary = atom_text(reader)?,
-                    b"content" => {
-                        entry.content = Some(Content::from_xml(reader, element.attributes())?)
+                Event::Start(element) => {
+                    let name = reader.decode(ctx.atom_name(element.name())).into_owned();
+                    ctx.enter(name.as_str());
+
+                    if ctx.config.is_fidelity() {
+                        entry.element_order.push(name);
                     }
-                    n => {
-                        if let Some((ns, name)) = extension_name(element.name()) {
-                            parse_extension(
-                                reader,
-                                element.attributes(),
-                                ns,
-                                name,
-                                &mut entry.extensions,
-                            )?;
-                        } else {
-                            reader.read_to_end(n, &mut Vec::new())?;
+
+                    match ctx.atom_name(element.name()) {
+                        b"id" => entry.id = ctx.read_text(reader)?.unwrap_or_default(),
+                        b"title" => entry.title = ctx.read_text(reader)?.unwrap_or_default(),
+                        b"updated" => {
+                            entry.updated = ctx
+                                .read_date(reader, "updated")?
+                                .unwrap_or_else(default_fixed_datetime)
+                        }
+                        b"author" => {
+                            entry
+                                .authors
+                                .push(Person::from_xml(reader, element.attributes(), ctx)?)
+                        }
+                        b"category" => entry.categories.push(Category::from_xml(
+                            reader,
+                            element.attributes(),
+                            ctx,
+                        )?),
+                        b"contributor" => entry.contributors.push(Person::from_xml(
+                            reader,
+                            element.attributes(),
+                            ctx,
+                        )?),
+                        b"link" => {
+                            entry
+                                .links
+                                .push(Link::from_xml(reader, element.attributes(), ctx)?)
+                        }
+                        b"published" => entry.published = ctx.read_date(reader, "published")?,
+                        b"rights" => entry.rights = ctx.read_text(reader)?,
+                        b"source" => {
+                            entry.source =
+                                Some(Source::from_xml(reader, element.attributes(), ctx)?)
+                        }
+                        b"summary" => entry.summary = ctx.read_text(reader)?,
+                        b"content" => {
+                            entry.content =
+                                Some(Content::from_xml(reader, element.attributes(), ctx)?)
+                        }
+                        _ => {
+                            if let Some((ns, name)) = extension_name(ctx.atom_name(element.name()))
+                            {
+                                parse_extension(
+                                    reader,
+                                    element.attributes(),
+                                    ns,
+                                    name,
+                                    &mut entry.extensions,
+                                    ctx.config.get_max_depth(),
+                                )?;
+                            } else if ctx.config.is_fidelity() {
+                                entry.unknown_elements.push(parse_extension_element(
+                                    reader,
+                                    element.attributes(),
+                                    ctx.config.get_max_depth(),
+                                )?);
+                            } else {
+                                ctx.skip(reader, element.name())?;
+                            }
                         }
                     }
-                },
+
+                    ctx.leave();
+                }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
                 _ => {}
@@ -550,54 +1772,222 @@ impl FromXml for Entry {
             buf.clear();
         }
 
+        if let Some(policy) = ctx.config.get_link_policy() {
+            entry.enforce_link_policy(policy);
+        }
+
         Ok(entry)
     }
 }
 
 impl ToXml for Entry {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>, ctx: &WriteContext) -> Result<(), XmlError> {
+        self.write_element(writer, None, ctx)
+    }
+}
+
+impl Entry {
+    /// Write the `entry` element, declaring the Atom namespace and the given namespaces if this
+    /// is the root element of a document.
+    fn write_element<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        namespaces: Option<&BTreeMap<String, String>>,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError> {
         let name = b"entry";
-        writer.write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
-        writer.write_text_element(b"title", &*self.title)?;
-        writer.write_text_element(b"id", &*self.id)?;
-        writer.write_text_element(b"updated", &*self.updated.to_rfc3339())?;
-        writer.write_objects_named(&self.authors, "author")?;
-        writer.write_objects(&self.categories)?;
-        writer.write_objects_named(&self.contributors, "contributor")?;
-        writer.write_objects(&self.links)?;
-
-        if let Some(ref published) = self.published {
-            writer.write_text_element(b"published", &published.to_rfc3339())?;
-        }
+        let mut element = BytesStart::borrowed(name, name.len());
+
+        if let Some(namespaces) = namespaces {
+            element.push_attribute(("xmlns", "http://www.w3.org/2005/Atom"));
 
-        if let Some(ref rights) = self.rights {
-            writer.write_text_element(b"rights", &**rights)?;
+            for (ns, uri) in namespaces {
+                element.push_attribute((format!("xmlns:{}", ns).as_bytes(), uri.as_bytes()));
+            }
         }
 
-        if let Some(ref source) = self.source {
-            writer.write_object(source)?;
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        writer.write_event(Event::Start(element))?;
+
+        let mut defaults = [
+            "title",
+            "id",
+            "updated",
+            "author",
+            "category",
+            "contributor",
+            "link",
+            "published",
+            "rights",
+            "source",
+            "summary",
+            "content",
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+        defaults.extend(extension_names(&self.extensions));
+        defaults.extend(self.unknown_elements.iter().map(|e| e.name().to_string()));
+
+        write_children(
+            writer,
+            &self.element_order,
+            &defaults,
+            |writer, name, index| self.write_child(writer, name, index, ctx),
+        )?;
+
+        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+
+        Ok(())
+    }
+
+    pub(crate) fn rename_prefixes(&mut self, renames: &HashMap<String, String>) {
+        rename_prefixes(&mut self.extensions, renames);
+
+        for link in &mut self.links {
+            link.rename_prefixes(renames);
         }
 
-        if let Some(ref summary) = self.summary {
-            writer.write_text_element(b"summary", &**summary)?;
+        if let Some(ref mut source) = self.source {
+            source.rename_prefixes(renames);
         }
 
-        if let Some(ref content) = self.content {
-            writer.write_object(content)?;
+        for name in &mut self.element_order {
+            if let Some(renamed) = rename_prefix(name, renames) {
+                *name = renamed;
+            }
         }
+    }
+
+    fn write_child<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        name: &str,
+        index: usize,
+        ctx: &WriteContext,
+    ) -> Result<bool, XmlError> {
+        match name {
+            "title" if index == 0 => writer.write_text_construct(b"title", &*self.title, ctx)?,
+            "id" if index == 0 => writer.write_text_element(b"id", &*self.id)?,
+            "updated" if index == 0 => {
+                writer.write_text_element(b"updated", &*self.updated.to_rfc3339())?
+            }
+            "author" => match self.authors.get(index) {
+                Some(author) => writer.write_object_named(author, "author", ctx)?,
+                None => return Ok(false),
+            },
+            "category" => match self.categories.get(index) {
+                Some(category) => writer.write_object(category, ctx)?,
+                None => return Ok(false),
+            },
+            "contributor" => match self.contributors.get(index) {
+                Some(contributor) => writer.write_object_named(contributor, "contributor", ctx)?,
+                None => return Ok(false),
+            },
+            "link" => match self.links.get(index) {
+                Some(link) => writer.write_object(link, ctx)?,
+                None => return Ok(false),
+            },
+            "published" => match self.published.iter().nth(index) {
+                Some(published) => {
+                    writer.write_text_element(b"published", &*published.to_rfc3339())?
+                }
+                None => return Ok(false),
+            },
+            "rights" => match self.rights.iter().nth(index) {
+                Some(rights) => writer.write_text_construct(b"rights", &**rights, ctx)?,
+                None => return Ok(false),
+            },
+            "source" => match self.source.iter().nth(index) {
+                Some(source) => writer.write_object(source, ctx)?,
+                None => return Ok(false),
+            },
+            "summary" => match self.summary.iter().nth(index) {
+                Some(summary) => writer.write_text_construct(b"summary", &**summary, ctx)?,
+                None => return Ok(false),
+            },
+            "content" => match self.content.iter().nth(index) {
+                Some(content) => writer.write_object(content, ctx)?,
+                None => return Ok(false),
+            },
+            "title" | "id" | "updated" => return Ok(false),
+            name => {
+                let element = match extension_name(name.as_bytes()) {
+                    Some(_) => find_extension(&self.extensions, name, index),
+                    None => self
+                        .unknown_elements
+                        .iter()
+                        .filter(|element| element.name() == name)
+                        .nth(index),
+                };
 
-        for map in self.extensions.values() {
-            for extensions in map.values() {
-                writer.write_objects(extensions)?;
+                match element {
+                    Some(element) => writer.write_object(element, ctx)?,
+                    None => return Ok(false),
+                }
             }
         }
 
-        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        Ok(true)
+    }
+}
 
-        Ok(())
+impl TryFrom<&str> for Entry {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Error> {
+        read_entry_document(s.as_bytes(), &mut ReadContext::new(ReadConfig::default()))
     }
 }
 
+impl TryFrom<&[u8]> for Entry {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        read_entry_document(bytes, &mut ReadContext::new(ReadConfig::default()))
+    }
+}
+
+/// Read an entry document, whose root is an `entry` element.
+pub(crate) fn read_entry_document(xml: &[u8], ctx: &mut ReadContext) -> Result<Entry, Error> {
+    let xml = normalize_encoding(xml);
+    let mut reader = Reader::from_reader(&*xml);
+    reader.expand_empty_elements(true);
+
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Start(element) => {
+                for attr in element.attributes().with_checks(false).flatten() {
+                    if attr.key.starts_with(b"xmlns:") && &*attr.value == ATOM_NAMESPACE {
+                        ctx.atom_prefix = Some(attr.key[6..].to_vec());
+                    }
+                }
+
+                if ctx.atom_name(element.name()) != b"entry" {
+                    return Err(Error::WrongFormat {
+                        detected: DetectedFormat::from_root(element.name()),
+                    });
+                }
+
+                ctx.enter("entry");
+                return Entry::from_xml(&mut reader, element.attributes(), ctx)
+                    .map_err(|err| ctx.context(err));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    Err(Error::MissingElement {
+        name: "entry".to_string(),
+        path: "/".to_string(),
+    })
+}
+
 impl Default for Entry {
     fn default() -> Self {
         Entry {
@@ -614,6 +2004,10 @@ impl Default for Entry {
             summary: None,
             content: None,
             extensions: ExtensionMap::default(),
+            attrs: BTreeMap::new(),
+            unknown_elements: Vec::new(),
+            element_order: Vec::new(),
+            byte_range: None,
         }
     }
 }
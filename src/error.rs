@@ -1,24 +1,238 @@
+use std::fmt;
+use std::io;
 use std::str::Utf8Error;
 
 use quick_xml::Error as XmlError;
 use thiserror::Error;
 
+use crate::content::is_not_loaded;
+use crate::limit::exceeded_limit;
+
 #[derive(Debug, Error)]
+#[non_exhaustive]
 /// An error that occurred while performing an Atom operation.
 pub enum Error {
     /// Unable to parse XML.
     #[error("{0}")]
-    Xml(#[from] XmlError),
+    Xml(#[source] XmlError),
+    /// An I/O error occurred.
+    #[error("{0}")]
+    Io(#[source] io::Error),
+    /// Unable to decode base64 content.
+    #[error("{0}")]
+    Base64(#[from] base64::DecodeError),
     /// Unable to parse UTF8 in to a string.
     #[error("{0}")]
     Utf8(#[from] Utf8Error),
-    /// Input did not begin with an opening feed tag.
-    #[error("input did not begin with an opening feed tag")]
-    InvalidStartTag,
+    /// The input is a document other than an Atom feed.
+    #[error("expected an Atom feed but found {detected}")]
+    WrongFormat {
+        /// The kind of document that was found instead.
+        detected: DetectedFormat,
+    },
+    /// The document is nested more deeply than the configured limit.
+    #[error("document exceeds the maximum nesting depth")]
+    TooDeep,
+    /// The document is larger than the configured limit.
+    #[error("document exceeds the maximum size of {limit} bytes")]
+    TooLarge {
+        /// The maximum number of bytes that could be read.
+        limit: u64,
+    },
     /// Unexpected end of input.
     #[error("unexpected end of input")]
     Eof,
-    /// The format of the timestamp is wrong.
-    #[error("timestamps must be formatted by RFC3339, rather than {0}")]
-    WrongDatetime(String),
+    /// A required element is missing.
+    #[error("missing element {name} in {path}")]
+    MissingElement {
+        /// The name of the missing element.
+        name: String,
+        /// The path of the element it is missing from, such as `/feed/entry`.
+        path: String,
+    },
+    /// A date is not formatted as described by RFC 3339.
+    #[error("dates must be formatted as described by RFC 3339, rather than {value}")]
+    InvalidDate {
+        /// The malformed date.
+        value: String,
+    },
+    /// The value of an attribute is malformed.
+    #[error("invalid value for attribute {name}: {value}")]
+    InvalidAttribute {
+        /// The name of the attribute.
+        name: String,
+        /// The malformed value.
+        value: String,
+    },
+    /// The email address is malformed.
+    #[error("invalid email address: {0}")]
+    InvalidEmail(String),
+    /// The URI is not a valid absolute IRI.
+    #[error("invalid URI: {0}")]
+    InvalidUri(String),
+    /// The language tag is malformed.
+    #[error("invalid language tag: {0}")]
+    InvalidLanguageTag(String),
+    /// The value of an extension element could not be converted to the requested type.
+    #[error("invalid extension value: {0}")]
+    InvalidExtension(String),
+    /// Content read with `ReadConfig::lazy_content` was written before its value was loaded.
+    #[error("content must be loaded with Content::load before it is written")]
+    ContentNotLoaded,
+    /// Byte offsets were requested for a UTF-16 document, which is converted to UTF-8 before
+    /// it is read.
+    #[error("byte offsets cannot be recorded for UTF-16 documents")]
+    OffsetsUnsupported,
+    /// An AtomPub server responded with an unexpected HTTP status.
+    #[cfg(feature = "atompub")]
+    #[error("unexpected HTTP status {status}")]
+    HttpStatus {
+        /// The status code of the response.
+        status: u16,
+    },
+    /// An AtomPub server response is missing a required header.
+    #[cfg(feature = "atompub")]
+    #[error("missing HTTP header {0}")]
+    MissingHeader(String),
+    /// The signature of a document is missing or does not match it.
+    #[cfg(feature = "dsig")]
+    #[error("invalid signature: {0}")]
+    InvalidSignature(String),
+    /// An error occurred while reading a particular element of a feed.
+    #[error("{source} at {path}")]
+    Context {
+        /// The path of the element being read, such as `/feed/entry[2]/updated`.
+        path: String,
+        /// The index of the entry being read, if any.
+        entry: Option<usize>,
+        /// The underlying error.
+        #[source]
+        source: Box<Error>,
+    },
+}
+
+impl From<XmlError> for Error {
+    fn from(err: XmlError) -> Self {
+        if let XmlError::Io(ref io) = err {
+            if let Some(limit) = exceeded_limit(io) {
+                return Error::TooLarge { limit };
+            }
+            if is_not_loaded(io) {
+                return Error::ContentNotLoaded;
+            }
+        }
+
+        Error::Xml(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        match exceeded_limit(&err) {
+            Some(limit) => Error::TooLarge { limit },
+            None => Error::Io(err),
+        }
+    }
+}
+
+impl Error {
+    /// Return the path of the element being read when this error occurred, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed><entry><updated>yesterday</updated></entry></feed>"#;
+    /// let err = xml.parse::<Feed>().unwrap_err();
+    /// assert_eq!(err.path(), Some("/feed/entry[0]/updated"));
+    /// ```
+    pub fn path(&self) -> Option<&str> {
+        match *self {
+            Error::Context { ref path, .. } => Some(path.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the index of the entry being read when this error occurred, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed><entry></entry><entry><updated>yesterday</updated></entry></feed>"#;
+    /// let err = xml.parse::<Feed>().unwrap_err();
+    /// assert_eq!(err.entry_index(), Some(1));
+    /// ```
+    pub fn entry_index(&self) -> Option<usize> {
+        match *self {
+            Error::Context { entry, .. } => entry,
+            _ => None,
+        }
+    }
+
+    /// Return the underlying error, without the position it occurred at.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Error, Feed};
+    ///
+    /// let xml = r#"<feed><updated>yesterday</updated></feed>"#;
+    /// let err = xml.parse::<Feed>().unwrap_err();
+    /// match err.without_context() {
+    ///     Error::InvalidDate { value } => assert_eq!(value, "yesterday"),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn without_context(self) -> Error {
+        match self {
+            Error::Context { source, .. } => *source,
+            err => err,
+        }
+    }
+}
+
+/// The kind of document found when reading something other than an Atom feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetectedFormat {
+    /// An RSS 2.0 (or 0.9x) feed, with an `rss` root element.
+    Rss2,
+    /// An RSS 1.0 feed, with an `rdf:RDF` root element.
+    Rss1,
+    /// An HTML document.
+    Html,
+    /// Any other document.
+    Unknown,
+}
+
+impl DetectedFormat {
+    pub(crate) fn from_root(name: &[u8]) -> Self {
+        let local_name = match name.iter().position(|&b| b == b':') {
+            Some(colon) => &name[colon + 1..],
+            None => name,
+        };
+
+        if name.eq_ignore_ascii_case(b"html") {
+            DetectedFormat::Html
+        } else if name == b"rss" {
+            DetectedFormat::Rss2
+        } else if local_name == b"RDF" {
+            DetectedFormat::Rss1
+        } else {
+            DetectedFormat::Unknown
+        }
+    }
+}
+
+impl fmt::Display for DetectedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DetectedFormat::Rss2 => f.write_str("an RSS 2.0 feed"),
+            DetectedFormat::Rss1 => f.write_str("an RSS 1.0 feed"),
+            DetectedFormat::Html => f.write_str("an HTML document"),
+            DetectedFormat::Unknown => f.write_str("an unknown document"),
+        }
+    }
 }
@@ -1,21 +1,28 @@
 use std::collections::HashMap;
-use std::io::Write;
+use std::io::{BufRead, Write};
 use std::str;
 
+use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Writer;
+use quick_xml::{Reader, Writer};
 
-use crate::toxml::ToXml;
+use crate::error::Error;
+use crate::fromxml::{FromXml, ReadContext};
+use crate::toxml::{ToXml, WriteContext};
+use crate::util::IntoOption;
 
+mod typed;
 pub(crate) mod util;
 
+pub use self::typed::{EntryExt, ExtensionValue};
+
 /// A map of extension namespace prefixes to local names to elements.
 pub type ExtensionMap = HashMap<String, HashMap<String, Vec<Extension>>>;
 
 /// A namespaced extension.
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Builder)]
 #[builder(setter(into), default)]
 pub struct Extension {
     /// The qualified name of the extension element.
@@ -44,6 +51,21 @@ impl Extension {
         self.name.as_str()
     }
 
+    /// Return a mutable reference to the qualified name of this extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut extension = Extension::default();
+    /// extension.name_mut().push_str("ext:name");
+    /// assert_eq!(extension.name(), "ext:name");
+    /// ```
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+
     /// Set the qualified name of this extension.
     ///
     /// # Examples
@@ -69,13 +91,28 @@ impl Extension {
     /// use atom_syndication::extension::Extension;
     ///
     /// let mut extension = Extension::default();
-    /// extension.set_value("John Doe".to_string());
+    /// extension.set_value("John Doe");
     /// assert_eq!(extension.value(), Some("John Doe"));
     /// ```
     pub fn value(&self) -> Option<&str> {
         self.value.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the text content of this extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut extension = Extension::default();
+    /// *extension.value_mut() = Some("John Doe".to_string());
+    /// assert_eq!(extension.value(), Some("John Doe"));
+    /// ```
+    pub fn value_mut(&mut self) -> &mut Option<String> {
+        &mut self.value
+    }
+
     /// Set the text content of this extension.
     ///
     /// # Examples
@@ -84,13 +121,13 @@ impl Extension {
     /// use atom_syndication::extension::Extension;
     ///
     /// let mut extension = Extension::default();
-    /// extension.set_value("John Doe".to_string());
+    /// extension.set_value("John Doe");
     /// ```
     pub fn set_value<V>(&mut self, value: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.value = value.into();
+        self.value = value.into_option();
     }
 
     /// Return the attributes for the extension element.
@@ -111,6 +148,23 @@ impl Extension {
         &self.attrs
     }
 
+    /// Return a mutable reference to the attributes for the extension element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut extension = Extension::default();
+    /// extension
+    ///     .attrs_mut()
+    ///     .insert("email".to_string(), "johndoe@example.com".to_string());
+    /// assert_eq!(extension.attrs().len(), 1);
+    /// ```
+    pub fn attrs_mut(&mut self) -> &mut HashMap<String, String> {
+        &mut self.attrs
+    }
+
     /// Set the attributes for the extension element.
     ///
     /// # Examples
@@ -149,6 +203,25 @@ impl Extension {
         &self.children
     }
 
+    /// Return a mutable reference to the children of the extension element.
+    ///
+    /// A map of local names to child elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut extension = Extension::default();
+    /// extension
+    ///     .children_mut()
+    ///     .insert("ext:child".to_string(), Vec::new());
+    /// assert!(extension.children().contains_key("ext:child"));
+    /// ```
+    pub fn children_mut(&mut self) -> &mut HashMap<String, Vec<Extension>> {
+        &mut self.children
+    }
+
     /// Set the children of the extension element.
     ///
     /// A map of local names to child elements.
@@ -170,19 +243,33 @@ impl Extension {
     }
 }
 
+impl FromXml for Extension {
+    fn from_xml<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
+        self::util::parse_extension_element(reader, atts, ctx.config.get_max_depth())
+    }
+}
+
 impl ToXml for Extension {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>, ctx: &WriteContext) -> Result<(), XmlError> {
         let name = self.name.as_bytes();
         let mut element = BytesStart::borrowed(name, name.len());
-        element.extend_attributes(self.attrs.iter().map(|a| (a.0.as_bytes(), a.1.as_bytes())));
+        let mut attrs = self.attrs.iter().collect::<Vec<_>>();
+        attrs.sort();
+        element.extend_attributes(attrs.into_iter().map(|a| (a.0.as_str(), a.1.as_str())));
         writer.write_event(Event::Start(element))?;
 
         if let Some(value) = self.value.as_ref() {
             writer.write_event(Event::Text(BytesText::from_escaped(value.as_bytes())))?;
         }
 
-        for extension in self.children.values().flatten() {
-            extension.to_xml(writer)?;
+        let mut children = self.children.iter().collect::<Vec<_>>();
+        children.sort_by_key(|&(name, _)| name);
+        for extension in children.into_iter().flat_map(|(_, items)| items) {
+            extension.to_xml(writer, ctx)?;
         }
 
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
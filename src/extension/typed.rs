@@ -0,0 +1,165 @@
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::extension::Extension;
+use crate::util::FixedDateTime;
+
+/// A value that can be read from and written to an extension element.
+///
+/// Implement this trait to define a typed view over the extension elements of a namespace, then
+/// access them through [`EntryExt`].
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::extension::{Extension, ExtensionValue};
+/// use atom_syndication::Error;
+///
+/// struct Rating(u8);
+///
+/// impl ExtensionValue for Rating {
+///     fn from_extension(extension: &Extension) -> Result<Self, Error> {
+///         u8::from_extension(extension).map(Rating)
+///     }
+///
+///     fn to_extension(&self) -> Extension {
+///         self.0.to_extension()
+///     }
+/// }
+/// ```
+pub trait ExtensionValue: Sized {
+    /// Read a value from an extension element.
+    fn from_extension(extension: &Extension) -> Result<Self, Error>;
+
+    /// Write the value to a new extension element. The name of the element is set by the caller.
+    fn to_extension(&self) -> Extension;
+}
+
+impl ExtensionValue for String {
+    fn from_extension(extension: &Extension) -> Result<Self, Error> {
+        Ok(extension.value().unwrap_or_default().to_string())
+    }
+
+    fn to_extension(&self) -> Extension {
+        let mut extension = Extension::default();
+        extension.set_value(self.as_str());
+        extension
+    }
+}
+
+impl ExtensionValue for FixedDateTime {
+    fn from_extension(extension: &Extension) -> Result<Self, Error> {
+        let value = extension.value().unwrap_or_default();
+        FixedDateTime::parse_from_rfc3339(value).map_err(|_| Error::InvalidDate {
+            value: value.into(),
+        })
+    }
+
+    fn to_extension(&self) -> Extension {
+        let mut extension = Extension::default();
+        extension.set_value(self.to_rfc3339());
+        extension
+    }
+}
+
+macro_rules! impl_extension_value {
+    ($($ty:ty),*) => {
+        $(
+            impl ExtensionValue for $ty {
+                fn from_extension(extension: &Extension) -> Result<Self, Error> {
+                    let value = extension.value().unwrap_or_default();
+                    value
+                        .parse()
+                        .map_err(|_| Error::InvalidExtension(value.to_string()))
+                }
+
+                fn to_extension(&self) -> Extension {
+                    let mut extension = Extension::default();
+                    extension.set_value(self.to_string());
+                    extension
+                }
+            }
+        )*
+    };
+}
+
+impl_extension_value!(bool, i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+/// Typed access to the extension elements of an entry.
+///
+/// Extensions are looked up by namespace prefix and local name, and converted with
+/// [`ExtensionValue`].
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::extension::EntryExt;
+/// use atom_syndication::Entry;
+///
+/// let mut entry = Entry::default();
+/// entry.set_extension("media", "rating", &5u32);
+/// assert_eq!(entry.extension::<u32>("media", "rating").unwrap(), Some(5));
+/// ```
+pub trait EntryExt {
+    /// Return the first extension element with the given prefix and name as a typed value.
+    ///
+    /// Returns `Ok(None)` if there is no such element and an error if it cannot be converted.
+    fn extension<T: ExtensionValue>(&self, prefix: &str, name: &str) -> Result<Option<T>, Error>;
+
+    /// Return all of the extension elements with the given prefix and name as typed values.
+    fn extension_values<T: ExtensionValue>(
+        &self,
+        prefix: &str,
+        name: &str,
+    ) -> Result<Vec<T>, Error>;
+
+    /// Replace the extension elements with the given prefix and name with a single typed value.
+    fn set_extension<T: ExtensionValue>(&mut self, prefix: &str, name: &str, value: &T);
+
+    /// Append a typed value as an extension element with the given prefix and name.
+    fn push_extension<T: ExtensionValue>(&mut self, prefix: &str, name: &str, value: &T);
+}
+
+impl EntryExt for Entry {
+    fn extension<T: ExtensionValue>(&self, prefix: &str, name: &str) -> Result<Option<T>, Error> {
+        self.extensions()
+            .get(prefix)
+            .and_then(|map| map.get(name))
+            .and_then(|items| items.first())
+            .map(T::from_extension)
+            .transpose()
+    }
+
+    fn extension_values<T: ExtensionValue>(
+        &self,
+        prefix: &str,
+        name: &str,
+    ) -> Result<Vec<T>, Error> {
+        self.extensions()
+            .get(prefix)
+            .and_then(|map| map.get(name))
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .map(T::from_extension)
+            .collect()
+    }
+
+    fn set_extension<T: ExtensionValue>(&mut self, prefix: &str, name: &str, value: &T) {
+        if let Some(map) = self.extensions_mut().get_mut(prefix) {
+            map.remove(name);
+        }
+
+        self.push_extension(prefix, name, value);
+    }
+
+    fn push_extension<T: ExtensionValue>(&mut self, prefix: &str, name: &str, value: &T) {
+        let mut extension = value.to_extension();
+        extension.set_name(format!("{}:{}", prefix, name));
+
+        self.extensions_mut()
+            .entry(prefix.to_string())
+            .or_default()
+            .entry(name.to_string())
+            .or_default()
+            .push(extension);
+    }
+}
@@ -23,13 +23,14 @@ pub fn parse_extension<R>(
     ns: &[u8],
     name: &[u8],
     extensions: &mut ExtensionMap,
+    max_depth: usize,
 ) -> Result<(), Error>
 where
     R: BufRead,
 {
     let ns = str::from_utf8(ns)?;
     let name = str::from_utf8(name)?;
-    let ext = parse_extension_element(reader, atts)?;
+    let ext = parse_extension_element(reader, atts, max_depth)?;
 
     if !extensions.contains_key(ns) {
         extensions.insert(ns.to_string(), HashMap::new());
@@ -54,9 +55,77 @@ where
     Ok(())
 }
 
-fn parse_extension_element<R: BufRead>(
+pub fn extension_names(extensions: &ExtensionMap) -> Vec<String> {
+    let mut names = Vec::new();
+
+    for (ns, map) in extensions {
+        for name in map.keys() {
+            names.push(format!("{}:{}", ns, name));
+        }
+    }
+
+    // sorted so that extensions are written in the same order every time
+    names.sort();
+    names
+}
+
+pub fn find_extension<'a>(
+    extensions: &'a ExtensionMap,
+    name: &str,
+    index: usize,
+) -> Option<&'a Extension> {
+    let (ns, name) = extension_name(name.as_bytes())?;
+    let ns = str::from_utf8(ns).ok()?;
+    let name = str::from_utf8(name).ok()?;
+    extensions.get(ns)?.get(name)?.get(index)
+}
+
+pub fn rename_prefix(name: &str, renames: &HashMap<String, String>) -> Option<String> {
+    let (ns, name) = extension_name(name.as_bytes())?;
+    let ns = str::from_utf8(ns).ok()?;
+    let name = str::from_utf8(name).ok()?;
+    renames.get(ns).map(|prefix| format!("{}:{}", prefix, name))
+}
+
+pub fn rename_prefixes(extensions: &mut ExtensionMap, renames: &HashMap<String, String>) {
+    fn rename_extension(extension: &mut Extension, renames: &HashMap<String, String>) {
+        if let Some(name) = rename_prefix(&extension.name, renames) {
+            extension.name = name;
+        }
+
+        for children in extension.children.values_mut() {
+            for child in children {
+                rename_extension(child, renames);
+            }
+        }
+    }
+
+    let mut renamed = Vec::new();
+
+    for (ns, prefix) in renames {
+        if let Some(mut map) = extensions.remove(ns) {
+            for items in map.values_mut() {
+                for extension in items {
+                    rename_extension(extension, renames);
+                }
+            }
+
+            renamed.push((prefix.clone(), map));
+        }
+    }
+
+    for (prefix, map) in renamed {
+        let target = extensions.entry(prefix).or_default();
+        for (name, items) in map {
+            target.entry(name).or_default().extend(items);
+        }
+    }
+}
+
+pub fn parse_extension_element<R: BufRead>(
     reader: &mut Reader<R>,
     mut atts: Attributes,
+    max_depth: usize,
 ) -> Result<Extension, Error> {
     let mut extension = Extension::default();
     let mut buf = Vec::new();
@@ -72,7 +141,11 @@ fn parse_extension_element<R: BufRead>(
     loop {
         match reader.read_event(&mut buf)? {
             Event::Start(element) => {
-                let ext = parse_extension_element(reader, element.attributes())?;
+                if max_depth == 0 {
+                    return Err(Error::TooDeep);
+                }
+
+                let ext = parse_extension_element(reader, element.attributes(), max_depth - 1)?;
                 let name = str::from_utf8(element.local_name())?;
 
                 if !extension.children.contains_key(name) {
@@ -1,28 +1,48 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use std::io::{self, Read};
 use std::io::{BufRead, Write};
+use std::iter::FromIterator;
 use std::str::{self, FromStr};
+use std::{slice, vec};
 
 use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
 use quick_xml::Error as XmlError;
 use quick_xml::Reader;
 use quick_xml::Writer;
 
 use crate::category::Category;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use crate::compress;
+use crate::config::{EntryOrder, ReadConfig, WriteConfig};
 use crate::entry::Entry;
-use crate::error::Error;
-use crate::extension::util::{extension_name, parse_extension};
-use crate::extension::ExtensionMap;
-use crate::fromxml::FromXml;
+use crate::error::{DetectedFormat, Error};
+use crate::extension::util::{
+    extension_name, extension_names, find_extension, parse_extension, parse_extension_element,
+    rename_prefix, rename_prefixes,
+};
+use crate::extension::{Extension, ExtensionMap};
+use crate::fromxml::{FromXml, ReadContext, ATOM_NAMESPACE};
 use crate::generator::Generator;
+use crate::index::EntryIndex;
+use crate::limit::LimitedReader;
 use crate::link::Link;
 use crate::person::Person;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{atom_any_text, atom_datetime, atom_text, default_fixed_datetime, FixedDateTime};
+use crate::stats::{CountingReader, ParseStats, Timer};
+use crate::toxml::{write_children, ToXml, WriteContext, WriterExt};
+use crate::utf8::{invalid_message, Utf8Check};
+use crate::util::{
+    collect_attrs, default_fixed_datetime, normalize_encoding, resolve_iri, starts_with_bom,
+    starts_with_html_doctype, to_utc, FixedDateTime, Fnv64, IntoOption,
+};
+use crate::validate::{validate_feed, Severity, ValidationIssue, ValidationReport};
+use crate::writer::CountingWriter;
 
 /// Represents an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq, Builder)]
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
 #[builder(setter(into), default)]
 pub struct Feed {
     /// A human-readable title for the feed.
@@ -55,9 +75,50 @@ pub struct Feed {
     extensions: ExtensionMap,
     /// The namespaces present in the feed tag.
     namespaces: HashMap<String, String>,
+    /// Additional attributes of the element that are not otherwise represented.
+    attrs: BTreeMap<String, String>,
+    /// Child elements that are not otherwise represented.
+    #[builder(setter(skip))]
+    unknown_elements: Vec<Extension>,
+    /// The names of the child elements in the order they were read.
+    #[builder(setter(skip))]
+    element_order: Vec<String>,
+    /// An optional index of the entries by id.
+    #[builder(setter(skip))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    entry_index: EntryIndex,
 }
 
 impl Feed {
+    /// Create a feed with the given title, id and updated date.
+    ///
+    /// Every Atom feed must have these elements, so prefer this to `Default` when building a feed
+    /// to publish.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FixedDateTime};
+    ///
+    /// let updated: FixedDateTime = "2017-06-03T15:15:44-05:00".parse().unwrap();
+    /// let feed = Feed::new("Feed Title", "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6", updated);
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// assert_eq!(feed.updated(), &updated);
+    /// ```
+    pub fn new<T, I, U>(title: T, id: I, updated: U) -> Self
+    where
+        T: Into<String>,
+        I: Into<String>,
+        U: Into<FixedDateTime>,
+    {
+        Feed {
+            title: title.into(),
+            id: id.into(),
+            updated: updated.into(),
+            ..Default::default()
+        }
+    }
+
     /// Attempt to read an Atom feed from the reader.
     ///
     /// # Examples
@@ -71,6 +132,217 @@ impl Feed {
     /// let feed = Feed::read_from(BufReader::new(file)).unwrap();
     /// ```
     pub fn read_from<B: BufRead>(reader: B) -> Result<Feed, Error> {
+        Feed::read_with_config(reader, &ReadConfig::default())
+    }
+
+    /// Attempt to read an Atom feed from a byte slice.
+    ///
+    /// A byte order mark at the start of the input is skipped, and UTF-16 input is converted to
+    /// UTF-8 before being read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let bytes = b"\xEF\xBB\xBF<feed><title>Feed Title</title></feed>";
+    /// let feed = Feed::read_from_slice(bytes).unwrap();
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// ```
+    pub fn read_from_slice(bytes: &[u8]) -> Result<Feed, Error> {
+        Feed::read_from(&*normalize_encoding(bytes))
+    }
+
+    /// Attempt to read an Atom feed from a string.
+    ///
+    /// A byte order mark at the start of the string is skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::read_from_str("<feed><title>Feed Title</title></feed>").unwrap();
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// ```
+    pub fn read_from_str(s: &str) -> Result<Feed, Error> {
+        Feed::read_from(s.trim_start_matches('\u{feff}').as_bytes())
+    }
+
+    /// Attempt to read an Atom feed from the reader using the given configuration.
+    ///
+    /// A byte order mark at the start of the input is skipped, and UTF-16 input is converted to
+    /// UTF-8 before being read. The byte order mark takes precedence over the encoding in the XML
+    /// declaration.
+    ///
+    /// With the `gzip` or `zstd` feature enabled, input compressed in that format is detected by
+    /// its magic bytes and decompressed while it is read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::BufReader;
+    /// use std::fs::File;
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let file = File::open("example.xml").unwrap();
+    /// let config = ReadConfig::new().fidelity(true);
+    /// let feed = Feed::read_with_config(BufReader::new(file), &config).unwrap();
+    /// ```
+    pub fn read_with_config<B: BufRead>(reader: B, config: &ReadConfig) -> Result<Feed, Error> {
+        Feed::read_with_context(reader, &mut ReadContext::new(config.clone()))
+    }
+
+    /// Attempt to read the metadata of an Atom feed from the reader, without its entries.
+    ///
+    /// Reading stops at the first `entry` element, so the rest of the document is neither read
+    /// nor parsed. This is much cheaper than reading the whole feed when only its title, links
+    /// or updated date are needed, such as when discovering feeds or polling them for changes.
+    /// Metadata elements that follow the entries are not read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <title>Feed Title</title>
+    ///     <updated>2017-06-03T15:15:44-05:00</updated>
+    ///     <entry><title>Entry Title</title></entry>
+    ///     <entry><title>Unclosed
+    /// "#;
+    /// let feed = Feed::read_metadata(xml.as_bytes()).unwrap();
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// assert!(feed.entries().is_empty());
+    /// ```
+    pub fn read_metadata<B: BufRead>(reader: B) -> Result<Feed, Error> {
+        Feed::read_with_config(reader, &ReadConfig::new().metadata_only(true))
+    }
+
+    /// Attempt to read an Atom feed from the reader using the given configuration, returning
+    /// statistics about the parse along with the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let xml = "<feed><rank>1</rank><entry></entry></feed>";
+    /// let (feed, stats) = Feed::read_with_stats(xml.as_bytes(), &ReadConfig::default()).unwrap();
+    /// assert_eq!(stats.bytes(), xml.len());
+    /// assert_eq!(stats.entries(), 1);
+    /// assert_eq!(stats.skipped_elements()["rank"], 1);
+    /// ```
+    pub fn read_with_stats<B: BufRead>(
+        reader: B,
+        config: &ReadConfig,
+    ) -> Result<(Feed, ParseStats), Error> {
+        let start = Timer::start();
+        let mut reader = CountingReader::new(reader);
+        let mut ctx = ReadContext::new(config.clone());
+        let feed = Feed::read_with_context(&mut reader, &mut ctx)?;
+
+        let stats = ParseStats {
+            bytes: reader.count(),
+            entries: feed.entries.len(),
+            skipped_elements: ctx.skipped,
+            duration: start.elapsed(),
+            issues: ctx.issues,
+        };
+        Ok((feed, stats))
+    }
+
+    fn read_with_context<B: BufRead>(reader: B, ctx: &mut ReadContext) -> Result<Feed, Error> {
+        #[cfg(feature = "tracing")]
+        {
+            let _span = tracing::debug_span!("read_feed").entered();
+            let start = Timer::start();
+            let result = Feed::read_decompressed(reader, ctx);
+            let elapsed_us = start.elapsed().as_micros() as u64;
+
+            match result {
+                Ok(ref feed) => tracing::debug!(
+                    entries = feed.entries.len(),
+                    skipped = ctx.skipped.values().sum::<usize>(),
+                    elapsed_us,
+                    "read feed"
+                ),
+                Err(ref err) => tracing::debug!(error = %err, elapsed_us, "failed to read feed"),
+            }
+
+            result
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        Feed::read_decompressed(reader, ctx)
+    }
+
+    fn read_decompressed<B: BufRead>(reader: B, ctx: &mut ReadContext) -> Result<Feed, Error> {
+        let limit = ctx.config.get_max_bytes().unwrap_or(u64::MAX);
+        let reader = LimitedReader::new(reader, limit);
+
+        #[cfg(any(feature = "gzip", feature = "zstd"))]
+        {
+            let mut reader = reader;
+            let start = compress::peek(&mut reader)?;
+            let format = compress::detect(&start);
+            let reader = io::Cursor::new(start).chain(reader);
+
+            if let Some(format) = format {
+                let reader = LimitedReader::new(format.decompress(reader)?, limit);
+                return Feed::read_uncompressed(reader, ctx);
+            }
+
+            Feed::read_uncompressed(reader, ctx)
+        }
+
+        #[cfg(not(any(feature = "gzip", feature = "zstd")))]
+        Feed::read_uncompressed(reader, ctx)
+    }
+
+    fn read_uncompressed<B: BufRead>(mut reader: B, ctx: &mut ReadContext) -> Result<Feed, Error> {
+        let start = reader.fill_buf()?;
+
+        // HTML doctypes are often lowercase, which is not valid XML.
+        if starts_with_html_doctype(start) {
+            return Err(Error::WrongFormat {
+                detected: DetectedFormat::Html,
+            });
+        }
+
+        if starts_with_bom(start) {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes)?;
+
+            // offsets count the bytes of the input, which only UTF-8 documents keep
+            if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+                ctx.offset = 3;
+            } else if ctx.config.is_entry_offsets() || ctx.config.is_lazy_content() {
+                return Err(Error::OffsetsUnsupported);
+            }
+
+            return Feed::read_xml(&*normalize_encoding(&bytes), ctx);
+        }
+
+        Feed::read_xml(reader, ctx)
+    }
+
+    fn read_xml<B: BufRead>(reader: B, ctx: &mut ReadContext) -> Result<Feed, Error> {
+        let mut reader = Utf8Check::new(reader);
+        let feed = Feed::read_root(&mut reader, ctx)?;
+
+        if let Some((count, first)) = reader.invalid() {
+            ctx.issues.push(ValidationIssue::new(
+                Severity::Warning,
+                "/",
+                invalid_message(count, first),
+            ));
+        }
+
+        Ok(feed)
+    }
+
+    fn read_root<B: BufRead>(reader: B, ctx: &mut ReadContext) -> Result<Feed, Error> {
         let mut reader = Reader::from_reader(reader);
         reader.expand_empty_elements(true);
 
@@ -79,8 +351,16 @@ impl Feed {
         loop {
             match reader.read_event(&mut buf)? {
                 Event::Start(element) => {
-                    if element.name() == b"feed" {
-                        let mut feed = Feed::from_xml(&mut reader, element.attributes())?;
+                    for attr in element.attributes().with_checks(false).flatten() {
+                        if attr.key.starts_with(b"xmlns:") && &*attr.value == ATOM_NAMESPACE {
+                            ctx.atom_prefix = Some(attr.key[6..].to_vec());
+                        }
+                    }
+
+                    if ctx.atom_name(element.name()) == b"feed" {
+                        ctx.enter("feed");
+                        let mut feed = Feed::from_xml(&mut reader, element.attributes(), ctx)
+                            .map_err(|err| ctx.context(err))?;
 
                         for attr in element.attributes().with_checks(false) {
                             if let Ok(attr) = attr {
@@ -88,7 +368,7 @@ impl Feed {
                                     continue;
                                 }
 
-                                let key = str::from_utf8(&attr.key[6..])?.to_string();
+                                let key = reader.decode(&attr.key[6..]).into_owned();
                                 let value = attr.unescape_and_decode_value(&reader)?;
                                 feed.namespaces.insert(key, value);
                             }
@@ -96,7 +376,9 @@ impl Feed {
 
                         return Ok(feed);
                     } else {
-                        return Err(Error::InvalidStartTag);
+                        return Err(Error::WrongFormat {
+                            detected: DetectedFormat::from_root(element.name()),
+                        });
                     }
                 }
                 Event::Eof => break,
@@ -106,11 +388,19 @@ impl Feed {
             buf.clear();
         }
 
-        Err(Error::Eof)
+        Err(Error::MissingElement {
+            name: "feed".to_string(),
+            path: "/".to_string(),
+        })
     }
 
     /// Attempt to write this Atom feed to a writer.
     ///
+    /// The output is buffered and flushed once the feed has been written, so the writer does not
+    /// need to be buffered. The same feed is always written to the same bytes: namespace
+    /// declarations, extension attributes and extensions without a recorded order are written
+    /// sorted by name.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -124,9 +414,138 @@ impl Feed {
     /// feed.write_to(out).unwrap();
     /// ```
     pub fn write_to<W: Write>(&self, writer: W) -> Result<W, Error> {
-        let mut writer = Writer::new(writer);
-        self.to_xml(&mut writer)?;
-        Ok(writer.into_inner())
+        self.write_with_config(writer, &WriteConfig::default())
+    }
+
+    /// Attempt to write this Atom feed to a writer using the given configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use atom_syndication::{Feed, WriteConfig};
+    ///
+    /// let mut namespaces = HashMap::new();
+    /// namespaces.insert("d".to_string(), "http://purl.org/dc/elements/1.1/".to_string());
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_namespaces(namespaces);
+    ///
+    /// let config = WriteConfig::new()
+    ///     .namespace("dc", "http://purl.org/dc/elements/1.1/")
+    ///     .declaration(true)
+    ///     .indent(b' ', 2);
+    /// let xml = feed.write_with_config(Vec::new(), &config).unwrap();
+    /// let xml = String::from_utf8(xml).unwrap();
+    /// assert!(xml.starts_with(r#"<?xml version="1.0" encoding="utf-8"?>"#));
+    /// assert!(xml.contains(r#"xmlns:dc="http://purl.org/dc/elements/1.1/""#));
+    /// ```
+    pub fn write_with_config<W: Write>(&self, writer: W, config: &WriteConfig) -> Result<W, Error> {
+        self.write_counted(writer, config).map(|(writer, _)| writer)
+    }
+
+    /// Attempt to write this Atom feed to a writer using the given configuration, returning the
+    /// writer and the number of bytes written to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, WriteConfig};
+    ///
+    /// let feed = Feed::default();
+    /// let (xml, len) = feed.write_counted(Vec::new(), &WriteConfig::default()).unwrap();
+    /// assert_eq!(len, xml.len());
+    /// ```
+    pub fn write_counted<W: Write>(
+        &self,
+        writer: W,
+        config: &WriteConfig,
+    ) -> Result<(W, usize), Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("write_feed", entries = self.entries.len()).entered();
+        #[cfg(feature = "tracing")]
+        let start = Timer::start();
+
+        let mut writer = match config.get_indent() {
+            Some((indent_char, size)) => {
+                Writer::new_with_indent(CountingWriter::buffered(writer), indent_char, size)
+            }
+            None => Writer::new(CountingWriter::buffered(writer)),
+        };
+        let ctx = WriteContext::new(config.clone());
+
+        if config.is_declaration() && !config.is_fragment() {
+            let decl = BytesDecl::new(b"1.0", Some(b"utf-8"), None);
+            writer.write_event(Event::Decl(decl))?;
+        }
+
+        if config.namespaces().is_empty()
+            && matches!(config.get_entry_order(), EntryOrder::Document)
+        {
+            self.to_xml(&mut writer, &ctx)?;
+        } else {
+            let mut feed = self.clone();
+            feed.apply_namespaces(config.namespaces());
+            feed.apply_entry_order(config.get_entry_order());
+            feed.to_xml(&mut writer, &ctx)?;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            entries = self.entries.len(),
+            elapsed_us = start.elapsed().as_micros() as u64,
+            "wrote feed"
+        );
+
+        CountingWriter::finish(writer.into_inner())
+    }
+
+    /// Return a strong entity tag for this feed, for answering conditional requests.
+    ///
+    /// The tag is a quoted 64-bit FNV-1a hash of the feed as written with the default
+    /// configuration. Since the same feed is always written to the same bytes, the tag only
+    /// changes when the feed does. Fails if the feed cannot be written, such as when it has
+    /// content that has not been loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_title("Feed Title");
+    /// let etag = feed.etag().unwrap();
+    /// assert!(etag.starts_with('"') && etag.ends_with('"'));
+    /// assert_eq!(feed.clone().etag().unwrap(), etag);
+    /// assert_ne!(feed.with_title("Other Title").etag().unwrap(), etag);
+    /// ```
+    pub fn etag(&self) -> Result<String, Error> {
+        let hash = self.write_to(Fnv64::new())?.finish();
+        Ok(format!("\"{:016x}\"", hash))
+    }
+
+    /// Check this feed against the requirements and recommendations of the Atom specification.
+    ///
+    /// Unlike parsing, validation does not stop at the first problem. The returned report lists
+    /// every issue found, such as missing or relative ids, missing authors, missing dates,
+    /// duplicate entry ids and duplicate alternate links, along with its severity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Severity};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// feed.set_id("feed-1");
+    ///
+    /// let report = feed.validate();
+    /// assert!(!report.is_valid());
+    /// assert!(report
+    ///     .errors()
+    ///     .any(|issue| issue.message() == "id feed-1 is not an absolute IRI"));
+    /// ```
+    pub fn validate(&self) -> ValidationReport {
+        validate_feed(self)
     }
 
     /// Return the title of this feed.
@@ -144,6 +563,21 @@ impl Feed {
         self.title.as_str()
     }
 
+    /// Return a mutable reference to the title of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.title_mut().push_str("Feed Title");
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// ```
+    pub fn title_mut(&mut self) -> &mut String {
+        &mut self.title
+    }
+
     /// Set the title of this feed.
     ///
     /// # Examples
@@ -161,6 +595,23 @@ impl Feed {
         self.title = title.into();
     }
 
+    /// Set the title of this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_title("Feed Title");
+    /// ```
+    pub fn with_title<V>(mut self, title: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_title(title);
+        self
+    }
+
     /// Return the unique URI of this feed.
     ///
     /// # Examples
@@ -176,6 +627,21 @@ impl Feed {
         self.id.as_str()
     }
 
+    /// Return a mutable reference to the unique URI of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.id_mut().push_str("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// assert_eq!(feed.id(), "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// ```
+    pub fn id_mut(&mut self) -> &mut String {
+        &mut self.id
+    }
+
     /// Set the unique URI of this feed.
     ///
     /// # Examples
@@ -193,6 +659,23 @@ impl Feed {
         self.id = id.into();
     }
 
+    /// Set the unique URI of this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// ```
+    pub fn with_id<V>(mut self, id: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_id(id);
+        self
+    }
+
     /// Return the last time that this feed was modified.
     ///
     /// # Examples
@@ -210,6 +693,23 @@ impl Feed {
         &self.updated
     }
 
+    /// Return a mutable reference to the last time that this feed was modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut feed = Feed::default();
+    /// *feed.updated_mut() = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn updated_mut(&mut self) -> &mut FixedDateTime {
+        &mut self.updated
+    }
+
     /// Set the last time that this feed was modified.
     ///
     /// # Examples
@@ -229,6 +729,25 @@ impl Feed {
         self.updated = updated.into();
     }
 
+    /// Set the last time that this feed was modified and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let feed = Feed::default().with_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// ```
+    pub fn with_updated<V>(mut self, updated: V) -> Self
+    where
+        V: Into<FixedDateTime>,
+    {
+        self.set_updated(updated);
+        self
+    }
+
     /// Return the authors of this feed.
     ///
     /// # Examples
@@ -244,6 +763,21 @@ impl Feed {
         self.authors.as_slice()
     }
 
+    /// Return a mutable reference to the authors of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.authors_mut().push(Person::default());
+    /// assert_eq!(feed.authors().len(), 1);
+    /// ```
+    pub fn authors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.authors
+    }
+
     /// Set the authors of this feed.
     ///
     /// # Examples
@@ -261,27 +795,74 @@ impl Feed {
         self.authors = authors.into();
     }
 
-    /// Return the categories this feed belongs to.
+    /// Set the authors of this feed and return it.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Category};
+    /// use atom_syndication::{Feed, Person};
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_categories(vec![Category::default()]);
-    /// assert_eq!(feed.categories().len(), 1);
+    /// let feed = Feed::default().with_authors(vec![Person::default()]);
     /// ```
-    pub fn categories(&self) -> &[Category] {
-        self.categories.as_slice()
+    pub fn with_authors<V>(mut self, authors: V) -> Self
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.set_authors(authors);
+        self
     }
 
-    /// Set the categories this feed belongs to.
+    /// Add an author to this feed.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Category};
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_author(Person::default());
+    /// assert_eq!(feed.authors().len(), 1);
+    /// ```
+    pub fn push_author(&mut self, author: Person) {
+        self.authors.push(author);
+    }
+
+    /// Return the categories this feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Category};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_categories(vec![Category::default()]);
+    /// assert_eq!(feed.categories().len(), 1);
+    /// ```
+    pub fn categories(&self) -> &[Category] {
+        self.categories.as_slice()
+    }
+
+    /// Return a mutable reference to the categories this feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Category};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.categories_mut().push(Category::default());
+    /// assert_eq!(feed.categories().len(), 1);
+    /// ```
+    pub fn categories_mut(&mut self) -> &mut Vec<Category> {
+        &mut self.categories
+    }
+
+    /// Set the categories this feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Category};
     ///
     /// let mut feed = Feed::default();
     /// feed.set_categories(vec![Category::default()]);
@@ -293,6 +874,38 @@ impl Feed {
         self.categories = categories.into();
     }
 
+    /// Set the categories this feed belongs to and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Category};
+    ///
+    /// let feed = Feed::default().with_categories(vec![Category::default()]);
+    /// ```
+    pub fn with_categories<V>(mut self, categories: V) -> Self
+    where
+        V: Into<Vec<Category>>,
+    {
+        self.set_categories(categories);
+        self
+    }
+
+    /// Add a category to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_category(Category::default());
+    /// assert_eq!(feed.categories().len(), 1);
+    /// ```
+    pub fn push_category(&mut self, category: Category) {
+        self.categories.push(category);
+    }
+
     /// Return the contributors to this feed.
     ///
     /// # Examples
@@ -308,6 +921,21 @@ impl Feed {
         self.contributors.as_slice()
     }
 
+    /// Return a mutable reference to the contributors to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.contributors_mut().push(Person::default());
+    /// assert_eq!(feed.contributors().len(), 1);
+    /// ```
+    pub fn contributors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.contributors
+    }
+
     /// Set the contributors to this feed.
     ///
     /// # Examples
@@ -325,54 +953,948 @@ impl Feed {
         self.contributors = contributors.into();
     }
 
-    /// Return the name of the software used to generate this feed.
+    /// Set the contributors to this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let feed = Feed::default().with_contributors(vec![Person::default()]);
+    /// ```
+    pub fn with_contributors<V>(mut self, contributors: V) -> Self
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.set_contributors(contributors);
+        self
+    }
+
+    /// Add a contributor to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_contributor(Person::default());
+    /// assert_eq!(feed.contributors().len(), 1);
+    /// ```
+    pub fn push_contributor(&mut self, contributor: Person) {
+        self.contributors.push(contributor);
+    }
+
+    /// Return the name of the software used to generate this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Generator};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_generator(Generator::default());
+    /// assert!(feed.generator().is_some());
+    /// ```
+    pub fn generator(&self) -> Option<&Generator> {
+        self.generator.as_ref()
+    }
+
+    /// Return a mutable reference to the name of the software used to generate this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Generator};
+    ///
+    /// let mut feed = Feed::default();
+    /// *feed.generator_mut() = Some(Generator::default());
+    /// assert!(feed.generator().is_some());
+    /// ```
+    pub fn generator_mut(&mut self) -> &mut Option<Generator> {
+        &mut self.generator
+    }
+
+    /// Set the name of the software used to generate this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Generator};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_generator(Generator::default());
+    /// ```
+    pub fn set_generator<V>(&mut self, generator: V)
+    where
+        V: Into<Option<Generator>>,
+    {
+        self.generator = generator.into()
+    }
+
+    /// Set the name of the software used to generate this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Generator};
+    ///
+    /// let feed = Feed::default().with_generator(Generator::default());
+    /// ```
+    pub fn with_generator<V>(mut self, generator: V) -> Self
+    where
+        V: Into<Option<Generator>>,
+    {
+        self.set_generator(generator);
+        self
+    }
+
+    /// Set the generator of this feed to this crate, including its name, version and repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_generator_default();
+    ///
+    /// let generator = feed.generator().unwrap();
+    /// assert_eq!(generator.value(), "atom_syndication");
+    /// assert_eq!(generator.version(), Some(env!("CARGO_PKG_VERSION")));
+    /// ```
+    pub fn set_generator_default(&mut self) {
+        let mut generator = Generator::default();
+        generator.set_value(env!("CARGO_PKG_NAME"));
+        generator.set_uri(env!("CARGO_PKG_REPOSITORY").to_string());
+        generator.set_version(env!("CARGO_PKG_VERSION").to_string());
+        self.generator = Some(generator);
+    }
+
+    /// Return the icon for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_icon("http://example.com/icon.png");
+    /// assert_eq!(feed.icon(), Some("http://example.com/icon.png"));
+    /// ```
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_ref().map(String::as_str)
+    }
+
+    /// Return a mutable reference to the icon for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// *feed.icon_mut() = Some("http://example.com/icon.png".to_string());
+    /// assert_eq!(feed.icon(), Some("http://example.com/icon.png"));
+    /// ```
+    pub fn icon_mut(&mut self) -> &mut Option<String> {
+        &mut self.icon
+    }
+
+    /// Set the icon for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_icon("http://example.com/icon.png");
+    /// ```
+    pub fn set_icon<V>(&mut self, icon: V)
+    where
+        V: IntoOption<String>,
+    {
+        self.icon = icon.into_option()
+    }
+
+    /// Set the icon for this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_icon("http://example.com/icon.png");
+    /// ```
+    pub fn with_icon<V>(mut self, icon: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_icon(icon);
+        self
+    }
+
+    /// Return the Web pages related to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![Link::default()]);
+    /// assert_eq!(feed.links().len(), 1);
+    /// ```
+    pub fn links(&self) -> &[Link] {
+        self.links.as_slice()
+    }
+
+    /// Return a mutable reference to the Web pages related to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.links_mut().push(Link::default());
+    /// assert_eq!(feed.links().len(), 1);
+    /// ```
+    pub fn links_mut(&mut self) -> &mut Vec<Link> {
+        &mut self.links
+    }
+
+    /// Set the Web pages related to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![Link::default()]);
+    /// ```
+    pub fn set_links<V>(&mut self, links: V)
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.links = links.into();
+    }
+
+    /// Set the Web pages related to this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let feed = Feed::default().with_links(vec![Link::default()]);
+    /// ```
+    pub fn with_links<V>(mut self, links: V) -> Self
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.set_links(links);
+        self
+    }
+
+    /// Add a link to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_link(Link::default());
+    /// assert_eq!(feed.links().len(), 1);
+    /// ```
+    pub fn push_link(&mut self, link: Link) {
+        self.links.push(link);
+    }
+
+    /// Return the URI of the `rel="self"` link for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_self_link("http://example.com/feed.xml");
+    /// assert_eq!(feed.self_link(), Some("http://example.com/feed.xml"));
+    /// ```
+    pub fn self_link(&self) -> Option<&str> {
+        self.link_href("self")
+    }
+
+    /// Set the URI of the `rel="self"` link for this feed.
+    ///
+    /// If the feed already has a `rel="self"` link its URI is replaced, otherwise a new link is
+    /// added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_self_link("http://example.com/feed.xml");
+    /// feed.set_self_link("http://example.com/atom.xml");
+    /// assert_eq!(feed.links().len(), 1);
+    /// ```
+    pub fn set_self_link<V>(&mut self, href: V)
+    where
+        V: Into<String>,
+    {
+        self.set_link_href("self", href.into());
+    }
+
+    /// Set the URI of the `rel="self"` link for this feed and return it.
+    ///
+    /// If the feed already has a `rel="self"` link its URI is replaced, otherwise a new link is
+    /// added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_self_link("http://example.com/feed.xml");
+    /// assert_eq!(feed.self_link(), Some("http://example.com/feed.xml"));
+    /// ```
+    pub fn with_self_link<V>(mut self, href: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_self_link(href);
+        self
+    }
+
+    /// Return the URI of the `rel="alternate"` link for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_alternate_link("http://example.com/");
+    /// assert_eq!(feed.alternate_link(), Some("http://example.com/"));
+    /// ```
+    pub fn alternate_link(&self) -> Option<&str> {
+        self.link_href("alternate")
+    }
+
+    /// Set the URI of the `rel="alternate"` link for this feed.
+    ///
+    /// If the feed already has a `rel="alternate"` link its URI is replaced, otherwise a new link
+    /// is added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_alternate_link("http://example.com/");
+    /// ```
+    pub fn set_alternate_link<V>(&mut self, href: V)
+    where
+        V: Into<String>,
+    {
+        self.set_link_href("alternate", href.into());
+    }
+
+    /// Set the URI of the `rel="alternate"` link for this feed and return it.
+    ///
+    /// If the feed already has a `rel="alternate"` link its URI is replaced, otherwise a new link
+    /// is added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_alternate_link("http://example.com/");
+    /// ```
+    pub fn with_alternate_link<V>(mut self, href: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_alternate_link(href);
+        self
+    }
+
+    fn link_href(&self, rel: &str) -> Option<&str> {
+        self.links
+            .iter()
+            .find(|link| link.rel() == rel)
+            .map(Link::href)
+    }
+
+    pub(crate) fn set_link_href(&mut self, rel: &str, href: String) {
+        match self.links.iter_mut().find(|link| link.rel() == rel) {
+            Some(link) => link.set_href(href),
+            None => {
+                let mut link = Link::default();
+                link.set_rel(rel);
+                link.set_href(href);
+                self.links.push(link);
+            }
+        }
+    }
+
+    /// Return the logo for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_logo("http://example.com/logo.png");
+    /// assert_eq!(feed.logo(), Some("http://example.com/logo.png"));
+    /// ```
+    pub fn logo(&self) -> Option<&str> {
+        self.logo.as_ref().map(String::as_str)
+    }
+
+    /// Return a mutable reference to the logo for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// *feed.logo_mut() = Some("http://example.com/logo.png".to_string());
+    /// assert_eq!(feed.logo(), Some("http://example.com/logo.png"));
+    /// ```
+    pub fn logo_mut(&mut self) -> &mut Option<String> {
+        &mut self.logo
+    }
+
+    /// Set the logo for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_logo("http://example.com/logo.png");
+    /// ```
+    pub fn set_logo<V>(&mut self, logo: V)
+    where
+        V: IntoOption<String>,
+    {
+        self.logo = logo.into_option()
+    }
+
+    /// Set the logo for this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_logo("http://example.com/logo.png");
+    /// ```
+    pub fn with_logo<V>(mut self, logo: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_logo(logo);
+        self
+    }
+
+    /// Return the information about the rights held in and over this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_rights("© 2017 John Doe");
+    /// assert_eq!(feed.rights(), Some("© 2017 John Doe"));
+    /// ```
+    pub fn rights(&self) -> Option<&str> {
+        self.rights.as_ref().map(String::as_str)
+    }
+
+    /// Return a mutable reference to the information about the rights held in and over this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// *feed.rights_mut() = Some("© 2017 John Doe".to_string());
+    /// assert_eq!(feed.rights(), Some("© 2017 John Doe"));
+    /// ```
+    pub fn rights_mut(&mut self) -> &mut Option<String> {
+        &mut self.rights
+    }
+
+    /// Set the information about the rights held in and over this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_rights("© 2017 John Doe");
+    /// ```
+    pub fn set_rights<V>(&mut self, rights: V)
+    where
+        V: IntoOption<String>,
+    {
+        self.rights = rights.into_option()
+    }
+
+    /// Set the information about the rights held in and over this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_rights("© 2017 John Doe");
+    /// ```
+    pub fn with_rights<V>(mut self, rights: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_rights(rights);
+        self
+    }
+
+    /// Return the description or subtitle of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_subtitle("Feed subtitle");
+    /// assert_eq!(feed.subtitle(), Some("Feed subtitle"));
+    /// ```
+    pub fn subtitle(&self) -> Option<&str> {
+        self.subtitle.as_ref().map(String::as_str)
+    }
+
+    /// Return a mutable reference to the description or subtitle of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// *feed.subtitle_mut() = Some("Feed subtitle".to_string());
+    /// assert_eq!(feed.subtitle(), Some("Feed subtitle"));
+    /// ```
+    pub fn subtitle_mut(&mut self) -> &mut Option<String> {
+        &mut self.subtitle
+    }
+
+    /// Set the description or subtitle of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_subtitle("Feed subtitle");
+    /// ```
+    pub fn set_subtitle<V>(&mut self, subtitle: V)
+    where
+        V: IntoOption<String>,
+    {
+        self.subtitle = subtitle.into_option()
+    }
+
+    /// Set the description or subtitle of this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_subtitle("Feed subtitle");
+    /// ```
+    pub fn with_subtitle<V>(mut self, subtitle: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_subtitle(subtitle);
+        self
+    }
+
+    /// Return the entries in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default()]);
+    /// assert_eq!(feed.entries().len(), 1);
+    /// ```
+    pub fn entries(&self) -> &[Entry] {
+        self.entries.as_slice()
+    }
+
+    /// Return a mutable reference to the entries in this feed.
+    ///
+    /// If the entries have been indexed with `index_entries`, the index is rebuilt on the next
+    /// mutable lookup and lookups fall back to a linear scan until then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.entries_mut().push(Entry::default());
+    /// assert_eq!(feed.entries().len(), 1);
+    /// ```
+    pub fn entries_mut(&mut self) -> &mut Vec<Entry> {
+        self.entry_index.invalidate();
+        &mut self.entries
+    }
+
+    /// Set the entries in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default()]);
+    /// ```
+    pub fn set_entries<V>(&mut self, entries: V)
+    where
+        V: Into<Vec<Entry>>,
+    {
+        self.entries = entries.into();
+        self.entry_index.invalidate();
+        self.entry_index.sync(&self.entries);
+    }
+
+    /// Set the entries in this feed and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let feed = Feed::default().with_entries(vec![Entry::default()]);
+    /// ```
+    pub fn with_entries<V>(mut self, entries: V) -> Self
+    where
+        V: Into<Vec<Entry>>,
+    {
+        self.set_entries(entries);
+        self
+    }
+
+    /// Add an entry to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_entry(Entry::default());
+    /// assert_eq!(feed.entries().len(), 1);
+    /// ```
+    pub fn push_entry(&mut self, entry: Entry) {
+        self.entry_index.sync(&self.entries);
+        self.entries.push(entry);
+        self.entry_index.push(&self.entries);
+    }
+
+    /// Remove the entry with the given id from this feed and return it.
+    ///
+    /// If several entries share the id, only the first is removed. Returns `None` if there is no
+    /// entry with the id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_entry(Entry::default().with_id("urn:uuid:1"));
+    /// feed.push_entry(Entry::default().with_id("urn:uuid:2"));
+    ///
+    /// let entry = feed.remove_entry("urn:uuid:1").unwrap();
+    /// assert_eq!(entry.id(), "urn:uuid:1");
+    /// assert_eq!(feed.entries().len(), 1);
+    /// assert!(feed.remove_entry("urn:uuid:1").is_none());
+    /// ```
+    pub fn remove_entry(&mut self, id: &str) -> Option<Entry> {
+        self.entry_index.sync(&self.entries);
+        let index = self.entry_index.position(&self.entries, id)?;
+        let entry = self.entries.remove(index);
+        self.entry_index.invalidate();
+        self.entry_index.sync(&self.entries);
+        Some(entry)
+    }
+
+    /// Build an index of the entries in this feed by id, so that `entry`, `entry_mut` and
+    /// `remove_entry` do not have to scan every entry.
+    ///
+    /// The index is kept up to date as entries are added, removed or modified through this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_entry(Entry::default().with_id("urn:uuid:1"));
+    /// feed.index_entries();
+    /// feed.push_entry(Entry::default().with_id("urn:uuid:2"));
+    /// assert!(feed.entry("urn:uuid:2").is_some());
+    /// ```
+    pub fn index_entries(&mut self) {
+        self.entry_index.enable(&self.entries);
+    }
+
+    /// Return the entry with the given id, or `None` if there is no such entry.
+    ///
+    /// If several entries share the id, the first is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_entry(Entry::default().with_id("urn:uuid:1").with_title("Entry Title"));
+    /// assert_eq!(
+    ///     feed.entry("urn:uuid:1").map(Entry::title),
+    ///     Some("Entry Title")
+    /// );
+    /// assert!(feed.entry("urn:uuid:2").is_none());
+    /// ```
+    pub fn entry(&self, id: &str) -> Option<&Entry> {
+        let index = self.entry_index.position(&self.entries, id)?;
+        self.entries.get(index)
+    }
+
+    /// Return a mutable reference to the entry with the given id, or `None` if there is no such
+    /// entry.
+    ///
+    /// If several entries share the id, the first is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.push_entry(Entry::default().with_id("urn:uuid:1"));
+    /// feed.index_entries();
+    ///
+    /// feed.entry_mut("urn:uuid:1").unwrap().set_id("urn:uuid:2");
+    /// assert!(feed.entry("urn:uuid:1").is_none());
+    /// assert!(feed.entry("urn:uuid:2").is_some());
+    /// ```
+    pub fn entry_mut(&mut self, id: &str) -> Option<&mut Entry> {
+        self.entry_index.sync(&self.entries);
+        let index = self.entry_index.position(&self.entries, id)?;
+        self.entry_index.set_pending(index);
+        self.entries.get_mut(index)
+    }
+
+    /// Set the `updated` time of the entries of this feed that are missing one.
+    ///
+    /// Each entry gets its published time or, if it has none, the `updated` time of the feed.
+    /// An entry that was read without an `updated` element has the default time of
+    /// 1970-01-01T00:00:00Z, which is treated as missing. Returns the number of entries whose
+    /// time was set.
+    ///
+    /// The same repair can be made while reading with `ReadConfig::fill_missing_updated`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <updated>2017-06-04T00:00:00Z</updated>
+    ///     <entry><published>2017-06-03T15:15:44-05:00</published></entry>
+    ///     <entry></entry>
+    /// </feed>"#;
+    /// let mut feed = xml.parse::<Feed>().unwrap();
+    ///
+    /// assert_eq!(feed.fill_missing_updated(), 2);
+    /// assert_eq!(feed.entries()[0].updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// assert_eq!(feed.entries()[1].updated(), feed.updated());
+    /// ```
+    pub fn fill_missing_updated(&mut self) -> usize {
+        let missing = default_fixed_datetime();
+        let mut filled = 0;
+
+        for entry in &mut self.entries {
+            if entry.fill_missing_updated() {
+                filled += 1;
+            } else if *entry.updated() == missing && self.updated != missing {
+                entry.set_updated(self.updated);
+                filled += 1;
+            }
+        }
+
+        filled
+    }
+
+    /// Convert the dates of this feed and its entries to UTC.
+    ///
+    /// The dates keep the instant they represent, and are written with a `+00:00` offset. This
+    /// makes feeds that mix UTC offsets simpler to sort, compare and deduplicate as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <updated>2017-06-03T15:15:44-05:00</updated>
+    ///     <entry><published>2017-06-04T02:00:00+09:00</published></entry>
+    /// </feed>"#;
+    /// let mut feed = xml.parse::<Feed>().unwrap();
+    /// feed.normalize_dates_utc();
+    ///
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T20:15:44+00:00");
+    /// let published = feed.entries()[0].published().unwrap();
+    /// assert_eq!(published.to_rfc3339(), "2017-06-03T17:00:00+00:00");
+    /// ```
+    pub fn normalize_dates_utc(&mut self) {
+        self.updated = to_utc(&self.updated);
+
+        for entry in &mut self.entries {
+            entry.normalize_dates_utc();
+        }
+    }
+
+    /// Return the extensions for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::extension::{ExtensionMap, Extension};
+    ///
+    /// let extension = Extension::default();
+    ///
+    /// let mut item_map = HashMap::<String, Vec<Extension>>::new();
+    /// item_map.insert("ext:name".to_string(), vec![extension]);
+    ///
+    /// let mut extension_map = ExtensionMap::default();
+    /// extension_map.insert("ext".to_string(), item_map);
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_extensions(extension_map);
+    /// assert_eq!(feed.extensions()
+    ///                .get("ext")
+    ///                .and_then(|m| m.get("ext:name"))
+    ///                .map(|v| v.len()),
+    ///            Some(1));
+    /// ```
+    pub fn extensions(&self) -> &ExtensionMap {
+        &self.extensions
+    }
+
+    /// Return a mutable reference to the extensions for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.extensions_mut()
+    ///     .entry("ext".to_string())
+    ///     .or_insert_with(HashMap::new)
+    ///     .insert("ext:name".to_string(), vec![Extension::default()]);
+    /// assert!(feed.extensions().contains_key("ext"));
+    /// ```
+    pub fn extensions_mut(&mut self) -> &mut ExtensionMap {
+        &mut self.extensions
+    }
+
+    /// Set the extensions for this feed.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Generator};
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::extension::ExtensionMap;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_generator(Generator::default());
-    /// assert!(feed.generator().is_some());
+    /// feed.set_extensions(ExtensionMap::default());
     /// ```
-    pub fn generator(&self) -> Option<&Generator> {
-        self.generator.as_ref()
+    pub fn set_extensions<V>(&mut self, extensions: V)
+    where
+        V: Into<ExtensionMap>,
+    {
+        self.extensions = extensions.into()
     }
 
-    /// Set the name of the software used to generate this feed.
+    /// Set the extensions for this feed and return it.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Generator};
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::extension::ExtensionMap;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_generator(Generator::default());
+    /// let feed = Feed::default().with_extensions(ExtensionMap::default());
     /// ```
-    pub fn set_generator<V>(&mut self, generator: V)
+    pub fn with_extensions<V>(mut self, extensions: V) -> Self
     where
-        V: Into<Option<Generator>>,
+        V: Into<ExtensionMap>,
     {
-        self.generator = generator.into()
+        self.set_extensions(extensions);
+        self
     }
 
-    /// Return the icon for this feed.
+    /// Return the namespaces for this feed.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::collections::HashMap;
     /// use atom_syndication::Feed;
     ///
+    /// let mut namespaces = HashMap::new();
+    /// namespaces.insert("ext".to_string(), "http://example.com".to_string());
+    ///
     /// let mut feed = Feed::default();
-    /// feed.set_icon("http://example.com/icon.png".to_string());
-    /// assert_eq!(feed.icon(), Some("http://example.com/icon.png"));
+    /// feed.set_namespaces(namespaces);
+    /// assert_eq!(feed.namespaces().get("ext").map(|s| s.as_str()), Some("http://example.com"));
     /// ```
-    pub fn icon(&self) -> Option<&str> {
-        self.icon.as_ref().map(String::as_str)
+    pub fn namespaces(&self) -> &HashMap<String, String> {
+        &self.namespaces
     }
 
-    /// Set the icon for this feed.
+    /// Return a mutable reference to the namespaces for this feed.
     ///
     /// # Examples
     ///
@@ -380,63 +1902,73 @@ impl Feed {
     /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_icon("http://example.com/icon.png".to_string());
+    /// feed.namespaces_mut()
+    ///     .insert("ext".to_string(), "http://example.com".to_string());
+    /// assert_eq!(feed.namespaces().get("ext").map(|s| s.as_str()), Some("http://example.com"));
     /// ```
-    pub fn set_icon<V>(&mut self, icon: V)
-    where
-        V: Into<Option<String>>,
-    {
-        self.icon = icon.into()
+    pub fn namespaces_mut(&mut self) -> &mut HashMap<String, String> {
+        &mut self.namespaces
     }
 
-    /// Return the Web pages related to this feed.
+    /// Set the namespaces for this feed.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Link};
+    /// use std::collections::HashMap;
+    /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_links(vec![Link::default()]);
-    /// assert_eq!(feed.links().len(), 1);
+    /// feed.set_namespaces(HashMap::new());
     /// ```
-    pub fn links(&self) -> &[Link] {
-        self.links.as_slice()
+    pub fn set_namespaces<V>(&mut self, namespaces: V)
+    where
+        V: Into<HashMap<String, String>>,
+    {
+        self.namespaces = namespaces.into()
     }
 
-    /// Set the Web pages related to this feed.
+    /// Set the namespaces for this feed and return it.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Link};
+    /// use std::collections::HashMap;
+    /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_links(vec![Link::default()]);
+    /// let feed = Feed::default().with_namespaces(HashMap::new());
     /// ```
-    pub fn set_links<V>(&mut self, links: V)
+    pub fn with_namespaces<V>(mut self, namespaces: V) -> Self
     where
-        V: Into<Vec<Link>>,
+        V: Into<HashMap<String, String>>,
     {
-        self.links = links.into();
+        self.set_namespaces(namespaces);
+        self
     }
 
-    /// Return the logo for this feed.
+    /// Return the additional attributes of this feed.
+    ///
+    /// These are attributes of the element that are not otherwise represented, such as `xml:lang`
+    /// or vendor-specific attributes. They are written back out when serializing.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::collections::BTreeMap;
     /// use atom_syndication::Feed;
     ///
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("xml:lang".to_string(), "en".to_string());
+    ///
     /// let mut feed = Feed::default();
-    /// feed.set_logo("http://example.com/logo.png".to_string());
-    /// assert_eq!(feed.logo(), Some("http://example.com/logo.png"));
+    /// feed.set_attrs(attrs);
+    /// assert_eq!(feed.attrs().get("xml:lang").map(String::as_str), Some("en"));
     /// ```
-    pub fn logo(&self) -> Option<&str> {
-        self.logo.as_ref().map(String::as_str)
+    pub fn attrs(&self) -> &BTreeMap<String, String> {
+        &self.attrs
     }
 
-    /// Set the logo for this feed.
+    /// Return a mutable reference to the additional attributes of this feed.
     ///
     /// # Examples
     ///
@@ -444,48 +1976,51 @@ impl Feed {
     /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_logo("http://example.com/logo.png".to_string());
+    /// feed.attrs_mut()
+    ///     .insert("xml:lang".to_string(), "en".to_string());
+    /// assert_eq!(feed.attrs().len(), 1);
     /// ```
-    pub fn set_logo<V>(&mut self, logo: V)
-    where
-        V: Into<Option<String>>,
-    {
-        self.logo = logo.into()
+    pub fn attrs_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.attrs
     }
 
-    /// Return the information about the rights held in and over this feed.
+    /// Set the additional attributes of this feed.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::collections::BTreeMap;
     /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_rights("© 2017 John Doe".to_string());
-    /// assert_eq!(feed.rights(), Some("© 2017 John Doe"));
+    /// feed.set_attrs(BTreeMap::new());
     /// ```
-    pub fn rights(&self) -> Option<&str> {
-        self.rights.as_ref().map(String::as_str)
+    pub fn set_attrs<V>(&mut self, attrs: V)
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.attrs = attrs.into();
     }
 
-    /// Set the information about the rights held in and over this feed.
+    /// Set the additional attributes of this feed and return it.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::collections::BTreeMap;
     /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_rights("© 2017 John Doe".to_string());
+    /// let feed = Feed::default().with_attrs(BTreeMap::new());
     /// ```
-    pub fn set_rights<V>(&mut self, rights: V)
+    pub fn with_attrs<V>(mut self, attrs: V) -> Self
     where
-        V: Into<Option<String>>,
+        V: Into<BTreeMap<String, String>>,
     {
-        self.rights = rights.into()
+        self.set_attrs(attrs);
+        self
     }
 
-    /// Return the description or subtitle of this feed.
+    /// Return the language of this feed from its `xml:lang` attribute.
     ///
     /// # Examples
     ///
@@ -493,14 +2028,15 @@ impl Feed {
     /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_subtitle("Feed subtitle".to_string());
-    /// assert_eq!(feed.subtitle(), Some("Feed subtitle"));
+    /// feed.set_lang("en");
+    /// assert_eq!(feed.lang(), Some("en"));
     /// ```
-    pub fn subtitle(&self) -> Option<&str> {
-        self.subtitle.as_ref().map(String::as_str)
+    pub fn lang(&self) -> Option<&str> {
+        self.attrs.get("xml:lang").map(String::as_str)
     }
 
-    /// Set the description or subtitle of this feed.
+    /// Set the language of this feed, which is written as the `xml:lang` attribute of the `feed`
+    /// element and applies to every entry that does not set its own.
     ///
     /// # Examples
     ///
@@ -508,184 +2044,283 @@ impl Feed {
     /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_subtitle("Feed subtitle".to_string());
+    /// feed.set_lang("en");
+    /// assert!(feed.to_string().starts_with(r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="en">"#));
+    ///
+    /// feed.set_lang(None);
+    /// assert_eq!(feed.lang(), None);
     /// ```
-    pub fn set_subtitle<V>(&mut self, subtitle: V)
+    pub fn set_lang<V>(&mut self, lang: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.subtitle = subtitle.into()
+        match lang.into_option() {
+            Some(lang) => self.attrs.insert("xml:lang".to_string(), lang),
+            None => self.attrs.remove("xml:lang"),
+        };
     }
 
-    /// Return the entries in this feed.
+    /// Return the base URI of this feed from its `xml:base` attribute.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Entry};
+    /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_entries(vec![Entry::default()]);
-    /// assert_eq!(feed.entries().len(), 1);
+    /// feed.set_base("http://example.com/");
+    /// assert_eq!(feed.base(), Some("http://example.com/"));
     /// ```
-    pub fn entries(&self) -> &[Entry] {
-        self.entries.as_slice()
+    pub fn base(&self) -> Option<&str> {
+        self.attrs.get("xml:base").map(String::as_str)
     }
 
-    /// Set the entries in this feed.
+    /// Set the base URI of this feed, which is written as the `xml:base` attribute of the `feed`
+    /// element and applies to every relative reference within it.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Entry};
+    /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_entries(vec![Entry::default()]);
+    /// feed.set_base("http://example.com/");
+    /// assert!(feed.to_string().contains(r#" xml:base="http://example.com/""#));
     /// ```
-    pub fn set_entries<V>(&mut self, entries: V)
+    pub fn set_base<V>(&mut self, base: V)
     where
-        V: Into<Vec<Entry>>,
+        V: IntoOption<String>,
     {
-        self.entries = entries.into();
+        match base.into_option() {
+            Some(base) => self.attrs.insert("xml:base".to_string(), base),
+            None => self.attrs.remove("xml:base"),
+        };
     }
 
-    /// Return the extensions for this feed.
+    /// Return the language that applies to an entry of this feed: its own `xml:lang` attribute
+    /// if it has one, and the language of this feed otherwise.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
-    /// use atom_syndication::Feed;
-    /// use atom_syndication::extension::{ExtensionMap, Extension};
+    /// use atom_syndication::{Entry, Feed};
     ///
-    /// let extension = Extension::default();
-    ///
-    /// let mut item_map = HashMap::<String, Vec<Extension>>::new();
-    /// item_map.insert("ext:name".to_string(), vec![extension]);
+    /// let mut feed = Feed::default();
+    /// feed.set_lang("en");
     ///
-    /// let mut extension_map = ExtensionMap::default();
-    /// extension_map.insert("ext".to_string(), item_map);
+    /// let mut entry = Entry::default();
+    /// assert_eq!(feed.entry_lang(&entry), Some("en"));
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_extensions(extension_map);
-    /// assert_eq!(feed.extensions()
-    ///                .get("ext")
-    ///                .and_then(|m| m.get("ext:name"))
-    ///                .map(|v| v.len()),
-    ///            Some(1));
+    /// entry.attrs_mut().insert("xml:lang".to_string(), "fr".to_string());
+    /// assert_eq!(feed.entry_lang(&entry), Some("fr"));
     /// ```
-    pub fn extensions(&self) -> &ExtensionMap {
-        &self.extensions
+    pub fn entry_lang<'a>(&'a self, entry: &'a Entry) -> Option<&'a str> {
+        entry
+            .attrs()
+            .get("xml:lang")
+            .map(String::as_str)
+            .or_else(|| self.lang())
     }
 
-    /// Set the extensions for this feed.
+    /// Return the base URI that applies to an entry of this feed: its own `xml:base` attribute
+    /// resolved against the base URI of this feed, or the base URI of this feed if it has none.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Feed;
-    /// use atom_syndication::extension::ExtensionMap;
+    /// use atom_syndication::{Entry, Feed};
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_extensions(ExtensionMap::default());
+    /// feed.set_base("http://example.com/blog/");
+    ///
+    /// let mut entry = Entry::default();
+    /// assert_eq!(feed.entry_base(&entry).as_deref(), Some("http://example.com/blog/"));
+    ///
+    /// entry.attrs_mut().insert("xml:base".to_string(), "2020/".to_string());
+    /// assert_eq!(feed.entry_base(&entry).as_deref(), Some("http://example.com/blog/2020/"));
     /// ```
-    pub fn set_extensions<V>(&mut self, extensions: V)
-    where
-        V: Into<ExtensionMap>,
-    {
-        self.extensions = extensions.into()
+    pub fn entry_base(&self, entry: &Entry) -> Option<String> {
+        match (self.base(), entry.attrs().get("xml:base")) {
+            (Some(base), Some(entry_base)) => Some(resolve_iri(base, entry_base)),
+            (None, Some(entry_base)) => Some(entry_base.clone()),
+            (base, None) => base.map(str::to_string),
+        }
     }
 
-    /// Return the namespaces for this feed.
+    /// Return the language of this feed from its `xml:lang` attribute as a BCP 47 language tag.
+    ///
+    /// The case of the subtags is normalized. Returns `None` if the attribute is missing or is
+    /// not a well-formed language tag.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
     /// use atom_syndication::Feed;
     ///
-    /// let mut namespaces = HashMap::new();
-    /// namespaces.insert("ext".to_string(), "http://example.com".to_string());
-    ///
-    /// let mut feed = Feed::default();
-    /// feed.set_namespaces(namespaces);
-    /// assert_eq!(feed.namespaces().get("ext").map(|s| s.as_str()), Some("http://example.com"));
+    /// let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="EN-gb"></feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.language_tag().unwrap().as_str(), "en-GB");
     /// ```
-    pub fn namespaces(&self) -> &HashMap<String, String> {
-        &self.namespaces
+    #[cfg(feature = "language-tags")]
+    pub fn language_tag(&self) -> Option<language_tags::LanguageTag> {
+        self.attrs
+            .get("xml:lang")
+            .and_then(|lang| language_tags::LanguageTag::parse(lang).ok())
     }
 
-    /// Set the namespaces for this feed.
+    /// Return the child elements of this feed that are not otherwise represented.
+    ///
+    /// These are only collected when the feed is read with fidelity mode enabled and are written
+    /// back out when serializing.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
-    /// use atom_syndication::Feed;
+    /// use atom_syndication::{Feed, ReadConfig};
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_namespaces(HashMap::new());
+    /// let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><rank>1</rank></feed>"#;
+    /// let config = ReadConfig::new().fidelity(true);
+    /// let feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    /// assert_eq!(feed.unknown_elements()[0].name(), "rank");
     /// ```
-    pub fn set_namespaces<V>(&mut self, namespaces: V)
-    where
-        V: Into<HashMap<String, String>>,
-    {
-        self.namespaces = namespaces.into()
+    pub fn unknown_elements(&self) -> &[Extension] {
+        self.unknown_elements.as_slice()
     }
 }
 
 impl FromXml for Feed {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
         let mut feed = Feed::default();
+        collect_attrs(reader, atts, &mut feed.attrs)?;
+        // namespace declarations are kept separately
+        feed.attrs
+            .retain(|key, _| key != "xmlns" && !key.starts_with("xmlns:"));
         let mut buf = Vec::new();
+        // entries that are skipped while reading still count towards the index of the next
+        let mut entry_index = 0;
 
         loop {
+            let position = reader.buffer_position();
+
             match reader.read_event(&mut buf)? {
-                Event::Start(element) => match element.name() {
-                    b"title" => {
-                        feed.title =
-                            atom_any_text(reader, element.attributes())?.unwrap_or_default()
+                Event::Start(element) => {
+                    let name = reader.decode(ctx.atom_name(element.name())).into_owned();
+                    if name == "entry" && ctx.config.is_metadata_only() {
+                        break;
                     }
-                    b"id" => feed.id = atom_text(reader)?.unwrap_or_default(),
-                    b"updated" => {
-                        feed.updated = atom_datetime(reader)?.unwrap_or_else(default_fixed_datetime)
+
+                    if name == "entry" {
+                        ctx.enter(format!("entry[{}]", entry_index));
+                    } else {
+                        ctx.enter(name.as_str());
                     }
-                    b"author" => feed
-                        .authors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    b"category" => feed
-                        .categories
-                        .push(Category::from_xml(reader, element.attributes())?),
-                    b"contributor" => feed
-                        .contributors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    b"generator" => {
-                        feed.generator = Some(Generator::from_xml(reader, element.attributes())?)
+
+                    if ctx.config.is_fidelity() {
+                        feed.element_order.push(name);
                     }
-                    b"icon" => feed.icon = atom_text(reader)?,
-                    b"link" => feed
-                        .links
-                        .push(Link::from_xml(reader, element.attributes())?),
-                    b"logo" => feed.logo = atom_text(reader)?,
-                    b"rights" => feed.rights = atom_text(reader)?,
-                    b"subtitle" => feed.subtitle = atom_text(reader)?,
-                    b"entry" => feed
-                        .entries
-                        .push(Entry::from_xml(reader, element.attributes())?),
-                    n => {
-                        if let Some((ns, name)) = extension_name(element.name()) {
-                            parse_extension(
-                                reader,
-                                element.attributes(),
-                                ns,
-                                name,
-                                &mut feed.extensions,
-                            )?;
-                        } else {
-                            reader.read_to_end(n, &mut Vec::new())?;
+
+                    match ctx.atom_name(element.name()) {
+                        b"title" => {
+                            feed.title = ctx
+                                .read_any_text(reader, element.attributes())?
+                                .unwrap_or_default()
+                        }
+                        b"id" => feed.id = ctx.read_text(reader)?.unwrap_or_default(),
+                        b"updated" => {
+                            feed.updated = ctx
+                                .read_date(reader, "updated")?
+                                .unwrap_or_else(default_fixed_datetime)
+                        }
+                        b"author" => {
+                            feed.authors
+                                .push(Person::from_xml(reader, element.attributes(), ctx)?)
+                        }
+                        b"category" => feed.categories.push(Category::from_xml(
+                            reader,
+                            element.attributes(),
+                            ctx,
+                        )?),
+                        b"contributor" => feed.contributors.push(Person::from_xml(
+                            reader,
+                            element.attributes(),
+                            ctx,
+                        )?),
+                        b"generator" => {
+                            feed.generator =
+                                Some(Generator::from_xml(reader, element.attributes(), ctx)?)
+                        }
+                        b"icon" => feed.icon = ctx.read_text(reader)?,
+                        b"link" => {
+                            feed.links
+                                .push(Link::from_xml(reader, element.attributes(), ctx)?)
+                        }
+                        b"logo" => feed.logo = ctx.read_text(reader)?,
+                        b"rights" => feed.rights = ctx.read_text(reader)?,
+                        b"subtitle" => feed.subtitle = ctx.read_text(reader)?,
+                        b"entry" => {
+                            #[cfg(feature = "tracing")]
+                            let _span =
+                                tracing::trace_span!("read_entry", index = entry_index).entered();
+
+                            ctx.entry = Some(entry_index);
+                            entry_index += 1;
+                            let depth = ctx.path.len();
+
+                            match Entry::from_xml(reader, element.attributes(), ctx) {
+                                Ok(mut entry) => {
+                                    if ctx.config.is_entry_offsets() {
+                                        entry.set_byte_range(
+                                            ctx.offset + position
+                                                ..ctx.offset + reader.buffer_position(),
+                                        );
+                                    }
+
+                                    #[cfg(feature = "tracing")]
+                                    tracing::trace!(
+                                        id = entry.id(),
+                                        links = entry.links().len(),
+                                        categories = entry.categories().len(),
+                                        "read entry"
+                                    );
+
+                                    feed.entries.push(entry);
+                                }
+                                Err(err) => {
+                                    ctx.skip_broken_entry(reader, element.name(), depth, err)?
+                                }
+                            }
+
+                            ctx.entry = None;
+                        }
+                        _ => {
+                            if let Some((ns, name)) = extension_name(ctx.atom_name(element.name()))
+                            {
+                                parse_extension(
+                                    reader,
+                                    element.attributes(),
+                                    ns,
+                                    name,
+                                    &mut feed.extensions,
+                                    ctx.config.get_max_depth(),
+                                )?;
+                            } else if ctx.config.is_fidelity() {
+                                feed.unknown_elements.push(parse_extension_element(
+                                    reader,
+                                    element.attributes(),
+                                    ctx.config.get_max_depth(),
+                                )?);
+                            } else {
+                                ctx.skip(reader, element.name())?;
+                            }
                         }
                     }
-                },
+
+                    ctx.leave();
+                }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
                 _ => {}
@@ -694,61 +2329,258 @@ impl FromXml for Feed {
             buf.clear();
         }
 
+        if let Some(policy) = ctx.config.get_link_policy() {
+            feed.enforce_link_policy(policy);
+        }
+
+        if ctx.config.is_fill_missing_updated() {
+            feed.fill_missing_updated();
+        }
+
         Ok(feed)
     }
 }
 
 impl ToXml for Feed {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>, ctx: &WriteContext) -> Result<(), XmlError> {
+        self.write_open(writer, true, ctx)?;
+        writer.write_event(Event::End(BytesEnd::borrowed(b"feed")))?;
+        Ok(())
+    }
+}
+
+impl Feed {
+    /// Clone this feed without its entries.
+    pub(crate) fn clone_without_entries(&self) -> Feed {
+        Feed {
+            title: self.title.clone(),
+            id: self.id.clone(),
+            updated: self.updated,
+            authors: self.authors.clone(),
+            categories: self.categories.clone(),
+            contributors: self.contributors.clone(),
+            generator: self.generator.clone(),
+            icon: self.icon.clone(),
+            links: self.links.clone(),
+            logo: self.logo.clone(),
+            rights: self.rights.clone(),
+            subtitle: self.subtitle.clone(),
+            entries: Vec::new(),
+            extensions: self.extensions.clone(),
+            namespaces: self.namespaces.clone(),
+            attrs: self.attrs.clone(),
+            unknown_elements: self.unknown_elements.clone(),
+            element_order: self.element_order.clone(),
+            entry_index: EntryIndex::default(),
+        }
+    }
+
+    fn apply_entry_order(&mut self, entry_order: EntryOrder) {
+        match entry_order {
+            EntryOrder::Document => {}
+            EntryOrder::NewestFirst => self
+                .entries_mut()
+                .sort_by(|a, b| b.updated().cmp(a.updated())),
+            EntryOrder::OldestFirst => self
+                .entries_mut()
+                .sort_by(|a, b| a.updated().cmp(b.updated())),
+            EntryOrder::Custom(compare) => self.entries_mut().sort_by(compare),
+        }
+    }
+
+    /// Write the start tag of this feed and its children, leaving the element open. The entries
+    /// are only written if `entries` is set.
+    pub(crate) fn write_open<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        entries: bool,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError> {
         let name = b"feed";
         let mut element = BytesStart::borrowed(name, name.len());
-        element.push_attribute(("xmlns", "http://www.w3.org/2005/Atom"));
 
-        for (ns, uri) in &self.namespaces {
-            element.push_attribute((format!("xmlns:{}", ns).as_bytes(), uri.as_bytes()));
+        if !ctx.config.is_fragment() {
+            element.push_attribute(("xmlns", "http://www.w3.org/2005/Atom"));
+
+            let mut namespaces = self.namespaces.iter().collect::<Vec<_>>();
+            namespaces.sort();
+            for (ns, uri) in namespaces {
+                element.push_attribute((format!("xmlns:{}", ns).as_bytes(), uri.as_bytes()));
+            }
         }
 
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+
         writer.write_event(Event::Start(element))?;
-        writer.write_text_element(b"title", &*self.title)?;
-        writer.write_text_element(b"id", &*self.id)?;
-        writer.write_text_element(b"updated", &*self.updated.to_rfc3339())?;
-        writer.write_objects_named(&self.authors, "author")?;
-        writer.write_objects(&self.categories)?;
-        writer.write_objects_named(&self.contributors, "contributor")?;
-
-        if let Some(ref generator) = self.generator {
-            writer.write_object(generator)?;
+
+        let mut defaults = [
+            "title",
+            "id",
+            "updated",
+            "author",
+            "category",
+            "contributor",
+            "generator",
+            "icon",
+            "link",
+            "logo",
+            "rights",
+            "subtitle",
+            "entry",
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+        defaults.extend(extension_names(&self.extensions));
+        defaults.extend(self.unknown_elements.iter().map(|e| e.name().to_string()));
+
+        write_children(
+            writer,
+            &self.element_order,
+            &defaults,
+            |writer, name, index| match name {
+                "entry" if !entries => Ok(false),
+                name => self.write_child(writer, name, index, ctx),
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Declare the given namespaces, renaming the prefixes of extensions bound to the same URIs.
+    /// A prefix of this feed that the given namespaces bind to another URI is moved to a prefix
+    /// with a numeric suffix. Returns the prefixes that were renamed.
+    pub(crate) fn apply_namespaces(
+        &mut self,
+        namespaces: &BTreeMap<String, String>,
+    ) -> HashMap<String, String> {
+        let mut renames = HashMap::new();
+        let mut moved = Vec::new();
+
+        for (prefix, uri) in &self.namespaces {
+            if let Some((preferred, _)) = namespaces.iter().find(|&(_, u)| u == uri) {
+                if preferred != prefix {
+                    renames.insert(prefix.clone(), preferred.clone());
+                }
+            } else if namespaces.contains_key(prefix) {
+                let mut renamed = prefix.clone();
+                let mut suffix = 1;
+                while namespaces.contains_key(&renamed)
+                    || self.namespaces.contains_key(&renamed)
+                    || moved.iter().any(|(p, _)| *p == renamed)
+                {
+                    renamed = format!("{}{}", prefix, suffix);
+                    suffix += 1;
+                }
+
+                renames.insert(prefix.clone(), renamed.clone());
+                moved.push((renamed, uri.clone()));
+            }
+        }
+
+        for prefix in renames.keys() {
+            self.namespaces.remove(prefix);
+        }
+
+        for (prefix, uri) in namespaces.iter().chain(moved.iter().map(|(p, u)| (p, u))) {
+            self.namespaces.insert(prefix.clone(), uri.clone());
         }
 
-        if let Some(ref icon) = self.icon {
-            writer.write_text_element(b"icon", &**icon)?;
+        if renames.is_empty() {
+            return renames;
         }
 
-        writer.write_objects(&self.links)?;
+        rename_prefixes(&mut self.extensions, &renames);
 
-        if let Some(ref logo) = self.logo {
-            writer.write_text_element(b"logo", &**logo)?;
+        for name in &mut self.element_order {
+            if let Some(renamed) = rename_prefix(name, &renames) {
+                *name = renamed;
+            }
         }
 
-        if let Some(ref rights) = self.rights {
-            writer.write_text_element(b"rights", &**rights)?;
+        for link in &mut self.links {
+            link.rename_prefixes(&renames);
         }
 
-        if let Some(ref subtitle) = self.subtitle {
-            writer.write_text_element(b"subtitle", &**subtitle)?;
+        for entry in &mut self.entries {
+            entry.rename_prefixes(&renames);
         }
 
-        writer.write_objects(&self.entries)?;
+        renames
+    }
 
-        for map in self.extensions.values() {
-            for extensions in map.values() {
-                writer.write_objects(extensions)?;
+    fn write_child<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        name: &str,
+        index: usize,
+        ctx: &WriteContext,
+    ) -> Result<bool, XmlError> {
+        match name {
+            "title" if index == 0 => writer.write_text_construct(b"title", &*self.title, ctx)?,
+            "id" if index == 0 => writer.write_text_element(b"id", &*self.id)?,
+            "updated" if index == 0 => {
+                writer.write_text_element(b"updated", &*self.updated.to_rfc3339())?
             }
-        }
+            "author" => match self.authors.get(index) {
+                Some(author) => writer.write_object_named(author, "author", ctx)?,
+                None => return Ok(false),
+            },
+            "category" => match self.categories.get(index) {
+                Some(category) => writer.write_object(category, ctx)?,
+                None => return Ok(false),
+            },
+            "contributor" => match self.contributors.get(index) {
+                Some(contributor) => writer.write_object_named(contributor, "contributor", ctx)?,
+                None => return Ok(false),
+            },
+            "generator" => match self.generator.iter().nth(index) {
+                Some(generator) => writer.write_object(generator, ctx)?,
+                None => return Ok(false),
+            },
+            "icon" => match self.icon.iter().nth(index) {
+                Some(icon) => writer.write_text_element(b"icon", &**icon)?,
+                None => return Ok(false),
+            },
+            "link" => match self.links.get(index) {
+                Some(link) => writer.write_object(link, ctx)?,
+                None => return Ok(false),
+            },
+            "logo" => match self.logo.iter().nth(index) {
+                Some(logo) => writer.write_text_element(b"logo", &**logo)?,
+                None => return Ok(false),
+            },
+            "rights" => match self.rights.iter().nth(index) {
+                Some(rights) => writer.write_text_construct(b"rights", &**rights, ctx)?,
+                None => return Ok(false),
+            },
+            "subtitle" => match self.subtitle.iter().nth(index) {
+                Some(subtitle) => writer.write_text_construct(b"subtitle", &**subtitle, ctx)?,
+                None => return Ok(false),
+            },
+            "entry" => match self.entries.get(index) {
+                Some(entry) => writer.write_object(entry, ctx)?,
+                None => return Ok(false),
+            },
+            "title" | "id" | "updated" => return Ok(false),
+            name => {
+                let element = match extension_name(name.as_bytes()) {
+                    Some(_) => find_extension(&self.extensions, name, index),
+                    None => self
+                        .unknown_elements
+                        .iter()
+                        .filter(|element| element.name() == name)
+                        .nth(index),
+                };
 
-        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+                match element {
+                    Some(element) => writer.write_object(element, ctx)?,
+                    None => return Ok(false),
+                }
+            }
+        }
 
-        Ok(())
+        Ok(true)
     }
 }
 
@@ -756,7 +2588,64 @@ impl FromStr for Feed {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Error> {
-        Feed::read_from(s.as_bytes())
+        Feed::read_from_str(s)
+    }
+}
+
+impl TryFrom<&str> for Feed {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Error> {
+        Feed::read_from_str(s)
+    }
+}
+
+impl TryFrom<&[u8]> for Feed {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        Feed::read_from_slice(bytes)
+    }
+}
+
+impl IntoIterator for Feed {
+    type Item = Entry;
+    type IntoIter = vec::IntoIter<Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Feed {
+    type Item = &'a Entry;
+    type IntoIter = slice::Iter<'a, Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl FromIterator<Entry> for Feed {
+    /// Create a feed with the given entries. The updated date of the feed is the latest of its
+    /// entries, and its other metadata is left empty.
+    fn from_iter<I: IntoIterator<Item = Entry>>(entries: I) -> Self {
+        let mut feed = Feed::default();
+        feed.extend(entries);
+        if let Some(updated) = feed.entries.iter().map(Entry::updated).max() {
+            feed.updated = *updated;
+        }
+        feed
+    }
+}
+
+impl Extend<Entry> for Feed {
+    fn extend<I: IntoIterator<Item = Entry>>(&mut self, entries: I) {
+        self.entry_index.sync(&self.entries);
+        for entry in entries {
+            self.entries.push(entry);
+            self.entry_index.push(&self.entries);
+        }
     }
 }
 
@@ -786,6 +2675,10 @@ impl Default for Feed {
             entries: Vec::new(),
             extensions: ExtensionMap::default(),
             namespaces: HashMap::default(),
+            attrs: BTreeMap::new(),
+            unknown_elements: Vec::new(),
+            element_order: Vec::new(),
+            entry_index: EntryIndex::default(),
         }
     }
 }
@@ -0,0 +1,404 @@
+//! Conversions between this crate's types and the unified model of the `feed-rs` crate.
+//!
+//! `feed-rs` parses Atom, RSS and JSON Feed documents into a single model, while this crate
+//! writes Atom precisely. Converting a `feed_rs::model::Feed` into a `Feed` allows a feed read
+//! in any format to be written as Atom.
+//!
+//! The `feed-rs` model has no place for extensions, unknown elements or the type of text
+//! constructs other than content, so these are lost when converting to it. Text is converted
+//! as is, so HTML stays markup.
+
+use chrono::Utc;
+use feed_rs::model;
+
+use crate::category::Category;
+use crate::content::Content;
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::generator::Generator;
+use crate::link::Link;
+use crate::person::Person;
+use crate::source::Source;
+use crate::util::{default_fixed_datetime, FixedDateTime};
+
+fn text(text: Option<model::Text>) -> Option<String> {
+    text.map(|text| text.content)
+}
+
+fn model_text(content: &str) -> model::Text {
+    model::Text {
+        content_type: "text/plain".parse().unwrap(),
+        src: None,
+        content: content.to_string(),
+    }
+}
+
+fn date(date: Option<chrono::DateTime<Utc>>) -> FixedDateTime {
+    date.map(FixedDateTime::from)
+        .unwrap_or_else(default_fixed_datetime)
+}
+
+fn model_date(date: &FixedDateTime) -> Option<chrono::DateTime<Utc>> {
+    if *date == default_fixed_datetime() {
+        None
+    } else {
+        Some(date.with_timezone(&Utc))
+    }
+}
+
+fn model_image(uri: Option<&str>) -> Option<model::Image> {
+    uri.map(|uri| model::Image {
+        uri: uri.to_string(),
+        title: None,
+        link: None,
+        width: None,
+        height: None,
+        description: None,
+    })
+}
+
+/// Return the `type` attribute of Atom content for a media type, which is omitted for text.
+fn content_type(media_type: &str) -> Option<String> {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    match essence {
+        "text/plain" => None,
+        "text/html" => Some("html".to_string()),
+        "application/xhtml+xml" => Some("xhtml".to_string()),
+        essence => Some(essence.to_string()),
+    }
+}
+
+/// Return the media type for the `type` attribute of Atom content.
+fn media_type(content_type: Option<&str>) -> &str {
+    match content_type {
+        None | Some("text") => "text/plain",
+        Some("html") => "text/html",
+        Some("xhtml") => "application/xhtml+xml",
+        Some(content_type) => content_type,
+    }
+}
+
+impl From<model::Person> for Person {
+    fn from(person: model::Person) -> Self {
+        let mut result = Person::default();
+        result.set_name(person.name);
+        result.set_email(person.email);
+        result.set_uri(person.uri);
+        result
+    }
+}
+
+impl<'a> From<&'a Person> for model::Person {
+    fn from(person: &'a Person) -> Self {
+        model::Person {
+            name: person.name().to_string(),
+            uri: person.uri().map(String::from),
+            email: person.email().map(String::from),
+        }
+    }
+}
+
+impl From<model::Category> for Category {
+    fn from(category: model::Category) -> Self {
+        let mut result = Category::default();
+        result.set_term(category.term);
+        result.set_scheme(category.scheme);
+        result.set_label(category.label);
+        result
+    }
+}
+
+impl<'a> From<&'a Category> for model::Category {
+    fn from(category: &'a Category) -> Self {
+        model::Category {
+            term: category.term().to_string(),
+            scheme: category.scheme().map(String::from),
+            label: category.label().map(String::from),
+            subcategories: Vec::new(),
+        }
+    }
+}
+
+impl From<model::Generator> for Generator {
+    fn from(generator: model::Generator) -> Self {
+        let mut result = Generator::default();
+        result.set_value(generator.content);
+        result.set_uri(generator.uri);
+        result.set_version(generator.version);
+        result
+    }
+}
+
+impl<'a> From<&'a Generator> for model::Generator {
+    fn from(generator: &'a Generator) -> Self {
+        model::Generator {
+            content: generator.value().to_string(),
+            uri: generator.uri().map(String::from),
+            version: generator.version().map(String::from),
+        }
+    }
+}
+
+impl From<model::Link> for Link {
+    fn from(link: model::Link) -> Self {
+        let mut result = Link::default();
+        result.set_href(link.href);
+        if let Some(rel) = link.rel {
+            result.set_rel(rel);
+        }
+        result.set_hreflang(link.href_lang.and_then(|lang| lang.parse().ok()));
+        result.set_mime_type(link.media_type);
+        result.set_title(link.title);
+        result.set_length(link.length);
+        result
+    }
+}
+
+impl<'a> From<&'a Link> for model::Link {
+    fn from(link: &'a Link) -> Self {
+        model::Link {
+            href: link.href().to_string(),
+            rel: Some(link.rel().to_string()),
+            media_type: link.mime_type().map(String::from),
+            href_lang: link.hreflang().map(|lang| lang.as_str().to_string()),
+            title: link.title().map(String::from),
+            length: link.length(),
+        }
+    }
+}
+
+impl From<model::Content> for Content {
+    fn from(content: model::Content) -> Self {
+        let mut result = Content::default();
+        result.set_value(content.body);
+        result.set_src(content.src.map(|src| src.href));
+        result.set_content_type(self::content_type(content.content_type.as_str()));
+        result
+    }
+}
+
+impl<'a> From<&'a Content> for model::Content {
+    fn from(content: &'a Content) -> Self {
+        let media_type = media_type(content.content_type());
+        model::Content {
+            body: content.value().map(String::from),
+            content_type: media_type
+                .parse()
+                .unwrap_or_else(|_| "text/plain".parse().unwrap()),
+            length: None,
+            src: content.src().map(|src| model::Link {
+                href: src.to_string(),
+                rel: None,
+                media_type: Some(media_type.to_string()),
+                href_lang: None,
+                title: None,
+                length: None,
+            }),
+        }
+    }
+}
+
+impl From<model::Entry> for Entry {
+    /// Create an entry from a `feed-rs` entry.
+    ///
+    /// A missing title or updated date is left empty. The URL of the source feed, if any,
+    /// becomes the id and `self` link of the source.
+    fn from(entry: model::Entry) -> Self {
+        let mut result = Entry::default();
+        result.set_id(entry.id);
+        result.set_title(text(entry.title).unwrap_or_default());
+        result.set_updated(date(entry.updated));
+        result.set_authors(
+            entry
+                .authors
+                .into_iter()
+                .map(Person::from)
+                .collect::<Vec<_>>(),
+        );
+        result.set_categories(
+            entry
+                .categories
+                .into_iter()
+                .map(Category::from)
+                .collect::<Vec<_>>(),
+        );
+        result.set_contributors(
+            entry
+                .contributors
+                .into_iter()
+                .map(Person::from)
+                .collect::<Vec<_>>(),
+        );
+        result.set_links(entry.links.into_iter().map(Link::from).collect::<Vec<_>>());
+        result.set_published(entry.published.map(FixedDateTime::from));
+        result.set_rights(text(entry.rights));
+        result.set_source(entry.source.map(|url| {
+            let mut source = Source::default();
+            source.set_id(url.clone());
+            source.set_links(vec![Link::default().with_rel("self").with_href(url)]);
+            source
+        }));
+        result.set_summary(text(entry.summary));
+        result.set_content(entry.content.map(Content::from));
+
+        if let Some(lang) = entry.language {
+            result.attrs_mut().insert("xml:lang".to_string(), lang);
+        }
+        if let Some(base) = entry.base {
+            result.attrs_mut().insert("xml:base".to_string(), base);
+        }
+
+        result
+    }
+}
+
+impl<'a> From<&'a Entry> for model::Entry {
+    /// Create a `feed-rs` entry from an entry.
+    ///
+    /// The source is represented by the URL of its `self` link, or its id if it has none.
+    fn from(entry: &'a Entry) -> Self {
+        model::Entry {
+            id: entry.id().to_string(),
+            title: Some(model_text(entry.title())),
+            updated: model_date(entry.updated()),
+            authors: entry.authors().iter().map(model::Person::from).collect(),
+            content: entry.content().map(model::Content::from),
+            links: entry.links().iter().map(model::Link::from).collect(),
+            summary: entry.summary().map(model_text),
+            categories: entry
+                .categories()
+                .iter()
+                .map(model::Category::from)
+                .collect(),
+            contributors: entry
+                .contributors()
+                .iter()
+                .map(model::Person::from)
+                .collect(),
+            published: entry.published().map(|date| date.with_timezone(&Utc)),
+            source: entry.source().map(|source| {
+                source
+                    .links()
+                    .iter()
+                    .find(|link| link.rel() == "self")
+                    .map_or(source.id(), |link| link.href())
+                    .to_string()
+            }),
+            rights: entry.rights().map(model_text),
+            media: Vec::new(),
+            language: entry.attrs().get("xml:lang").cloned(),
+            base: entry.attrs().get("xml:base").cloned(),
+        }
+    }
+}
+
+impl From<model::Feed> for Feed {
+    /// Create a feed from a `feed-rs` feed of any format.
+    ///
+    /// A missing title or updated date is left empty. The description becomes the subtitle and
+    /// the URLs of the icon and logo images are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let rss = r#"<rss version="2.0"><channel>
+    ///     <title>Channel Title</title>
+    ///     <link>http://example.com/</link>
+    ///     <description>Channel description</description>
+    ///     <item><guid>urn:item:1</guid><title>Item Title</title></item>
+    /// </channel></rss>"#;
+    ///
+    /// let feed = Feed::from(feed_rs::parser::parse(rss.as_bytes()).unwrap());
+    /// assert_eq!(feed.title(), "Channel Title");
+    /// assert_eq!(feed.subtitle(), Some("Channel description"));
+    /// assert_eq!(feed.entries()[0].id(), "urn:item:1");
+    /// ```
+    fn from(feed: model::Feed) -> Self {
+        let mut result = Feed::default();
+        result.set_id(feed.id);
+        result.set_title(text(feed.title).unwrap_or_default());
+        result.set_updated(date(feed.updated));
+        result.set_authors(
+            feed.authors
+                .into_iter()
+                .map(Person::from)
+                .collect::<Vec<_>>(),
+        );
+        result.set_categories(
+            feed.categories
+                .into_iter()
+                .map(Category::from)
+                .collect::<Vec<_>>(),
+        );
+        result.set_contributors(
+            feed.contributors
+                .into_iter()
+                .map(Person::from)
+                .collect::<Vec<_>>(),
+        );
+        result.set_generator(feed.generator.map(Generator::from));
+        result.set_icon(feed.icon.map(|icon| icon.uri));
+        result.set_links(feed.links.into_iter().map(Link::from).collect::<Vec<_>>());
+        result.set_logo(feed.logo.map(|logo| logo.uri));
+        result.set_rights(text(feed.rights));
+        result.set_subtitle(text(feed.description));
+        result.set_lang(feed.language);
+        result.set_entries(
+            feed.entries
+                .into_iter()
+                .map(Entry::from)
+                .collect::<Vec<_>>(),
+        );
+        result
+    }
+}
+
+impl<'a> From<&'a Feed> for model::Feed {
+    /// Create a `feed-rs` feed from a feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// feed.set_entries(vec![Entry::default().with_id("urn:entry:1")]);
+    ///
+    /// let model = feed_rs::model::Feed::from(&feed);
+    /// assert_eq!(model.title.unwrap().content, "Feed Title");
+    /// assert_eq!(model.entries[0].id, "urn:entry:1");
+    /// ```
+    fn from(feed: &'a Feed) -> Self {
+        model::Feed {
+            feed_type: model::FeedType::Atom,
+            id: feed.id().to_string(),
+            title: Some(model_text(feed.title())),
+            updated: model_date(feed.updated()),
+            authors: feed.authors().iter().map(model::Person::from).collect(),
+            description: feed.subtitle().map(model_text),
+            links: feed.links().iter().map(model::Link::from).collect(),
+            categories: feed
+                .categories()
+                .iter()
+                .map(model::Category::from)
+                .collect(),
+            contributors: feed
+                .contributors()
+                .iter()
+                .map(model::Person::from)
+                .collect(),
+            generator: feed.generator().map(model::Generator::from),
+            icon: model_image(feed.icon()),
+            language: feed.lang().map(String::from),
+            logo: model_image(feed.logo()),
+            published: None,
+            rating: None,
+            rights: feed.rights().map(model_text),
+            ttl: None,
+            entries: feed.entries().iter().map(model::Entry::from).collect(),
+        }
+    }
+}
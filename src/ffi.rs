@@ -0,0 +1,261 @@
+//! A C interface for reading and writing feeds.
+//!
+//! Feeds are returned as opaque pointers which must be released with `atom_feed_free`. Strings
+//! returned by these functions are allocated by this library and must be released with
+//! `atom_string_free`. Entries are borrowed from their feed and must not be used after the feed
+//! has been freed.
+//!
+//! The functions are declared in `include/atom_syndication.h`. The crate is not built as a
+//! shared library by default; build one with
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! ```c
+//! char *error = NULL;
+//! AtomFeed *feed = atom_feed_parse(xml, &error);
+//! if (feed == NULL) {
+//!     fprintf(stderr, "%s\n", error);
+//!     atom_string_free(error);
+//!     return;
+//! }
+//!
+//! char *title = atom_feed_title(feed);
+//! printf("%s\n", title);
+//! atom_string_free(title);
+//!
+//! for (size_t i = 0; i < atom_feed_entry_count(feed); i++) {
+//!     const AtomEntry *entry = atom_feed_entry(feed, i);
+//!     char *id = atom_entry_id(entry);
+//!     printf("%s\n", id);
+//!     atom_string_free(id);
+//! }
+//!
+//! atom_feed_free(feed);
+//! ```
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::entry::Entry;
+use crate::feed::Feed;
+
+/// An opaque handle to a feed.
+pub type AtomFeed = Feed;
+
+/// An opaque handle to an entry.
+pub type AtomEntry = Entry;
+
+fn into_c_string<S: Into<Vec<u8>>>(s: S) -> *mut c_char {
+    let mut bytes = s.into();
+    bytes.retain(|&b| b != 0);
+    CString::new(bytes)
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+fn optional_c_string(s: Option<&str>) -> *mut c_char {
+    s.map_or(ptr::null_mut(), into_c_string)
+}
+
+/// Parse a feed from a NUL-terminated UTF-8 string.
+///
+/// Returns null if the feed could not be parsed. In that case, if `error` is not null, it is set
+/// to a description of the error which must be released with `atom_string_free`.
+///
+/// # Safety
+///
+/// `xml` must be a valid pointer to a NUL-terminated string, and `error` must be null or a valid
+/// pointer to a `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_feed_parse(
+    xml: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut AtomFeed {
+    if xml.is_null() {
+        return ptr::null_mut();
+    }
+
+    let result = CStr::from_ptr(xml)
+        .to_str()
+        .map_err(|err| err.to_string())
+        .and_then(|xml| Feed::read_from_str(xml).map_err(|err| err.to_string()));
+
+    match result {
+        Ok(feed) => Box::into_raw(Box::new(feed)),
+        Err(err) => {
+            if !error.is_null() {
+                *error = into_c_string(err);
+            }
+
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Release a feed returned by `atom_feed_parse`.
+///
+/// # Safety
+///
+/// `feed` must be null or a pointer returned by `atom_feed_parse` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn atom_feed_free(feed: *mut AtomFeed) {
+    if !feed.is_null() {
+        drop(Box::from_raw(feed));
+    }
+}
+
+/// Release a string returned by this library.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn atom_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Serialize a feed to XML.
+///
+/// # Safety
+///
+/// `feed` must be a valid pointer returned by `atom_feed_parse`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_feed_to_string(feed: *const AtomFeed) -> *mut c_char {
+    match feed.as_ref() {
+        Some(feed) => into_c_string(feed.to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Return the title of a feed.
+///
+/// # Safety
+///
+/// `feed` must be a valid pointer returned by `atom_feed_parse`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_feed_title(feed: *const AtomFeed) -> *mut c_char {
+    optional_c_string(feed.as_ref().map(Feed::title))
+}
+
+/// Return the id of a feed.
+///
+/// # Safety
+///
+/// `feed` must be a valid pointer returned by `atom_feed_parse`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_feed_id(feed: *const AtomFeed) -> *mut c_char {
+    optional_c_string(feed.as_ref().map(Feed::id))
+}
+
+/// Return the last updated date of a feed as an RFC 3339 string.
+///
+/// # Safety
+///
+/// `feed` must be a valid pointer returned by `atom_feed_parse`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_feed_updated(feed: *const AtomFeed) -> *mut c_char {
+    match feed.as_ref() {
+        Some(feed) => into_c_string(feed.updated().to_rfc3339()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Return the number of entries in a feed.
+///
+/// # Safety
+///
+/// `feed` must be a valid pointer returned by `atom_feed_parse`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_feed_entry_count(feed: *const AtomFeed) -> usize {
+    feed.as_ref().map_or(0, |feed| feed.entries().len())
+}
+
+/// Return the entry at `index` in a feed, or null if the index is out of bounds.
+///
+/// The entry is borrowed from the feed and is valid until the feed is freed.
+///
+/// # Safety
+///
+/// `feed` must be a valid pointer returned by `atom_feed_parse`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_feed_entry(feed: *const AtomFeed, index: usize) -> *const AtomEntry {
+    feed.as_ref()
+        .and_then(|feed| feed.entries().get(index))
+        .map_or(ptr::null(), |entry| entry as *const Entry)
+}
+
+/// Return the title of an entry.
+///
+/// # Safety
+///
+/// `entry` must be a valid pointer returned by `atom_feed_entry`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_entry_title(entry: *const AtomEntry) -> *mut c_char {
+    optional_c_string(entry.as_ref().map(Entry::title))
+}
+
+/// Return the id of an entry.
+///
+/// # Safety
+///
+/// `entry` must be a valid pointer returned by `atom_feed_entry`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_entry_id(entry: *const AtomEntry) -> *mut c_char {
+    optional_c_string(entry.as_ref().map(Entry::id))
+}
+
+/// Return the last updated date of an entry as an RFC 3339 string.
+///
+/// # Safety
+///
+/// `entry` must be a valid pointer returned by `atom_feed_entry`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_entry_updated(entry: *const AtomEntry) -> *mut c_char {
+    match entry.as_ref() {
+        Some(entry) => into_c_string(entry.updated().to_rfc3339()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Return the summary of an entry, or null if it has none.
+///
+/// # Safety
+///
+/// `entry` must be a valid pointer returned by `atom_feed_entry`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_entry_summary(entry: *const AtomEntry) -> *mut c_char {
+    optional_c_string(entry.as_ref().and_then(Entry::summary))
+}
+
+/// Return the content of an entry, or null if it has none.
+///
+/// # Safety
+///
+/// `entry` must be a valid pointer returned by `atom_feed_entry`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_entry_content(entry: *const AtomEntry) -> *mut c_char {
+    optional_c_string(
+        entry
+            .as_ref()
+            .and_then(Entry::content)
+            .and_then(|content| content.value()),
+    )
+}
+
+/// Return the URI of the alternate link of an entry, or null if it has none.
+///
+/// # Safety
+///
+/// `entry` must be a valid pointer returned by `atom_feed_entry`.
+#[no_mangle]
+pub unsafe extern "C" fn atom_entry_link(entry: *const AtomEntry) -> *mut c_char {
+    optional_c_string(entry.as_ref().and_then(|entry| {
+        entry
+            .links()
+            .iter()
+            .find(|link| link.rel() == "alternate")
+            .map(|link| link.href())
+    }))
+}
@@ -0,0 +1,91 @@
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::Error;
+use crate::feed::Feed;
+
+/// The number of temporary files created by this process, which makes their names unique.
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Create a new temporary file next to the file at the given path.
+///
+/// Names that are already taken, such as by a file left behind by an earlier process with the
+/// same id, are skipped.
+fn create_temp_file(path: &Path, file_name: &OsStr) -> io::Result<(PathBuf, File)> {
+    loop {
+        let mut temp_name = OsString::from(".");
+        temp_name.push(file_name);
+        temp_name.push(format!(
+            ".{}.{}.tmp",
+            process::id(),
+            TEMP_FILES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let temp_path = path.with_file_name(temp_name);
+
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+        {
+            Ok(file) => return Ok((temp_path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+impl Feed {
+    /// Attempt to read an Atom feed from the file at the given path.
+    ///
+    /// A byte order mark at the start of the file is skipped, and UTF-16 files are converted to
+    /// UTF-8 before being read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::read_from_file("example.xml").unwrap();
+    /// ```
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Feed, Error> {
+        let bytes = fs::read(path)?;
+        Feed::read_from_slice(&bytes)
+    }
+
+    /// Attempt to write this Atom feed to the file at the given path.
+    ///
+    /// The feed is first written to a new temporary file in the same directory, which is then
+    /// renamed into place, so that readers never see a partially written file. Each write uses
+    /// its own temporary file, so threads writing the same path do not interfere.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::read_from_file("example.xml").unwrap();
+    /// feed.write_to_file("out.xml").unwrap();
+    /// ```
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path is not a file"))?;
+
+        let (temp_path, file) = create_temp_file(path, file_name)?;
+        let result = self
+            .write_to(file)
+            .and_then(|file| file.sync_all().map_err(Error::from))
+            .and_then(|_| fs::rename(&temp_path, path).map_err(Error::from));
+
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+
+        result
+    }
+}
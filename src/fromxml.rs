@@ -1,10 +1,270 @@
+//! Parsing of elements from XML.
+//!
+//! Implement [`FromXml`] to read a custom element type with the same machinery used for the
+//! elements of a feed. The helpers re-exported here read the text, dates and attributes of an
+//! element the way the rest of the crate does.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::BufRead;
+//!
+//! use atom_syndication::fromxml::{atom_text, FromXml, ReadContext};
+//! use atom_syndication::{Error, ReadConfig};
+//! use quick_xml::events::attributes::Attributes;
+//! use quick_xml::events::Event;
+//! use quick_xml::Reader;
+//!
+//! struct Rating(String);
+//!
+//! impl FromXml for Rating {
+//!     fn from_xml<R: BufRead>(
+//!         reader: &mut Reader<R>,
+//!         _: Attributes,
+//!         _: &mut ReadContext,
+//!     ) -> Result<Self, Error> {
+//!         Ok(Rating(atom_text(reader)?.unwrap_or_default()))
+//!     }
+//! }
+//!
+//! let mut reader = Reader::from_str("<media:rating>adult</media:rating>");
+//! reader.trim_text(true);
+//! let mut buf = Vec::new();
+//! let mut ctx = ReadContext::new(ReadConfig::default());
+//!
+//! let rating = match reader.read_event(&mut buf).unwrap() {
+//!     Event::Start(element) => {
+//!         Rating::from_xml(&mut reader, element.attributes(), &mut ctx).unwrap()
+//!     }
+//!     _ => unreachable!(),
+//! };
+//! assert_eq!(rating.0, "adult");
+//! ```
+
+use std::collections::BTreeMap;
 use std::io::BufRead;
 
 use quick_xml::events::attributes::Attributes;
+use quick_xml::events::Event;
 use quick_xml::Reader;
 
+use crate::config::ReadConfig;
 use crate::error::Error;
+use crate::intern::{Interner, Slot, Text};
+use crate::util::{read_any_text, read_text, FixedDateTime};
+use crate::validate::{Severity, ValidationIssue};
+
+pub use crate::util::{atom_any_text, atom_datetime, atom_text, atom_xhtml, collect_attrs};
+
+/// The URI of the Atom namespace.
+pub const ATOM_NAMESPACE: &[u8] = b"http://www.w3.org/2005/Atom";
+
+/// The state shared by the elements of a document while it is being read.
+pub struct ReadContext {
+    /// The options the document is read with.
+    pub config: ReadConfig,
+    /// The prefix bound to the Atom namespace by the document, if any.
+    pub(crate) atom_prefix: Option<Vec<u8>>,
+    /// The path of the element being read.
+    pub(crate) path: Vec<String>,
+    /// The index of the entry being read, if any.
+    pub(crate) entry: Option<usize>,
+    /// The number of times each unknown element has been skipped.
+    pub(crate) skipped: BTreeMap<String, usize>,
+    /// The problems found in the document that did not stop it from being read.
+    pub(crate) issues: Vec<ValidationIssue>,
+    /// The offset in the document of the input being read.
+    pub(crate) offset: usize,
+    /// The text shared by people and categories, if enabled by `ReadConfig::intern`.
+    pub(crate) interner: Option<Interner>,
+}
+
+impl ReadContext {
+    /// Create a context for reading a document with the given options.
+    pub fn new(config: ReadConfig) -> Self {
+        let interner = if config.is_intern() {
+            Some(Interner::default())
+        } else {
+            None
+        };
+
+        ReadContext {
+            config,
+            atom_prefix: None,
+            path: Vec::new(),
+            entry: None,
+            skipped: BTreeMap::new(),
+            issues: Vec::new(),
+            offset: 0,
+            interner,
+        }
+    }
+
+    /// Return the value as text shared with the same text read before, if enabled by
+    /// `ReadConfig::intern`.
+    pub(crate) fn intern<T: Slot>(&mut self, value: T) -> Text<T> {
+        match self.interner {
+            Some(ref mut interner) => interner.intern(value),
+            None => Text::Owned(value),
+        }
+    }
+
+    /// Record that reading has moved into a child element.
+    pub(crate) fn enter<S: Into<String>>(&mut self, name: S) {
+        self.path.push(name.into());
+    }
+
+    /// Record that the child element entered last has been read.
+    pub(crate) fn leave(&mut self) {
+        self.path.pop();
+    }
+
+    /// Skip an unknown element whose start tag has just been read, recording its name.
+    pub(crate) fn skip<B: BufRead>(
+        &mut self,
+        reader: &mut Reader<B>,
+        name: &[u8],
+    ) -> Result<(), Error> {
+        let local_name = reader.decode(self.atom_name(name)).into_owned();
+        *self.skipped.entry(local_name).or_insert(0) += 1;
+        reader.read_to_end(name, &mut Vec::new())?;
+        Ok(())
+    }
+
+    /// Record a problem with the element being read that did not stop the document from being
+    /// read.
+    pub(crate) fn report<M: Into<String>>(&mut self, severity: Severity, message: M) {
+        let location = format!("/{}", self.path.join("/"));
+        self.issues
+            .push(ValidationIssue::new(severity, location, message));
+    }
+
+    /// Read the text content of an element up to its end tag, as described for [`atom_text`].
+    ///
+    /// With `ReadConfig::html_entities`, the named character references of HTML are resolved.
+    pub(crate) fn read_text<B: BufRead>(
+        &self,
+        reader: &mut Reader<B>,
+    ) -> Result<Option<String>, Error> {
+        read_text(reader, self.config.is_html_entities())
+    }
+
+    /// Read the content of a text construct up to its end tag, as described for
+    /// [`atom_any_text`].
+    ///
+    /// With `ReadConfig::html_entities`, the named character references of HTML are resolved in
+    /// text that is not XHTML.
+    pub(crate) fn read_any_text<B: BufRead>(
+        &self,
+        reader: &mut Reader<B>,
+        atts: Attributes,
+    ) -> Result<Option<String>, Error> {
+        read_any_text(reader, atts, self.config.is_html_entities())
+    }
+
+    /// Read the RFC 3339 date of the element `name` up to its end tag.
+    ///
+    /// With `ReadConfig::lenient_dates`, a malformed date is reported as a warning and read as
+    /// missing rather than failing.
+    pub(crate) fn read_date<B: BufRead>(
+        &mut self,
+        reader: &mut Reader<B>,
+        name: &str,
+    ) -> Result<Option<FixedDateTime>, Error> {
+        match atom_datetime(reader) {
+            Err(Error::InvalidDate { value }) if self.config.is_lenient_dates() => {
+                self.report(
+                    Severity::Warning,
+                    format!(
+                        "{} date is not formatted as described by RFC 3339: {}",
+                        name, value
+                    ),
+                );
+                Ok(None)
+            }
+            result => result,
+        }
+    }
+
+    /// Recover from an error in the entry element `name` when `ReadConfig::skip_broken_entries`
+    /// is enabled, by reporting the error and skipping the rest of the entry. `depth` is the
+    /// length of the path of the entry.
+    ///
+    /// Returns the error if it cannot be recovered from.
+    pub(crate) fn skip_broken_entry<B: BufRead>(
+        &mut self,
+        reader: &mut Reader<B>,
+        name: &[u8],
+        depth: usize,
+        err: Error,
+    ) -> Result<(), Error> {
+        // the XML reader cannot continue after an error of its own
+        let recoverable = match err {
+            Error::Xml(_) | Error::Io(_) | Error::TooLarge { .. } | Error::Eof => false,
+            _ => self.config.is_skip_broken_entries(),
+        };
+        if !recoverable {
+            return Err(err);
+        }
+
+        let mut buf = Vec::new();
+        let mut nested = 0usize;
+        loop {
+            match reader.read_event(&mut buf) {
+                Ok(Event::Start(element)) if element.name() == name => nested += 1,
+                Ok(Event::End(element)) if element.name() == name => {
+                    if nested == 0 {
+                        break;
+                    }
+                    nested -= 1;
+                }
+                Ok(Event::Eof) | Err(_) => return Err(err),
+                Ok(_) => {}
+            }
+
+            buf.clear();
+        }
+
+        self.report(Severity::Error, err.to_string());
+        self.path.truncate(depth);
+        Ok(())
+    }
+
+    /// Attach the position that reading stopped at to an error.
+    pub(crate) fn context(&self, err: Error) -> Error {
+        if self.path.is_empty() {
+            return err;
+        }
+
+        Error::Context {
+            path: format!("/{}", self.path.join("/")),
+            entry: self.entry,
+            source: Box::new(err),
+        }
+    }
+
+    /// Return the local name of an element, stripping the prefix if it is bound to the Atom
+    /// namespace.
+    pub(crate) fn atom_name<'a>(&self, name: &'a [u8]) -> &'a [u8] {
+        match self.atom_prefix {
+            Some(ref prefix)
+                if name.starts_with(prefix) && name.get(prefix.len()) == Some(&b':') =>
+            {
+                &name[prefix.len() + 1..]
+            }
+            _ => name,
+        }
+    }
+}
 
+/// An element that can be read from XML.
 pub trait FromXml: Sized {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error>;
+    /// Read the element from `reader`, which is positioned just after its start tag. `atts` are
+    /// the attributes of the start tag. Reading consumes everything up to and including the
+    /// matching end tag.
+    fn from_xml<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error>;
 }
@@ -0,0 +1,254 @@
+//! Generation of arbitrary feeds for property testing.
+//!
+//! The values generated only contain text that survives being written and read back, so a feed
+//! written with `to_string` and parsed again compares equal to the original.
+
+use std::collections::HashMap;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use chrono::{FixedOffset, TimeZone};
+
+use crate::category::Category;
+use crate::content::Content;
+use crate::entry::Entry;
+use crate::extension::{Extension, ExtensionMap};
+use crate::feed::Feed;
+use crate::generator::Generator;
+use crate::lang::LanguageTag;
+use crate::link::Link;
+use crate::person::Person;
+use crate::source::Source;
+use crate::util::FixedDateTime;
+
+/// The prefix of the extensions generated for feeds and entries.
+const EXTENSION_PREFIX: &str = "ext";
+
+/// The namespace bound to `EXTENSION_PREFIX` in generated feeds.
+const EXTENSION_NAMESPACE: &str = "http://example.com/ext";
+
+/// Characters that need no escaping in text or attribute values.
+const TEXT_CHARS: &[char] = &[
+    'a', 'b', 'c', 'x', 'y', 'z', 'A', 'Z', '0', '9', ' ', '.', ',', '-', '_', '/', ':', '#', '?',
+    '=', '!', 'é', 'ß', '€', '中', '😀',
+];
+
+const LOCAL_NAMES: &[&str] = &["alpha", "beta", "gamma", "delta"];
+const LINK_RELS: &[&str] = &["alternate", "related", "self", "enclosure", "via"];
+const LANGUAGE_TAGS: &[&str] = &["en", "en-US", "fr", "zh-Hant-TW"];
+const MIME_TYPES: &[&str] = &["text/html", "application/atom+xml", "audio/mpeg"];
+const TEXT_TYPES: &[&str] = &["text", "html"];
+
+/// Generate text that is not empty and has no surrounding whitespace.
+fn text(u: &mut Unstructured<'_>) -> Result<String> {
+    let len = u.int_in_range(1..=16)?;
+    let mut text = String::with_capacity(len);
+    for _ in 0..len {
+        text.push(*u.choose(TEXT_CHARS)?);
+    }
+
+    let trimmed = text.trim();
+    Ok(if trimmed.is_empty() {
+        "text".to_string()
+    } else {
+        trimmed.to_string()
+    })
+}
+
+fn optional_text(u: &mut Unstructured<'_>) -> Result<Option<String>> {
+    Ok(if u.arbitrary()? { Some(text(u)?) } else { None })
+}
+
+fn choice(u: &mut Unstructured<'_>, choices: &[&str]) -> Result<String> {
+    Ok((*u.choose(choices)?).to_string())
+}
+
+/// Generate a date with a precision of one second.
+fn datetime(u: &mut Unstructured<'_>) -> Result<FixedDateTime> {
+    let offset = FixedOffset::east_opt(u.int_in_range(-14 * 4..=14 * 4)? * 15 * 60).unwrap();
+    let seconds = u.int_in_range(0..=253_402_300_799)?;
+    Ok(offset.timestamp_opt(seconds, 0).unwrap())
+}
+
+fn list<'a, T: Arbitrary<'a>>(u: &mut Unstructured<'a>, max: usize) -> Result<Vec<T>> {
+    let len = u.int_in_range(0..=max)?;
+    (0..len).map(|_| T::arbitrary(u)).collect()
+}
+
+/// Generate extensions under `EXTENSION_PREFIX`, keyed by their local names.
+fn extension_map(u: &mut Unstructured<'_>) -> Result<ExtensionMap> {
+    let mut map = HashMap::new();
+    for extension in list::<Extension>(u, 2)? {
+        let name = extension.name()[EXTENSION_PREFIX.len() + 1..].to_string();
+        map.entry(name).or_insert_with(Vec::new).push(extension);
+    }
+
+    let mut extensions = ExtensionMap::new();
+    if !map.is_empty() {
+        extensions.insert(EXTENSION_PREFIX.to_string(), map);
+    }
+    Ok(extensions)
+}
+
+/// Generate an extension element nested at most `depth` levels deep.
+fn extension(u: &mut Unstructured<'_>, depth: usize) -> Result<Extension> {
+    let mut element = Extension::default();
+    let name = choice(u, LOCAL_NAMES)?;
+    element.set_name(format!("{}:{}", EXTENSION_PREFIX, name));
+
+    if depth > 0 && u.arbitrary()? {
+        let mut children = HashMap::new();
+        for _ in 0..u.int_in_range(1..=2)? {
+            let child = extension(u, depth - 1)?;
+            let name = child.name()[EXTENSION_PREFIX.len() + 1..].to_string();
+            children.entry(name).or_insert_with(Vec::new).push(child);
+        }
+        element.set_children(children);
+        // the reader keeps the empty text content of elements with children
+        element.set_value(String::new());
+    } else {
+        element.set_value(text(u)?);
+    }
+
+    Ok(element)
+}
+
+impl<'a> Arbitrary<'a> for Extension {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        extension(u, 2)
+    }
+}
+
+impl<'a> Arbitrary<'a> for LanguageTag {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(choice(u, LANGUAGE_TAGS)?.parse().unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Person {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut person = Person::default();
+        person.set_name(text(u)?);
+        person.set_email(optional_text(u)?);
+        person.set_uri(optional_text(u)?);
+        Ok(person)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Category {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut category = Category::default();
+        category.set_term(text(u)?);
+        category.set_scheme(optional_text(u)?);
+        category.set_label(optional_text(u)?);
+        Ok(category)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Generator {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut generator = Generator::default();
+        generator.set_value(text(u)?);
+        generator.set_uri(optional_text(u)?);
+        generator.set_version(optional_text(u)?);
+        Ok(generator)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Link {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut link = Link::default();
+        link.set_href(text(u)?);
+        link.set_rel(choice(u, LINK_RELS)?);
+        link.set_hreflang(Option::<LanguageTag>::arbitrary(u)?);
+        if u.arbitrary()? {
+            link.set_mime_type(choice(u, MIME_TYPES)?);
+        }
+        link.set_title(optional_text(u)?);
+        link.set_length(Option::<u64>::arbitrary(u)?);
+        Ok(link)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Content {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut content = Content::default();
+        if u.arbitrary()? {
+            content.set_src(text(u)?);
+            content.set_content_type(choice(u, MIME_TYPES)?);
+        } else {
+            content.set_value(text(u)?);
+            content.set_content_type(choice(u, TEXT_TYPES)?);
+        }
+        Ok(content)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Source {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut source = Source::default();
+        source.set_title(text(u)?);
+        source.set_id(text(u)?);
+        source.set_updated(datetime(u)?);
+        source.set_authors(list(u, 2)?);
+        source.set_categories(list(u, 2)?);
+        source.set_contributors(list(u, 2)?);
+        source.set_generator(Option::<Generator>::arbitrary(u)?);
+        source.set_icon(optional_text(u)?);
+        source.set_links(list(u, 2)?);
+        source.set_logo(optional_text(u)?);
+        source.set_rights(optional_text(u)?);
+        source.set_subtitle(optional_text(u)?);
+        Ok(source)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Entry {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut entry = Entry::default();
+        entry.set_title(text(u)?);
+        entry.set_id(text(u)?);
+        entry.set_updated(datetime(u)?);
+        entry.set_authors(list(u, 2)?);
+        entry.set_categories(list(u, 2)?);
+        entry.set_contributors(list(u, 2)?);
+        entry.set_links(list(u, 3)?);
+        if u.arbitrary()? {
+            entry.set_published(datetime(u)?);
+        }
+        entry.set_rights(optional_text(u)?);
+        entry.set_source(Option::<Source>::arbitrary(u)?);
+        entry.set_summary(optional_text(u)?);
+        entry.set_content(Option::<Content>::arbitrary(u)?);
+        entry.set_extensions(extension_map(u)?);
+        Ok(entry)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Feed {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut feed = Feed::default();
+        feed.set_title(text(u)?);
+        feed.set_id(text(u)?);
+        feed.set_updated(datetime(u)?);
+        feed.set_authors(list(u, 2)?);
+        feed.set_categories(list(u, 2)?);
+        feed.set_contributors(list(u, 2)?);
+        feed.set_generator(Option::<Generator>::arbitrary(u)?);
+        feed.set_icon(optional_text(u)?);
+        feed.set_links(list(u, 3)?);
+        feed.set_logo(optional_text(u)?);
+        feed.set_rights(optional_text(u)?);
+        feed.set_subtitle(optional_text(u)?);
+        feed.set_entries(list(u, 4)?);
+        feed.set_extensions(extension_map(u)?);
+
+        let mut namespaces = HashMap::new();
+        namespaces.insert(
+            EXTENSION_PREFIX.to_string(),
+            EXTENSION_NAMESPACE.to_string(),
+        );
+        feed.set_namespaces(namespaces);
+
+        Ok(feed)
+    }
+}
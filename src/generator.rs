@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::{BufRead, Write};
 
 use quick_xml::events::attributes::Attributes;
@@ -7,13 +8,13 @@ use quick_xml::Reader;
 use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
-use crate::util::atom_text;
+use crate::fromxml::{FromXml, ReadContext};
+use crate::toxml::{ToXml, WriteContext};
+use crate::util::IntoOption;
 
 /// Represents the generator of an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Builder)]
 #[builder(setter(into), default)]
 pub struct Generator {
     /// The name of the generator.
@@ -22,6 +23,8 @@ pub struct Generator {
     uri: Option<String>,
     /// The generator version.
     version: Option<String>,
+    /// Additional attributes of the element that are not otherwise represented.
+    attrs: BTreeMap<String, String>,
 }
 
 impl Generator {
@@ -40,6 +43,21 @@ impl Generator {
         self.value.as_str()
     }
 
+    /// Return a mutable reference to the name of the generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// generator.value_mut().push_str("Feed Generator");
+    /// assert_eq!(generator.value(), "Feed Generator");
+    /// ```
+    pub fn value_mut(&mut self) -> &mut String {
+        &mut self.value
+    }
+
     /// Set the name of the generator.
     ///
     /// # Examples
@@ -66,13 +84,28 @@ impl Generator {
     /// use atom_syndication::Generator;
     ///
     /// let mut generator = Generator::default();
-    /// generator.set_uri("http://example.com/generator".to_string());
+    /// generator.set_uri("http://example.com/generator");
     /// assert_eq!(generator.uri(), Some("http://example.com/generator"));
     /// ```
     pub fn uri(&self) -> Option<&str> {
         self.uri.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the URI for the generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// *generator.uri_mut() = Some("http://example.com/generator".to_string());
+    /// assert_eq!(generator.uri(), Some("http://example.com/generator"));
+    /// ```
+    pub fn uri_mut(&mut self) -> &mut Option<String> {
+        &mut self.uri
+    }
+
     /// Set the URI for the generator.
     ///
     /// # Examples
@@ -81,13 +114,13 @@ impl Generator {
     /// use atom_syndication::Generator;
     ///
     /// let mut generator = Generator::default();
-    /// generator.set_uri("http://example.com/generator".to_string());
+    /// generator.set_uri("http://example.com/generator");
     /// ```
     pub fn set_uri<V>(&mut self, uri: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.uri = uri.into()
+        self.uri = uri.into_option()
     }
 
     /// Return the version of the generator.
@@ -98,13 +131,28 @@ impl Generator {
     /// use atom_syndication::Generator;
     ///
     /// let mut generator = Generator::default();
-    /// generator.set_version("1.0".to_string());
+    /// generator.set_version("1.0");
     /// assert_eq!(generator.version(), Some("1.0"));
     /// ```
     pub fn version(&self) -> Option<&str> {
         self.version.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the version of the generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// *generator.version_mut() = Some("1.0".to_string());
+    /// assert_eq!(generator.version(), Some("1.0"));
+    /// ```
+    pub fn version_mut(&mut self) -> &mut Option<String> {
+        &mut self.version
+    }
+
     /// Set the version of the generator.
     ///
     /// # Examples
@@ -113,18 +161,78 @@ impl Generator {
     /// use atom_syndication::Generator;
     ///
     /// let mut generator = Generator::default();
-    /// generator.set_version("1.0".to_string());
+    /// generator.set_version("1.0");
     /// ```
     pub fn set_version<V>(&mut self, version: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
+    {
+        self.version = version.into_option()
+    }
+
+    /// Return the additional attributes of this generator.
+    ///
+    /// These are attributes of the element that are not otherwise represented, such as `xml:lang`
+    /// or vendor-specific attributes. They are written back out when serializing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("xml:lang".to_string(), "en".to_string());
+    ///
+    /// let mut generator = Generator::default();
+    /// generator.set_attrs(attrs);
+    /// assert_eq!(generator.attrs().get("xml:lang").map(String::as_str), Some("en"));
+    /// ```
+    pub fn attrs(&self) -> &BTreeMap<String, String> {
+        &self.attrs
+    }
+
+    /// Return a mutable reference to the additional attributes of this generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// generator.attrs_mut()
+    ///     .insert("xml:lang".to_string(), "en".to_string());
+    /// assert_eq!(generator.attrs().len(), 1);
+    /// ```
+    pub fn attrs_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.attrs
+    }
+
+    /// Set the additional attributes of this generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// generator.set_attrs(BTreeMap::new());
+    /// ```
+    pub fn set_attrs<V>(&mut self, attrs: V)
+    where
+        V: Into<BTreeMap<String, String>>,
     {
-        self.version = version.into()
+        self.attrs = attrs.into();
     }
 }
 
 impl FromXml for Generator {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, mut atts: Attributes) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        mut atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
         let mut generator = Generator::default();
 
         for attr in atts.with_checks(false) {
@@ -132,19 +240,28 @@ impl FromXml for Generator {
                 match att.key {
                     b"uri" => generator.uri = Some(att.unescape_and_decode_value(reader)?),
                     b"version" => generator.version = Some(att.unescape_and_decode_value(reader)?),
-                    _ => {}
+                    key => {
+                        let key = reader.decode(key).into_owned();
+                        generator
+                            .attrs
+                            .insert(key, att.unescape_and_decode_value(reader)?);
+                    }
                 }
             }
         }
 
-        generator.value = atom_text(reader)?.unwrap_or_default();
+        generator.value = ctx.read_text(reader)?.unwrap_or_default();
 
         Ok(generator)
     }
 }
 
 impl ToXml for Generator {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        _ctx: &WriteContext,
+    ) -> Result<(), XmlError> {
         let name = b"generator";
         let mut element = BytesStart::borrowed(name, name.len());
 
@@ -156,8 +273,10 @@ impl ToXml for Generator {
             element.push_attribute(("version", &**version));
         }
 
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+
         writer.write_event(Event::Start(element))?;
-        writer.write_event(Event::Text(BytesText::from_escaped(self.value.as_bytes())))?;
+        writer.write_event(Event::Text(BytesText::from_plain_str(&self.value)))?;
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
 
         Ok(())
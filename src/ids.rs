@@ -0,0 +1,119 @@
+//! Generating ids for entries that are missing them.
+
+use uuid::Uuid;
+
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::util::to_utc;
+
+/// The namespace of the UUIDs generated from the title and published date of an entry.
+fn title_namespace() -> Uuid {
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, b"urn:atom-syndication:entry-title")
+}
+
+impl Entry {
+    /// Generate a deterministic id for this entry from its other elements.
+    ///
+    /// The id is a `urn:uuid:` URI holding a version 5 UUID of the `alternate` link of the
+    /// entry, or, if it has none, of its title and published date. The same entry always gets
+    /// the same id, so it can still be deduplicated and tracked across fetches. Returns `None`
+    /// if the entry has no link, title or published date to generate the id from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let entry = Entry::default().with_links(vec![Link::default().with_href("http://example.com/")]);
+    /// assert_eq!(
+    ///     entry.generate_id().as_deref(),
+    ///     Some("urn:uuid:0a300ee9-f9e4-5697-a51a-efc7fafaba67")
+    /// );
+    /// assert_eq!(Entry::default().generate_id(), None);
+    /// ```
+    pub fn generate_id(&self) -> Option<String> {
+        let alternate = self
+            .links()
+            .iter()
+            .find(|link| link.rel() == "alternate" && !link.href().trim().is_empty());
+
+        let uuid = match alternate {
+            Some(link) => Uuid::new_v5(&Uuid::NAMESPACE_URL, link.href().trim().as_bytes()),
+            None => {
+                let title = self.title().trim();
+                let published = self.published().map(|date| to_utc(date).to_rfc3339());
+                if title.is_empty() && published.is_none() {
+                    return None;
+                }
+
+                let name = format!("{}\n{}", title, published.unwrap_or_default());
+                Uuid::new_v5(&title_namespace(), name.as_bytes())
+            }
+        };
+
+        Some(format!("urn:uuid:{}", uuid))
+    }
+
+    /// Set the id of this entry to a generated one if it is missing or blank.
+    ///
+    /// See [`generate_id`](#method.generate_id) for how the id is generated. Returns whether
+    /// the id was set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default().with_title("Entry Title");
+    /// assert!(entry.fill_missing_id());
+    /// assert!(entry.id().starts_with("urn:uuid:"));
+    /// assert!(!entry.fill_missing_id());
+    /// ```
+    pub fn fill_missing_id(&mut self) -> bool {
+        if !self.id().trim().is_empty() {
+            return false;
+        }
+
+        match self.generate_id() {
+            Some(id) => {
+                self.set_id(id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Feed {
+    /// Set the ids of the entries of this feed that are missing or blank to generated ones.
+    ///
+    /// See [`Entry::generate_id`](struct.Entry.html#method.generate_id) for how the ids are
+    /// generated. Returns the number of entries whose id was set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><id>urn:entry:1</id></entry>
+    ///     <entry><link href="http://example.com/2"/></entry>
+    /// </feed>"#;
+    /// let mut feed = xml.parse::<Feed>().unwrap();
+    ///
+    /// assert_eq!(feed.fill_missing_ids(), 1);
+    /// assert_eq!(feed.entries()[0].id(), "urn:entry:1");
+    /// assert!(feed.entries()[1].id().starts_with("urn:uuid:"));
+    /// ```
+    pub fn fill_missing_ids(&mut self) -> usize {
+        let mut filled = 0;
+
+        for entry in self.entries_mut() {
+            if entry.fill_missing_id() {
+                filled += 1;
+            }
+        }
+
+        filled
+    }
+}
@@ -0,0 +1,168 @@
+//! Finding the lead image of an entry, for showing the entry as a card.
+
+use crate::entry::Entry;
+use crate::extension::Extension;
+use crate::util::{markup_to_text, resolve_iri};
+
+/// Return the value of an attribute in the attribute list of an HTML start tag.
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        if rest.is_empty() {
+            return None;
+        }
+
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let key = &rest[..end];
+        rest = rest[end..].trim_start();
+
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            match after.chars().next() {
+                Some(quote @ '"') | Some(quote @ '\'') => {
+                    let close = after[1..].find(quote).map_or(after.len(), |i| i + 1);
+                    rest = after.get(close + 1..).unwrap_or("");
+                    &after[1..close]
+                }
+                _ => {
+                    let close = after.find(char::is_whitespace).unwrap_or(after.len());
+                    rest = &after[close..];
+                    &after[..close]
+                }
+            }
+        } else {
+            ""
+        };
+
+        if key.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+    }
+}
+
+/// Return the `src` of the first image in HTML markup, skipping images of a single pixel that
+/// are used to track views.
+fn first_image(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let mut from = 0;
+
+    while let Some(start) = lower[from..].find("<img").map(|i| from + i) {
+        let after = start + "<img".len();
+        from = after;
+
+        if !html[after..].starts_with(|c: char| c.is_whitespace() || c == '/') {
+            continue;
+        }
+
+        let mut quote = None;
+        let end = html[after..]
+            .char_indices()
+            .find(|&(_, c)| match (c, quote) {
+                ('"', None) | ('\'', None) => {
+                    quote = Some(c);
+                    false
+                }
+                (c, Some(q)) if c == q => {
+                    quote = None;
+                    false
+                }
+                ('>', None) => true,
+                _ => false,
+            })
+            .map_or(html.len(), |(i, _)| after + i);
+        let attributes = &html[after..end];
+
+        let is_pixel = |name| attribute(attributes, name).map(str::trim) == Some("1");
+        if is_pixel("width") || is_pixel("height") {
+            continue;
+        }
+
+        match attribute(attributes, "src").map(|src| markup_to_text(src.trim())) {
+            Some(ref src) if !src.is_empty() => return Some(src.clone()),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Return the URL of the first `media:thumbnail`, looking inside `media:group` and
+/// `media:content` elements as well.
+fn thumbnail(extensions: &[Extension]) -> Option<&str> {
+    extensions.iter().find_map(|extension| {
+        let local_name = extension.name().rsplit(':').next().unwrap_or_default();
+        if local_name == "thumbnail" {
+            if let Some(url) = extension.attrs().get("url") {
+                return Some(url.as_str());
+            }
+        }
+
+        extension
+            .children()
+            .iter()
+            .filter(|&(name, _)| name == "thumbnail" || name == "content" || name == "group")
+            .find_map(|(_, children)| thumbnail(children))
+    })
+}
+
+impl Entry {
+    /// Return the URL of the image that best represents this entry.
+    ///
+    /// This is the `media:thumbnail` of the entry if it has one, or else the `src` of the first
+    /// `<img>` in its HTML content or summary. Images of a single pixel, which are used to track
+    /// views, are skipped. Relative URLs are resolved against the `xml:base` of the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content(Content::html(
+    ///     r#"<img src="/pixel.gif" width="1" height="1"><p><img alt="" src="/cover.jpg"></p>"#,
+    /// ));
+    /// entry
+    ///     .attrs_mut()
+    ///     .insert("xml:base".to_string(), "http://example.com/posts/".to_string());
+    ///
+    /// assert_eq!(
+    ///     entry.lead_image_url().as_deref(),
+    ///     Some("http://example.com/cover.jpg")
+    /// );
+    /// ```
+    pub fn lead_image_url(&self) -> Option<String> {
+        let url = self
+            .extensions()
+            .get("media")
+            .and_then(|map| {
+                ["thumbnail", "group", "content"]
+                    .iter()
+                    .filter_map(|name| map.get(*name))
+                    .find_map(|extensions| thumbnail(extensions))
+            })
+            .map(String::from)
+            .or_else(|| {
+                let content = self
+                    .content()
+                    .filter(|content| {
+                        matches!(
+                            content.content_type(),
+                            Some("html") | Some("xhtml") | Some("text/html")
+                        )
+                    })
+                    .and_then(|content| content.value());
+                content
+                    .and_then(first_image)
+                    .or_else(|| self.summary().and_then(first_image))
+            })?;
+
+        match self.attrs().get("xml:base") {
+            Some(base) => Some(resolve_iri(base, &url)),
+            None => Some(url),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::entry::Entry;
+
+/// An optional index of entry ids to their positions in a feed.
+///
+/// The index is only built once it has been enabled. Entries handed out mutably may have their
+/// id changed, so the position of the last such entry is remembered and checked on lookup until
+/// the index is next synchronized. Any other mutable access to the entries invalidates the whole
+/// index, which is then rebuilt lazily.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EntryIndex {
+    enabled: bool,
+    positions: Option<HashMap<String, usize>>,
+    pending: Option<usize>,
+}
+
+impl EntryIndex {
+    pub fn enable(&mut self, entries: &[Entry]) {
+        self.enabled = true;
+        self.rebuild(entries);
+    }
+
+    pub fn invalidate(&mut self) {
+        self.positions = None;
+        self.pending = None;
+    }
+
+    pub fn sync(&mut self, entries: &[Entry]) {
+        if !self.enabled {
+            return;
+        }
+
+        let positions = match self.positions {
+            Some(ref mut positions) => positions,
+            None => return self.rebuild(entries),
+        };
+
+        let pending = match self.pending.take() {
+            Some(pending) => pending,
+            None => return,
+        };
+
+        let id = entries.get(pending).map(Entry::id);
+        if id.and_then(|id| positions.get(id)) == Some(&pending) {
+            return;
+        }
+
+        // The id of the entry has changed, so the entry that now comes first for its old id has
+        // to be found again.
+        let stale = positions
+            .iter()
+            .find(|&(_, &position)| position == pending)
+            .map(|(id, _)| id.clone());
+
+        if let Some(stale) = stale {
+            positions.remove(&stale);
+
+            if let Some(position) = entries.iter().position(|entry| entry.id() == stale) {
+                positions.insert(stale, position);
+            }
+        }
+
+        if let Some(id) = id {
+            let position = positions.entry(id.to_string()).or_insert(pending);
+            *position = (*position).min(pending);
+        }
+    }
+
+    pub fn push(&mut self, entries: &[Entry]) {
+        if let (Some(positions), Some(entry)) = (self.positions.as_mut(), entries.last()) {
+            positions
+                .entry(entry.id().to_string())
+                .or_insert(entries.len() - 1);
+        }
+    }
+
+    pub fn set_pending(&mut self, position: usize) {
+        if self.positions.is_some() {
+            self.pending = Some(position);
+        }
+    }
+
+    pub fn position(&self, entries: &[Entry], id: &str) -> Option<usize> {
+        let positions = match self.positions {
+            Some(ref positions) => positions,
+            None => return entries.iter().position(|entry| entry.id() == id),
+        };
+
+        let pending = self
+            .pending
+            .filter(|&pending| entries.get(pending).map(Entry::id) == Some(id));
+
+        match positions.get(id) {
+            Some(&position) if entries.get(position).map(Entry::id) == Some(id) => {
+                Some(pending.map_or(position, |pending| pending.min(position)))
+            }
+            Some(_) => entries.iter().position(|entry| entry.id() == id),
+            None => pending,
+        }
+    }
+
+    fn rebuild(&mut self, entries: &[Entry]) {
+        let mut positions = HashMap::with_capacity(entries.len());
+
+        for (position, entry) in entries.iter().enumerate() {
+            positions.entry(entry.id().to_string()).or_insert(position);
+        }
+
+        self.positions = Some(positions);
+        self.pending = None;
+    }
+}
+
+// The index is derived from the entries, so it never affects whether two feeds are equal.
+impl PartialEq for EntryIndex {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for EntryIndex {}
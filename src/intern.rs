@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// The text of a field that may be shared with the same text elsewhere in a document.
+///
+/// Shared text is copied into an owned value the first time it is borrowed mutably, so the
+/// accessors of the model can keep handing out `&mut String` and `&mut Option<String>`.
+#[derive(Clone)]
+pub(crate) enum Text<T> {
+    Shared(Arc<str>),
+    Owned(T),
+}
+
+/// A type of field that can hold shared text.
+pub(crate) trait Slot: Sized {
+    fn text(&self) -> Option<&str>;
+    fn from_shared(text: &str) -> Self;
+}
+
+impl Slot for String {
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+
+    fn from_shared(text: &str) -> Self {
+        text.to_string()
+    }
+}
+
+impl Slot for Option<String> {
+    fn text(&self) -> Option<&str> {
+        self.as_deref()
+    }
+
+    fn from_shared(text: &str) -> Self {
+        Some(text.to_string())
+    }
+}
+
+impl<T: Slot> Text<T> {
+    fn text(&self) -> Option<&str> {
+        match *self {
+            Text::Shared(ref text) => Some(text),
+            Text::Owned(ref value) => value.text(),
+        }
+    }
+
+    /// Return a mutable reference to the value, copying shared text first.
+    pub(crate) fn to_mut(&mut self) -> &mut T {
+        if let Text::Shared(ref text) = *self {
+            *self = Text::Owned(T::from_shared(text));
+        }
+
+        match *self {
+            Text::Owned(ref mut value) => value,
+            Text::Shared(_) => unreachable!(),
+        }
+    }
+}
+
+impl Text<String> {
+    pub(crate) fn as_str(&self) -> &str {
+        self.text().unwrap_or_default()
+    }
+}
+
+impl Text<Option<String>> {
+    pub(crate) fn as_deref(&self) -> Option<&str> {
+        self.text()
+    }
+}
+
+impl<T: Default> Default for Text<T> {
+    fn default() -> Self {
+        Text::Owned(T::default())
+    }
+}
+
+impl<T> From<T> for Text<T> {
+    fn from(value: T) -> Self {
+        Text::Owned(value)
+    }
+}
+
+impl<T: Slot + fmt::Debug> fmt::Debug for Text<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Text::Shared(ref text) => T::from_shared(text).fmt(f),
+            Text::Owned(ref value) => value.fmt(f),
+        }
+    }
+}
+
+impl<T: Slot> PartialEq for Text<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.text() == other.text()
+    }
+}
+
+impl<T: Slot> Eq for Text<T> {}
+
+impl<T: Slot> Hash for Text<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text().hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Slot + serde::Serialize> serde::Serialize for Text<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Text::Shared(ref text) => T::from_shared(text).serialize(serializer),
+            Text::Owned(ref value) => value.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Text<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Text::Owned)
+    }
+}
+
+/// The distinct text of the people and categories read from a document.
+#[derive(Debug, Default)]
+pub(crate) struct Interner {
+    texts: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    /// Return the value as text shared with the same text interned before.
+    pub(crate) fn intern<T: Slot>(&mut self, value: T) -> Text<T> {
+        let text = match value.text() {
+            Some(text) => text,
+            None => return Text::Owned(value),
+        };
+
+        match self.texts.get(text) {
+            Some(shared) => Text::Shared(shared.clone()),
+            None => {
+                let shared = Arc::<str>::from(text);
+                self.texts.insert(shared.clone());
+                Text::Shared(shared)
+            }
+        }
+    }
+}
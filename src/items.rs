@@ -0,0 +1,218 @@
+use crate::category::Category;
+use crate::content::Content;
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::link::Link;
+use crate::person::Person;
+use crate::util::FixedDateTime;
+
+/// A type that can be published as an entry of a feed, such as the post or article type of an
+/// application.
+///
+/// Only the permalink, title and last modified time of the item are required. The other methods
+/// default to leaving the corresponding element out of the entry, and `to_entry` builds the
+/// entry from them.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Content, Feed, FixedDateTime, IntoEntry};
+///
+/// struct Post {
+///     slug: &'static str,
+///     title: &'static str,
+///     body: &'static str,
+///     modified: FixedDateTime,
+/// }
+///
+/// impl IntoEntry for Post {
+///     fn permalink(&self) -> String {
+///         format!("https://example.com/posts/{}", self.slug)
+///     }
+///
+///     fn title(&self) -> String {
+///         self.title.to_string()
+///     }
+///
+///     fn updated(&self) -> FixedDateTime {
+///         self.modified
+///     }
+///
+///     fn content(&self) -> Option<Content> {
+///         Some(Content::html(self.body))
+///     }
+/// }
+///
+/// let post = Post {
+///     slug: "hello",
+///     title: "Hello",
+///     body: "<p>Hello, world!</p>",
+///     modified: "2017-06-03T15:15:44-05:00".parse().unwrap(),
+/// };
+///
+/// let entry = post.to_entry();
+/// assert_eq!(entry.id(), "https://example.com/posts/hello");
+/// assert_eq!(entry.links()[0].rel(), "alternate");
+///
+/// let feed = Feed::from_items(vec![post]);
+/// assert_eq!(feed.updated(), entry.updated());
+/// ```
+pub trait IntoEntry {
+    /// Return the URL at which the item is published.
+    fn permalink(&self) -> String;
+
+    /// Return the title of the item.
+    fn title(&self) -> String;
+
+    /// Return the last time that the item was modified.
+    fn updated(&self) -> FixedDateTime;
+
+    /// Return the permanent, unique identifier of the item.
+    ///
+    /// Defaults to the permalink.
+    fn id(&self) -> String {
+        self.permalink()
+    }
+
+    /// Return the time that the item was first published.
+    fn published(&self) -> Option<FixedDateTime> {
+        None
+    }
+
+    /// Return a short summary of the item.
+    fn summary(&self) -> Option<String> {
+        None
+    }
+
+    /// Return the content of the item.
+    fn content(&self) -> Option<Content> {
+        None
+    }
+
+    /// Return the authors of the item.
+    fn authors(&self) -> Vec<Person> {
+        Vec::new()
+    }
+
+    /// Return the categories the item belongs to.
+    fn categories(&self) -> Vec<Category> {
+        Vec::new()
+    }
+
+    /// Build an entry for the item.
+    ///
+    /// The entry has an `alternate` link to the permalink of the item.
+    fn to_entry(&self) -> Entry {
+        Entry::default()
+            .with_id(self.id())
+            .with_title(self.title())
+            .with_updated(self.updated())
+            .with_published(self.published())
+            .with_summary(self.summary())
+            .with_content(self.content())
+            .with_authors(self.authors())
+            .with_categories(self.categories())
+            .with_links(vec![Link::default()
+                .with_rel("alternate")
+                .with_href(self.permalink())])
+    }
+}
+
+impl<T> IntoEntry for &T
+where
+    T: IntoEntry + ?Sized,
+{
+    fn permalink(&self) -> String {
+        (**self).permalink()
+    }
+
+    fn title(&self) -> String {
+        (**self).title()
+    }
+
+    fn updated(&self) -> FixedDateTime {
+        (**self).updated()
+    }
+
+    fn id(&self) -> String {
+        (**self).id()
+    }
+
+    fn published(&self) -> Option<FixedDateTime> {
+        (**self).published()
+    }
+
+    fn summary(&self) -> Option<String> {
+        (**self).summary()
+    }
+
+    fn content(&self) -> Option<Content> {
+        (**self).content()
+    }
+
+    fn authors(&self) -> Vec<Person> {
+        (**self).authors()
+    }
+
+    fn categories(&self) -> Vec<Category> {
+        (**self).categories()
+    }
+
+    fn to_entry(&self) -> Entry {
+        (**self).to_entry()
+    }
+}
+
+impl Feed {
+    /// Create a feed with an entry for each of the given items.
+    ///
+    /// The entries keep the order of `items`. The updated date of the feed is the latest of its
+    /// entries, and its other metadata is left empty to be filled in by the caller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FixedDateTime, IntoEntry};
+    ///
+    /// struct Post(u32);
+    ///
+    /// impl IntoEntry for Post {
+    ///     fn permalink(&self) -> String {
+    ///         format!("https://example.com/posts/{}", self.0)
+    ///     }
+    ///
+    ///     fn title(&self) -> String {
+    ///         format!("Post {}", self.0)
+    ///     }
+    ///
+    ///     fn updated(&self) -> FixedDateTime {
+    ///         format!("2017-06-0{}T00:00:00Z", self.0).parse().unwrap()
+    ///     }
+    /// }
+    ///
+    /// let posts = vec![Post(1), Post(3), Post(2)];
+    /// let mut feed = Feed::from_items(&posts);
+    /// feed.set_title("Posts");
+    /// feed.set_id("https://example.com/");
+    ///
+    /// assert_eq!(feed.entries().len(), 3);
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T00:00:00+00:00");
+    /// ```
+    pub fn from_items<I>(items: I) -> Feed
+    where
+        I: IntoIterator,
+        I::Item: IntoEntry,
+    {
+        let mut feed = Feed::default();
+        let entries = items
+            .into_iter()
+            .map(|item| item.to_entry())
+            .collect::<Vec<_>>();
+
+        if let Some(updated) = entries.iter().map(Entry::updated).max() {
+            feed.set_updated(*updated);
+        }
+        feed.set_entries(entries);
+        feed
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// A language tag, such as `en` or `en-US`, identifying the language of a resource.
+///
+/// The tag is checked to be well formed when it is parsed: it must consist of one or more
+/// subtags of one to eight ASCII letters or digits separated by hyphens, the first of which is
+/// made of letters only.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LanguageTag(String);
+
+impl LanguageTag {
+    /// Return the language tag as a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::LanguageTag;
+    ///
+    /// let tag = "en-US".parse::<LanguageTag>().unwrap();
+    /// assert_eq!(tag.as_str(), "en-US");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Return the primary language subtag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::LanguageTag;
+    ///
+    /// let tag = "en-US".parse::<LanguageTag>().unwrap();
+    /// assert_eq!(tag.primary_language(), "en");
+    /// ```
+    pub fn primary_language(&self) -> &str {
+        self.subtags().next().unwrap_or_default()
+    }
+
+    /// Return the region subtag, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::LanguageTag;
+    ///
+    /// let tag = "zh-Hant-TW".parse::<LanguageTag>().unwrap();
+    /// assert_eq!(tag.region(), Some("TW"));
+    ///
+    /// let tag = "en".parse::<LanguageTag>().unwrap();
+    /// assert_eq!(tag.region(), None);
+    /// ```
+    pub fn region(&self) -> Option<&str> {
+        self.subtags().skip(1).find(|subtag| {
+            (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+                || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()))
+        })
+    }
+
+    /// Return an iterator over the subtags of the language tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::LanguageTag;
+    ///
+    /// let tag = "en-US".parse::<LanguageTag>().unwrap();
+    /// assert_eq!(tag.subtags().collect::<Vec<_>>(), vec!["en", "US"]);
+    /// ```
+    pub fn subtags(&self) -> impl Iterator<Item = &str> {
+        self.0.split('-')
+    }
+
+    /// Parse this tag as a BCP 47 language tag, normalizing the case of its subtags.
+    ///
+    /// Returns an error if the tag is not well formed according to RFC 5646.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::LanguageTag;
+    ///
+    /// let tag = "EN-us".parse::<LanguageTag>().unwrap();
+    /// assert_eq!(tag.to_bcp47().unwrap().as_str(), "en-US");
+    /// ```
+    #[cfg(feature = "language-tags")]
+    pub fn to_bcp47(&self) -> Result<language_tags::LanguageTag, Error> {
+        language_tags::LanguageTag::parse(&self.0)
+            .map_err(|_| Error::InvalidLanguageTag(self.0.clone()))
+    }
+}
+
+impl FromStr for LanguageTag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut subtags = s.split('-');
+        let valid = match subtags.next() {
+            Some(primary) => {
+                is_subtag(primary)
+                    && primary.chars().all(|c| c.is_ascii_alphabetic())
+                    && subtags.all(is_subtag)
+            }
+            None => false,
+        };
+
+        if valid {
+            Ok(LanguageTag(s.to_string()))
+        } else {
+            Err(Error::InvalidLanguageTag(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for LanguageTag {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+fn is_subtag(subtag: &str) -> bool {
+    !subtag.is_empty() && subtag.len() <= 8 && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+}
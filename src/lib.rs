@@ -55,30 +55,146 @@ extern crate quick_xml;
 
 extern crate chrono;
 
+extern crate base64;
+
+#[cfg(feature = "mime")]
+extern crate mime;
+
+#[cfg(feature = "language-tags")]
+extern crate language_tags;
+
+#[cfg(feature = "fuzz")]
+extern crate arbitrary;
+
+#[cfg(feature = "gzip")]
+extern crate flate2;
+
+#[cfg(feature = "zstd")]
+extern crate zstd;
+
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+#[cfg(feature = "tokio")]
+extern crate futures_core;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+
+#[cfg(feature = "feed-rs")]
+extern crate feed_rs;
+
+#[cfg(feature = "activitystreams")]
+extern crate serde_json;
+
+#[cfg(feature = "generate-ids")]
+extern crate uuid;
+
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+
+#[cfg(feature = "http")]
+extern crate encoding_rs;
+#[cfg(feature = "http")]
+extern crate http;
+
+#[cfg(feature = "dsig")]
+extern crate hmac;
+#[cfg(feature = "dsig")]
+extern crate sha2;
+
+#[cfg(feature = "activitystreams")]
+mod activity;
+mod aggregate;
+mod archive;
 mod category;
+#[cfg(feature = "http")]
+mod charset;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod compress;
+mod config;
 mod content;
+mod entities;
 mod entry;
 mod feed;
+#[cfg(feature = "feed-rs")]
+mod feedrs;
 mod generator;
+#[cfg(feature = "generate-ids")]
+mod ids;
+#[cfg(feature = "lead-image")]
+mod image;
+mod lang;
+mod limit;
 mod link;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod normalize;
+mod parser;
 mod person;
+mod policy;
+#[cfg(feature = "rss1")]
+mod rss1;
+mod select;
 mod source;
+mod stats;
+#[cfg(feature = "tokio")]
+mod stream;
 
 mod error;
-mod fromxml;
-mod toxml;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod file;
+#[cfg(feature = "fuzz")]
+mod fuzz;
+mod index;
+mod intern;
+mod items;
+mod utf8;
 mod util;
+mod validate;
+mod writer;
 
 /// Types and functions for namespaced extensions.
 pub mod extension;
+pub mod fromxml;
+pub mod toxml;
+
+#[cfg(feature = "atompub")]
+pub mod atompub;
+pub mod cache;
+#[cfg(feature = "comments")]
+pub mod comments;
+#[cfg(feature = "dsig")]
+pub mod dsig;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "license")]
+pub mod license;
+#[cfg(feature = "opds")]
+pub mod opds;
+#[cfg(feature = "opensearch")]
+pub mod opensearch;
 
+pub use crate::archive::FeedArchive;
 pub use crate::category::{Category, CategoryBuilder};
+pub use crate::config::{CdataPolicy, EntryOrder, ReadConfig, WriteConfig};
 pub use crate::content::{Content, ContentBuilder};
-pub use crate::entry::{Entry, EntryBuilder};
-pub use crate::error::Error;
+pub use crate::entry::{Entry, EntryBuilder, EntryDateKey, EntryIdentity};
+pub use crate::error::{DetectedFormat, Error};
 pub use crate::feed::{Feed, FeedBuilder};
 pub use crate::generator::{Generator, GeneratorBuilder};
+pub use crate::items::IntoEntry;
+pub use crate::lang::LanguageTag;
+pub use crate::limit::LimitedReader;
 pub use crate::link::{Link, LinkBuilder};
+pub use crate::parser::FeedParser;
 pub use crate::person::{Person, PersonBuilder};
+pub use crate::policy::LinkPolicy;
 pub use crate::source::{Source, SourceBuilder};
-pub use crate::util::FixedDateTime;
+pub use crate::stats::{FeedStats, ParseStats};
+#[cfg(feature = "tokio")]
+pub use crate::stream::EntryStream;
+pub use crate::util::{FixedDateTime, IntoOption};
+pub use crate::validate::{Severity, ValidationIssue, ValidationReport};
+#[cfg(feature = "tokio")]
+pub use crate::writer::AsyncEntryWriter;
+pub use crate::writer::{EntryWriter, FeedWriter};
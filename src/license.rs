@@ -0,0 +1,276 @@
+//! Support for the licensing metadata of feeds and entries: the `rel="license"` links of the
+//! [Atom License Extension](https://tools.ietf.org/html/rfc4946) and the `cc:license` element
+//! of the [Creative Commons](http://web.resource.org/cc/) RDF vocabulary that RSS 1.0 feeds and
+//! some blogging tools use.
+//!
+//! The `cc:license` element is looked up with the conventional `cc` prefix. Licenses are
+//! always written as `rel="license"` links, which every Atom reader can see.
+//!
+//! # Examples
+//!
+//! ```
+//! use atom_syndication::Feed;
+//!
+//! let xml = r#"<feed xmlns:cc="http://web.resource.org/cc/"
+//!     xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+//!     <link rel="license" href="http://creativecommons.org/licenses/by/4.0/"/>
+//!     <entry><cc:license rdf:resource="http://creativecommons.org/licenses/by-sa/4.0/"/></entry>
+//! </feed>"#;
+//! let feed = xml.parse::<Feed>().unwrap();
+//!
+//! assert_eq!(feed.license(), Some("http://creativecommons.org/licenses/by/4.0/"));
+//! assert_eq!(
+//!     feed.entries()[0].license(),
+//!     Some("http://creativecommons.org/licenses/by-sa/4.0/")
+//! );
+//! ```
+
+use crate::entry::Entry;
+use crate::extension::{Extension, ExtensionMap};
+use crate::feed::Feed;
+use crate::link::Link;
+use crate::util::IntoOption;
+
+/// The namespace of the Creative Commons RDF vocabulary.
+pub const CC_NAMESPACE: &str = "http://web.resource.org/cc/";
+
+/// The relation of a link to the license of a feed or entry.
+pub const LICENSE: &str = "license";
+
+/// Return the URL of a `cc:license` element, from its text or its `rdf:resource` attribute.
+fn cc_license_url(license: &Extension) -> Option<&str> {
+    license
+        .value()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .or_else(|| license.attrs().get("rdf:resource").map(String::as_str))
+}
+
+/// Return the URLs of the license links followed by those of the `cc:license` elements that
+/// are not already linked.
+fn licenses<'a>(links: &'a [Link], extensions: &'a ExtensionMap) -> Vec<&'a str> {
+    let mut licenses: Vec<&str> = links
+        .iter()
+        .filter(|link| link.rel() == LICENSE)
+        .map(Link::href)
+        .collect();
+
+    let cc_licenses = extensions
+        .get("cc")
+        .and_then(|map| map.get("license"))
+        .into_iter()
+        .flatten()
+        .filter_map(cc_license_url);
+    for url in cc_licenses {
+        if !licenses.contains(&url) {
+            licenses.push(url);
+        }
+    }
+
+    licenses
+}
+
+/// Replace the license links with a link for each URL.
+fn set_license_links<I, V>(links: &mut Vec<Link>, urls: I)
+where
+    I: IntoIterator<Item = V>,
+    V: Into<String>,
+{
+    links.retain(|link| link.rel() != LICENSE);
+    links.extend(urls.into_iter().map(|url| Link::new(url).with_rel(LICENSE)));
+}
+
+fn remove_cc_licenses(extensions: &mut ExtensionMap) {
+    if let Some(map) = extensions.get_mut("cc") {
+        map.remove("license");
+        if map.is_empty() {
+            extensions.remove("cc");
+        }
+    }
+}
+
+impl Entry {
+    /// Return the URL of the first license of this entry.
+    ///
+    /// See [`licenses`](#method.licenses) for where the licenses are read from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let link = Link::new("http://creativecommons.org/licenses/by/4.0/").with_rel("license");
+    /// let entry = Entry::default().with_links(vec![link]);
+    /// assert_eq!(entry.license(), Some("http://creativecommons.org/licenses/by/4.0/"));
+    /// ```
+    pub fn license(&self) -> Option<&str> {
+        self.licenses().into_iter().next()
+    }
+
+    /// Return the URLs of the licenses of this entry.
+    ///
+    /// The URLs of the `rel="license"` links come first, followed by those of the `cc:license`
+    /// elements that are not also linked. A `cc:license` URL is read from the text of the
+    /// element or, as in RSS 1.0 feeds, from its `rdf:resource` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed xmlns:cc="http://web.resource.org/cc/">
+    ///     <entry>
+    ///         <link rel="license" href="http://creativecommons.org/licenses/by/4.0/"/>
+    ///         <cc:license>http://creativecommons.org/licenses/by/4.0/</cc:license>
+    ///         <cc:license>http://www.gnu.org/licenses/gpl-3.0.html</cc:license>
+    ///     </entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// assert_eq!(
+    ///     feed.entries()[0].licenses(),
+    ///     vec![
+    ///         "http://creativecommons.org/licenses/by/4.0/",
+    ///         "http://www.gnu.org/licenses/gpl-3.0.html",
+    ///     ]
+    /// );
+    /// ```
+    pub fn licenses(&self) -> Vec<&str> {
+        licenses(self.links(), self.extensions())
+    }
+
+    /// Set the license of this entry, replacing its licenses.
+    ///
+    /// The license is written as a `rel="license"` link and any `cc:license` elements are
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_license("http://creativecommons.org/licenses/by/4.0/");
+    /// assert_eq!(entry.links()[0].rel(), "license");
+    ///
+    /// entry.set_license(None);
+    /// assert_eq!(entry.license(), None);
+    /// ```
+    pub fn set_license<V>(&mut self, url: V)
+    where
+        V: IntoOption<String>,
+    {
+        self.set_licenses(url.into_option());
+    }
+
+    /// Set the licenses of this entry, replacing its licenses.
+    ///
+    /// The licenses are written as `rel="license"` links and any `cc:license` elements are
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_licenses(vec![
+    ///     "http://creativecommons.org/licenses/by/4.0/",
+    ///     "http://www.gnu.org/licenses/gpl-3.0.html",
+    /// ]);
+    /// assert_eq!(entry.licenses().len(), 2);
+    /// ```
+    pub fn set_licenses<I, V>(&mut self, urls: I)
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        set_license_links(self.links_mut(), urls);
+        remove_cc_licenses(self.extensions_mut());
+    }
+}
+
+impl Feed {
+    /// Return the URL of the first license of this feed.
+    ///
+    /// See [`licenses`](#method.licenses) for where the licenses are read from. The license of
+    /// a feed does not apply to its entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let link = Link::new("http://creativecommons.org/licenses/by/4.0/").with_rel("license");
+    /// let feed = Feed::default().with_links(vec![link]);
+    /// assert_eq!(feed.license(), Some("http://creativecommons.org/licenses/by/4.0/"));
+    /// ```
+    pub fn license(&self) -> Option<&str> {
+        self.licenses().into_iter().next()
+    }
+
+    /// Return the URLs of the licenses of this feed.
+    ///
+    /// The URLs of the `rel="license"` links come first, followed by those of the `cc:license`
+    /// elements that are not also linked. A `cc:license` URL is read from the text of the
+    /// element or from its `rdf:resource` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed xmlns:cc="http://web.resource.org/cc/">
+    ///     <cc:license>http://creativecommons.org/licenses/by/4.0/</cc:license>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.licenses(), vec!["http://creativecommons.org/licenses/by/4.0/"]);
+    /// ```
+    pub fn licenses(&self) -> Vec<&str> {
+        licenses(self.links(), self.extensions())
+    }
+
+    /// Set the license of this feed, replacing its licenses.
+    ///
+    /// The license is written as a `rel="license"` link and any `cc:license` elements are
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_license("http://creativecommons.org/licenses/by/4.0/");
+    /// feed.set_license(None);
+    /// assert_eq!(feed.license(), None);
+    /// ```
+    pub fn set_license<V>(&mut self, url: V)
+    where
+        V: IntoOption<String>,
+    {
+        self.set_licenses(url.into_option());
+    }
+
+    /// Set the licenses of this feed, replacing its licenses.
+    ///
+    /// The licenses are written as `rel="license"` links and any `cc:license` elements are
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_licenses(vec!["http://creativecommons.org/licenses/by/4.0/"]);
+    /// assert_eq!(feed.links()[0].rel(), "license");
+    /// ```
+    pub fn set_licenses<I, V>(&mut self, urls: I)
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        set_license_links(self.links_mut(), urls);
+        remove_cc_licenses(self.extensions_mut());
+    }
+}
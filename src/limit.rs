@@ -0,0 +1,125 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::io::{self, BufRead, Read};
+
+/// A reader that fails once more than a given number of bytes have been read from the reader it
+/// wraps.
+///
+/// Reading past the limit returns an I/O error, which the functions that read feeds report as
+/// `Error::TooLarge`. This guards against endless or very large responses without buffering
+/// them. `ReadConfig::max_bytes` applies the same limit to the readers passed to
+/// `Feed::read_with_config`.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Error, Feed, LimitedReader};
+///
+/// let xml = "<feed><title>Feed Title</title></feed>";
+/// let feed = Feed::read_from(LimitedReader::new(xml.as_bytes(), 1024)).unwrap();
+/// assert_eq!(feed.title(), "Feed Title");
+///
+/// match Feed::read_from(LimitedReader::new(xml.as_bytes(), 16)).map_err(Error::without_context) {
+///     Err(Error::TooLarge { limit }) => assert_eq!(limit, 16),
+///     other => panic!("expected too large error, got {:?}", other.map(|_| ())),
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LimitedReader<R> {
+    inner: R,
+    limit: u64,
+    remaining: u64,
+}
+
+impl<R> LimitedReader<R> {
+    /// Wrap a reader, allowing at most `limit` bytes to be read from it.
+    pub fn new(inner: R, limit: u64) -> Self {
+        LimitedReader {
+            inner,
+            limit,
+            remaining: limit,
+        }
+    }
+
+    /// Return the number of bytes that may be read.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Return the number of bytes that may still be read.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn exceeded(&self) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, LimitExceeded(self.limit))
+    }
+
+    /// Return the number of bytes that may still be read, capped to fit a buffer length.
+    fn available(&self) -> usize {
+        self.remaining.min(usize::MAX as u64) as usize
+    }
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.remaining == 0 {
+            // Only fail if there is more input, so a document of exactly the limit is allowed.
+            return match self.inner.read(&mut [0])? {
+                0 => Ok(0),
+                _ => Err(self.exceeded()),
+            };
+        }
+
+        let max = buf.len().min(self.available());
+        let len = self.inner.read(&mut buf[..max])?;
+        self.remaining -= len as u64;
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for LimitedReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.remaining == 0 && !self.inner.fill_buf()?.is_empty() {
+            return Err(self.exceeded());
+        }
+
+        let available = self.available();
+        let buf = self.inner.fill_buf()?;
+        Ok(&buf[..buf.len().min(available)])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.available());
+        self.remaining -= amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+/// The I/O error returned by a `LimitedReader` once its limit has been exceeded.
+#[derive(Debug)]
+pub(crate) struct LimitExceeded(pub(crate) u64);
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "document exceeds the maximum size of {} bytes", self.0)
+    }
+}
+
+impl StdError for LimitExceeded {}
+
+/// Return the limit that was exceeded if the I/O error was returned by a `LimitedReader`.
+pub(crate) fn exceeded_limit(err: &io::Error) -> Option<u64> {
+    err.get_ref()
+        .and_then(|inner| inner.downcast_ref::<LimitExceeded>())
+        .map(|exceeded| exceeded.0)
+}
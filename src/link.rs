@@ -1,18 +1,25 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
 use std::io::{BufRead, Write};
 
 use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesStart, Event};
+use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Error as XmlError;
 use quick_xml::Reader;
 use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
+use crate::extension::util::{extension_name, parse_extension, rename_prefixes};
+use crate::extension::ExtensionMap;
+use crate::fromxml::{FromXml, ReadContext};
+use crate::lang::LanguageTag;
+use crate::toxml::{ToXml, WriteContext};
+use crate::util::IntoOption;
+use crate::validate::Severity;
 
 /// Represents a link in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq, Builder)]
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
 #[builder(setter(into), default)]
 pub struct Link {
     /// The URI of the referenced resource.
@@ -20,13 +27,30 @@ pub struct Link {
     /// The link relationship type.
     rel: String,
     /// The language of the resource.
-    hreflang: Option<String>,
+    hreflang: Option<LanguageTag>,
     /// The MIME type of the resource.
     mime_type: Option<String>,
     /// Human-readable information about the link.
     title: Option<String>,
     /// The length of the resource, in bytes.
-    length: Option<String>,
+    length: Option<u64>,
+    /// Additional attributes of the element that are not otherwise represented.
+    attrs: BTreeMap<String, String>,
+    /// The extension elements of the link, such as OPDS prices.
+    extensions: ExtensionMap,
+}
+
+// Extension maps cannot be hashed, so links that differ only in their extensions share a hash.
+impl Hash for Link {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.href.hash(state);
+        self.rel.hash(state);
+        self.hreflang.hash(state);
+        self.mime_type.hash(state);
+        self.title.hash(state);
+        self.length.hash(state);
+        self.attrs.hash(state);
+    }
 }
 
 impl Default for Link {
@@ -38,11 +62,37 @@ impl Default for Link {
             mime_type: Default::default(),
             title: Default::default(),
             length: Default::default(),
+            attrs: Default::default(),
+            extensions: Default::default(),
         }
     }
 }
 
 impl Link {
+    /// Create an `alternate` link to the given URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::new("http://example.com/feed")
+    ///     .with_rel("self")
+    ///     .with_mime_type("application/atom+xml");
+    /// assert_eq!(link.href(), "http://example.com/feed");
+    /// assert_eq!(link.rel(), "self");
+    /// assert_eq!(Link::new("http://example.com/").rel(), "alternate");
+    /// ```
+    pub fn new<V>(href: V) -> Self
+    where
+        V: Into<String>,
+    {
+        Link {
+            href: href.into(),
+            ..Default::default()
+        }
+    }
+
     /// Return the URI the referenced resource.
     ///
     /// # Examples
@@ -58,6 +108,21 @@ impl Link {
         self.href.as_str()
     }
 
+    /// Return a mutable reference to the URI the referenced resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.href_mut().push_str("http://example.com");
+    /// assert_eq!(link.href(), "http://example.com");
+    /// ```
+    pub fn href_mut(&mut self) -> &mut String {
+        &mut self.href
+    }
+
     /// Set the URI of the referenced resource.
     ///
     /// # Examples
@@ -75,6 +140,23 @@ impl Link {
         self.href = href.into()
     }
 
+    /// Set the URI of the referenced resource and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::default().with_href("http://example.com");
+    /// ```
+    pub fn with_href<V>(mut self, href: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_href(href);
+        self
+    }
+
     /// Return the relation type of this link.
     ///
     /// # Examples
@@ -90,6 +172,21 @@ impl Link {
         self.rel.as_str()
     }
 
+    /// Return a mutable reference to the relation type of this link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.rel_mut().replace_range(.., "enclosure");
+    /// assert_eq!(link.rel(), "enclosure");
+    /// ```
+    pub fn rel_mut(&mut self) -> &mut String {
+        &mut self.rel
+    }
+
     /// Set the relation type of this link.
     ///
     /// # Examples
@@ -107,19 +204,57 @@ impl Link {
         self.rel = rel.into()
     }
 
-    /// Return the language of the referenced resource.
+    /// Set the relation type of this link and return it.
     ///
     /// # Examples
     ///
     /// ```
     /// use atom_syndication::Link;
     ///
+    /// let link = Link::default().with_rel("alternate");
+    /// ```
+    pub fn with_rel<V>(mut self, rel: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_rel(rel);
+        self
+    }
+
+    /// Return the language of the referenced resource.
+    ///
+    /// A malformed `hreflang` attribute is reported as a warning when reading and read as
+    /// missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{LanguageTag, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_hreflang("en-US".parse::<LanguageTag>().unwrap());
+    ///
+    /// let hreflang = link.hreflang().unwrap();
+    /// assert_eq!(hreflang.primary_language(), "en");
+    /// assert_eq!(hreflang.region(), Some("US"));
+    /// ```
+    pub fn hreflang(&self) -> Option<&LanguageTag> {
+        self.hreflang.as_ref()
+    }
+
+    /// Return a mutable reference to the language of the referenced resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{LanguageTag, Link};
+    ///
     /// let mut link = Link::default();
-    /// link.set_hreflang("en".to_string());
-    /// assert_eq!(link.hreflang(), Some("en"));
+    /// *link.hreflang_mut() = Some("en".parse::<LanguageTag>().unwrap());
+    /// assert_eq!(link.hreflang().map(LanguageTag::as_str), Some("en"));
     /// ```
-    pub fn hreflang(&self) -> Option<&str> {
-        self.hreflang.as_ref().map(String::as_str)
+    pub fn hreflang_mut(&mut self) -> &mut Option<LanguageTag> {
+        &mut self.hreflang
     }
 
     /// Set the language of the referenced resource.
@@ -127,18 +262,35 @@ impl Link {
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Link;
+    /// use atom_syndication::{LanguageTag, Link};
     ///
     /// let mut link = Link::default();
-    /// link.set_hreflang("en".to_string());
+    /// link.set_hreflang("en".parse::<LanguageTag>().unwrap());
     /// ```
     pub fn set_hreflang<V>(&mut self, hreflang: V)
     where
-        V: Into<Option<String>>,
+        V: Into<Option<LanguageTag>>,
     {
         self.hreflang = hreflang.into()
     }
 
+    /// Set the language of the referenced resource and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{LanguageTag, Link};
+    ///
+    /// let link = Link::default().with_hreflang("en".parse::<LanguageTag>().unwrap());
+    /// ```
+    pub fn with_hreflang<V>(mut self, hreflang: V) -> Self
+    where
+        V: Into<Option<LanguageTag>>,
+    {
+        self.set_hreflang(hreflang);
+        self
+    }
+
     /// Return the MIME type of the referenced resource.
     ///
     /// # Examples
@@ -147,13 +299,28 @@ impl Link {
     /// use atom_syndication::Link;
     ///
     /// let mut link = Link::default();
-    /// link.set_mime_type("text/html".to_string());
+    /// link.set_mime_type("text/html");
     /// assert_eq!(link.mime_type(), Some("text/html"));
     /// ```
     pub fn mime_type(&self) -> Option<&str> {
         self.mime_type.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the MIME type of the referenced resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// *link.mime_type_mut() = Some("text/html".to_string());
+    /// assert_eq!(link.mime_type(), Some("text/html"));
+    /// ```
+    pub fn mime_type_mut(&mut self) -> &mut Option<String> {
+        &mut self.mime_type
+    }
+
     /// Set the MIME type of the referenced resource.
     ///
     /// # Examples
@@ -162,13 +329,68 @@ impl Link {
     /// use atom_syndication::Link;
     ///
     /// let mut link = Link::default();
-    /// link.set_mime_type("text/html".to_string());
+    /// link.set_mime_type("text/html");
     /// ```
     pub fn set_mime_type<V>(&mut self, mime_type: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.mime_type = mime_type.into()
+        self.mime_type = mime_type.into_option()
+    }
+
+    /// Set the MIME type of the referenced resource and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::default().with_mime_type("text/html");
+    /// ```
+    pub fn with_mime_type<V>(mut self, mime_type: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_mime_type(mime_type);
+        self
+    }
+
+    /// Return the media type of the referenced resource.
+    ///
+    /// Returns `None` if the type is missing or is not a valid MIME type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_mime_type("audio/mpeg");
+    ///
+    /// let mime = link.mime().unwrap();
+    /// assert_eq!(mime.type_(), mime::AUDIO);
+    /// ```
+    #[cfg(feature = "mime")]
+    pub fn mime(&self) -> Option<mime::Mime> {
+        self.mime_type
+            .as_ref()
+            .and_then(|mime_type| mime_type.parse().ok())
+    }
+
+    /// Set the MIME type of the referenced resource to the given media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_mime(mime::TEXT_HTML);
+    /// assert_eq!(link.mime_type(), Some("text/html"));
+    /// ```
+    #[cfg(feature = "mime")]
+    pub fn set_mime(&mut self, mime: mime::Mime) {
+        self.mime_type = Some(mime.to_string());
     }
 
     /// Return the title of the referenced resource.
@@ -179,13 +401,28 @@ impl Link {
     /// use atom_syndication::Link;
     ///
     /// let mut link = Link::default();
-    /// link.set_title("Article Title".to_string());
+    /// link.set_title("Article Title");
     /// assert_eq!(link.title(), Some("Article Title"));
     /// ```
     pub fn title(&self) -> Option<&str> {
         self.title.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the title of the referenced resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// *link.title_mut() = Some("Article Title".to_string());
+    /// assert_eq!(link.title(), Some("Article Title"));
+    /// ```
+    pub fn title_mut(&mut self) -> &mut Option<String> {
+        &mut self.title
+    }
+
     /// Set the title of the referenced resource.
     ///
     /// # Examples
@@ -194,13 +431,30 @@ impl Link {
     /// use atom_syndication::Link;
     ///
     /// let mut link = Link::default();
-    /// link.set_title("Article Title".to_string());
+    /// link.set_title("Article Title");
     /// ```
     pub fn set_title<V>(&mut self, title: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
+    {
+        self.title = title.into_option()
+    }
+
+    /// Set the title of the referenced resource and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::default().with_title("Article Title");
+    /// ```
+    pub fn with_title<V>(mut self, title: V) -> Self
+    where
+        V: IntoOption<String>,
     {
-        self.title = title.into()
+        self.set_title(title);
+        self
     }
 
     /// Return the content length of the referenced resource in bytes.
@@ -211,11 +465,26 @@ impl Link {
     /// use atom_syndication::Link;
     ///
     /// let mut link = Link::default();
-    /// link.set_length("1000".to_string());
-    /// assert_eq!(link.length(), Some("1000"));
+    /// link.set_length(1000);
+    /// assert_eq!(link.length(), Some(1000));
     /// ```
-    pub fn length(&self) -> Option<&str> {
-        self.length.as_ref().map(String::as_str)
+    pub fn length(&self) -> Option<u64> {
+        self.length
+    }
+
+    /// Return a mutable reference to the content length of the referenced resource in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// *link.length_mut() = Some(1000);
+    /// assert_eq!(link.length(), Some(1000));
+    /// ```
+    pub fn length_mut(&mut self) -> &mut Option<u64> {
+        &mut self.length
     }
 
     /// Set the content length of the referenced resource in bytes.
@@ -226,18 +495,193 @@ impl Link {
     /// use atom_syndication::Link;
     ///
     /// let mut link = Link::default();
-    /// link.set_length("1000".to_string());
+    /// link.set_length(1000);
     /// ```
     pub fn set_length<V>(&mut self, length: V)
     where
-        V: Into<Option<String>>,
+        V: Into<Option<u64>>,
     {
         self.length = length.into()
     }
+
+    /// Set the content length of the referenced resource in bytes and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::default().with_length(1000);
+    /// ```
+    pub fn with_length<V>(mut self, length: V) -> Self
+    where
+        V: Into<Option<u64>>,
+    {
+        self.set_length(length);
+        self
+    }
+
+    /// Return the additional attributes of this link.
+    ///
+    /// These are attributes of the element that are not otherwise represented, such as `xml:lang`
+    /// or vendor-specific attributes. They are written back out when serializing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("xml:lang".to_string(), "en".to_string());
+    ///
+    /// let mut link = Link::default();
+    /// link.set_attrs(attrs);
+    /// assert_eq!(link.attrs().get("xml:lang").map(String::as_str), Some("en"));
+    /// ```
+    pub fn attrs(&self) -> &BTreeMap<String, String> {
+        &self.attrs
+    }
+
+    /// Return a mutable reference to the additional attributes of this link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.attrs_mut()
+    ///     .insert("xml:lang".to_string(), "en".to_string());
+    /// assert_eq!(link.attrs().len(), 1);
+    /// ```
+    pub fn attrs_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.attrs
+    }
+
+    /// Set the additional attributes of this link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_attrs(BTreeMap::new());
+    /// ```
+    pub fn set_attrs<V>(&mut self, attrs: V)
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.attrs = attrs.into();
+    }
+
+    /// Set the additional attributes of this link and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::default().with_attrs(BTreeMap::new());
+    /// ```
+    pub fn with_attrs<V>(mut self, attrs: V) -> Self
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.set_attrs(attrs);
+        self
+    }
+
+    /// Return the extension elements of this link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let xml = r#"<feed xmlns:opds="http://opds-spec.org/2010/catalog"><entry>
+    ///     <link href="http://example.com/book.epub">
+    ///         <opds:price currencycode="USD">1.99</opds:price>
+    ///     </link>
+    /// </entry></feed>"#;
+    ///
+    /// let feed = xml.parse::<atom_syndication::Feed>().unwrap();
+    /// let link = &feed.entries()[0].links()[0];
+    /// assert_eq!(link.extensions()["opds"]["price"][0].value(), Some("1.99"));
+    /// ```
+    pub fn extensions(&self) -> &ExtensionMap {
+        &self.extensions
+    }
+
+    /// Return a mutable reference to the extension elements of this link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.extensions_mut()
+    ///     .entry("ext".to_string())
+    ///     .or_default()
+    ///     .insert("name".to_string(), vec![Extension::default()]);
+    /// assert!(link.extensions().contains_key("ext"));
+    /// ```
+    pub fn extensions_mut(&mut self) -> &mut ExtensionMap {
+        &mut self.extensions
+    }
+
+    /// Set the extension elements of this link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::ExtensionMap;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_extensions(ExtensionMap::default());
+    /// ```
+    pub fn set_extensions<V>(&mut self, extensions: V)
+    where
+        V: Into<ExtensionMap>,
+    {
+        self.extensions = extensions.into();
+    }
+
+    /// Set the extension elements of this link and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::ExtensionMap;
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::default().with_extensions(ExtensionMap::default());
+    /// ```
+    pub fn with_extensions<V>(mut self, extensions: V) -> Self
+    where
+        V: Into<ExtensionMap>,
+    {
+        self.set_extensions(extensions);
+        self
+    }
+
+    pub(crate) fn rename_prefixes(&mut self, renames: &HashMap<String, String>) {
+        rename_prefixes(&mut self.extensions, renames);
+    }
 }
 
 impl FromXml for Link {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, mut atts: Attributes) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        mut atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
         let mut link = Link::default();
 
         for attr in atts.with_checks(false) {
@@ -245,30 +689,79 @@ impl FromXml for Link {
                 match att.key {
                     b"href" => link.href = att.unescape_and_decode_value(reader)?,
                     b"rel" => link.rel = att.unescape_and_decode_value(reader)?,
-                    b"hreflang" => link.hreflang = Some(att.unescape_and_decode_value(reader)?),
+                    b"hreflang" => {
+                        let hreflang = att.unescape_and_decode_value(reader)?;
+                        match hreflang.parse() {
+                            Ok(hreflang) => link.hreflang = Some(hreflang),
+                            Err(_) => ctx.report(
+                                Severity::Warning,
+                                format!("hreflang is not a valid language tag: {}", hreflang),
+                            ),
+                        }
+                    }
                     b"type" => link.mime_type = Some(att.unescape_and_decode_value(reader)?),
                     b"title" => link.title = Some(att.unescape_and_decode_value(reader)?),
-                    b"length" => link.length = Some(att.unescape_and_decode_value(reader)?),
-                    _ => {}
+                    b"length" => {
+                        let length = att.unescape_and_decode_value(reader)?;
+                        match length.trim().parse() {
+                            Ok(length) => link.length = Some(length),
+                            Err(_) if length.trim().is_empty() => {}
+                            Err(_) => {
+                                return Err(Error::InvalidAttribute {
+                                    name: "length".to_string(),
+                                    value: length,
+                                })
+                            }
+                        }
+                    }
+                    key => {
+                        let key = reader.decode(key).into_owned();
+                        link.attrs
+                            .insert(key, att.unescape_and_decode_value(reader)?);
+                    }
                 }
             }
         }
 
-        reader.read_to_end(b"link", &mut Vec::new())?;
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event(&mut buf)? {
+                Event::Start(element) => {
+                    if let Some((ns, name)) = extension_name(ctx.atom_name(element.name())) {
+                        parse_extension(
+                            reader,
+                            element.attributes(),
+                            ns,
+                            name,
+                            &mut link.extensions,
+                            ctx.config.get_max_depth(),
+                        )?;
+                    } else {
+                        ctx.skip(reader, element.name())?;
+                    }
+                }
+                Event::End(_) => break,
+                Event::Eof => return Err(Error::Eof),
+                _ => {}
+            }
+
+            buf.clear();
+        }
 
         Ok(link)
     }
 }
 
 impl ToXml for Link {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>, ctx: &WriteContext) -> Result<(), XmlError> {
         let name = b"link";
         let mut element = BytesStart::borrowed(name, name.len());
         element.push_attribute(("href", &*self.href));
         element.push_attribute(("rel", &*self.rel));
 
         if let Some(ref hreflang) = self.hreflang {
-            element.push_attribute(("hreflang", &**hreflang));
+            element.push_attribute(("hreflang", hreflang.as_str()));
         }
 
         if let Some(ref mime_type) = self.mime_type {
@@ -279,11 +772,30 @@ impl ToXml for Link {
             element.push_attribute(("title", &**title));
         }
 
-        if let Some(ref length) = self.length {
-            element.push_attribute(("length", &**length));
+        if let Some(length) = self.length {
+            element.push_attribute(("length", &*length.to_string()));
+        }
+
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+
+        if self.extensions.is_empty() {
+            writer.write_event(Event::Empty(element))?;
+            return Ok(());
+        }
+
+        writer.write_event(Event::Start(element))?;
+
+        let mut names = self.extensions.iter().collect::<Vec<_>>();
+        names.sort_by_key(|&(ns, _)| ns);
+        for (_, map) in names {
+            let mut items = map.iter().collect::<Vec<_>>();
+            items.sort_by_key(|&(name, _)| name);
+            for extension in items.into_iter().flat_map(|(_, items)| items) {
+                extension.to_xml(writer, ctx)?;
+            }
         }
 
-        writer.write_event(Event::Empty(element))?;
+        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
 
         Ok(())
     }
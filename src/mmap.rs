@@ -0,0 +1,36 @@
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::error::Error;
+use crate::feed::Feed;
+
+impl Feed {
+    /// Attempt to read an Atom feed from the file at the given path by mapping it into memory.
+    ///
+    /// The file is read like [`read_from_file`](#method.read_from_file), without copying the
+    /// whole file into memory first. Only the reading is done in place: the feed that is
+    /// returned owns copies of its text like a feed read any other way.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified, by this or any other process, until this
+    /// function returns. Changing the mapped file while it is read is undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use atom_syndication::Feed;
+    ///
+    /// // Safety: the archived feed is never modified.
+    /// let feed = unsafe { Feed::read_from_mmap("example.xml") }.unwrap();
+    /// ```
+    pub unsafe fn read_from_mmap<P: AsRef<Path>>(path: P) -> Result<Feed, Error> {
+        let file = File::open(path)?;
+        // Safety: the map is only read before it is dropped, and the caller guarantees the file
+        // is not modified meanwhile.
+        let map = unsafe { Mmap::map(&file)? };
+        Feed::read_from_slice(&map)
+    }
+}
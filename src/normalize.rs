@@ -0,0 +1,207 @@
+use crate::category::Category;
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::generator::Generator;
+use crate::link::Link;
+use crate::person::Person;
+use crate::source::Source;
+use crate::util::to_utc;
+
+fn trim(text: &mut String) {
+    let trimmed = text.trim();
+    if trimmed.len() != text.len() {
+        *text = trimmed.to_string();
+    }
+}
+
+/// Trim an optional text, removing it if nothing is left.
+fn trim_option(text: &mut Option<String>) {
+    if let Some(ref mut value) = *text {
+        trim(value);
+        if value.is_empty() {
+            *text = None;
+        }
+    }
+}
+
+/// Return whether `trim_option` would change an optional text.
+///
+/// Text shared with `ReadConfig::intern` is copied when it is borrowed mutably, so it is only
+/// borrowed if it changes.
+fn needs_trim(text: Option<&str>) -> bool {
+    matches!(text, Some(text) if text.is_empty() || text.trim().len() != text.len())
+}
+
+fn normalize_people(people: &mut [Person]) {
+    for person in people.iter_mut() {
+        if needs_trim(Some(person.name())) {
+            trim(person.name_mut());
+        }
+        if needs_trim(person.email()) {
+            trim_option(person.email_mut());
+        }
+        if needs_trim(person.uri()) {
+            trim_option(person.uri_mut());
+        }
+    }
+}
+
+/// Trim the categories and remove those with the same term and scheme as an earlier one,
+/// keeping the first label that was given.
+fn normalize_categories(categories: &mut Vec<Category>) {
+    let mut unique: Vec<Category> = Vec::with_capacity(categories.len());
+
+    for mut category in categories.drain(..) {
+        if needs_trim(Some(category.term())) {
+            trim(category.term_mut());
+        }
+        if needs_trim(category.scheme()) {
+            trim_option(category.scheme_mut());
+        }
+        if needs_trim(category.label()) {
+            trim_option(category.label_mut());
+        }
+
+        let existing = unique
+            .iter_mut()
+            .find(|other| other.term() == category.term() && other.scheme() == category.scheme());
+        match existing {
+            Some(existing) => {
+                if existing.label().is_none() {
+                    existing.set_label(category.label().map(String::from));
+                }
+            }
+            None => unique.push(category),
+        }
+    }
+
+    *categories = unique;
+}
+
+fn normalize_links(links: &mut [Link]) {
+    for link in links.iter_mut() {
+        trim(link.href_mut());
+        trim(link.rel_mut());
+        trim_option(link.title_mut());
+    }
+}
+
+fn normalize_generator(generator: &mut Option<Generator>) {
+    if let Some(ref mut generator) = *generator {
+        trim(generator.value_mut());
+        trim_option(generator.uri_mut());
+        trim_option(generator.version_mut());
+    }
+}
+
+fn normalize_source(source: &mut Source) {
+    trim(source.title_mut());
+    trim(source.id_mut());
+    normalize_people(source.authors_mut());
+    normalize_categories(source.categories_mut());
+    normalize_people(source.contributors_mut());
+    normalize_generator(source.generator_mut());
+    trim_option(source.icon_mut());
+    normalize_links(source.links_mut());
+    trim_option(source.logo_mut());
+    trim_option(source.rights_mut());
+    trim_option(source.subtitle_mut());
+}
+
+impl Entry {
+    /// Normalize this entry so that it is written the same way regardless of the quirks of
+    /// the generator it came from.
+    ///
+    /// Whitespace around the text of the title, id, summary, rights, people, categories and
+    /// links of the entry and its source is trimmed, and optional text that is left empty is
+    /// removed. Categories with the same term and scheme as an earlier one are removed, and the
+    /// dates are converted to UTC. The content is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Entry};
+    ///
+    /// let mut entry = Entry::default()
+    ///     .with_title("\n  Entry Title  \n")
+    ///     .with_categories(vec![
+    ///         Category::default().with_term("rust"),
+    ///         Category::default().with_term(" rust "),
+    ///     ]);
+    /// entry.normalize();
+    ///
+    /// assert_eq!(entry.title(), "Entry Title");
+    /// assert_eq!(entry.categories().len(), 1);
+    /// ```
+    pub fn normalize(&mut self) {
+        trim(self.title_mut());
+        trim(self.id_mut());
+        trim_option(self.summary_mut());
+        trim_option(self.rights_mut());
+        normalize_people(self.authors_mut());
+        normalize_categories(self.categories_mut());
+        normalize_people(self.contributors_mut());
+        normalize_links(self.links_mut());
+
+        if let Some(ref mut source) = *self.source_mut() {
+            normalize_source(source);
+        }
+
+        self.normalize_dates_utc();
+    }
+}
+
+impl Feed {
+    /// Normalize this feed so that it is written the same way regardless of the quirks of the
+    /// generator it came from.
+    ///
+    /// The metadata of the feed is normalized like that of its entries, as described for
+    /// [`Entry::normalize`](struct.Entry.html#method.normalize), and the entries are ordered
+    /// with the most recently updated first. Entries updated at the same time are ordered with
+    /// the most recently published first and then by id, so the order does not depend on the
+    /// order of the document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <title> Feed Title </title>
+    ///     <entry><id>urn:entry:1</id><updated>2017-06-03T15:15:44-05:00</updated></entry>
+    ///     <entry><id>urn:entry:2</id><updated>2017-06-04T15:15:44-05:00</updated></entry>
+    /// </feed>"#;
+    /// let mut feed = xml.parse::<Feed>().unwrap();
+    /// feed.normalize();
+    ///
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// assert_eq!(feed.entries()[0].id(), "urn:entry:2");
+    /// assert_eq!(feed.entries()[0].updated().to_rfc3339(), "2017-06-04T20:15:44+00:00");
+    /// ```
+    pub fn normalize(&mut self) {
+        trim(self.title_mut());
+        trim(self.id_mut());
+        normalize_people(self.authors_mut());
+        normalize_categories(self.categories_mut());
+        normalize_people(self.contributors_mut());
+        normalize_generator(self.generator_mut());
+        trim_option(self.icon_mut());
+        normalize_links(self.links_mut());
+        trim_option(self.logo_mut());
+        trim_option(self.rights_mut());
+        trim_option(self.subtitle_mut());
+        let updated = to_utc(self.updated());
+        self.set_updated(updated);
+
+        let entries = self.entries_mut();
+        for entry in entries.iter_mut() {
+            entry.normalize();
+        }
+        entries.sort_by(|a, b| {
+            b.updated()
+                .cmp(a.updated())
+                .then_with(|| b.published().cmp(&a.published()))
+                .then_with(|| a.id().cmp(b.id()))
+        });
+    }
+}
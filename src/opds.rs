@@ -0,0 +1,412 @@
+//! Support for [OPDS](https://specs.opds.io/opds-1.2) catalogs of electronic publications.
+//!
+//! An OPDS catalog is an Atom feed whose entries describe publications. Navigation feeds link
+//! to other catalog feeds, while acquisition feeds list publications with acquisition links to
+//! download, buy or borrow them. Facet links offer alternative views of an acquisition feed.
+//!
+//! OPDS elements and attributes are looked up with the conventional `opds` and `thr` prefixes.
+//!
+//! # Examples
+//!
+//! ```
+//! use atom_syndication::opds::{self, CatalogKind, Price};
+//! use atom_syndication::{Entry, Feed, Link};
+//!
+//! let mut link = Link::default()
+//!     .with_rel(opds::ACQUISITION_BUY)
+//!     .with_href("http://example.com/book.epub")
+//!     .with_mime_type("application/epub+zip");
+//! link.push_price(Price::new("4.99", "EUR"));
+//!
+//! let mut feed = Feed::default();
+//! feed.namespaces_mut()
+//!     .insert("opds".to_string(), opds::NAMESPACE.to_string());
+//! feed.set_entries(vec![Entry::default().with_links(vec![link])]);
+//!
+//! let feed = feed.to_string().parse::<Feed>().unwrap();
+//! assert_eq!(feed.catalog_kind(), Some(CatalogKind::Acquisition));
+//!
+//! let link = feed.entries()[0].acquisition_links().next().unwrap();
+//! assert_eq!(link.prices(), vec![Price::new("4.99", "EUR")]);
+//! ```
+
+use crate::entry::Entry;
+use crate::extension::Extension;
+use crate::feed::Feed;
+use crate::link::Link;
+
+/// The OPDS catalog namespace.
+pub const NAMESPACE: &str = "http://opds-spec.org/2010/catalog";
+/// The namespace of the Atom threading extension, which defines the `thr:count` attribute.
+pub const THREADING_NAMESPACE: &str = "http://purl.org/syndication/thread/1.0";
+
+/// The relation of a generic acquisition link.
+pub const ACQUISITION: &str = "http://opds-spec.org/acquisition";
+/// The relation of a link to a publication that can be acquired without payment.
+pub const ACQUISITION_OPEN_ACCESS: &str = "http://opds-spec.org/acquisition/open-access";
+/// The relation of a link to borrow a publication.
+pub const ACQUISITION_BORROW: &str = "http://opds-spec.org/acquisition/borrow";
+/// The relation of a link to buy a publication.
+pub const ACQUISITION_BUY: &str = "http://opds-spec.org/acquisition/buy";
+/// The relation of a link to a sample or preview of a publication.
+pub const ACQUISITION_SAMPLE: &str = "http://opds-spec.org/acquisition/sample";
+/// The relation of a link to subscribe to a publication.
+pub const ACQUISITION_SUBSCRIBE: &str = "http://opds-spec.org/acquisition/subscribe";
+/// The relation of a facet link.
+pub const FACET: &str = "http://opds-spec.org/facet";
+/// The relation of a link to the cover image of a publication.
+pub const IMAGE: &str = "http://opds-spec.org/image";
+/// The relation of a link to a thumbnail of the cover image of a publication.
+pub const THUMBNAIL: &str = "http://opds-spec.org/image/thumbnail";
+
+/// The media type of navigation feeds.
+pub const NAVIGATION_TYPE: &str = "application/atom+xml;profile=opds-catalog;kind=navigation";
+/// The media type of acquisition feeds.
+pub const ACQUISITION_TYPE: &str = "application/atom+xml;profile=opds-catalog;kind=acquisition";
+
+/// The kind of an OPDS catalog feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CatalogKind {
+    /// A feed whose entries link to other catalog feeds.
+    Navigation,
+    /// A feed whose entries are publications.
+    Acquisition,
+}
+
+impl CatalogKind {
+    /// Return the kind of catalog feed identified by a media type, if it is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds::CatalogKind;
+    ///
+    /// let media_type = "application/atom+xml; profile=opds-catalog; kind=navigation";
+    /// assert_eq!(CatalogKind::from_media_type(media_type), Some(CatalogKind::Navigation));
+    /// assert_eq!(CatalogKind::from_media_type("application/atom+xml"), None);
+    /// ```
+    pub fn from_media_type(media_type: &str) -> Option<CatalogKind> {
+        let mut params = media_type.split(';').map(str::trim);
+        if !params.next()?.eq_ignore_ascii_case("application/atom+xml") {
+            return None;
+        }
+
+        let mut catalog = false;
+        let mut kind = None;
+        for param in params {
+            match param.to_ascii_lowercase().as_str() {
+                "profile=opds-catalog" => catalog = true,
+                "kind=navigation" => kind = Some(CatalogKind::Navigation),
+                "kind=acquisition" => kind = Some(CatalogKind::Acquisition),
+                _ => {}
+            }
+        }
+
+        if catalog {
+            kind
+        } else {
+            None
+        }
+    }
+
+    /// Return the media type of this kind of catalog feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds::{CatalogKind, ACQUISITION_TYPE};
+    ///
+    /// assert_eq!(CatalogKind::Acquisition.media_type(), ACQUISITION_TYPE);
+    /// ```
+    pub fn media_type(&self) -> &'static str {
+        match *self {
+            CatalogKind::Navigation => NAVIGATION_TYPE,
+            CatalogKind::Acquisition => ACQUISITION_TYPE,
+        }
+    }
+}
+
+/// The price of a publication, from an `opds:price` element.
+///
+/// The amount is kept as written to avoid rounding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Price {
+    amount: String,
+    currency_code: String,
+}
+
+impl Price {
+    /// Create a price from an amount and an ISO 4217 currency code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds::Price;
+    ///
+    /// let price = Price::new("4.99", "EUR");
+    /// assert_eq!(price.amount(), "4.99");
+    /// assert_eq!(price.currency_code(), "EUR");
+    /// ```
+    pub fn new<A, C>(amount: A, currency_code: C) -> Self
+    where
+        A: Into<String>,
+        C: Into<String>,
+    {
+        Price {
+            amount: amount.into(),
+            currency_code: currency_code.into(),
+        }
+    }
+
+    /// Return the amount of this price.
+    pub fn amount(&self) -> &str {
+        &self.amount
+    }
+
+    /// Return the ISO 4217 currency code of this price.
+    pub fn currency_code(&self) -> &str {
+        &self.currency_code
+    }
+}
+
+impl Link {
+    /// Return whether this is an acquisition link, with any of the acquisition relations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds;
+    /// use atom_syndication::Link;
+    ///
+    /// assert!(Link::default().with_rel(opds::ACQUISITION_BORROW).is_acquisition());
+    /// assert!(!Link::default().with_rel(opds::FACET).is_acquisition());
+    /// ```
+    pub fn is_acquisition(&self) -> bool {
+        match self.rel().strip_prefix(ACQUISITION) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+
+    /// Return the kind of catalog feed this link points to, if it points to one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds::{CatalogKind, NAVIGATION_TYPE};
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::default().with_mime_type(NAVIGATION_TYPE);
+    /// assert_eq!(link.catalog_kind(), Some(CatalogKind::Navigation));
+    /// ```
+    pub fn catalog_kind(&self) -> Option<CatalogKind> {
+        self.mime_type().and_then(CatalogKind::from_media_type)
+    }
+
+    /// Return the prices of the publication this link acquires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds::Price;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.push_price(Price::new("4.99", "EUR"));
+    /// link.push_price(Price::new("5.99", "USD"));
+    /// assert_eq!(link.prices()[1].currency_code(), "USD");
+    /// ```
+    pub fn prices(&self) -> Vec<Price> {
+        self.extensions()
+            .get("opds")
+            .and_then(|map| map.get("price"))
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .map(|price| {
+                Price::new(
+                    price.value().unwrap_or_default().trim(),
+                    price.attrs().get("currencycode").map_or("", String::as_str),
+                )
+            })
+            .collect()
+    }
+
+    /// Add an `opds:price` element to this link.
+    ///
+    /// The feed the link is written in must declare the `opds` prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds::Price;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.push_price(Price::new("4.99", "EUR"));
+    /// assert_eq!(link.prices().len(), 1);
+    /// ```
+    pub fn push_price(&mut self, price: Price) {
+        let mut extension = Extension::default();
+        extension.set_name("opds:price");
+        extension.set_value(price.amount);
+        extension
+            .attrs_mut()
+            .insert("currencycode".to_string(), price.currency_code);
+
+        self.extensions_mut()
+            .entry("opds".to_string())
+            .or_default()
+            .entry("price".to_string())
+            .or_default()
+            .push(extension);
+    }
+
+    /// Return the `opds:facetGroup` of this facet link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default().with_rel(opds::FACET);
+    /// link.attrs_mut()
+    ///     .insert("opds:facetGroup".to_string(), "Language".to_string());
+    /// assert_eq!(link.facet_group(), Some("Language"));
+    /// ```
+    pub fn facet_group(&self) -> Option<&str> {
+        self.attrs().get("opds:facetGroup").map(String::as_str)
+    }
+
+    /// Return whether this facet link is the active facet of its group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default().with_rel(opds::FACET);
+    /// link.attrs_mut()
+    ///     .insert("opds:activeFacet".to_string(), "true".to_string());
+    /// assert!(link.is_active_facet());
+    /// ```
+    pub fn is_active_facet(&self) -> bool {
+        self.attrs().get("opds:activeFacet").map(String::as_str) == Some("true")
+    }
+
+    /// Return the number of publications in this facet, from the `thr:count` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds;
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default().with_rel(opds::FACET);
+    /// link.attrs_mut()
+    ///     .insert("thr:count".to_string(), "42".to_string());
+    /// assert_eq!(link.facet_count(), Some(42));
+    /// ```
+    pub fn facet_count(&self) -> Option<u64> {
+        self.attrs()
+            .get("thr:count")
+            .and_then(|count| count.trim().parse().ok())
+    }
+}
+
+impl Entry {
+    /// Return the acquisition links of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds;
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let entry = Entry::default().with_links(vec![
+    ///     Link::default().with_href("http://example.com/book"),
+    ///     Link::default()
+    ///         .with_rel(opds::ACQUISITION_OPEN_ACCESS)
+    ///         .with_href("http://example.com/book.epub"),
+    /// ]);
+    ///
+    /// let links = entry.acquisition_links().collect::<Vec<_>>();
+    /// assert_eq!(links.len(), 1);
+    /// assert_eq!(links[0].href(), "http://example.com/book.epub");
+    /// ```
+    pub fn acquisition_links(&self) -> impl Iterator<Item = &Link> {
+        self.links().iter().filter(|link| link.is_acquisition())
+    }
+}
+
+impl Feed {
+    /// Return whether this feed is an OPDS navigation or acquisition feed.
+    ///
+    /// The kind is taken from the media type of the `self` link. Without one, a feed with
+    /// acquisition links in its entries is an acquisition feed and a feed whose entries link to
+    /// other catalog feeds is a navigation feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds::{CatalogKind, NAVIGATION_TYPE};
+    /// use atom_syndication::{Entry, Feed, Link};
+    ///
+    /// let link = Link::default()
+    ///     .with_rel("subsection")
+    ///     .with_href("http://example.com/catalog/new")
+    ///     .with_mime_type(NAVIGATION_TYPE);
+    ///
+    /// let mut feed = Feed::default();
+    /// assert_eq!(feed.catalog_kind(), None);
+    ///
+    /// feed.set_entries(vec![Entry::default().with_links(vec![link])]);
+    /// assert_eq!(feed.catalog_kind(), Some(CatalogKind::Navigation));
+    /// ```
+    pub fn catalog_kind(&self) -> Option<CatalogKind> {
+        let kind = self
+            .links()
+            .iter()
+            .find(|link| link.rel() == "self")
+            .and_then(Link::catalog_kind);
+        if kind.is_some() {
+            return kind;
+        }
+
+        let entries = self.entries();
+        if entries
+            .iter()
+            .any(|entry| entry.acquisition_links().next().is_some())
+        {
+            Some(CatalogKind::Acquisition)
+        } else if entries
+            .iter()
+            .flat_map(|entry| entry.links())
+            .any(|link| link.catalog_kind().is_some())
+        {
+            Some(CatalogKind::Navigation)
+        } else {
+            None
+        }
+    }
+
+    /// Return the facet links of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opds;
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![
+    ///     Link::default().with_rel("self"),
+    ///     Link::default().with_rel(opds::FACET).with_title("French"),
+    /// ]);
+    /// assert_eq!(feed.facets().next().unwrap().title(), Some("French"));
+    /// ```
+    pub fn facets(&self) -> impl Iterator<Item = &Link> {
+        self.links().iter().filter(|link| link.rel() == FACET)
+    }
+}
@@ -0,0 +1,546 @@
+//! Support for the [OpenSearch 1.1](https://github.com/dewitt/opensearch) response elements.
+//!
+//! A feed of search results describes the size of the result set with
+//! `opensearch:totalResults`, `opensearch:startIndex` and `opensearch:itemsPerPage`, and the
+//! search that produced it, along with related searches, with `opensearch:Query` elements.
+//!
+//! The elements are read with whatever prefix the feed binds to the OpenSearch namespace.
+//! Setting them declares the namespace with the `opensearch` prefix if the feed has no prefix
+//! for it yet.
+//!
+//! # Examples
+//!
+//! ```
+//! use atom_syndication::opensearch::Query;
+//! use atom_syndication::Feed;
+//!
+//! let mut feed = Feed::default();
+//! feed.set_total_results(Some(4230000));
+//! feed.set_start_index(Some(21));
+//! feed.set_items_per_page(Some(10));
+//! feed.set_queries(vec![Query::new("request").with_search_terms("New York History")]);
+//!
+//! let feed = feed.to_string().parse::<Feed>().unwrap();
+//! assert_eq!(feed.total_results().unwrap(), Some(4230000));
+//! assert_eq!(feed.queries()[0].search_terms(), Some("New York History"));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::extension::Extension;
+use crate::feed::Feed;
+
+/// The OpenSearch 1.1 namespace.
+pub const NAMESPACE: &str = "http://a9.com/-/spec/opensearch/1.1/";
+
+/// A search request described by an `opensearch:Query` element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    role: String,
+    search_terms: Option<String>,
+    title: Option<String>,
+    count: Option<u64>,
+    start_index: Option<u64>,
+    start_page: Option<u64>,
+    total_results: Option<u64>,
+    language: Option<String>,
+}
+
+impl Query {
+    /// Create a query with the given role, such as `request`, `example`, `related`,
+    /// `correction`, `subset` or `superset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("related");
+    /// assert_eq!(query.role(), "related");
+    /// ```
+    pub fn new<V>(role: V) -> Self
+    where
+        V: Into<String>,
+    {
+        Query {
+            role: role.into(),
+            ..Query::default()
+        }
+    }
+
+    /// Return the role of this query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// assert_eq!(Query::new("request").role(), "request");
+    /// ```
+    pub fn role(&self) -> &str {
+        &self.role
+    }
+
+    /// Return the search terms of this query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("request").with_search_terms("cat");
+    /// assert_eq!(query.search_terms(), Some("cat"));
+    /// ```
+    pub fn search_terms(&self) -> Option<&str> {
+        self.search_terms.as_deref()
+    }
+
+    /// Set the search terms of this query and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("request").with_search_terms("cat");
+    /// ```
+    pub fn with_search_terms<V>(mut self, search_terms: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.search_terms = Some(search_terms.into());
+        self
+    }
+
+    /// Return the human readable title of this query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("related").with_title("Related searches");
+    /// assert_eq!(query.title(), Some("Related searches"));
+    /// ```
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Set the human readable title of this query and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("related").with_title("Related searches");
+    /// ```
+    pub fn with_title<V>(mut self, title: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Return the number of results per page requested by this query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// assert_eq!(Query::new("request").with_count(10).count(), Some(10));
+    /// ```
+    pub fn count(&self) -> Option<u64> {
+        self.count
+    }
+
+    /// Set the number of results per page requested by this query and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("request").with_count(10);
+    /// ```
+    pub fn with_count(mut self, count: u64) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Return the index of the first result requested by this query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// assert_eq!(Query::new("request").with_start_index(21).start_index(), Some(21));
+    /// ```
+    pub fn start_index(&self) -> Option<u64> {
+        self.start_index
+    }
+
+    /// Set the index of the first result requested by this query and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("request").with_start_index(21);
+    /// ```
+    pub fn with_start_index(mut self, start_index: u64) -> Self {
+        self.start_index = Some(start_index);
+        self
+    }
+
+    /// Return the page of results requested by this query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// assert_eq!(Query::new("request").with_start_page(3).start_page(), Some(3));
+    /// ```
+    pub fn start_page(&self) -> Option<u64> {
+        self.start_page
+    }
+
+    /// Set the page of results requested by this query and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("request").with_start_page(3);
+    /// ```
+    pub fn with_start_page(mut self, start_page: u64) -> Self {
+        self.start_page = Some(start_page);
+        self
+    }
+
+    /// Return the expected number of results of this query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("related").with_total_results(1200);
+    /// assert_eq!(query.total_results(), Some(1200));
+    /// ```
+    pub fn total_results(&self) -> Option<u64> {
+        self.total_results
+    }
+
+    /// Set the expected number of results of this query and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("related").with_total_results(1200);
+    /// ```
+    pub fn with_total_results(mut self, total_results: u64) -> Self {
+        self.total_results = Some(total_results);
+        self
+    }
+
+    /// Return the language of the results requested by this query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("request").with_language("en");
+    /// assert_eq!(query.language(), Some("en"));
+    /// ```
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Set the language of the results requested by this query and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    ///
+    /// let query = Query::new("request").with_language("en");
+    /// ```
+    pub fn with_language<V>(mut self, language: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.language = Some(language.into());
+        self
+    }
+
+    fn from_extension(extension: &Extension) -> Query {
+        let attrs = extension.attrs();
+        let number = |name: &str| attrs.get(name).and_then(|value| value.trim().parse().ok());
+
+        Query {
+            role: attrs.get("role").cloned().unwrap_or_default(),
+            search_terms: attrs.get("searchTerms").cloned(),
+            title: attrs.get("title").cloned(),
+            count: number("count"),
+            start_index: number("startIndex"),
+            start_page: number("startPage"),
+            total_results: number("totalResults"),
+            language: attrs.get("language").cloned(),
+        }
+    }
+
+    fn to_extension(&self, prefix: &str) -> Extension {
+        let mut attrs = HashMap::new();
+        attrs.insert("role".to_string(), self.role.clone());
+
+        let strings = [
+            ("searchTerms", &self.search_terms),
+            ("title", &self.title),
+            ("language", &self.language),
+        ];
+        for &(name, value) in &strings {
+            if let Some(value) = value {
+                attrs.insert(name.to_string(), value.clone());
+            }
+        }
+
+        let numbers = [
+            ("count", self.count),
+            ("startIndex", self.start_index),
+            ("startPage", self.start_page),
+            ("totalResults", self.total_results),
+        ];
+        for &(name, value) in &numbers {
+            if let Some(value) = value {
+                attrs.insert(name.to_string(), value.to_string());
+            }
+        }
+
+        let mut extension = Extension::default();
+        extension.set_name(format!("{}:Query", prefix));
+        extension.set_attrs(attrs);
+        extension
+    }
+}
+
+impl Feed {
+    /// Return the prefix bound to the OpenSearch namespace, binding `opensearch` if there is
+    /// none.
+    fn opensearch_prefix(&mut self) -> String {
+        if let Some(prefix) = self.find_opensearch_prefix() {
+            return prefix.to_string();
+        }
+
+        self.namespaces_mut()
+            .insert("opensearch".to_string(), NAMESPACE.to_string());
+        "opensearch".to_string()
+    }
+
+    fn find_opensearch_prefix(&self) -> Option<&str> {
+        self.namespaces()
+            .iter()
+            .find(|&(_, uri)| uri == NAMESPACE)
+            .map(|(prefix, _)| prefix.as_str())
+    }
+
+    fn opensearch_elements(&self, name: &str) -> &[Extension] {
+        self.find_opensearch_prefix()
+            .and_then(|prefix| self.extensions().get(prefix))
+            .and_then(|map| map.get(name))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn opensearch_number(&self, name: &str) -> Result<Option<u64>, Error> {
+        self.opensearch_elements(name)
+            .first()
+            .map(|element| {
+                let value = element.value().unwrap_or_default();
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| Error::InvalidExtension(value.to_string()))
+            })
+            .transpose()
+    }
+
+    fn set_opensearch_elements(&mut self, name: &str, elements: Vec<Extension>) {
+        let prefix = self.opensearch_prefix();
+        let map = self.extensions_mut().entry(prefix).or_default();
+        if elements.is_empty() {
+            map.remove(name);
+        } else {
+            map.insert(name.to_string(), elements);
+        }
+    }
+
+    fn set_opensearch_number(&mut self, name: &str, value: Option<u64>) {
+        let prefix = self.opensearch_prefix();
+        let elements = value
+            .map(|value| {
+                let mut extension = Extension::default();
+                extension.set_name(format!("{}:{}", prefix, name));
+                extension.set_value(value.to_string());
+                extension
+            })
+            .into_iter()
+            .collect();
+        self.set_opensearch_elements(name, elements);
+    }
+
+    /// Return the number of search results, from `opensearch:totalResults`.
+    ///
+    /// Returns an error if the element is not a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed xmlns:os="http://a9.com/-/spec/opensearch/1.1/">
+    ///     <os:totalResults>4230000</os:totalResults>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.total_results().unwrap(), Some(4230000));
+    /// ```
+    pub fn total_results(&self) -> Result<Option<u64>, Error> {
+        self.opensearch_number("totalResults")
+    }
+
+    /// Set the number of search results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_total_results(Some(4230000));
+    /// assert_eq!(feed.total_results().unwrap(), Some(4230000));
+    /// ```
+    pub fn set_total_results(&mut self, total_results: Option<u64>) {
+        self.set_opensearch_number("totalResults", total_results);
+    }
+
+    /// Return the index of the first search result in this feed, from `opensearch:startIndex`.
+    ///
+    /// Returns an error if the element is not a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_start_index(Some(21));
+    /// assert_eq!(feed.start_index().unwrap(), Some(21));
+    /// ```
+    pub fn start_index(&self) -> Result<Option<u64>, Error> {
+        self.opensearch_number("startIndex")
+    }
+
+    /// Set the index of the first search result in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_start_index(Some(21));
+    /// ```
+    pub fn set_start_index(&mut self, start_index: Option<u64>) {
+        self.set_opensearch_number("startIndex", start_index);
+    }
+
+    /// Return the number of search results per page, from `opensearch:itemsPerPage`.
+    ///
+    /// Returns an error if the element is not a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_items_per_page(Some(10));
+    /// assert_eq!(feed.items_per_page().unwrap(), Some(10));
+    /// ```
+    pub fn items_per_page(&self) -> Result<Option<u64>, Error> {
+        self.opensearch_number("itemsPerPage")
+    }
+
+    /// Set the number of search results per page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_items_per_page(Some(10));
+    /// ```
+    pub fn set_items_per_page(&mut self, items_per_page: Option<u64>) {
+        self.set_opensearch_number("itemsPerPage", items_per_page);
+    }
+
+    /// Return the `opensearch:Query` elements of this feed.
+    ///
+    /// Numeric attributes that are not numbers are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed xmlns:opensearch="http://a9.com/-/spec/opensearch/1.1/">
+    ///     <opensearch:Query role="request" searchTerms="cat" startPage="1" />
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// let query = &feed.queries()[0];
+    /// assert_eq!(query.role(), "request");
+    /// assert_eq!(query.search_terms(), Some("cat"));
+    /// assert_eq!(query.start_page(), Some(1));
+    /// ```
+    pub fn queries(&self) -> Vec<Query> {
+        self.opensearch_elements("Query")
+            .iter()
+            .map(Query::from_extension)
+            .collect()
+    }
+
+    /// Set the `opensearch:Query` elements of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::opensearch::Query;
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_queries(vec![Query::new("request").with_search_terms("cat")]);
+    /// assert_eq!(feed.queries().len(), 1);
+    /// ```
+    pub fn set_queries<V>(&mut self, queries: V)
+    where
+        V: Into<Vec<Query>>,
+    {
+        let prefix = self.opensearch_prefix();
+        let elements = queries
+            .into()
+            .iter()
+            .map(|query| query.to_extension(&prefix))
+            .collect();
+        self.set_opensearch_elements("Query", elements);
+    }
+}
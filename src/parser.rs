@@ -0,0 +1,409 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::config::ReadConfig;
+use crate::entry::Entry;
+use crate::error::{DetectedFormat, Error};
+use crate::fromxml::{FromXml, ReadContext, ATOM_NAMESPACE};
+use crate::utf8::{find_invalid, invalid_message};
+use crate::validate::{Severity, ValidationIssue};
+
+/// Reads the entries of a feed from bytes that are pushed in as they arrive.
+///
+/// The bytes are scanned for the boundaries of elements and each entry is read as soon as its
+/// end tag has been seen, so no `BufRead` is needed and the first entry is available before the
+/// whole document has been received. Only the bytes of the entry being read are kept in memory.
+/// The metadata of the feed is not read. The document is expected to be encoded as UTF-8, and
+/// invalid UTF-8 is replaced with U+FFFD REPLACEMENT CHARACTER and reported in
+/// [`issues`](#method.issues).
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::FeedParser;
+///
+/// let mut parser = FeedParser::new();
+///
+/// let entries = parser.feed_bytes(b"<feed><entry><id>urn:entry:1</id></en").unwrap();
+/// assert!(entries.is_empty());
+///
+/// let entries = parser.feed_bytes(b"try><entry><id>urn:entry:2</id>").unwrap();
+/// assert_eq!(entries[0].id(), "urn:entry:1");
+///
+/// let entries = parser.feed_bytes(b"</entry></feed>").unwrap();
+/// assert_eq!(entries[0].id(), "urn:entry:2");
+///
+/// parser.finish().unwrap();
+/// ```
+pub struct FeedParser {
+    buf: Vec<u8>,
+    /// The offset in the document of the start of `buf`.
+    offset: usize,
+    pos: usize,
+    depth: usize,
+    entry_start: Option<usize>,
+    entries: usize,
+    received: u64,
+    root: Root,
+    ctx: ReadContext,
+}
+
+impl Default for FeedParser {
+    fn default() -> Self {
+        FeedParser::new()
+    }
+}
+
+#[derive(PartialEq)]
+enum Root {
+    Missing,
+    Open,
+    Closed,
+}
+
+/// A markup token found in the bytes pushed so far.
+enum Token {
+    Start { empty: bool },
+    End,
+    Other,
+}
+
+impl FeedParser {
+    /// Create a parser for a new document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::FeedParser;
+    ///
+    /// let parser = FeedParser::new();
+    /// ```
+    pub fn new() -> Self {
+        FeedParser::with_config(&ReadConfig::default())
+    }
+
+    /// Create a parser for a new document that reads entries with the given options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{FeedParser, ReadConfig};
+    ///
+    /// let mut parser = FeedParser::with_config(&ReadConfig::new().fidelity(true));
+    /// let entries = parser.feed_bytes(b"<feed><entry><rank>1</rank></entry>").unwrap();
+    /// assert_eq!(entries[0].unknown_elements()[0].name(), "rank");
+    /// ```
+    pub fn with_config(config: &ReadConfig) -> Self {
+        FeedParser {
+            buf: Vec::new(),
+            offset: 0,
+            pos: 0,
+            depth: 0,
+            entry_start: None,
+            entries: 0,
+            received: 0,
+            root: Root::Missing,
+            ctx: ReadContext::new(config.clone()),
+        }
+    }
+
+    /// Push the next bytes of the document and return the entries that have been completed by
+    /// them, in document order.
+    ///
+    /// Bytes following the end of the feed are ignored. Pushing more bytes in total than
+    /// `ReadConfig::max_bytes` allows fails with `Error::TooLarge`. Once an error has been
+    /// returned the parser should not be used any further.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::FeedParser;
+    ///
+    /// let mut parser = FeedParser::new();
+    /// let entries = parser.feed_bytes(b"<feed><entry><id>urn:entry:1</id></entry>").unwrap();
+    /// assert_eq!(entries.len(), 1);
+    /// ```
+    pub fn feed_bytes(&mut self, bytes: &[u8]) -> Result<Vec<Entry>, Error> {
+        self.received += bytes.len() as u64;
+        if let Some(limit) = self.ctx.config.get_max_bytes() {
+            if self.received > limit {
+                return Err(Error::TooLarge { limit });
+            }
+        }
+
+        self.buf.extend_from_slice(bytes);
+        let mut entries = Vec::new();
+
+        while self.root != Root::Closed {
+            let start = match self.buf[self.pos..].iter().position(|&b| b == b'<') {
+                Some(offset) => self.pos + offset,
+                None => {
+                    self.pos = self.buf.len();
+                    break;
+                }
+            };
+
+            let (token, end) = match scan_token(&self.buf[start..]) {
+                Some((token, len)) => (token, start + len),
+                None => {
+                    self.pos = start;
+                    break;
+                }
+            };
+            self.pos = end;
+
+            match token {
+                Token::Start { empty } => self.start(start, end, empty, &mut entries)?,
+                Token::End => {
+                    self.depth = self.depth.saturating_sub(1);
+                    match self.depth {
+                        0 => self.root = Root::Closed,
+                        1 => {
+                            if let Some(entry_start) = self.entry_start.take() {
+                                entries.extend(self.read_entry(entry_start, end)?);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Token::Other => {}
+            }
+        }
+
+        let keep = self.entry_start.unwrap_or(self.pos);
+        self.buf.drain(..keep);
+        self.offset += keep;
+        self.pos -= keep;
+        self.entry_start = self.entry_start.map(|start| start - keep);
+
+        Ok(entries)
+    }
+
+    /// Return the problems found in the entries read so far that did not stop them from being
+    /// read, such as the broken entries skipped with `ReadConfig::skip_broken_entries`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{FeedParser, ReadConfig};
+    ///
+    /// let mut parser = FeedParser::with_config(&ReadConfig::new().skip_broken_entries(true));
+    /// let entries = parser
+    ///     .feed_bytes(b"<feed><entry><updated>now</updated></entry><entry></entry></feed>")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(entries.len(), 1);
+    /// assert_eq!(parser.issues()[0].location(), "/feed/entry[0]/updated");
+    /// ```
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.ctx.issues
+    }
+
+    /// Signal the end of the document, checking that the feed was complete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Error, FeedParser};
+    ///
+    /// let mut parser = FeedParser::new();
+    /// parser.feed_bytes(b"<feed><entry>").unwrap();
+    ///
+    /// let err = parser.finish().unwrap_err();
+    /// assert_eq!(err.path(), Some("/feed"));
+    /// ```
+    pub fn finish(&mut self) -> Result<(), Error> {
+        match self.root {
+            Root::Missing => Err(Error::MissingElement {
+                name: "feed".to_string(),
+                path: "/".to_string(),
+            }),
+            Root::Open => Err(self.ctx.context(Error::Eof)),
+            Root::Closed => Ok(()),
+        }
+    }
+
+    fn start(
+        &mut self,
+        start: usize,
+        end: usize,
+        empty: bool,
+        entries: &mut Vec<Entry>,
+    ) -> Result<(), Error> {
+        match self.depth {
+            0 => self.read_root(start, end)?,
+            1 if self.ctx.atom_name(tag_name(&self.buf[start..end])) == b"entry" => {
+                if empty {
+                    entries.extend(self.read_entry(start, end)?);
+                } else {
+                    self.entry_start = Some(start);
+                }
+            }
+            _ => {}
+        }
+
+        if empty {
+            if self.depth == 0 {
+                self.root = Root::Closed;
+            }
+        } else {
+            self.depth += 1;
+        }
+
+        Ok(())
+    }
+
+    fn read_root(&mut self, start: usize, end: usize) -> Result<(), Error> {
+        let mut reader = Reader::from_reader(&self.buf[start..end]);
+        reader.trim_text(true);
+        let mut buf = Vec::new();
+
+        if let Event::Start(element) | Event::Empty(element) = reader.read_event(&mut buf)? {
+            for attr in element.attributes().with_checks(false).flatten() {
+                if attr.key.starts_with(b"xmlns:") && &*attr.value == ATOM_NAMESPACE {
+                    self.ctx.atom_prefix = Some(attr.key[6..].to_vec());
+                }
+            }
+
+            if self.ctx.atom_name(element.name()) != b"feed" {
+                return Err(Error::WrongFormat {
+                    detected: DetectedFormat::from_root(element.name()),
+                });
+            }
+        }
+
+        self.ctx.enter("feed");
+        self.root = Root::Open;
+        Ok(())
+    }
+
+    /// Read the entry between `start` and `end`, returning `None` if it is broken and
+    /// `ReadConfig::skip_broken_entries` is enabled.
+    fn read_entry(&mut self, start: usize, end: usize) -> Result<Option<Entry>, Error> {
+        let index = self.entries;
+        self.entries += 1;
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("read_entry", index).entered();
+
+        self.ctx.enter(format!("entry[{}]", index));
+        self.ctx.entry = Some(index);
+
+        if let Some((count, first)) = find_invalid(&self.buf[start..end]) {
+            let first = self.offset + start + first;
+            self.ctx
+                .report(Severity::Warning, invalid_message(count, first));
+        }
+
+        self.ctx.offset = self.offset + start;
+        let mut reader = Reader::from_reader(&self.buf[start..end]);
+        reader.expand_empty_elements(true);
+        let mut buf = Vec::new();
+
+        let result = loop {
+            match reader.read_event(&mut buf) {
+                Ok(Event::Start(element)) => {
+                    break Entry::from_xml(&mut reader, element.attributes(), &mut self.ctx);
+                }
+                Ok(Event::Eof) => break Err(Error::Eof),
+                Ok(_) => {}
+                Err(err) => break Err(err.into()),
+            }
+
+            buf.clear();
+        };
+
+        // the bytes of the entry are complete, so a broken entry can always be skipped
+        let entry = match result {
+            Ok(mut entry) => {
+                if self.ctx.config.is_entry_offsets() {
+                    entry.set_byte_range(self.offset + start..self.offset + end);
+                }
+                Some(entry)
+            }
+            Err(err) if self.ctx.config.is_skip_broken_entries() => {
+                self.ctx.report(Severity::Error, err.to_string());
+                None
+            }
+            Err(err) => return Err(self.ctx.context(err)),
+        };
+
+        self.ctx.path.truncate(1);
+        self.ctx.entry = None;
+        Ok(entry)
+    }
+}
+
+/// Find the end of the markup token at the start of `bytes`, which begins with `<`. Returns
+/// `None` if the token is not complete yet.
+fn scan_token(bytes: &[u8]) -> Option<(Token, usize)> {
+    const COMMENT: &[u8] = b"<!--";
+    const CDATA: &[u8] = b"<![CDATA[";
+
+    if bytes.starts_with(b"<?") {
+        return find(bytes, b"?>").map(|end| (Token::Other, end + 2));
+    }
+
+    if bytes.starts_with(COMMENT) {
+        return find(&bytes[COMMENT.len()..], b"-->")
+            .map(|end| (Token::Other, COMMENT.len() + end + 3));
+    }
+
+    if bytes.starts_with(CDATA) {
+        return find(&bytes[CDATA.len()..], b"]]>")
+            .map(|end| (Token::Other, CDATA.len() + end + 3));
+    }
+
+    if COMMENT.starts_with(bytes) || CDATA.starts_with(bytes) {
+        return None;
+    }
+
+    if bytes.starts_with(b"<!") {
+        return tag_end(bytes, true).map(|end| (Token::Other, end));
+    }
+
+    let end = tag_end(bytes, false)?;
+    if bytes.starts_with(b"</") {
+        Some((Token::End, end))
+    } else {
+        let empty = bytes[..end].ends_with(b"/>");
+        Some((Token::Start { empty }, end))
+    }
+}
+
+/// Return the position just after the `>` closing a tag, skipping quoted attribute values and,
+/// for declarations, bracketed internal subsets.
+fn tag_end(bytes: &[u8], brackets: bool) -> Option<usize> {
+    let mut quote = None;
+    let mut depth = 0;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        match (quote, b) {
+            (Some(q), _) if b == q => quote = None,
+            (Some(_), _) => {}
+            (None, b'"') | (None, b'\'') => quote = Some(b),
+            (None, b'[') if brackets => depth += 1,
+            (None, b']') if brackets => depth -= 1,
+            (None, b'>') if depth <= 0 => return Some(i + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Return the qualified name of the start tag in `bytes`.
+fn tag_name(bytes: &[u8]) -> &[u8] {
+    let name = &bytes[1..];
+    let end = name
+        .iter()
+        .position(|&b| b.is_ascii_whitespace() || b == b'/' || b == b'>')
+        .unwrap_or(name.len());
+    &name[..end]
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
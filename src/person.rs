@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::{BufRead, Write};
 
 use quick_xml::events::attributes::Attributes;
@@ -7,24 +8,49 @@ use quick_xml::Reader;
 use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::{ToXmlNamed, WriterExt};
-use crate::util::atom_text;
+use crate::fromxml::{FromXml, ReadContext};
+use crate::intern::Text;
+use crate::toxml::{ToXmlNamed, WriteContext, WriterExt};
+use crate::util::{collect_attrs, is_valid_email, is_valid_iri, IntoOption};
 
 /// Represents a person in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq, Builder)]
-#[builder(setter(into), default)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct Person {
     /// A human-readable name for the person.
-    name: String,
+    name: Text<String>,
     /// An email address for the person.
-    email: Option<String>,
+    email: Text<Option<String>>,
     /// A Web page for the person.
-    uri: Option<String>,
+    uri: Text<Option<String>>,
+    /// Additional attributes of the element that are not otherwise represented.
+    attrs: BTreeMap<String, String>,
 }
 
 impl Person {
+    /// Create a person with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let person = Person::new("John Doe")
+    ///     .with_email("johndoe@example.com")
+    ///     .with_uri("http://example.com");
+    /// assert_eq!(person.name(), "John Doe");
+    /// assert_eq!(person.email(), Some("johndoe@example.com"));
+    /// ```
+    pub fn new<V>(name: V) -> Self
+    where
+        V: Into<String>,
+    {
+        Person {
+            name: Text::Owned(name.into()),
+            ..Default::default()
+        }
+    }
+
     /// Return the name of this person.
     ///
     /// # Examples
@@ -40,6 +66,21 @@ impl Person {
         self.name.as_str()
     }
 
+    /// Return a mutable reference to the name of this person.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.name_mut().push_str("John Doe");
+    /// assert_eq!(person.name(), "John Doe");
+    /// ```
+    pub fn name_mut(&mut self) -> &mut String {
+        self.name.to_mut()
+    }
+
     /// Return the name of this person.
     ///
     /// # Examples
@@ -54,7 +95,24 @@ impl Person {
     where
         V: Into<String>,
     {
-        self.name = name.into()
+        self.name = Text::Owned(name.into());
+    }
+
+    /// Set the name of this person and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let person = Person::default().with_name("John Doe");
+    /// ```
+    pub fn with_name<V>(mut self, name: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_name(name);
+        self
     }
 
     /// Return the email address for this person.
@@ -65,11 +123,26 @@ impl Person {
     /// use atom_syndication::Person;
     ///
     /// let mut person = Person::default();
-    /// person.set_email("johndoe@example.com".to_string());
+    /// person.set_email("johndoe@example.com");
     /// assert_eq!(person.email(), Some("johndoe@example.com"));
     /// ```
     pub fn email(&self) -> Option<&str> {
-        self.email.as_ref().map(String::as_str)
+        self.email.as_deref()
+    }
+
+    /// Return a mutable reference to the email address for this person.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// *person.email_mut() = Some("johndoe@example.com".to_string());
+    /// assert_eq!(person.email(), Some("johndoe@example.com"));
+    /// ```
+    pub fn email_mut(&mut self) -> &mut Option<String> {
+        self.email.to_mut()
     }
 
     /// Set the email address for this person.
@@ -80,13 +153,30 @@ impl Person {
     /// use atom_syndication::Person;
     ///
     /// let mut person = Person::default();
-    /// person.set_email("johndoe@example.com".to_string());
+    /// person.set_email("johndoe@example.com");
     /// ```
     pub fn set_email<V>(&mut self, email: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.email = email.into()
+        self.email = Text::Owned(email.into_option());
+    }
+
+    /// Set the email address for this person and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let person = Person::default().with_email("johndoe@example.com");
+    /// ```
+    pub fn with_email<V>(mut self, email: V) -> Self
+    where
+        V: IntoOption<String>,
+    {
+        self.set_email(email);
+        self
     }
 
     /// Return the Web page for this person.
@@ -97,11 +187,26 @@ impl Person {
     /// use atom_syndication::Person;
     ///
     /// let mut person = Person::default();
-    /// person.set_uri("http://example.com".to_string());
+    /// person.set_uri("http://example.com");
     /// assert_eq!(person.uri(), Some("http://example.com"));
     /// ```
     pub fn uri(&self) -> Option<&str> {
-        self.uri.as_ref().map(String::as_str)
+        self.uri.as_deref()
+    }
+
+    /// Return a mutable reference to the Web page for this person.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// *person.uri_mut() = Some("http://example.com".to_string());
+    /// assert_eq!(person.uri(), Some("http://example.com"));
+    /// ```
+    pub fn uri_mut(&mut self) -> &mut Option<String> {
+        self.uri.to_mut()
     }
 
     /// Set the Web page for this person.
@@ -112,28 +217,220 @@ impl Person {
     /// use atom_syndication::Person;
     ///
     /// let mut person = Person::default();
-    /// person.set_uri("http://example.com".to_string());
+    /// person.set_uri("http://example.com");
     /// ```
     pub fn set_uri<V>(&mut self, uri: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
+    {
+        self.uri = Text::Owned(uri.into_option());
+    }
+
+    /// Set the Web page for this person and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let person = Person::default().with_uri("http://example.com");
+    /// ```
+    pub fn with_uri<V>(mut self, uri: V) -> Self
+    where
+        V: IntoOption<String>,
     {
-        self.uri = uri.into()
+        self.set_uri(uri);
+        self
+    }
+
+    /// Return the additional attributes of this person.
+    ///
+    /// These are attributes of the element that are not otherwise represented, such as `xml:lang`
+    /// or vendor-specific attributes. They are written back out when serializing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Person;
+    ///
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("xml:lang".to_string(), "en".to_string());
+    ///
+    /// let mut person = Person::default();
+    /// person.set_attrs(attrs);
+    /// assert_eq!(person.attrs().get("xml:lang").map(String::as_str), Some("en"));
+    /// ```
+    pub fn attrs(&self) -> &BTreeMap<String, String> {
+        &self.attrs
+    }
+
+    /// Return a mutable reference to the additional attributes of this person.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.attrs_mut()
+    ///     .insert("xml:lang".to_string(), "en".to_string());
+    /// assert_eq!(person.attrs().len(), 1);
+    /// ```
+    pub fn attrs_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.attrs
+    }
+
+    /// Set the additional attributes of this person.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.set_attrs(BTreeMap::new());
+    /// ```
+    pub fn set_attrs<V>(&mut self, attrs: V)
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.attrs = attrs.into();
+    }
+
+    /// Set the additional attributes of this person and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Person;
+    ///
+    /// let person = Person::default().with_attrs(BTreeMap::new());
+    /// ```
+    pub fn with_attrs<V>(mut self, attrs: V) -> Self
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.set_attrs(attrs);
+        self
+    }
+
+    /// Check that the email address and Web page of this person are well formed.
+    ///
+    /// Returns an error if the email address is not of the form `local@domain` or the Web page is
+    /// not an absolute IRI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.set_name("John Doe");
+    /// person.set_email("johndoe@example.com");
+    /// person.set_uri("http://example.com");
+    /// assert!(person.validate().is_ok());
+    ///
+    /// person.set_email("johndoe");
+    /// assert!(person.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(email) = self.email() {
+            if !is_valid_email(email) {
+                return Err(Error::InvalidEmail(email.to_string()));
+            }
+        }
+
+        if let Some(uri) = self.uri() {
+            if !is_valid_iri(uri) {
+                return Err(Error::InvalidUri(uri.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for [`Person`](struct.Person.html).
+///
+/// The text of a person is stored so that it can be shared, which the derived builders of the
+/// other types cannot express, so this builder is written out with the same methods.
+#[derive(Clone, Default)]
+pub struct PersonBuilder {
+    name: Option<String>,
+    email: Option<Option<String>>,
+    uri: Option<Option<String>>,
+    attrs: Option<BTreeMap<String, String>>,
+}
+
+impl PersonBuilder {
+    /// A human-readable name for the person.
+    pub fn name<VALUE: Into<String>>(&mut self, value: VALUE) -> &mut Self {
+        self.name = Some(value.into());
+        self
+    }
+
+    /// An email address for the person.
+    pub fn email<VALUE: Into<Option<String>>>(&mut self, value: VALUE) -> &mut Self {
+        self.email = Some(value.into());
+        self
+    }
+
+    /// A Web page for the person.
+    pub fn uri<VALUE: Into<Option<String>>>(&mut self, value: VALUE) -> &mut Self {
+        self.uri = Some(value.into());
+        self
+    }
+
+    /// Additional attributes of the element that are not otherwise represented.
+    pub fn attrs<VALUE: Into<BTreeMap<String, String>>>(&mut self, value: VALUE) -> &mut Self {
+        self.attrs = Some(value.into());
+        self
+    }
+
+    /// Builds a new `Person`.
+    ///
+    /// # Errors
+    ///
+    /// Fields that have not been set are left at their default values, so this does not fail.
+    pub fn build(&self) -> Result<Person, String> {
+        Ok(Person {
+            name: Text::Owned(self.name.clone().unwrap_or_default()),
+            email: Text::Owned(self.email.clone().unwrap_or_default()),
+            uri: Text::Owned(self.uri.clone().unwrap_or_default()),
+            attrs: self.attrs.clone().unwrap_or_default(),
+        })
     }
 }
 
 impl FromXml for Person {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
         let mut person = Person::default();
+        collect_attrs(reader, atts, &mut person.attrs)?;
         let mut buf = Vec::new();
 
         loop {
             match reader.read_event(&mut buf)? {
-                Event::Start(element) => match element.name() {
-                    b"name" => person.name = atom_text(reader)?.unwrap_or_default(),
-                    b"email" => person.email = atom_text(reader)?,
-                    b"uri" => person.uri = atom_text(reader)?,
-                    n => reader.read_to_end(n, &mut Vec::new())?,
+                Event::Start(element) => match ctx.atom_name(element.name()) {
+                    b"name" => {
+                        let name = ctx.read_text(reader)?.unwrap_or_default();
+                        person.name = ctx.intern(name);
+                    }
+                    b"email" => {
+                        let email = ctx.read_text(reader)?;
+                        person.email = ctx.intern(email);
+                    }
+                    b"uri" => {
+                        let uri = ctx.read_text(reader)?;
+                        person.uri = ctx.intern(uri);
+                    }
+                    _ => ctx.skip(reader, element.name())?,
                 },
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
@@ -148,21 +445,28 @@ impl FromXml for Person {
 }
 
 impl ToXmlNamed for Person {
-    fn to_xml_named<W, N>(&self, writer: &mut Writer<W>, name: N) -> Result<(), XmlError>
+    fn to_xml_named<W, N>(
+        &self,
+        writer: &mut Writer<W>,
+        name: N,
+        _ctx: &WriteContext,
+    ) -> Result<(), XmlError>
     where
         W: Write,
         N: AsRef<[u8]>,
     {
         let name = name.as_ref();
-        writer.write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
-        writer.write_text_element(b"name", &*self.name)?;
+        let mut element = BytesStart::borrowed(name, name.len());
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        writer.write_event(Event::Start(element))?;
+        writer.write_text_element(b"name", self.name())?;
 
-        if let Some(ref email) = self.email {
-            writer.write_text_element(b"email", &*email)?;
+        if let Some(email) = self.email() {
+            writer.write_text_element(b"email", email)?;
         }
 
-        if let Some(ref uri) = self.uri {
-            writer.write_text_element(b"uri", &*uri)?;
+        if let Some(uri) = self.uri() {
+            writer.write_text_element(b"uri", uri)?;
         }
 
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
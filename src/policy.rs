@@ -0,0 +1,258 @@
+use std::collections::BTreeSet;
+
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::link::Link;
+
+/// The URI schemes that links and content may use.
+///
+/// Links are allowed when their scheme is in the policy or when they are relative references,
+/// which have no scheme. By default only `http`, `https` and `mailto` are allowed, which keeps
+/// out `javascript:` and `data:` URLs that would run or embed code when the feed is displayed.
+///
+/// The policy applies to the `href` of links and the `src` of content. It can be enforced on a
+/// feed that has been read with
+/// [`Feed::enforce_link_policy`](struct.Feed.html#method.enforce_link_policy), or while reading
+/// with [`ReadConfig::link_policy`](struct.ReadConfig.html#method.link_policy).
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::LinkPolicy;
+///
+/// let policy = LinkPolicy::new().allow_scheme("ftp");
+/// assert!(policy.is_allowed("ftp://example.com/file.txt"));
+/// assert!(policy.is_allowed("/relative/path"));
+/// assert!(!policy.is_allowed("javascript:alert(1)"));
+/// assert!(!policy.is_allowed(" JavaScript:alert(1)"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkPolicy {
+    schemes: BTreeSet<String>,
+}
+
+impl Default for LinkPolicy {
+    fn default() -> Self {
+        LinkPolicy::new()
+    }
+}
+
+impl LinkPolicy {
+    /// Create a policy that allows the `http`, `https` and `mailto` schemes.
+    pub fn new() -> Self {
+        LinkPolicy {
+            schemes: ["http", "https", "mailto"]
+                .iter()
+                .map(|scheme| scheme.to_string())
+                .collect(),
+        }
+    }
+
+    /// Create a policy that only allows relative references.
+    pub fn relative_only() -> Self {
+        LinkPolicy {
+            schemes: BTreeSet::new(),
+        }
+    }
+
+    /// Allow a scheme. Schemes are compared without regard to case.
+    pub fn allow_scheme<S>(mut self, scheme: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.schemes.insert(scheme.as_ref().to_ascii_lowercase());
+        self
+    }
+
+    /// Disallow a scheme that is allowed by default.
+    pub fn disallow_scheme<S>(mut self, scheme: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.schemes.remove(&scheme.as_ref().to_ascii_lowercase());
+        self
+    }
+
+    /// Return the allowed schemes, in lowercase.
+    pub fn schemes(&self) -> impl Iterator<Item = &str> {
+        self.schemes.iter().map(String::as_str)
+    }
+
+    /// Return whether the policy allows a URL.
+    ///
+    /// Like a browser, the check ignores leading spaces and control characters and the tabs and
+    /// newlines within the scheme, so they cannot be used to hide a scheme.
+    pub fn is_allowed(&self, url: &str) -> bool {
+        match scheme(url) {
+            Some(scheme) => self.schemes.contains(&scheme),
+            None => true,
+        }
+    }
+}
+
+/// Return the scheme of a URL in lowercase, or `None` for a relative reference.
+fn scheme(url: &str) -> Option<String> {
+    let url = url.trim_start_matches(|c: char| c <= ' ');
+    let mut scheme = String::new();
+
+    for c in url.chars() {
+        match c {
+            '\t' | '\n' | '\r' => {}
+            ':' if !scheme.is_empty() => return Some(scheme),
+            c if c.is_ascii_alphabetic() => scheme.push(c.to_ascii_lowercase()),
+            c if !scheme.is_empty() && (c.is_ascii_digit() || "+-.".contains(c)) => scheme.push(c),
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+fn enforce_links(links: &mut Vec<Link>, policy: &LinkPolicy, removed: &mut Vec<String>) {
+    links.retain(|link| {
+        let allowed = policy.is_allowed(link.href());
+        if !allowed {
+            removed.push(link.href().to_string());
+        }
+        allowed
+    });
+}
+
+fn link_violations<'a>(links: &'a [Link], policy: &LinkPolicy, found: &mut Vec<&'a str>) {
+    found.extend(
+        links
+            .iter()
+            .map(Link::href)
+            .filter(|href| !policy.is_allowed(href)),
+    );
+}
+
+impl Entry {
+    /// Remove the links and content `src` of this entry, and of its source, that the policy
+    /// does not allow. Returns the URLs that were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry, Link, LinkPolicy};
+    ///
+    /// let mut entry = Entry::default()
+    ///     .with_links(vec![
+    ///         Link::default().with_href("javascript:alert(1)"),
+    ///         Link::default().with_href("http://example.com/"),
+    ///     ])
+    ///     .with_content(Content::default().with_src("data:text/html,<script>alert(1)</script>"));
+    ///
+    /// let removed = entry.enforce_link_policy(&LinkPolicy::default());
+    /// assert_eq!(removed.len(), 2);
+    /// assert_eq!(entry.links().len(), 1);
+    /// assert_eq!(entry.content().unwrap().src(), None);
+    /// ```
+    pub fn enforce_link_policy(&mut self, policy: &LinkPolicy) -> Vec<String> {
+        let mut removed = Vec::new();
+        enforce_links(self.links_mut(), policy, &mut removed);
+
+        if let Some(source) = self.source_mut() {
+            enforce_links(source.links_mut(), policy, &mut removed);
+        }
+
+        if let Some(content) = self.content_mut() {
+            if let Some(src) = content.src() {
+                if !policy.is_allowed(src) {
+                    removed.push(src.to_string());
+                    content.set_src(None);
+                }
+            }
+        }
+
+        removed
+    }
+
+    /// Return the URLs of the links and content `src` of this entry, and of its source, that the
+    /// policy does not allow, without removing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link, LinkPolicy};
+    ///
+    /// let entry = Entry::default().with_links(vec![Link::default().with_href("javascript:void(0)")]);
+    /// assert_eq!(
+    ///     entry.link_policy_violations(&LinkPolicy::default()),
+    ///     vec!["javascript:void(0)"]
+    /// );
+    /// ```
+    pub fn link_policy_violations(&self, policy: &LinkPolicy) -> Vec<&str> {
+        let mut found = Vec::new();
+        link_violations(self.links(), policy, &mut found);
+
+        if let Some(source) = self.source() {
+            link_violations(source.links(), policy, &mut found);
+        }
+
+        if let Some(src) = self.content().and_then(|content| content.src()) {
+            if !policy.is_allowed(src) {
+                found.push(src);
+            }
+        }
+
+        found
+    }
+}
+
+impl Feed {
+    /// Remove the links and content `src` of this feed and its entries that the policy does not
+    /// allow. Returns the URLs that were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, LinkPolicy};
+    ///
+    /// let xml = r#"<feed>
+    ///     <link href="http://example.com/"/>
+    ///     <entry><link href="javascript:alert(1)"/></entry>
+    /// </feed>"#;
+    /// let mut feed = xml.parse::<Feed>().unwrap();
+    ///
+    /// let removed = feed.enforce_link_policy(&LinkPolicy::default());
+    /// assert_eq!(removed, vec!["javascript:alert(1)"]);
+    /// assert!(feed.entries()[0].links().is_empty());
+    /// ```
+    pub fn enforce_link_policy(&mut self, policy: &LinkPolicy) -> Vec<String> {
+        let mut removed = Vec::new();
+        enforce_links(self.links_mut(), policy, &mut removed);
+
+        for entry in self.entries_mut() {
+            removed.extend(entry.enforce_link_policy(policy));
+        }
+
+        removed
+    }
+
+    /// Return the URLs of the links and content `src` of this feed and its entries that the
+    /// policy does not allow, without removing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, LinkPolicy};
+    ///
+    /// let xml = r#"<feed><entry><content src="data:text/plain,hi"/></entry></feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// assert_eq!(
+    ///     feed.link_policy_violations(&LinkPolicy::default()),
+    ///     vec!["data:text/plain,hi"]
+    /// );
+    /// ```
+    pub fn link_policy_violations(&self, policy: &LinkPolicy) -> Vec<&str> {
+        let mut found = Vec::new();
+        link_violations(self.links(), policy, &mut found);
+
+        for entry in self.entries() {
+            found.extend(entry.link_policy_violations(policy));
+        }
+
+        found
+    }
+}
@@ -0,0 +1,403 @@
+//! Reading of RSS 1.0 (RDF Site Summary) feeds into the Atom model.
+//!
+//! The channel becomes the feed and each item becomes an entry. Dublin Core metadata supplies
+//! the authors, dates, categories, rights and language, which RSS 1.0 itself has no elements
+//! for, and `content:encoded` becomes HTML content.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use quick_xml::events::attributes::Attributes;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::category::Category;
+use crate::content::Content;
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::feed::Feed;
+use crate::link::Link;
+use crate::person::Person;
+use crate::util::{atom_text, default_fixed_datetime, FixedDateTime};
+
+const RDF_NAMESPACE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const RSS_NAMESPACE: &str = "http://purl.org/rss/1.0/";
+const RSS_090_NAMESPACE: &str = "http://my.netscape.com/rdf/simple/0.9/";
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+const CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
+
+/// The vocabulary an element belongs to.
+#[derive(Clone, Copy, PartialEq)]
+enum Vocabulary {
+    Rdf,
+    Rss,
+    Dc,
+    Content,
+    Other,
+}
+
+/// The namespace prefixes declared on the root element.
+struct Namespaces(HashMap<Vec<u8>, String>);
+
+impl Namespaces {
+    fn new() -> Self {
+        let mut prefixes = HashMap::new();
+        prefixes.insert(b"rdf".to_vec(), RDF_NAMESPACE.to_string());
+        prefixes.insert(b"dc".to_vec(), DC_NAMESPACE.to_string());
+        prefixes.insert(b"content".to_vec(), CONTENT_NAMESPACE.to_string());
+        prefixes.insert(Vec::new(), RSS_NAMESPACE.to_string());
+        Namespaces(prefixes)
+    }
+
+    fn declare<B: BufRead>(&mut self, reader: &Reader<B>, element: &BytesStart) {
+        for attr in element.attributes().with_checks(false).flatten() {
+            let prefix = if attr.key == b"xmlns" {
+                Vec::new()
+            } else if attr.key.starts_with(b"xmlns:") {
+                attr.key[6..].to_vec()
+            } else {
+                continue;
+            };
+
+            if let Ok(uri) = attr.unescape_and_decode_value(reader) {
+                self.0.insert(prefix, uri);
+            }
+        }
+    }
+
+    /// Return the vocabulary and local name of an element or attribute name.
+    fn resolve<'a>(&self, name: &'a [u8]) -> (Vocabulary, &'a [u8]) {
+        let (prefix, local_name) = match name.iter().position(|&b| b == b':') {
+            Some(colon) => (&name[..colon], &name[colon + 1..]),
+            None => (&name[..0], name),
+        };
+
+        let vocabulary = match self.0.get(prefix).map(String::as_str) {
+            Some(RDF_NAMESPACE) => Vocabulary::Rdf,
+            Some(RSS_NAMESPACE) | Some(RSS_090_NAMESPACE) => Vocabulary::Rss,
+            Some(DC_NAMESPACE) => Vocabulary::Dc,
+            Some(CONTENT_NAMESPACE) => Vocabulary::Content,
+            _ => Vocabulary::Other,
+        };
+        (vocabulary, local_name)
+    }
+
+    /// Return the value of the `rdf:about` attribute of an element.
+    fn about<B: BufRead>(
+        &self,
+        reader: &Reader<B>,
+        mut atts: Attributes,
+    ) -> Result<Option<String>, Error> {
+        for attr in atts.with_checks(false).flatten() {
+            if self.resolve(attr.key) == (Vocabulary::Rdf, b"about") {
+                return Ok(Some(attr.unescape_and_decode_value(reader)?));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Parse a W3C date and time, the profile of ISO 8601 used by Dublin Core.
+///
+/// Dates without a time are taken to be midnight UTC and times without seconds are accepted.
+fn parse_date(value: &str) -> Result<FixedDateTime, Error> {
+    let value = value.trim();
+    if let Ok(date) = FixedDateTime::parse_from_rfc3339(value) {
+        return Ok(date);
+    }
+
+    // hh:mm without seconds
+    if value.len() > 16 && value.as_bytes()[10] == b'T' {
+        let with_seconds = format!("{}:00{}", &value[..16], &value[16..]);
+        if let Ok(date) = FixedDateTime::parse_from_rfc3339(&with_seconds) {
+            return Ok(date);
+        }
+    }
+
+    let date = match value.len() {
+        4 => NaiveDate::parse_from_str(&format!("{}-01-01", value), "%Y-%m-%d"),
+        7 => NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d"),
+        _ => NaiveDate::parse_from_str(value, "%Y-%m-%d"),
+    };
+    match date {
+        Ok(date) => Ok(Utc
+            .from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+            .into()),
+        Err(_) => Err(Error::InvalidDate {
+            value: value.to_string(),
+        }),
+    }
+}
+
+fn person(name: String) -> Person {
+    let mut person = Person::default();
+    person.set_name(name);
+    person
+}
+
+/// The Dublin Core metadata shared by channels and items.
+#[derive(Default)]
+struct DublinCore {
+    creators: Vec<Person>,
+    contributors: Vec<Person>,
+    date: Option<FixedDateTime>,
+    subjects: Vec<Category>,
+    rights: Option<String>,
+    language: Option<String>,
+}
+
+impl DublinCore {
+    /// Read a Dublin Core element whose start tag has just been read.
+    fn read<B: BufRead>(&mut self, reader: &mut Reader<B>, name: &[u8]) -> Result<(), Error> {
+        let text = match atom_text(reader)? {
+            Some(text) => text,
+            None => return Ok(()),
+        };
+
+        match name {
+            b"creator" | b"publisher" => self.creators.push(person(text)),
+            b"contributor" => self.contributors.push(person(text)),
+            b"date" if self.date.is_none() => self.date = Some(parse_date(&text)?),
+            b"subject" => self.subjects.push(Category::default().with_term(text)),
+            b"rights" => self.rights = Some(text),
+            b"language" => self.language = Some(text),
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn read_item<B: BufRead>(
+    reader: &mut Reader<B>,
+    namespaces: &Namespaces,
+    atts: Attributes,
+) -> Result<Entry, Error> {
+    let about = namespaces.about(reader, atts)?;
+    let mut entry = Entry::default();
+    let mut link = None;
+    let mut dc = DublinCore::default();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Start(element) => match namespaces.resolve(element.name()) {
+                (Vocabulary::Rss, b"title") => {
+                    entry.set_title(atom_text(reader)?.unwrap_or_default())
+                }
+                (Vocabulary::Rss, b"link") => link = atom_text(reader)?,
+                (Vocabulary::Rss, b"description") => entry.set_summary(atom_text(reader)?),
+                (Vocabulary::Content, b"encoded") => {
+                    entry.set_content(atom_text(reader)?.map(Content::html))
+                }
+                (Vocabulary::Dc, name) => dc.read(reader, name)?,
+                _ => {
+                    reader.read_to_end(element.name(), &mut Vec::new())?;
+                }
+            },
+            Event::End(_) => break,
+            Event::Eof => return Err(Error::Eof),
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    entry.set_id(about.or_else(|| link.clone()).unwrap_or_default());
+    if let Some(link) = link {
+        entry.set_links(vec![Link::default().with_href(link)]);
+    }
+    if let Some(date) = dc.date {
+        entry.set_updated(date);
+    }
+    entry.set_authors(dc.creators);
+    entry.set_contributors(dc.contributors);
+    entry.set_categories(dc.subjects);
+    entry.set_rights(dc.rights);
+    if let Some(language) = dc.language {
+        entry.attrs_mut().insert("xml:lang".to_string(), language);
+    }
+
+    Ok(entry)
+}
+
+fn read_channel<B: BufRead>(
+    reader: &mut Reader<B>,
+    namespaces: &Namespaces,
+    atts: Attributes,
+    feed: &mut Feed,
+) -> Result<(), Error> {
+    let about = namespaces.about(reader, atts)?;
+    let mut link = None;
+    let mut dc = DublinCore::default();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Start(element) => match namespaces.resolve(element.name()) {
+                (Vocabulary::Rss, b"title") => {
+                    feed.set_title(atom_text(reader)?.unwrap_or_default())
+                }
+                (Vocabulary::Rss, b"link") => link = atom_text(reader)?,
+                (Vocabulary::Rss, b"description") => feed.set_subtitle(atom_text(reader)?),
+                (Vocabulary::Dc, name) => dc.read(reader, name)?,
+                _ => {
+                    reader.read_to_end(element.name(), &mut Vec::new())?;
+                }
+            },
+            Event::End(_) => break,
+            Event::Eof => return Err(Error::Eof),
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    feed.set_id(about.or_else(|| link.clone()).unwrap_or_default());
+    if let Some(link) = link {
+        feed.set_alternate_link(link);
+    }
+    if let Some(date) = dc.date {
+        feed.set_updated(date);
+    }
+    feed.set_authors(dc.creators);
+    feed.set_contributors(dc.contributors);
+    feed.set_categories(dc.subjects);
+    feed.set_rights(dc.rights);
+    feed.set_lang(dc.language);
+
+    Ok(())
+}
+
+/// Read the `url` of the channel image.
+fn read_image<B: BufRead>(
+    reader: &mut Reader<B>,
+    namespaces: &Namespaces,
+) -> Result<Option<String>, Error> {
+    let mut url = None;
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Start(element) => match namespaces.resolve(element.name()) {
+                (Vocabulary::Rss, b"url") => url = atom_text(reader)?,
+                _ => {
+                    reader.read_to_end(element.name(), &mut Vec::new())?;
+                }
+            },
+            Event::End(_) => break,
+            Event::Eof => return Err(Error::Eof),
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    Ok(url)
+}
+
+fn read_rdf<B: BufRead>(reader: &mut Reader<B>, namespaces: &Namespaces) -> Result<Feed, Error> {
+    let mut feed = Feed::default();
+    let mut entries = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Start(element) => match namespaces.resolve(element.name()) {
+                (Vocabulary::Rss, b"channel") => {
+                    read_channel(reader, namespaces, element.attributes(), &mut feed)?
+                }
+                (Vocabulary::Rss, b"item") => {
+                    entries.push(read_item(reader, namespaces, element.attributes())?)
+                }
+                (Vocabulary::Rss, b"image") => feed.set_logo(read_image(reader, namespaces)?),
+                _ => {
+                    reader.read_to_end(element.name(), &mut Vec::new())?;
+                }
+            },
+            Event::End(_) => break,
+            Event::Eof => return Err(Error::Eof),
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    // RSS 1.0 has no date of its own, so fall back to the newest item.
+    if *feed.updated() == default_fixed_datetime() {
+        if let Some(updated) = entries.iter().map(|entry: &Entry| *entry.updated()).max() {
+            feed.set_updated(updated);
+        }
+    }
+    feed.set_entries(entries);
+    feed.namespaces_mut()
+        .insert("dc".to_string(), DC_NAMESPACE.to_string());
+
+    Ok(feed)
+}
+
+impl Feed {
+    /// Attempt to read an RSS 1.0 (RDF Site Summary) feed from the reader.
+    ///
+    /// The channel becomes the feed and its items become the entries, in document order. The
+    /// `rdf:about` URI of the channel and of each item is used as the id, falling back to its
+    /// link. Dublin Core creators and publishers become authors, `dc:date` becomes the updated
+    /// date, `dc:subject` becomes a category and `content:encoded` becomes HTML content. Without
+    /// a `dc:date` on the channel the feed is dated by its newest item.
+    ///
+    /// `Feed::read_from` reports RSS 1.0 input as `Error::WrongFormat` with
+    /// `DetectedFormat::Rss1`, so this can be used as a fallback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let rdf = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+    ///     xmlns="http://purl.org/rss/1.0/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+    ///     <channel rdf:about="http://example.com/rss">
+    ///         <title>Channel Title</title>
+    ///         <link>http://example.com/</link>
+    ///     </channel>
+    ///     <item rdf:about="http://example.com/story/1">
+    ///         <title>Story</title>
+    ///         <link>http://example.com/story/1</link>
+    ///         <dc:date>2017-06-03T15:15:44-05:00</dc:date>
+    ///     </item>
+    /// </rdf:RDF>"#;
+    ///
+    /// let feed = Feed::read_rss1(rdf.as_bytes()).unwrap();
+    /// assert_eq!(feed.title(), "Channel Title");
+    /// assert_eq!(feed.entries()[0].id(), "http://example.com/story/1");
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn read_rss1<B: BufRead>(reader: B) -> Result<Feed, Error> {
+        let mut reader = Reader::from_reader(reader);
+        reader.expand_empty_elements(true);
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event(&mut buf)? {
+                Event::Start(element) => {
+                    let mut namespaces = Namespaces::new();
+                    namespaces.declare(&reader, &element);
+                    if namespaces.resolve(element.name()) == (Vocabulary::Rdf, b"RDF") {
+                        return read_rdf(&mut reader, &namespaces);
+                    }
+                    break;
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+
+            buf.clear();
+        }
+
+        Err(Error::MissingElement {
+            name: "rdf:RDF".to_string(),
+            path: "/".to_string(),
+        })
+    }
+}
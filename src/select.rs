@@ -0,0 +1,244 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ptr;
+
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::person::Person;
+use crate::util::FixedDateTime;
+
+/// Add an entry to the group with the given key, unless it was the last entry added to it.
+fn push_entry<'a, K>(groups: &mut HashMap<K, Vec<&'a Entry>>, key: K, entry: &'a Entry)
+where
+    K: Eq + Hash,
+{
+    let group = groups.entry(key).or_default();
+    if !matches!(group.last(), Some(last) if ptr::eq(*last, entry)) {
+        group.push(entry);
+    }
+}
+
+/// Return whether a person is identified by a name or URI: by their URI if they have one, and
+/// by their name otherwise.
+fn is_person(person: &Person, name_or_uri: &str) -> bool {
+    match person.uri() {
+        Some(uri) => uri == name_or_uri,
+        None => person.name() == name_or_uri,
+    }
+}
+
+impl Feed {
+    /// Return the entries of this feed grouped by the terms of their categories.
+    ///
+    /// Each group holds the entries in the order of the feed, and an entry appears once in a
+    /// group even if it has several categories with the same term. Categories with the same
+    /// term in different schemes are grouped together; see
+    /// [`entries_by_scheme_and_category`](#method.entries_by_scheme_and_category) to keep
+    /// them apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><id>urn:entry:1</id><category term="rust"/></entry>
+    ///     <entry><id>urn:entry:2</id><category term="rust"/><category term="xml"/></entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// let groups = feed.entries_by_category();
+    ///
+    /// assert_eq!(groups["rust"].len(), 2);
+    /// assert_eq!(groups["xml"][0].id(), "urn:entry:2");
+    /// ```
+    pub fn entries_by_category(&self) -> HashMap<&str, Vec<&Entry>> {
+        let mut groups = HashMap::new();
+
+        for entry in self.entries() {
+            for category in entry.categories() {
+                push_entry(&mut groups, category.term(), entry);
+            }
+        }
+
+        groups
+    }
+
+    /// Return the entries of this feed grouped by the scheme and term of their categories.
+    ///
+    /// Each group holds the entries in the order of the feed. Categories without a scheme are
+    /// grouped under `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><category term="java" scheme="http://example.com/languages"/></entry>
+    ///     <entry><category term="java" scheme="http://example.com/islands"/></entry>
+    ///     <entry><category term="java"/></entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// let groups = feed.entries_by_scheme_and_category();
+    ///
+    /// assert_eq!(groups.len(), 3);
+    /// assert_eq!(groups[&(Some("http://example.com/islands"), "java")].len(), 1);
+    /// assert_eq!(groups[&(None, "java")].len(), 1);
+    /// ```
+    pub fn entries_by_scheme_and_category(&self) -> HashMap<(Option<&str>, &str), Vec<&Entry>> {
+        let mut groups = HashMap::new();
+
+        for entry in self.entries() {
+            for category in entry.categories() {
+                push_entry(&mut groups, (category.scheme(), category.term()), entry);
+            }
+        }
+
+        groups
+    }
+
+    /// Return the distinct authors of the entries of this feed, in the order they first appear.
+    ///
+    /// Authors are the same if they have the same URI or, when they have none, the same name.
+    /// Only the authors of the entries themselves are included, not those of the feed, which
+    /// are returned by [`authors`](#method.authors).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><author><name>John</name><uri>http://example.com/john</uri></author></entry>
+    ///     <entry><author><name>John Doe</name><uri>http://example.com/john</uri></author></entry>
+    ///     <entry><author><name>Jane</name></author></entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    /// let authors = feed.entry_authors();
+    ///
+    /// assert_eq!(authors.len(), 2);
+    /// assert_eq!(authors[0].name(), "John");
+    /// assert_eq!(authors[1].name(), "Jane");
+    /// ```
+    pub fn entry_authors(&self) -> Vec<&Person> {
+        let mut seen = HashSet::new();
+
+        self.entries()
+            .iter()
+            .flat_map(|entry| entry.authors())
+            .filter(|author| seen.insert(author.uri().unwrap_or_else(|| author.name())))
+            .collect()
+    }
+
+    /// Return the entries of this feed by an author, in the order of the feed.
+    ///
+    /// An author with a URI is matched by the URI, and an author without one by their name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><id>urn:entry:1</id><author><name>John</name><uri>http://example.com/john</uri></author></entry>
+    ///     <entry><id>urn:entry:2</id><author><name>Jane</name></author></entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    ///
+    /// assert_eq!(feed.entries_by_author("http://example.com/john")[0].id(), "urn:entry:1");
+    /// assert_eq!(feed.entries_by_author("Jane")[0].id(), "urn:entry:2");
+    /// assert!(feed.entries_by_author("John").is_empty());
+    /// ```
+    pub fn entries_by_author(&self, name_or_uri: &str) -> Vec<&Entry> {
+        self.entries()
+            .iter()
+            .filter(|entry| {
+                entry
+                    .authors()
+                    .iter()
+                    .any(|author| is_person(author, name_or_uri))
+            })
+            .collect()
+    }
+
+    /// Return the entries of this feed published from `start` up to but not including `end`, in
+    /// the order of the feed.
+    ///
+    /// Entries without a published date are selected by their updated date. Dates are compared
+    /// as instants, so entries with different UTC offsets are selected correctly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use atom_syndication::{Feed, FixedDateTime};
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><id>urn:entry:1</id><published>2017-06-03T23:30:00-05:00</published></entry>
+    ///     <entry><id>urn:entry:2</id><published>2017-06-04T01:00:00+02:00</published></entry>
+    ///     <entry><id>urn:entry:3</id><updated>2017-06-02T12:00:00Z</updated></entry>
+    /// </feed>"#;
+    /// let feed = xml.parse::<Feed>().unwrap();
+    ///
+    /// let start = FixedDateTime::from_str("2017-06-03T00:00:00Z").unwrap();
+    /// let end = FixedDateTime::from_str("2017-06-04T00:00:00Z").unwrap();
+    /// let entries = feed.entries_between(start, end);
+    ///
+    /// assert_eq!(entries.len(), 1);
+    /// assert_eq!(entries[0].id(), "urn:entry:2");
+    /// ```
+    pub fn entries_between<S, E>(&self, start: S, end: E) -> Vec<&Entry>
+    where
+        S: Into<FixedDateTime>,
+        E: Into<FixedDateTime>,
+    {
+        let start = start.into();
+        let end = end.into();
+
+        self.entries()
+            .iter()
+            .filter(|entry| {
+                let date = entry.published().unwrap_or_else(|| entry.updated());
+                *date >= start && *date < end
+            })
+            .collect()
+    }
+
+    /// Split the entries of this feed into pages of at most `page_size` entries.
+    ///
+    /// Each page is a feed with a copy of the metadata of this feed and the next `page_size`
+    /// entries, so it can be written as the response to a request for a page. The pages are
+    /// created as they are iterated, so serving a single page only copies that page. A feed
+    /// without entries has no pages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// for i in 0..5 {
+    ///     feed.push_entry(Entry::default().with_id(format!("urn:entry:{}", i)));
+    /// }
+    ///
+    /// let pages = feed.paginate(2).collect::<Vec<_>>();
+    /// assert_eq!(pages.len(), 3);
+    /// assert_eq!(pages[1].title(), "Feed Title");
+    /// assert_eq!(pages[1].entries()[0].id(), "urn:entry:2");
+    /// assert_eq!(pages[2].entries().len(), 1);
+    ///
+    /// let page = feed.paginate(2).nth(1).unwrap();
+    /// assert_eq!(page.entries()[1].id(), "urn:entry:3");
+    /// ```
+    pub fn paginate(&self, page_size: usize) -> impl Iterator<Item = Feed> + '_ {
+        self.entries()
+            .chunks(page_size)
+            .map(move |entries| self.clone_without_entries().with_entries(entries.to_vec()))
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
 use std::io::{BufRead, Write};
 
 use quick_xml::events::attributes::Attributes;
@@ -8,16 +10,19 @@ use quick_xml::Writer;
 
 use crate::category::Category;
 use crate::error::Error;
-use crate::fromxml::FromXml;
+use crate::extension::util::{extension_name, parse_extension, rename_prefixes};
+use crate::extension::ExtensionMap;
+use crate::feed::Feed;
+use crate::fromxml::{FromXml, ReadContext};
 use crate::generator::Generator;
 use crate::link::Link;
 use crate::person::Person;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{atom_datetime, atom_text, default_fixed_datetime, FixedDateTime};
+use crate::toxml::{ToXml, WriteContext, WriterExt};
+use crate::util::{collect_attrs, default_fixed_datetime, FixedDateTime, IntoOption};
 
 /// Represents the source of an Atom entry
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq, Builder)]
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
 #[builder(setter(into), default)]
 pub struct Source {
     /// A human-readable title for the feed.
@@ -44,6 +49,10 @@ pub struct Source {
     rights: Option<String>,
     /// A human-readable description or subtitle for the feed.
     subtitle: Option<String>,
+    /// The extensions for the feed.
+    extensions: ExtensionMap,
+    /// Additional attributes of the element that are not otherwise represented.
+    attrs: BTreeMap<String, String>,
 }
 
 impl Source {
@@ -62,6 +71,21 @@ impl Source {
         self.title.as_str()
     }
 
+    /// Return a mutable reference to the title of the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// source.title_mut().push_str("Feed Title");
+    /// assert_eq!(source.title(), "Feed Title");
+    /// ```
+    pub fn title_mut(&mut self) -> &mut String {
+        &mut self.title
+    }
+
     /// Set the title of the source feed.
     ///
     /// # Examples
@@ -94,6 +118,21 @@ impl Source {
         self.id.as_str()
     }
 
+    /// Return a mutable reference to the unique URI of the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// source.id_mut().push_str("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// assert_eq!(source.id(), "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// ```
+    pub fn id_mut(&mut self) -> &mut String {
+        &mut self.id
+    }
+
     /// Set the unique URI of the source feed.
     ///
     /// # Examples
@@ -128,6 +167,23 @@ impl Source {
         &self.updated
     }
 
+    /// Return a mutable reference to the last time that the source feed was modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut source = Source::default();
+    /// *source.updated_mut() = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    /// assert_eq!(source.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn updated_mut(&mut self) -> &mut FixedDateTime {
+        &mut self.updated
+    }
+
     /// Set the last time that the source feed was modified.
     ///
     /// # Examples
@@ -162,6 +218,21 @@ impl Source {
         self.authors.as_slice()
     }
 
+    /// Return a mutable reference to the authors of the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Person};
+    ///
+    /// let mut source = Source::default();
+    /// source.authors_mut().push(Person::default());
+    /// assert_eq!(source.authors().len(), 1);
+    /// ```
+    pub fn authors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.authors
+    }
+
     /// Set the authors of the source feed.
     ///
     /// # Examples
@@ -179,6 +250,21 @@ impl Source {
         self.authors = authors.into();
     }
 
+    /// Add an author to this source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Person, Source};
+    ///
+    /// let mut source = Source::default();
+    /// source.push_author(Person::default());
+    /// assert_eq!(source.authors().len(), 1);
+    /// ```
+    pub fn push_author(&mut self, author: Person) {
+        self.authors.push(author);
+    }
+
     /// Return the categories the source feed belongs to.
     ///
     /// # Examples
@@ -194,6 +280,21 @@ impl Source {
         self.categories.as_slice()
     }
 
+    /// Return a mutable reference to the categories the source feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Category};
+    ///
+    /// let mut source = Source::default();
+    /// source.categories_mut().push(Category::default());
+    /// assert_eq!(source.categories().len(), 1);
+    /// ```
+    pub fn categories_mut(&mut self) -> &mut Vec<Category> {
+        &mut self.categories
+    }
+
     /// Set the categories the source feed belongs to.
     ///
     /// # Examples
@@ -211,6 +312,21 @@ impl Source {
         self.categories = categories.into();
     }
 
+    /// Add a category to this source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Source};
+    ///
+    /// let mut source = Source::default();
+    /// source.push_category(Category::default());
+    /// assert_eq!(source.categories().len(), 1);
+    /// ```
+    pub fn push_category(&mut self, category: Category) {
+        self.categories.push(category);
+    }
+
     /// Return the contributors to the source feed.
     ///
     /// # Examples
@@ -226,6 +342,21 @@ impl Source {
         self.contributors.as_slice()
     }
 
+    /// Return a mutable reference to the contributors to the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Person};
+    ///
+    /// let mut source = Source::default();
+    /// source.contributors_mut().push(Person::default());
+    /// assert_eq!(source.contributors().len(), 1);
+    /// ```
+    pub fn contributors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.contributors
+    }
+
     /// Set the contributors to the source feed.
     ///
     /// # Examples
@@ -243,6 +374,21 @@ impl Source {
         self.contributors = contributors.into();
     }
 
+    /// Add a contributor to this source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Person, Source};
+    ///
+    /// let mut source = Source::default();
+    /// source.push_contributor(Person::default());
+    /// assert_eq!(source.contributors().len(), 1);
+    /// ```
+    pub fn push_contributor(&mut self, contributor: Person) {
+        self.contributors.push(contributor);
+    }
+
     /// Return the name of the software used to generate the source feed.
     ///
     /// # Examples
@@ -258,6 +404,21 @@ impl Source {
         self.generator.as_ref()
     }
 
+    /// Return a mutable reference to the name of the software used to generate the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Generator};
+    ///
+    /// let mut source = Source::default();
+    /// *source.generator_mut() = Some(Generator::default());
+    /// assert!(source.generator().is_some());
+    /// ```
+    pub fn generator_mut(&mut self) -> &mut Option<Generator> {
+        &mut self.generator
+    }
+
     /// Set the name of the software used to generate the source feed.
     ///
     /// # Examples
@@ -283,13 +444,28 @@ impl Source {
     /// use atom_syndication::Source;
     ///
     /// let mut source = Source::default();
-    /// source.set_icon("http://example.com/icon.png".to_string());
+    /// source.set_icon("http://example.com/icon.png");
     /// assert_eq!(source.icon(), Some("http://example.com/icon.png"));
     /// ```
     pub fn icon(&self) -> Option<&str> {
         self.icon.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the icon for the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// *source.icon_mut() = Some("http://example.com/icon.png".to_string());
+    /// assert_eq!(source.icon(), Some("http://example.com/icon.png"));
+    /// ```
+    pub fn icon_mut(&mut self) -> &mut Option<String> {
+        &mut self.icon
+    }
+
     /// Set the icon for the source feed.
     ///
     /// # Examples
@@ -298,13 +474,13 @@ impl Source {
     /// use atom_syndication::Source;
     ///
     /// let mut source = Source::default();
-    /// source.set_icon("http://example.com/icon.png".to_string());
+    /// source.set_icon("http://example.com/icon.png");
     /// ```
     pub fn set_icon<V>(&mut self, icon: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.icon = icon.into()
+        self.icon = icon.into_option()
     }
 
     /// Return the Web pages related to the source feed.
@@ -322,6 +498,21 @@ impl Source {
         self.links.as_slice()
     }
 
+    /// Return a mutable reference to the Web pages related to the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Link};
+    ///
+    /// let mut source = Source::default();
+    /// source.links_mut().push(Link::default());
+    /// assert_eq!(source.links().len(), 1);
+    /// ```
+    pub fn links_mut(&mut self) -> &mut Vec<Link> {
+        &mut self.links
+    }
+
     /// Set the Web pages related to the source feed.
     ///
     /// # Examples
@@ -339,6 +530,21 @@ impl Source {
         self.links = links.into();
     }
 
+    /// Add a link to this source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Link, Source};
+    ///
+    /// let mut source = Source::default();
+    /// source.push_link(Link::default());
+    /// assert_eq!(source.links().len(), 1);
+    /// ```
+    pub fn push_link(&mut self, link: Link) {
+        self.links.push(link);
+    }
+
     /// Return the logo for the source feed.
     ///
     /// # Examples
@@ -347,13 +553,28 @@ impl Source {
     /// use atom_syndication::Source;
     ///
     /// let mut source = Source::default();
-    /// source.set_logo("http://example.com/logo.png".to_string());
+    /// source.set_logo("http://example.com/logo.png");
     /// assert_eq!(source.logo(), Some("http://example.com/logo.png"));
     /// ```
     pub fn logo(&self) -> Option<&str> {
         self.logo.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the logo for the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// *source.logo_mut() = Some("http://example.com/logo.png".to_string());
+    /// assert_eq!(source.logo(), Some("http://example.com/logo.png"));
+    /// ```
+    pub fn logo_mut(&mut self) -> &mut Option<String> {
+        &mut self.logo
+    }
+
     /// Set the logo for the source feed.
     ///
     /// # Examples
@@ -362,13 +583,13 @@ impl Source {
     /// use atom_syndication::Source;
     ///
     /// let mut source = Source::default();
-    /// source.set_logo("http://example.com/logo.png".to_string());
+    /// source.set_logo("http://example.com/logo.png");
     /// ```
     pub fn set_logo<V>(&mut self, logo: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.logo = logo.into()
+        self.logo = logo.into_option()
     }
 
     /// Return the information about the rights held in and over the source feed.
@@ -379,13 +600,29 @@ impl Source {
     /// use atom_syndication::Source;
     ///
     /// let mut source = Source::default();
-    /// source.set_rights("© 2017 John Doe".to_string());
+    /// source.set_rights("© 2017 John Doe");
     /// assert_eq!(source.rights(), Some("© 2017 John Doe"));
     /// ```
     pub fn rights(&self) -> Option<&str> {
         self.rights.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the information about the rights held in and over the
+    /// source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// *source.rights_mut() = Some("© 2017 John Doe".to_string());
+    /// assert_eq!(source.rights(), Some("© 2017 John Doe"));
+    /// ```
+    pub fn rights_mut(&mut self) -> &mut Option<String> {
+        &mut self.rights
+    }
+
     /// Set the information about the rights held in and over the source feed.
     ///
     /// # Examples
@@ -394,13 +631,13 @@ impl Source {
     /// use atom_syndication::Source;
     ///
     /// let mut source = Source::default();
-    /// source.set_rights("© 2017 John Doe".to_string());
+    /// source.set_rights("© 2017 John Doe");
     /// ```
     pub fn set_rights<V>(&mut self, rights: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
     {
-        self.rights = rights.into()
+        self.rights = rights.into_option()
     }
 
     /// Return the description or subtitle of the source feed.
@@ -411,13 +648,28 @@ impl Source {
     /// use atom_syndication::Source;
     ///
     /// let mut source = Source::default();
-    /// source.set_subtitle("Feed subtitle".to_string());
+    /// source.set_subtitle("Feed subtitle");
     /// assert_eq!(source.subtitle(), Some("Feed subtitle"));
     /// ```
     pub fn subtitle(&self) -> Option<&str> {
         self.subtitle.as_ref().map(String::as_str)
     }
 
+    /// Return a mutable reference to the description or subtitle of the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// *source.subtitle_mut() = Some("Feed subtitle".to_string());
+    /// assert_eq!(source.subtitle(), Some("Feed subtitle"));
+    /// ```
+    pub fn subtitle_mut(&mut self) -> &mut Option<String> {
+        &mut self.subtitle
+    }
+
     /// Set the description or subtitle of the source feed.
     ///
     /// # Examples
@@ -426,50 +678,239 @@ impl Source {
     /// use atom_syndication::Source;
     ///
     /// let mut source = Source::default();
-    /// source.set_subtitle("Feed subtitle".to_string());
+    /// source.set_subtitle("Feed subtitle");
     /// ```
     pub fn set_subtitle<V>(&mut self, subtitle: V)
     where
-        V: Into<Option<String>>,
+        V: IntoOption<String>,
+    {
+        self.subtitle = subtitle.into_option()
+    }
+
+    /// Return the additional attributes of this source.
+    ///
+    /// These are attributes of the element that are not otherwise represented, such as `xml:lang`
+    /// or vendor-specific attributes. They are written back out when serializing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Source;
+    ///
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("xml:lang".to_string(), "en".to_string());
+    ///
+    /// let mut source = Source::default();
+    /// source.set_attrs(attrs);
+    /// assert_eq!(source.attrs().get("xml:lang").map(String::as_str), Some("en"));
+    /// ```
+    pub fn attrs(&self) -> &BTreeMap<String, String> {
+        &self.attrs
+    }
+
+    /// Return a mutable reference to the additional attributes of this source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// source.attrs_mut()
+    ///     .insert("xml:lang".to_string(), "en".to_string());
+    /// assert_eq!(source.attrs().len(), 1);
+    /// ```
+    pub fn attrs_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.attrs
+    }
+
+    /// Set the additional attributes of this source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// source.set_attrs(BTreeMap::new());
+    /// ```
+    pub fn set_attrs<V>(&mut self, attrs: V)
+    where
+        V: Into<BTreeMap<String, String>>,
     {
-        self.subtitle = subtitle.into()
+        self.attrs = attrs.into();
+    }
+
+    /// Return the extensions for the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use atom_syndication::Source;
+    /// use atom_syndication::extension::{ExtensionMap, Extension};
+    ///
+    /// let mut item_map = HashMap::<String, Vec<Extension>>::new();
+    /// item_map.insert("name".to_string(), vec![Extension::default()]);
+    ///
+    /// let mut extension_map = ExtensionMap::default();
+    /// extension_map.insert("ext".to_string(), item_map);
+    ///
+    /// let mut source = Source::default();
+    /// source.set_extensions(extension_map);
+    /// assert!(source.extensions().contains_key("ext"));
+    /// ```
+    pub fn extensions(&self) -> &ExtensionMap {
+        &self.extensions
+    }
+
+    /// Return a mutable reference to the extensions for the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut source = Source::default();
+    /// source.extensions_mut()
+    ///     .entry("ext".to_string())
+    ///     .or_default()
+    ///     .insert("name".to_string(), vec![Extension::default()]);
+    /// assert!(source.extensions().contains_key("ext"));
+    /// ```
+    pub fn extensions_mut(&mut self) -> &mut ExtensionMap {
+        &mut self.extensions
+    }
+
+    /// Set the extensions for the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    /// use atom_syndication::extension::ExtensionMap;
+    ///
+    /// let mut source = Source::default();
+    /// source.set_extensions(ExtensionMap::default());
+    /// ```
+    pub fn set_extensions<V>(&mut self, extensions: V)
+    where
+        V: Into<ExtensionMap>,
+    {
+        self.extensions = extensions.into();
+    }
+
+    pub(crate) fn rename_prefixes(&mut self, renames: &HashMap<String, String>) {
+        rename_prefixes(&mut self.extensions, renames);
+
+        for link in &mut self.links {
+            link.rename_prefixes(renames);
+        }
+    }
+}
+
+impl<'a> From<&'a Feed> for Source {
+    /// Create a source from the metadata of a feed, for recording the provenance of an entry
+    /// copied from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Source};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// feed.set_icon("http://example.com/icon.png");
+    ///
+    /// let source = Source::from(&feed);
+    /// assert_eq!(source.title(), "Feed Title");
+    /// assert_eq!(source.icon(), Some("http://example.com/icon.png"));
+    /// ```
+    fn from(feed: &'a Feed) -> Self {
+        Source {
+            title: feed.title().to_string(),
+            id: feed.id().to_string(),
+            updated: *feed.updated(),
+            authors: feed.authors().to_vec(),
+            categories: feed.categories().to_vec(),
+            contributors: feed.contributors().to_vec(),
+            generator: feed.generator().cloned(),
+            icon: feed.icon().map(String::from),
+            links: feed.links().to_vec(),
+            logo: feed.logo().map(String::from),
+            rights: feed.rights().map(String::from),
+            subtitle: feed.subtitle().map(String::from),
+            extensions: feed.extensions().clone(),
+            attrs: BTreeMap::new(),
+        }
     }
 }
 
 impl FromXml for Source {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes,
+        ctx: &mut ReadContext,
+    ) -> Result<Self, Error> {
         let mut source = Source::default();
+        collect_attrs(reader, atts, &mut source.attrs)?;
         let mut buf = Vec::new();
 
         loop {
             match reader.read_event(&mut buf)? {
-                Event::Start(element) => match element.name() {
-                    b"id" => source.id = atom_text(reader)?.unwrap_or_default(),
-                    b"title" => source.title = atom_text(reader)?.unwrap_or_default(),
+                Event::Start(element) => match ctx.atom_name(element.name()) {
+                    b"id" => source.id = ctx.read_text(reader)?.unwrap_or_default(),
+                    b"title" => source.title = ctx.read_text(reader)?.unwrap_or_default(),
                     b"updated" => {
-                        source.updated =
-                            atom_datetime(reader)?.unwrap_or_else(default_fixed_datetime)
+                        source.updated = ctx
+                            .read_date(reader, "updated")?
+                            .unwrap_or_else(default_fixed_datetime)
+                    }
+                    b"author" => {
+                        source
+                            .authors
+                            .push(Person::from_xml(reader, element.attributes(), ctx)?)
                     }
-                    b"author" => source
-                        .authors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    b"category" => source
-                        .categories
-                        .push(Category::from_xml(reader, element.attributes())?),
-                    b"contributor" => source
-                        .contributors
-                        .push(Person::from_xml(reader, element.attributes())?),
+                    b"category" => source.categories.push(Category::from_xml(
+                        reader,
+                        element.attributes(),
+                        ctx,
+                    )?),
+                    b"contributor" => source.contributors.push(Person::from_xml(
+                        reader,
+                        element.attributes(),
+                        ctx,
+                    )?),
                     b"generator" => {
-                        source.generator = Some(Generator::from_xml(reader, element.attributes())?)
+                        source.generator =
+                            Some(Generator::from_xml(reader, element.attributes(), ctx)?)
+                    }
+                    b"icon" => source.icon = ctx.read_text(reader)?,
+                    b"link" => {
+                        source
+                            .links
+                            .push(Link::from_xml(reader, element.attributes(), ctx)?)
+                    }
+                    b"logo" => source.logo = ctx.read_text(reader)?,
+                    b"rights" => source.rights = ctx.read_text(reader)?,
+                    b"subtitle" => source.subtitle = ctx.read_text(reader)?,
+                    _ => {
+                        if let Some((ns, name)) = extension_name(ctx.atom_name(element.name())) {
+                            parse_extension(
+                                reader,
+                                element.attributes(),
+                                ns,
+                                name,
+                                &mut source.extensions,
+                                ctx.config.get_max_depth(),
+                            )?;
+                        } else {
+                            ctx.skip(reader, element.name())?;
+                        }
                     }
-                    b"icon" => source.icon = atom_text(reader)?,
-                    b"link" => source
-                        .links
-                        .push(Link::from_xml(reader, element.attributes())?),
-                    b"logo" => source.logo = atom_text(reader)?,
-                    b"rights" => source.rights = atom_text(reader)?,
-                    b"subtitle" => source.subtitle = atom_text(reader)?,
-                    n => reader.read_to_end(n, &mut Vec::new())?,
                 },
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
@@ -484,36 +925,48 @@ impl FromXml for Source {
 }
 
 impl ToXml for Source {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>, ctx: &WriteContext) -> Result<(), XmlError> {
         let name = b"source";
-        writer.write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
-        writer.write_text_element(b"title", &*self.title)?;
+        let mut element = BytesStart::borrowed(name, name.len());
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        writer.write_event(Event::Start(element))?;
+        writer.write_text_construct(b"title", &*self.title, ctx)?;
         writer.write_text_element(b"id", &*self.id)?;
         writer.write_text_element(b"updated", &self.updated.to_rfc3339())?;
-        writer.write_objects_named(&self.authors, "author")?;
-        writer.write_objects(&self.categories)?;
-        writer.write_objects_named(&self.contributors, "contributor")?;
+        writer.write_objects_named(&self.authors, "author", ctx)?;
+        writer.write_objects(&self.categories, ctx)?;
+        writer.write_objects_named(&self.contributors, "contributor", ctx)?;
 
         if let Some(ref generator) = self.generator {
-            writer.write_object(generator)?;
+            writer.write_object(generator, ctx)?;
         }
 
         if let Some(ref icon) = self.icon {
             writer.write_text_element(b"icon", &**icon)?;
         }
 
-        writer.write_objects(&self.links)?;
+        writer.write_objects(&self.links, ctx)?;
 
         if let Some(ref logo) = self.logo {
             writer.write_text_element(b"logo", &**logo)?;
         }
 
         if let Some(ref rights) = self.rights {
-            writer.write_text_element(b"rights", &**rights)?;
+            writer.write_text_construct(b"rights", &**rights, ctx)?;
         }
 
         if let Some(ref subtitle) = self.subtitle {
-            writer.write_text_element(b"subtitle", &**subtitle)?;
+            writer.write_text_construct(b"subtitle", &**subtitle, ctx)?;
+        }
+
+        let mut names = self.extensions.iter().collect::<Vec<_>>();
+        names.sort_by_key(|&(ns, _)| ns);
+        for (_, map) in names {
+            let mut items = map.iter().collect::<Vec<_>>();
+            items.sort_by_key(|&(name, _)| name);
+            for (_, extensions) in items {
+                writer.write_objects(extensions, ctx)?;
+            }
         }
 
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
@@ -522,6 +975,26 @@ impl ToXml for Source {
     }
 }
 
+// Extensions are not hashed because the maps they are stored in are unordered. Sources that differ
+// only in their extensions hash the same, which is consistent with `Eq`.
+impl Hash for Source {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.title.hash(state);
+        self.id.hash(state);
+        self.updated.hash(state);
+        self.authors.hash(state);
+        self.categories.hash(state);
+        self.contributors.hash(state);
+        self.generator.hash(state);
+        self.icon.hash(state);
+        self.links.hash(state);
+        self.logo.hash(state);
+        self.rights.hash(state);
+        self.subtitle.hash(state);
+        self.attrs.hash(state);
+    }
+}
+
 impl Default for Source {
     fn default() -> Self {
         Source {
@@ -537,6 +1010,8 @@ impl Default for Source {
             logo: None,
             rights: None,
             subtitle: None,
+            extensions: ExtensionMap::default(),
+            attrs: BTreeMap::new(),
         }
     }
 }
@@ -0,0 +1,426 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, BufRead, Read};
+use std::time::Duration;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::Instant;
+
+use crate::feed::Feed;
+use crate::util::FixedDateTime;
+use crate::validate::ValidationIssue;
+
+/// Statistics collected while reading a feed.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Feed, ReadConfig};
+///
+/// let xml = "<feed><entry><rating>5</rating></entry><entry></entry></feed>";
+/// let (_, stats) = Feed::read_with_stats(xml.as_bytes(), &ReadConfig::default()).unwrap();
+/// assert_eq!(stats.entries(), 2);
+/// assert_eq!(stats.skipped_elements()["rating"], 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseStats {
+    pub(crate) bytes: usize,
+    pub(crate) entries: usize,
+    pub(crate) skipped_elements: BTreeMap<String, usize>,
+    pub(crate) duration: Duration,
+    pub(crate) issues: Vec<ValidationIssue>,
+}
+
+impl ParseStats {
+    /// Return the number of bytes consumed from the reader.
+    ///
+    /// For compressed input this is the size of the compressed data that was read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let xml = "<feed></feed>";
+    /// let (_, stats) = Feed::read_with_stats(xml.as_bytes(), &ReadConfig::default()).unwrap();
+    /// assert_eq!(stats.bytes(), 13);
+    /// ```
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Return the number of entries read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let xml = "<feed><entry></entry></feed>";
+    /// let (_, stats) = Feed::read_with_stats(xml.as_bytes(), &ReadConfig::default()).unwrap();
+    /// assert_eq!(stats.entries(), 1);
+    /// ```
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Return the local names of the unknown elements that were skipped, along with the number
+    /// of times each was skipped.
+    ///
+    /// Elements with a namespace prefix are read as extensions and are not counted. When the
+    /// feed is read with `ReadConfig::fidelity`, unknown elements of feeds and entries are kept
+    /// rather than skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let xml = "<feed><author><name>John</name><age>42</age></author></feed>";
+    /// let (_, stats) = Feed::read_with_stats(xml.as_bytes(), &ReadConfig::default()).unwrap();
+    /// assert_eq!(stats.skipped_elements()["age"], 1);
+    /// ```
+    pub fn skipped_elements(&self) -> &BTreeMap<String, usize> {
+        &self.skipped_elements
+    }
+
+    /// Return the time spent reading the feed.
+    ///
+    /// This is always zero on `wasm32-unknown-unknown`, which has no clock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use atom_syndication::{Feed, ReadConfig};
+    ///
+    /// let (_, stats) = Feed::read_with_stats("<feed></feed>".as_bytes(), &ReadConfig::default())
+    ///     .unwrap();
+    /// assert!(stats.duration() < Duration::from_secs(60));
+    /// ```
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Return the problems found in the document that did not stop it from being read, such as
+    /// the malformed dates read with `ReadConfig::lenient_dates` and the broken entries skipped
+    /// with `ReadConfig::skip_broken_entries`.
+    ///
+    /// The location of each issue is the path of the element it was found in, such as
+    /// `/feed/entry[0]/published`. Invalid UTF-8, which is replaced with U+FFFD REPLACEMENT
+    /// CHARACTER rather than failing, is reported once for the whole document at `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig, Severity};
+    ///
+    /// let xml = "<feed><entry><published>yesterday</published></entry></feed>";
+    /// let config = ReadConfig::new().lenient_dates(true);
+    /// let (feed, stats) = Feed::read_with_stats(xml.as_bytes(), &config).unwrap();
+    ///
+    /// assert_eq!(feed.entries()[0].published(), None);
+    /// assert_eq!(stats.issues()[0].severity(), Severity::Warning);
+    /// assert_eq!(stats.issues()[0].location(), "/feed/entry[0]/published");
+    /// ```
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+}
+
+/// Statistics about the entries of a feed, as returned by
+/// [`Feed::stats`](struct.Feed.html#method.stats).
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::Feed;
+///
+/// let xml = r#"<feed>
+///     <entry><category term="rust"/><content>Hello</content></entry>
+///     <entry><category term="rust"/><category term="xml"/></entry>
+/// </feed>"#;
+/// let stats = xml.parse::<Feed>().unwrap().stats();
+/// assert_eq!(stats.entries(), 2);
+/// assert_eq!(stats.categories()["rust"], 2);
+/// assert_eq!(stats.content_bytes(), 5);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedStats {
+    entries: usize,
+    published: Option<(FixedDateTime, FixedDateTime)>,
+    updated: Option<(FixedDateTime, FixedDateTime)>,
+    authors: usize,
+    categories: BTreeMap<String, usize>,
+    content_bytes: usize,
+}
+
+/// Widen a date range to include a date.
+fn extend_range(range: &mut Option<(FixedDateTime, FixedDateTime)>, date: FixedDateTime) {
+    match *range {
+        Some((ref mut oldest, ref mut newest)) => {
+            if date < *oldest {
+                *oldest = date;
+            }
+            if date > *newest {
+                *newest = date;
+            }
+        }
+        None => *range = Some((date, date)),
+    }
+}
+
+impl FeedStats {
+    /// Return the number of entries in the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let stats = "<feed><entry></entry></feed>".parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.entries(), 1);
+    /// ```
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Return the earliest published date of the entries, if any entry has one.
+    ///
+    /// Dates are compared as instants, so dates with different offsets are ordered correctly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><published>2017-06-03T15:15:44-05:00</published></entry>
+    ///     <entry><published>2017-06-03T21:15:44+02:00</published></entry>
+    /// </feed>"#;
+    /// let stats = xml.parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.oldest_published().unwrap().to_rfc3339(), "2017-06-03T21:15:44+02:00");
+    /// ```
+    pub fn oldest_published(&self) -> Option<&FixedDateTime> {
+        self.published.as_ref().map(|(oldest, _)| oldest)
+    }
+
+    /// Return the latest published date of the entries, if any entry has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><published>2017-06-03T15:15:44-05:00</published></entry>
+    ///     <entry><published>2017-06-03T21:15:44+02:00</published></entry>
+    /// </feed>"#;
+    /// let stats = xml.parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.newest_published().unwrap().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn newest_published(&self) -> Option<&FixedDateTime> {
+        self.published.as_ref().map(|(_, newest)| newest)
+    }
+
+    /// Return the earliest updated date of the entries, if the feed has any entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><updated>2017-06-03T15:15:44-05:00</updated></entry>
+    ///     <entry><updated>2017-06-04T15:15:44-05:00</updated></entry>
+    /// </feed>"#;
+    /// let stats = xml.parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.oldest_updated().unwrap().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn oldest_updated(&self) -> Option<&FixedDateTime> {
+        self.updated.as_ref().map(|(oldest, _)| oldest)
+    }
+
+    /// Return the latest updated date of the entries, if the feed has any entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><updated>2017-06-03T15:15:44-05:00</updated></entry>
+    ///     <entry><updated>2017-06-04T15:15:44-05:00</updated></entry>
+    /// </feed>"#;
+    /// let stats = xml.parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.newest_updated().unwrap().to_rfc3339(), "2017-06-04T15:15:44-05:00");
+    /// ```
+    pub fn newest_updated(&self) -> Option<&FixedDateTime> {
+        self.updated.as_ref().map(|(_, newest)| newest)
+    }
+
+    /// Return the number of distinct authors of the entries.
+    ///
+    /// Authors are the same if they have the same URI or, when they have none, the same name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><author><name>John</name></author></entry>
+    ///     <entry><author><name>John</name></author><author><name>Jane</name></author></entry>
+    /// </feed>"#;
+    /// let stats = xml.parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.authors(), 2);
+    /// ```
+    pub fn authors(&self) -> usize {
+        self.authors
+    }
+
+    /// Return the terms of the categories of the entries, along with the number of times each
+    /// was used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><category term="rust"/></entry>
+    ///     <entry><category term="rust"/><category term="xml"/></entry>
+    /// </feed>"#;
+    /// let stats = xml.parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.categories()["rust"], 2);
+    /// assert_eq!(stats.categories()["xml"], 1);
+    /// ```
+    pub fn categories(&self) -> &BTreeMap<String, usize> {
+        &self.categories
+    }
+
+    /// Return the total size in bytes of the inline content of the entries.
+    ///
+    /// Content given by a `src` URL is not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = "<feed><entry><content>Hello</content></entry></feed>";
+    /// let stats = xml.parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.content_bytes(), 5);
+    /// ```
+    pub fn content_bytes(&self) -> usize {
+        self.content_bytes
+    }
+}
+
+impl Feed {
+    /// Return statistics about the entries of this feed: their number, the range of their
+    /// published and updated dates, the number of distinct authors, how often each category term
+    /// is used and the total size of their content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <entry><author><name>John</name></author><category term="rust"/></entry>
+    /// </feed>"#;
+    /// let stats = xml.parse::<Feed>().unwrap().stats();
+    /// assert_eq!(stats.entries(), 1);
+    /// assert_eq!(stats.authors(), 1);
+    /// assert_eq!(stats.categories()["rust"], 1);
+    /// ```
+    pub fn stats(&self) -> FeedStats {
+        let mut stats = FeedStats {
+            entries: self.entries().len(),
+            ..Default::default()
+        };
+        let mut authors = HashSet::new();
+
+        for entry in self.entries() {
+            if let Some(published) = entry.published() {
+                extend_range(&mut stats.published, *published);
+            }
+            extend_range(&mut stats.updated, *entry.updated());
+
+            for author in entry.authors() {
+                authors.insert(author.uri().unwrap_or_else(|| author.name()));
+            }
+            for category in entry.categories() {
+                *stats
+                    .categories
+                    .entry(category.term().to_string())
+                    .or_insert(0) += 1;
+            }
+            if let Some(value) = entry.content().and_then(|content| content.value()) {
+                stats.content_bytes += value.len();
+            }
+        }
+
+        stats.authors = authors.len();
+        stats
+    }
+}
+
+/// A timer for the durations reported while reading and writing.
+///
+/// There is no clock on `wasm32-unknown-unknown`, where the timer always reads zero.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Timer {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    start: Instant,
+}
+
+impl Timer {
+    pub(crate) fn start() -> Self {
+        Timer {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        return self.start.elapsed();
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        return Duration::default();
+    }
+}
+
+/// A reader that counts the bytes consumed from the reader it wraps.
+pub(crate) struct CountingReader<B> {
+    inner: B,
+    count: usize,
+}
+
+impl<B> CountingReader<B> {
+    pub(crate) fn new(inner: B) -> Self {
+        CountingReader { inner, count: 0 }
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl<B: Read> Read for CountingReader<B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.count += len;
+        Ok(len)
+    }
+}
+
+impl<B: BufRead> BufRead for CountingReader<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.count += amt;
+        self.inner.consume(amt);
+    }
+}
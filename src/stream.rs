@@ -0,0 +1,127 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::config::ReadConfig;
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::feed::Feed;
+use crate::parser::FeedParser;
+
+/// The number of bytes read from the underlying reader at a time.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// A stream of the entries of a feed read from an asynchronous reader.
+///
+/// Entries are yielded in document order as soon as their end tag has been read. The stream ends
+/// after the first error.
+pub struct EntryStream<R> {
+    reader: R,
+    parser: FeedParser,
+    chunk: Vec<u8>,
+    ready: VecDeque<Entry>,
+    done: bool,
+}
+
+impl Feed {
+    /// Return a stream of the entries of the feed read from an asynchronous reader.
+    ///
+    /// Only the entry currently being read is held in memory, so a large feed can be processed
+    /// with stream combinators as it is downloaded. The metadata of the feed is not read. The
+    /// document is expected to be encoded as UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    /// use futures_util::StreamExt;
+    ///
+    /// let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+    ///     <title>Feed Title</title>
+    ///     <entry><id>urn:entry:1</id></entry>
+    ///     <entry><id>urn:entry:2</id></entry>
+    /// </feed>"#;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let ids = Feed::entry_stream(xml.as_bytes())
+    ///     .map(|entry| entry.unwrap().id().to_string())
+    ///     .collect::<Vec<_>>()
+    ///     .await;
+    /// assert_eq!(ids, vec!["urn:entry:1", "urn:entry:2"]);
+    /// # });
+    /// ```
+    pub fn entry_stream<R: AsyncRead + Unpin>(reader: R) -> EntryStream<R> {
+        Feed::entry_stream_with_config(reader, &ReadConfig::default())
+    }
+
+    /// Return a stream of the entries of the feed read from an asynchronous reader with the
+    /// given options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadConfig};
+    /// use futures_util::StreamExt;
+    ///
+    /// let xml = r#"<feed><entry><id>urn:entry:1</id><rank>1</rank></entry></feed>"#;
+    /// let config = ReadConfig::new().fidelity(true);
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut entries = Feed::entry_stream_with_config(xml.as_bytes(), &config);
+    /// let entry = entries.next().await.unwrap().unwrap();
+    /// assert_eq!(entry.unknown_elements()[0].name(), "rank");
+    /// # });
+    /// ```
+    pub fn entry_stream_with_config<R: AsyncRead + Unpin>(
+        reader: R,
+        config: &ReadConfig,
+    ) -> EntryStream<R> {
+        EntryStream {
+            reader,
+            parser: FeedParser::with_config(config),
+            chunk: vec![0; CHUNK_SIZE],
+            ready: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for EntryStream<R> {
+    type Item = Result<Entry, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(entry) = this.ready.pop_front() {
+                return Poll::Ready(Some(Ok(entry)));
+            }
+
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let mut buf = ReadBuf::new(&mut this.chunk);
+            let result = match Pin::new(&mut this.reader).poll_read(cx, &mut buf) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(err)) => Err(Error::from(err)),
+                Poll::Ready(Ok(())) if buf.filled().is_empty() => {
+                    this.done = true;
+                    this.parser.finish()
+                }
+                Poll::Ready(Ok(())) => this
+                    .parser
+                    .feed_bytes(buf.filled())
+                    .map(|entries| this.ready.extend(entries)),
+            };
+
+            if let Err(err) = result {
+                this.done = true;
+                return Poll::Ready(Some(Err(err)));
+            }
+        }
+    }
+}
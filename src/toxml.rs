@@ -1,63 +1,156 @@
+//! Serialization of elements to XML.
+//!
+//! Implement [`ToXml`] to write a custom element type with the same machinery used for the
+//! elements of a feed. [`WriterExt`] adds helpers for writing text elements and nested objects to
+//! a `quick_xml::Writer`.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::Write;
+//!
+//! use atom_syndication::toxml::{ToXml, WriteContext, WriterExt};
+//! use atom_syndication::WriteConfig;
+//! use quick_xml::{Error as XmlError, Writer};
+//!
+//! struct Rating(String);
+//!
+//! impl ToXml for Rating {
+//!     fn to_xml<W: Write>(
+//!         &self,
+//!         writer: &mut Writer<W>,
+//!         _: &WriteContext,
+//!     ) -> Result<(), XmlError> {
+//!         writer.write_text_element(b"media:rating", &self.0)
+//!     }
+//! }
+//!
+//! let mut writer = Writer::new(Vec::new());
+//! let ctx = WriteContext::new(WriteConfig::default());
+//! writer.write_object(Rating("adult".to_string()), &ctx).unwrap();
+//! assert_eq!(writer.into_inner(), b"<media:rating>adult</media:rating>");
+//! ```
+
+use std::collections::HashMap;
 use std::io::Write;
 
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Error as XmlError;
 use quick_xml::Writer;
 
+use crate::config::{CdataPolicy, WriteConfig};
+
+/// The state shared by the elements of a document while it is being written.
+pub struct WriteContext {
+    /// The options the document is written with.
+    pub config: WriteConfig,
+}
+
+impl WriteContext {
+    /// Create a context for writing a document with the given options.
+    pub fn new(config: WriteConfig) -> Self {
+        WriteContext { config }
+    }
+}
+
+/// An element that can be written as XML.
 pub trait ToXml {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError>;
+    /// Write the element, including its start and end tags.
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>, ctx: &WriteContext) -> Result<(), XmlError>;
 }
 
 impl<'a, T: ToXml> ToXml for &'a T {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
-        (*self).to_xml(writer)
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>, ctx: &WriteContext) -> Result<(), XmlError> {
+        (*self).to_xml(writer, ctx)
     }
 }
 
+/// An element that can be written as XML under a name chosen by its parent, such as a person
+/// written as either an `author` or a `contributor`.
 pub trait ToXmlNamed {
-    fn to_xml_named<W, N>(&self, writer: &mut Writer<W>, name: N) -> Result<(), XmlError>
+    /// Write the element with the given name, including its start and end tags.
+    fn to_xml_named<W, N>(
+        &self,
+        writer: &mut Writer<W>,
+        name: N,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError>
     where
         W: Write,
         N: AsRef<[u8]>;
 }
 
 impl<'a, T: ToXmlNamed> ToXmlNamed for &'a T {
-    fn to_xml_named<W, N>(&self, writer: &mut Writer<W>, name: N) -> Result<(), XmlError>
+    fn to_xml_named<W, N>(
+        &self,
+        writer: &mut Writer<W>,
+        name: N,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError>
     where
         W: Write,
         N: AsRef<[u8]>,
     {
-        (*self).to_xml_named(writer, name)
+        (*self).to_xml_named(writer, name, ctx)
     }
 }
 
+/// Helpers for writing elements with a `quick_xml::Writer`.
 pub trait WriterExt {
+    /// Write an element containing the given text, which is written as is.
     fn write_text_element<N, T>(&mut self, name: N, text: T) -> Result<(), XmlError>
     where
         N: AsRef<[u8]>,
         T: AsRef<[u8]>;
 
+    /// Write an element containing text for each of the given values.
     fn write_text_elements<N, T, I>(&mut self, name: N, values: I) -> Result<(), XmlError>
     where
         N: AsRef<[u8]>,
         T: AsRef<[u8]>,
         I: IntoIterator<Item = T>;
 
-    fn write_object<T>(&mut self, object: T) -> Result<(), XmlError>
+    /// Write an element containing human-readable text, using a CDATA section if the
+    /// configuration asks for one.
+    fn write_text_construct<N, T>(
+        &mut self,
+        name: N,
+        text: T,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError>
+    where
+        N: AsRef<[u8]>,
+        T: AsRef<str>;
+
+    /// Write an object.
+    fn write_object<T>(&mut self, object: T, ctx: &WriteContext) -> Result<(), XmlError>
     where
         T: ToXml;
 
-    fn write_object_named<T, N>(&mut self, object: T, name: N) -> Result<(), XmlError>
+    /// Write an object with the given element name.
+    fn write_object_named<T, N>(
+        &mut self,
+        object: T,
+        name: N,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError>
     where
         T: ToXmlNamed,
         N: AsRef<[u8]>;
 
-    fn write_objects<T, I>(&mut self, objects: I) -> Result<(), XmlError>
+    /// Write each of the given objects.
+    fn write_objects<T, I>(&mut self, objects: I, ctx: &WriteContext) -> Result<(), XmlError>
     where
         T: ToXml,
         I: IntoIterator<Item = T>;
 
-    fn write_objects_named<T, I, N>(&mut self, objects: I, name: N) -> Result<(), XmlError>
+    /// Write each of the given objects with the given element name.
+    fn write_objects_named<T, I, N>(
+        &mut self,
+        objects: I,
+        name: N,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError>
     where
         T: ToXmlNamed,
         I: IntoIterator<Item = T>,
@@ -90,43 +183,128 @@ impl<W: Write> WriterExt for Writer<W> {
         Ok(())
     }
 
-    fn write_object<T>(&mut self, object: T) -> Result<(), XmlError>
+    fn write_text_construct<N, T>(
+        &mut self,
+        name: N,
+        text: T,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError>
+    where
+        N: AsRef<[u8]>,
+        T: AsRef<str>,
+    {
+        let name = name.as_ref();
+        self.write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
+        write_text(self, text.as_ref(), ctx)?;
+        self.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        Ok(())
+    }
+
+    fn write_object<T>(&mut self, object: T, ctx: &WriteContext) -> Result<(), XmlError>
     where
         T: ToXml,
     {
-        object.to_xml(self)
+        object.to_xml(self, ctx)
     }
 
-    fn write_object_named<T, N>(&mut self, object: T, name: N) -> Result<(), XmlError>
+    fn write_object_named<T, N>(
+        &mut self,
+        object: T,
+        name: N,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError>
     where
         T: ToXmlNamed,
         N: AsRef<[u8]>,
     {
-        object.to_xml_named(self, name)
+        object.to_xml_named(self, name, ctx)
     }
 
-    fn write_objects<T, I>(&mut self, objects: I) -> Result<(), XmlError>
+    fn write_objects<T, I>(&mut self, objects: I, ctx: &WriteContext) -> Result<(), XmlError>
     where
         T: ToXml,
         I: IntoIterator<Item = T>,
     {
         for object in objects {
-            object.to_xml(self)?;
+            object.to_xml(self, ctx)?;
         }
 
         Ok(())
     }
 
-    fn write_objects_named<T, I, N>(&mut self, objects: I, name: N) -> Result<(), XmlError>
+    fn write_objects_named<T, I, N>(
+        &mut self,
+        objects: I,
+        name: N,
+        ctx: &WriteContext,
+    ) -> Result<(), XmlError>
     where
         T: ToXmlNamed,
         I: IntoIterator<Item = T>,
         N: AsRef<[u8]>,
     {
         for object in objects {
-            object.to_xml_named(self, name.as_ref())?;
+            object.to_xml_named(self, name.as_ref(), ctx)?;
         }
 
         Ok(())
     }
 }
+
+/// Write child elements, first in the recorded `order` and then any remaining children in the
+/// `defaults` order. `write_child` writes the `index`th child with the given name and returns
+/// `false` if there is no such child.
+pub(crate) fn write_children<W, F>(
+    writer: &mut Writer<W>,
+    order: &[String],
+    defaults: &[String],
+    mut write_child: F,
+) -> Result<(), XmlError>
+where
+    W: Write,
+    F: FnMut(&mut Writer<W>, &str, usize) -> Result<bool, XmlError>,
+{
+    let mut written = HashMap::<&str, usize>::new();
+
+    for name in order {
+        let index = written.entry(name.as_str()).or_insert(0);
+        if write_child(writer, name, *index)? {
+            *index += 1;
+        }
+    }
+
+    for name in defaults {
+        let index = written.entry(name.as_str()).or_insert(0);
+        while write_child(writer, name, *index)? {
+            *index += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write human-readable text, wrapping it in a CDATA section if the configured policy asks for
+/// one. A `]]>` in the text is split across two sections.
+pub fn write_text<W: Write>(
+    writer: &mut Writer<W>,
+    text: &str,
+    ctx: &WriteContext,
+) -> Result<(), XmlError> {
+    let cdata = match ctx.config.get_cdata() {
+        CdataPolicy::Never => false,
+        CdataPolicy::WhenNeeded => text.contains(&['<', '&'][..]),
+        CdataPolicy::Always => !text.is_empty(),
+    };
+
+    if !cdata {
+        writer.write_event(Event::Text(BytesText::from_escaped(text.as_bytes())))?;
+        return Ok(());
+    }
+
+    // The section is written as text since an indenting writer would otherwise add whitespace
+    // around it, changing the value.
+    let text = format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"));
+    writer.write_event(Event::Text(BytesText::from_escaped(text.as_bytes())))?;
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use std::io::{self, BufRead, Read};
+use std::str;
+
+use crate::util::declared_encoding;
+
+/// A reader that looks for invalid UTF-8 in the bytes consumed from the reader it wraps.
+///
+/// The XML reader replaces invalid UTF-8 with U+FFFD REPLACEMENT CHARACTER rather than failing,
+/// so a single corrupt byte does not make a document unreadable. This records where that
+/// happened so it can be reported. Documents that declare another encoding are not checked.
+pub(crate) struct Utf8Check<B> {
+    inner: B,
+    state: State,
+}
+
+struct State {
+    /// Whether the input is checked, or `None` until the XML declaration has been seen.
+    enabled: Option<bool>,
+    /// The offset in the input of the first byte after `pending`.
+    position: usize,
+    /// The start of a sequence that continues in the next bytes consumed.
+    pending: Vec<u8>,
+    invalid: usize,
+    first_invalid: Option<usize>,
+}
+
+impl<B> Utf8Check<B> {
+    pub(crate) fn new(inner: B) -> Self {
+        Utf8Check {
+            inner,
+            state: State {
+                enabled: None,
+                position: 0,
+                pending: Vec::new(),
+                invalid: 0,
+                first_invalid: None,
+            },
+        }
+    }
+
+    /// Return the number of invalid sequences that were found and the offset of the first.
+    pub(crate) fn invalid(&self) -> Option<(usize, usize)> {
+        let state = &self.state;
+        state.first_invalid.map(|first| (state.invalid, first))
+    }
+}
+
+impl State {
+    fn check(&mut self, bytes: &[u8]) {
+        if self.enabled != Some(true) || bytes.is_empty() {
+            self.position += bytes.len();
+            return;
+        }
+
+        let joined;
+        let mut bytes = bytes;
+        let mut start = self.position;
+        if !self.pending.is_empty() {
+            start -= self.pending.len();
+            self.pending.extend_from_slice(bytes);
+            joined = std::mem::take(&mut self.pending);
+            bytes = &joined;
+        }
+        self.position = start + bytes.len();
+
+        loop {
+            let err = match str::from_utf8(bytes) {
+                Ok(_) => break,
+                Err(err) => err,
+            };
+
+            let valid = err.valid_up_to();
+            match err.error_len() {
+                Some(len) => {
+                    self.invalid += 1;
+                    self.first_invalid.get_or_insert(start + valid);
+                    bytes = &bytes[valid + len..];
+                    start += valid + len;
+                }
+                None => {
+                    self.pending.extend_from_slice(&bytes[valid..]);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Return the number of invalid UTF-8 sequences in `bytes` and the offset of the first.
+pub(crate) fn find_invalid(bytes: &[u8]) -> Option<(usize, usize)> {
+    let mut state = State {
+        enabled: Some(true),
+        position: 0,
+        pending: Vec::new(),
+        invalid: 0,
+        first_invalid: None,
+    };
+    state.check(bytes);
+
+    // a sequence cut off by the end of the bytes is invalid too
+    if !state.pending.is_empty() {
+        state.invalid += 1;
+        state
+            .first_invalid
+            .get_or_insert(bytes.len() - state.pending.len());
+    }
+    state.first_invalid.map(|first| (state.invalid, first))
+}
+
+/// Describe the invalid UTF-8 that was replaced while reading.
+pub(crate) fn invalid_message(count: usize, first: usize) -> String {
+    format!(
+        "invalid UTF-8 replaced with U+FFFD in {} place{}, first at byte {}",
+        count,
+        if count == 1 { "" } else { "s" },
+        first
+    )
+}
+
+/// Return whether the XML declaration at the start of a document declares UTF-8 or no encoding.
+fn declares_utf8(start: &[u8]) -> bool {
+    match declared_encoding(start) {
+        Some(value) => value.eq_ignore_ascii_case(b"utf-8") || value.eq_ignore_ascii_case(b"utf8"),
+        None => true,
+    }
+}
+
+impl<B: Read> Read for Utf8Check<B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        if self.state.enabled.is_none() {
+            self.state.enabled = Some(declares_utf8(&buf[..len]));
+        }
+        self.state.check(&buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<B: BufRead> BufRead for Utf8Check<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        if self.state.enabled.is_none() {
+            self.state.enabled = Some(declares_utf8(buf));
+        }
+
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // the bytes have already been filled, so this returns them again without reading
+        match self.inner.fill_buf() {
+            Ok(buf) => self.state.check(&buf[..amt.min(buf.len())]),
+            Err(_) => self.state.position += amt,
+        }
+        self.inner.consume(amt);
+    }
+}
@@ -1,15 +1,70 @@
-use std::io::BufRead;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
 
+use chrono::Utc;
 use quick_xml::events::attributes::{Attribute, Attributes};
-use quick_xml::events::Event;
+use quick_xml::events::{BytesText, Event};
 use quick_xml::Reader;
 
+use crate::entities::resolve_html_entities;
 use crate::error::Error;
 use std::str::FromStr;
 
 /// Alias of `::chrono::DateTime<::chrono::FixedOffset>`
 pub type FixedDateTime = ::chrono::DateTime<::chrono::FixedOffset>;
 
+/// Conversion into an optional value.
+///
+/// This is accepted by the setters of optional fields, so that they can be given either a value
+/// or an `Option` of it.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::Entry;
+///
+/// let mut entry = Entry::default();
+/// entry.set_summary("Entry summary.");
+/// assert_eq!(entry.summary(), Some("Entry summary."));
+///
+/// entry.set_summary(None);
+/// assert_eq!(entry.summary(), None);
+/// ```
+pub trait IntoOption<T> {
+    /// Convert this value into an optional value.
+    fn into_option(self) -> Option<T>;
+}
+
+impl<T> IntoOption<T> for Option<T> {
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+impl IntoOption<String> for String {
+    fn into_option(self) -> Option<String> {
+        Some(self)
+    }
+}
+
+impl IntoOption<String> for &str {
+    fn into_option(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl IntoOption<String> for &String {
+    fn into_option(self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+/// Convert a date to UTC, keeping the instant it represents.
+pub fn to_utc(date: &FixedDateTime) -> FixedDateTime {
+    date.with_timezone(&Utc).into()
+}
+
 pub fn default_fixed_datetime() -> FixedDateTime {
     FixedDateTime::from_str("1970-01-01T00:00:00Z").unwrap()
 }
@@ -22,7 +77,157 @@ fn non_empty(string: String) -> Option<String> {
     }
 }
 
+/// Return whether the input starts with a UTF-8 or UTF-16 byte order mark.
+pub fn starts_with_bom(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0xEF, 0xBB, 0xBF])
+        || bytes.starts_with(&[0xFE, 0xFF])
+        || bytes.starts_with(&[0xFF, 0xFE])
+}
+
+/// Return whether the input starts with an HTML doctype, ignoring case and leading whitespace.
+pub fn starts_with_html_doctype(bytes: &[u8]) -> bool {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let doctype = b"<!doctype html";
+
+    match bytes[start..].get(..doctype.len()) {
+        Some(prefix) => prefix.eq_ignore_ascii_case(doctype),
+        None => false,
+    }
+}
+
+/// Strip a byte order mark from the input, converting UTF-16 input to UTF-8.
+///
+/// The byte order mark takes precedence over the XML declaration, so the encoding declaration is
+/// removed to make sure the document is read as UTF-8.
+pub fn normalize_encoding(bytes: &[u8]) -> Cow<'_, [u8]> {
+    let big_endian = if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return blank_encoding_declaration(&bytes[3..]);
+    } else if bytes.starts_with(&[0xFE, 0xFF]) {
+        true
+    } else if bytes.starts_with(&[0xFF, 0xFE]) {
+        false
+    } else {
+        return Cow::Borrowed(bytes);
+    };
+
+    let units = bytes[2..].chunks(2).map(|pair| match *pair {
+        [high, low] if big_endian => u16::from_be_bytes([high, low]),
+        [low, high] => u16::from_be_bytes([high, low]),
+        _ => 0xFFFD,
+    });
+    let decoded = std::char::decode_utf16(units)
+        .map(|c| c.unwrap_or(std::char::REPLACEMENT_CHARACTER))
+        .collect::<String>();
+
+    Cow::Owned(strip_encoding_declaration(&decoded).into_bytes())
+}
+
+/// Replace the encoding declaration of a UTF-8 document with spaces, so the document is read as
+/// UTF-8 while every other byte keeps its offset.
+fn blank_encoding_declaration(bytes: &[u8]) -> Cow<'_, [u8]> {
+    let end = match bytes.windows(2).position(|pair| pair == b"?>") {
+        Some(end) if bytes.starts_with(b"<?xml") => end,
+        _ => return Cow::Borrowed(bytes),
+    };
+
+    let decl = &bytes[..end];
+    let start = match decl.windows(9).position(|name| name == b" encoding") {
+        Some(start) => start,
+        None => return Cow::Borrowed(bytes),
+    };
+
+    let value_end = decl[start..]
+        .iter()
+        .position(|&b| b == b'"' || b == b'\'')
+        .and_then(|open| {
+            let quote = decl[start + open];
+            let close = decl[start + open + 1..].iter().position(|&b| b == quote)?;
+            Some(start + open + 1 + close + 1)
+        });
+
+    match value_end {
+        Some(value_end) => {
+            let mut blanked = bytes.to_vec();
+            for b in &mut blanked[start..value_end] {
+                *b = b' ';
+            }
+            Cow::Owned(blanked)
+        }
+        None => Cow::Borrowed(bytes),
+    }
+}
+
+/// Return the encoding named by the XML declaration at the start of a document, if any.
+pub(crate) fn declared_encoding(start: &[u8]) -> Option<&[u8]> {
+    if !start.starts_with(b"<?xml") {
+        return None;
+    }
+
+    let end = start.windows(2).position(|pair| pair == b"?>")?;
+    let decl = &start[..end];
+    let name = decl.windows(8).position(|name| name == b"encoding")?;
+    decl[name + 8..].split(|&b| b == b'"' || b == b'\'').nth(1)
+}
+
+pub(crate) fn strip_encoding_declaration(xml: &str) -> String {
+    let end = match xml.find("?>") {
+        Some(end) if xml.starts_with("<?xml") => end,
+        _ => return xml.to_string(),
+    };
+
+    let decl = &xml[..end];
+    let start = match decl.find(" encoding") {
+        Some(start) => start,
+        None => return xml.to_string(),
+    };
+
+    let value = decl[start..].find(&['"', '\''][..]).and_then(|open| {
+        let quote = decl[start + open..].chars().next()?;
+        let close = decl[start + open + 1..].find(quote)?;
+        Some(start + open + 1 + close + 1)
+    });
+
+    match value {
+        Some(value_end) => format!("{}{}", &xml[..start], &xml[value_end..]),
+        None => xml.to_string(),
+    }
+}
+
+/// Read all of the attributes of an element into `attrs`, unescaping their values.
+pub fn collect_attrs<B: BufRead>(
+    reader: &Reader<B>,
+    mut atts: Attributes,
+    attrs: &mut BTreeMap<String, String>,
+) -> Result<(), Error> {
+    for att in atts.with_checks(false).flatten() {
+        let key = reader.decode(att.key).into_owned();
+        attrs.insert(key, att.unescape_and_decode_value(reader)?);
+    }
+
+    Ok(())
+}
+
+/// Read the text content of an element up to its end tag.
+///
+/// Escaped markup, such as the value of a `type="html"` text construct, is unescaped and CDATA
+/// sections are included as is. Child elements, which only appear when a text construct is not
+/// marked as XHTML, are kept as markup rather than ending the text. Text that cannot be unescaped
+/// is kept in its escaped form.
+///
+/// Returns `None` if the element is empty.
 pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, Error> {
+    read_text(reader, false)
+}
+
+/// Read the text content of an element like [`atom_text`], resolving the named character
+/// references of HTML as well if `html_entities` is set.
+pub(crate) fn read_text<B: BufRead>(
+    reader: &mut Reader<B>,
+    html_entities: bool,
+) -> Result<Option<String>, Error> {
     let mut innerbuf = Vec::new();
     let mut depth = 0;
     let mut result = String::new();
@@ -32,7 +237,7 @@ pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, E
             Event::Start(start) => {
                 depth += 1;
                 result.push('<');
-                result.push_str(&start.unescape_and_decode(reader)?);
+                result.push_str(&reader.decode(&start));
                 result.push('>');
             }
             Event::End(end) => {
@@ -45,9 +250,8 @@ pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, E
                 result.push('>');
             }
             Event::Empty(start) => {
-                depth += 1;
                 result.push('<');
-                result.push_str(&start.unescape_and_decode(reader)?);
+                result.push_str(&reader.decode(&start));
                 result.push_str("/>");
             }
             Event::CData(text) => {
@@ -55,13 +259,21 @@ pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, E
                 result.push_str(&decoded);
             }
             Event::Text(text) => {
-                let decoded = text.unescape_and_decode(reader)?;
-                result.push_str(&decoded);
+                let unescaped = if html_entities {
+                    let resolved = resolve_html_entities(text.escaped());
+                    BytesText::from_escaped(resolved).unescape_and_decode(reader)
+                } else {
+                    text.unescape_and_decode(reader)
+                };
+
+                match unescaped {
+                    Ok(decoded) => result.push_str(&decoded),
+                    Err(_) => result.push_str(&reader.decode(text.escaped())),
+                }
             }
             Event::Comment(text) => {
-                let decoded = text.unescape_and_decode(reader)?;
                 result.push_str("<!--");
-                result.push_str(&decoded);
+                result.push_str(&reader.decode(text.escaped()));
                 result.push_str("-->");
             }
             Event::Decl(_decl) => {}
@@ -76,6 +288,9 @@ pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, E
     Ok(non_empty(result))
 }
 
+/// Read the XHTML content of an element up to its end tag, keeping the markup as is.
+///
+/// Returns `None` if the element is empty.
 pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, Error> {
     let mut innerbuf = Vec::new();
     let mut depth = 0;
@@ -99,7 +314,6 @@ pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>,
                 result.push('>');
             }
             Event::Empty(start) => {
-                depth += 1;
                 result.push('<');
                 result.push_str(&start.unescape_and_decode(reader)?);
                 result.push_str("/>");
@@ -130,9 +344,77 @@ pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>,
     Ok(non_empty(result))
 }
 
+/// Return whether XHTML markup consists of exactly one `div` element, as required for the value
+/// of `xhtml` content. Whitespace and comments around the `div` are allowed.
+pub fn is_xhtml_div(markup: &str) -> bool {
+    let mut reader = Reader::from_str(markup);
+    let mut buf = Vec::new();
+    let mut depth = 0usize;
+    let mut found = false;
+
+    loop {
+        let is_div = |name: &[u8]| name.rsplit(|&b| b == b':').next() == Some(&b"div"[..]);
+
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(start)) => {
+                if depth == 0 {
+                    if found || !is_div(start.name()) {
+                        return false;
+                    }
+                    found = true;
+                }
+                depth += 1;
+            }
+            Ok(Event::Empty(start)) => {
+                if depth == 0 {
+                    if found || !is_div(start.name()) {
+                        return false;
+                    }
+                    found = true;
+                }
+            }
+            Ok(Event::End(_)) => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            Ok(Event::Text(text)) => {
+                if depth == 0 && !text.escaped().iter().all(u8::is_ascii_whitespace) {
+                    return false;
+                }
+            }
+            Ok(Event::CData(_)) => {
+                if depth == 0 {
+                    return false;
+                }
+            }
+            Ok(Event::Eof) => return found && depth == 0,
+            Ok(_) => {}
+            Err(_) => return false,
+        }
+
+        buf.clear();
+    }
+}
+
+/// Read the content of a text construct up to its end tag, as XHTML if its `type` attribute
+/// is `xhtml` and as text otherwise.
+///
+/// Returns `None` if the element is empty.
 pub fn atom_any_text<B: BufRead>(
+    reader: &mut Reader<B>,
+    atts: Attributes,
+) -> Result<Option<String>, Error> {
+    read_any_text(reader, atts, false)
+}
+
+/// Read the content of a text construct like [`atom_any_text`], resolving the named character
+/// references of HTML in text that is not XHTML if `html_entities` is set.
+pub(crate) fn read_any_text<B: BufRead>(
     reader: &mut Reader<B>,
     mut atts: Attributes,
+    html_entities: bool,
 ) -> Result<Option<String>, Error> {
     let mut content_type = None;
     for attr in atts.with_checks(false) {
@@ -143,18 +425,383 @@ pub fn atom_any_text<B: BufRead>(
 
     match content_type {
         Some(ref t) if t == "xhtml" => atom_xhtml(reader),
-        _ => atom_text(reader),
+        _ => read_text(reader, html_entities),
     }
 }
 
+/// Read the RFC 3339 date of an element up to its end tag.
+///
+/// Returns `None` if the element is empty.
 pub fn atom_datetime<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<FixedDateTime>, Error> {
     if let Some(datetime_text) = atom_text(reader)? {
         let parse_result = FixedDateTime::parse_from_rfc3339(&datetime_text);
         match parse_result {
-            Err(_) => Err(Error::WrongDatetime(datetime_text)),
+            Err(_) => Err(Error::InvalidDate {
+                value: datetime_text,
+            }),
             Ok(datetime) => Ok(Some(datetime)),
         }
     } else {
         Ok(None)
     }
 }
+
+pub fn is_valid_email(email: &str) -> bool {
+    let mut parts = email.splitn(2, '@');
+    let (local, domain) = match (parts.next(), parts.next()) {
+        (Some(local), Some(domain)) => (local, domain),
+        _ => return false,
+    };
+
+    !local.is_empty()
+        && !domain.is_empty()
+        && !domain.contains('@')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains("..")
+        && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+pub fn is_valid_iri(iri: &str) -> bool {
+    let scheme = match iri.find(':') {
+        Some(index) => &iri[..index],
+        None => return false,
+    };
+
+    let mut chars = scheme.chars();
+    let scheme_valid = match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        }
+        _ => false,
+    };
+
+    scheme_valid
+        && !iri.chars().any(|c| {
+            c.is_whitespace()
+                || c.is_control()
+                || ['<', '>', '"', '{', '}', '|', '\\', '^', '`'].contains(&c)
+        })
+}
+
+/// Resolve an IRI reference against a base IRI as described by RFC 3986, section 5.2.
+pub fn resolve_iri(base: &str, reference: &str) -> String {
+    if is_valid_iri(reference) {
+        return reference.to_string();
+    }
+
+    let (scheme, rest) = match base.find(':') {
+        Some(index) => (&base[..index], &base[index + 1..]),
+        None => return reference.to_string(),
+    };
+
+    if reference.starts_with("//") {
+        return format!("{}:{}", scheme, reference);
+    }
+
+    let rest = &rest[..rest.find('#').unwrap_or(rest.len())];
+
+    if reference.is_empty() {
+        return format!("{}:{}", scheme, rest);
+    }
+
+    if reference.starts_with('#') {
+        return format!("{}:{}{}", scheme, rest, reference);
+    }
+
+    let rest = &rest[..rest.find('?').unwrap_or(rest.len())];
+    let (authority, path) = if let Some(hierarchy) = rest.strip_prefix("//") {
+        let end = hierarchy.find('/').map_or(rest.len(), |index| index + 2);
+        rest.split_at(end)
+    } else {
+        ("", rest)
+    };
+
+    if reference.starts_with('?') {
+        return format!("{}:{}{}{}", scheme, authority, path, reference);
+    }
+
+    let (reference, suffix) = match reference.find(&['?', '#'][..]) {
+        Some(index) => reference.split_at(index),
+        None => (reference, ""),
+    };
+
+    let merged = if reference.starts_with('/') {
+        reference.to_string()
+    } else if !authority.is_empty() && path.is_empty() {
+        format!("/{}", reference)
+    } else {
+        let directory = &path[..path.rfind('/').map_or(0, |index| index + 1)];
+        format!("{}{}", directory, reference)
+    };
+
+    format!(
+        "{}:{}{}{}",
+        scheme,
+        authority,
+        remove_dot_segments(&merged),
+        suffix
+    )
+}
+
+fn remove_dot_segments(path: &str) -> String {
+    let mut segments = Vec::new();
+    let mut parts = path.split('/').peekable();
+
+    while let Some(part) = parts.next() {
+        let last = parts.peek().is_none();
+        match part {
+            "." => {
+                if last {
+                    segments.push("");
+                }
+            }
+            ".." => {
+                if segments.len() > 1 {
+                    segments.pop();
+                }
+                if last {
+                    segments.push("");
+                }
+            }
+            _ => segments.push(part),
+        }
+    }
+
+    segments.join("/")
+}
+
+pub fn markup_to_text(markup: &str) -> String {
+    let mut text = String::with_capacity(markup.len());
+    let mut chars = markup.chars();
+    let mut quote = None;
+    let mut in_tag = false;
+
+    while let Some(c) = chars.next() {
+        if in_tag {
+            match (c, quote) {
+                ('"', None) | ('\'', None) => quote = Some(c),
+                (c, Some(q)) if c == q => quote = None,
+                ('>', None) => in_tag = false,
+                _ => {}
+            }
+        } else if c == '<' {
+            in_tag = true;
+        } else if c == '&' {
+            let rest = chars.as_str();
+            let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+                let c = match &rest[..end] {
+                    "lt" => '<',
+                    "gt" => '>',
+                    "amp" => '&',
+                    "quot" => '"',
+                    "apos" => '\'',
+                    "nbsp" => '\u{a0}',
+                    entity if entity.starts_with("#x") || entity.starts_with("#X") => {
+                        std::char::from_u32(u32::from_str_radix(&entity[2..], 16).ok()?)?
+                    }
+                    entity if entity.starts_with('#') => {
+                        std::char::from_u32(entity[1..].parse().ok()?)?
+                    }
+                    _ => return None,
+                };
+                Some((c, end))
+            });
+
+            match decoded {
+                Some((c, end)) => {
+                    text.push(c);
+                    chars = rest[end + 1..].chars();
+                }
+                None => text.push('&'),
+            }
+        } else {
+            text.push(c);
+        }
+    }
+
+    text
+}
+
+/// Count the words in plain text.
+///
+/// Runs of characters between whitespace that contain a letter or digit are words, while
+/// punctuation on its own is not. Chinese, Japanese and Korean characters each count as a word.
+pub fn count_words(text: &str) -> usize {
+    let is_cjk = |c: char| {
+        matches!(c as u32,
+            0x3040..=0x30ff // Hiragana and Katakana
+            | 0x3400..=0x4dbf // CJK Unified Ideographs Extension A
+            | 0x4e00..=0x9fff // CJK Unified Ideographs
+            | 0xac00..=0xd7af // Hangul Syllables
+            | 0xf900..=0xfaff // CJK Compatibility Ideographs
+            | 0x20000..=0x2fa1f) // Supplementary Ideographic Plane
+    };
+
+    text.split_whitespace()
+        .map(|word| {
+            let cjk = word.chars().filter(|&c| is_cjk(c)).count();
+            let other = word
+                .split(is_cjk)
+                .filter(|part| part.chars().any(char::is_alphanumeric))
+                .count();
+            cjk + other
+        })
+        .sum()
+}
+
+/// Resolve the `href` and `src` attributes of the tags in HTML markup against a base IRI.
+///
+/// Comments and the text between tags are copied unchanged.
+pub fn absolutize_urls(markup: &str, base: &str) -> String {
+    let mut output = String::with_capacity(markup.len());
+    let mut rest = markup;
+
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |end| end + 3);
+            output.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let mut quote = None;
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| match (c, quote) {
+                ('"', None) | ('\'', None) => {
+                    quote = Some(c);
+                    false
+                }
+                (c, Some(q)) if c == q => {
+                    quote = None;
+                    false
+                }
+                ('>', None) => true,
+                _ => false,
+            })
+            .map_or(rest.len(), |(end, _)| end + 1);
+        output.push_str(&absolutize_tag(&rest[..end], base));
+        rest = &rest[end..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn absolutize_tag(tag: &str, base: &str) -> String {
+    let bytes = tag.as_bytes();
+    let len = bytes.len();
+    let is_space = |b: u8| b.is_ascii_whitespace();
+    let mut output = String::with_capacity(tag.len());
+    let mut copied = 0;
+
+    let mut i = 1;
+    while i < len && !is_space(bytes[i]) && bytes[i] != b'>' && bytes[i] != b'/' {
+        i += 1;
+    }
+
+    loop {
+        while i < len && (is_space(bytes[i]) || bytes[i] == b'/') {
+            i += 1;
+        }
+        if i >= len || bytes[i] == b'>' {
+            break;
+        }
+
+        let key_start = i;
+        while i < len && !is_space(bytes[i]) && !matches!(bytes[i], b'=' | b'>' | b'/') {
+            i += 1;
+        }
+        let key = &tag[key_start..i];
+
+        while i < len && is_space(bytes[i]) {
+            i += 1;
+        }
+        if i >= len || bytes[i] != b'=' {
+            continue;
+        }
+        i += 1;
+        while i < len && is_space(bytes[i]) {
+            i += 1;
+        }
+
+        let (value_start, value_end) = match bytes.get(i) {
+            Some(&quote) if quote == b'"' || quote == b'\'' => {
+                let start = i + 1;
+                let end = tag[start..]
+                    .find(quote as char)
+                    .map_or(len, |end| start + end);
+                i = (end + 1).min(len);
+                (start, end)
+            }
+            _ => {
+                let start = i;
+                while i < len && !is_space(bytes[i]) && bytes[i] != b'>' {
+                    i += 1;
+                }
+                (start, i)
+            }
+        };
+
+        if key.eq_ignore_ascii_case("href") || key.eq_ignore_ascii_case("src") {
+            output.push_str(&tag[copied..value_start]);
+            output.push_str(&resolve_iri(base, tag[value_start..value_end].trim()));
+            copied = value_end;
+        }
+    }
+
+    output.push_str(&tag[copied..]);
+    output
+}
+
+/// The 64-bit FNV-1a hash, whose output is stable and can be persisted.
+pub struct Fnv64(u64);
+
+impl Fnv64 {
+    pub fn new() -> Self {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Hash a string prefixed with its length, so that consecutive strings can't run together.
+    pub fn write_str(&mut self, s: &str) {
+        self.write(&(s.len() as u64).to_le_bytes());
+        self.write(s.as_bytes());
+    }
+
+    pub fn write_option(&mut self, s: Option<&str>) {
+        match s {
+            Some(s) => {
+                self.write(&[1]);
+                self.write_str(s);
+            }
+            None => self.write(&[0]),
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Write for Fnv64 {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Fnv64::write(self, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
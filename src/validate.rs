@@ -0,0 +1,447 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::content::Content;
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::link::Link;
+use crate::person::Person;
+use crate::util::{default_fixed_datetime, is_valid_iri};
+
+/// The severity of a validation issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// The document is valid but does not follow a recommendation of the specification.
+    Warning,
+    /// The document violates a requirement of the specification.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A single problem found while validating a feed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidationIssue {
+    severity: Severity,
+    location: String,
+    message: String,
+}
+
+impl ValidationIssue {
+    pub(crate) fn new<L, M>(severity: Severity, location: L, message: M) -> Self
+    where
+        L: Into<String>,
+        M: Into<String>,
+    {
+        ValidationIssue {
+            severity,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Return the severity of this issue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Severity};
+    ///
+    /// let report = Feed::default().validate();
+    /// assert_eq!(report.issues()[0].severity(), Severity::Error);
+    /// ```
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Return the location of the element this issue applies to, such as `feed` or `entry[2]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let report = Feed::default().validate();
+    /// assert_eq!(report.issues()[0].location(), "feed");
+    /// ```
+    pub fn location(&self) -> &str {
+        self.location.as_str()
+    }
+
+    /// Return a description of this issue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let report = Feed::default().validate();
+    /// assert_eq!(report.issues()[0].message(), "missing id");
+    /// ```
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.message)
+    }
+}
+
+/// The issues found while validating a feed against the Atom specification.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Return all of the issues in this report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let report = Feed::default().validate();
+    /// assert!(!report.issues().is_empty());
+    /// ```
+    pub fn issues(&self) -> &[ValidationIssue] {
+        self.issues.as_slice()
+    }
+
+    /// Return the issues in this report with the `Error` severity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let report = Feed::default().validate();
+    /// assert!(report.errors().count() > 0);
+    /// ```
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Return the issues in this report with the `Warning` severity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let report = Feed::default().validate();
+    /// assert!(report
+    ///     .warnings()
+    ///     .any(|issue| issue.message() == "missing self link"));
+    /// ```
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// Return whether the report contains no errors. Warnings are allowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let report = Feed::default().validate();
+    /// assert!(!report.is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub(crate) fn push<L, M>(&mut self, severity: Severity, location: L, message: M)
+    where
+        L: Into<String>,
+        M: Into<String>,
+    {
+        self.issues
+            .push(ValidationIssue::new(severity, location, message));
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn validate_feed(feed: &Feed) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let location = "feed";
+
+    validate_id(&mut report, location, feed.id());
+
+    if feed.title().is_empty() {
+        report.push(Severity::Error, location, "missing title");
+    }
+
+    if *feed.updated() == default_fixed_datetime() {
+        report.push(Severity::Error, location, "missing updated date");
+    }
+
+    validate_persons(&mut report, location, feed.authors(), "author");
+    validate_persons(&mut report, location, feed.contributors(), "contributor");
+
+    if feed.self_link().is_none() {
+        report.push(Severity::Warning, location, "missing self link");
+    }
+
+    validate_alternates(&mut report, location, feed.links());
+
+    let mut ids = HashMap::new();
+
+    for (index, entry) in feed.entries().iter().enumerate() {
+        let location = format!("entry[{}]", index);
+        validate_entry(&mut report, &location, entry);
+
+        if entry.effective_authors(feed).is_empty() {
+            report.push(Severity::Error, location.as_str(), "missing author");
+        }
+
+        if !entry.id().is_empty() {
+            if let Some(first) = ids.insert(entry.id(), index) {
+                report.push(
+                    Severity::Warning,
+                    location.as_str(),
+                    format!("duplicate id {} also used by entry[{}]", entry.id(), first),
+                );
+            }
+        }
+    }
+
+    report
+}
+
+fn validate_entry(report: &mut ValidationReport, location: &str, entry: &Entry) {
+    validate_id(report, location, entry.id());
+
+    if entry.title().is_empty() {
+        report.push(Severity::Error, location, "missing title");
+    }
+
+    if *entry.updated() == default_fixed_datetime() {
+        report.push(Severity::Error, location, "missing updated date");
+    }
+
+    if let Some(published) = entry.published() {
+        if published > entry.updated() {
+            report.push(
+                Severity::Warning,
+                location,
+                "published date is later than updated date",
+            );
+        }
+    }
+
+    validate_persons(report, location, entry.authors(), "author");
+    validate_persons(report, location, entry.contributors(), "contributor");
+
+    validate_alternates(report, location, entry.links());
+
+    let has_alternate = entry.links().iter().any(|link| link.rel() == "alternate");
+    if entry.content().is_none() && !has_alternate {
+        report.push(
+            Severity::Error,
+            location,
+            "missing content and alternate link",
+        );
+    }
+
+    if let Some(content) = entry.content() {
+        if content.is_unwrapped_xhtml() {
+            report.push(
+                Severity::Error,
+                location,
+                "xhtml content is not wrapped in a single div",
+            );
+        }
+    }
+
+    if entry.content().and_then(Content::src).is_some() && entry.summary().is_none() {
+        report.push(
+            Severity::Error,
+            location,
+            "missing summary for content with src",
+        );
+    }
+}
+
+fn validate_id(report: &mut ValidationReport, location: &str, id: &str) {
+    if id.is_empty() {
+        report.push(Severity::Error, location, "missing id");
+    } else if !is_valid_iri(id) {
+        report.push(
+            Severity::Error,
+            location,
+            format!("id {} is not an absolute IRI", id),
+        );
+    }
+}
+
+fn validate_alternates(report: &mut ValidationReport, location: &str, links: &[Link]) {
+    for (index, first) in duplicate_alternates(links) {
+        report.push(
+            Severity::Error,
+            location,
+            format!(
+                "alternate link {} has the same type and hreflang as {}",
+                links[index].href(),
+                links[first].href()
+            ),
+        );
+    }
+}
+
+/// Return whether two alternate links have the same type and hreflang.
+fn same_alternate(a: &Link, b: &Link) -> bool {
+    fn eq(a: Option<&str>, b: Option<&str>) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    eq(a.mime_type(), b.mime_type())
+        && eq(
+            a.hreflang().map(|tag| tag.as_str()),
+            b.hreflang().map(|tag| tag.as_str()),
+        )
+}
+
+/// Return the index of each alternate link that duplicates an earlier one, along with the index
+/// of the earlier link.
+fn duplicate_alternates(links: &[Link]) -> Vec<(usize, usize)> {
+    let mut duplicates = Vec::new();
+
+    for (index, link) in links.iter().enumerate() {
+        if link.rel() != "alternate" {
+            continue;
+        }
+
+        let first = links[..index]
+            .iter()
+            .position(|other| other.rel() == "alternate" && same_alternate(other, link));
+        if let Some(first) = first {
+            duplicates.push((index, first));
+        }
+    }
+
+    duplicates
+}
+
+/// Remove the alternate links that have the same type and hreflang as an earlier one.
+fn remove_duplicate_alternates(links: &mut Vec<Link>) -> usize {
+    let duplicates = duplicate_alternates(links);
+    let mut index = 0;
+
+    links.retain(|_| {
+        let keep = !duplicates.iter().any(|&(duplicate, _)| duplicate == index);
+        index += 1;
+        keep
+    });
+
+    duplicates.len()
+}
+
+impl Entry {
+    /// Remove the `alternate` links of this entry that have the same type and hreflang as an
+    /// earlier one, which the specification does not allow. The first of the duplicates is
+    /// kept. Returns the number of links that were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default().with_links(vec![
+    ///     Link::default().with_href("http://example.com/1"),
+    ///     Link::default().with_href("http://example.com/2"),
+    ///     Link::default()
+    ///         .with_href("http://example.com/1.json")
+    ///         .with_mime_type("application/json"),
+    /// ]);
+    ///
+    /// assert_eq!(entry.remove_duplicate_alternates(), 1);
+    /// assert_eq!(entry.links().len(), 2);
+    /// assert_eq!(entry.links()[0].href(), "http://example.com/1");
+    /// ```
+    pub fn remove_duplicate_alternates(&mut self) -> usize {
+        remove_duplicate_alternates(self.links_mut())
+    }
+}
+
+impl Feed {
+    /// Remove the `alternate` links of this feed and its entries that have the same type and
+    /// hreflang as an earlier one, fixing the issues reported for them by
+    /// [`validate`](#method.validate). Returns the number of links that were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"<feed>
+    ///     <link href="http://example.com/"/>
+    ///     <link rel="alternate" href="http://example.com/index.html"/>
+    ///     <entry>
+    ///         <link href="http://example.com/1" hreflang="en"/>
+    ///         <link href="http://example.com/1/fr" hreflang="fr"/>
+    ///     </entry>
+    /// </feed>"#;
+    /// let mut feed = xml.parse::<Feed>().unwrap();
+    ///
+    /// assert_eq!(feed.remove_duplicate_alternates(), 1);
+    /// assert_eq!(feed.links().len(), 1);
+    /// assert_eq!(feed.entries()[0].links().len(), 2);
+    /// ```
+    pub fn remove_duplicate_alternates(&mut self) -> usize {
+        let mut removed = remove_duplicate_alternates(self.links_mut());
+
+        for entry in self.entries_mut() {
+            removed += entry.remove_duplicate_alternates();
+        }
+
+        removed
+    }
+}
+
+fn validate_persons(report: &mut ValidationReport, location: &str, persons: &[Person], kind: &str) {
+    for person in persons {
+        if person.name().is_empty() {
+            report.push(
+                Severity::Error,
+                location,
+                format!("{} is missing a name", kind),
+            );
+        }
+
+        if let Err(err) = person.validate() {
+            report.push(
+                Severity::Warning,
+                location,
+                format!("{} has an {}", kind, err),
+            );
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::io::{self, BufWriter, Write};
+#[cfg(feature = "tokio")]
+use std::mem;
+
+use quick_xml::events::{BytesDecl, BytesEnd, Event};
+use quick_xml::Error as XmlError;
+use quick_xml::Writer;
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::config::WriteConfig;
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::feed::Feed;
+use crate::toxml::{ToXml, WriteContext};
+
+/// Writes a feed whose entries are produced one at a time, without holding them all in memory.
+///
+/// The feed passed to `new` provides the metadata of the document, such as its title, id and
+/// links. Its entries are not written. Once the writer has been attached to an output, entries
+/// are written one by one in the order they are given.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Entry, Feed, FeedWriter};
+///
+/// let mut feed = Feed::default();
+/// feed.set_title("Feed Title");
+///
+/// let mut writer = FeedWriter::new(&feed).into_writer(Vec::new()).unwrap();
+/// for i in 0..3 {
+///     writer.write_entry(&Entry::default().with_id(format!("urn:entry:{}", i))).unwrap();
+/// }
+///
+/// let xml = String::from_utf8(writer.finish().unwrap()).unwrap();
+/// assert!(xml.ends_with("<id>urn:entry:2</id><updated>1970-01-01T00:00:00+00:00</updated></entry></feed>"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FeedWriter {
+    head: Feed,
+    config: WriteConfig,
+}
+
+impl FeedWriter {
+    /// Create a writer for a feed with the metadata of the given feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FeedWriter};
+    ///
+    /// let writer = FeedWriter::new(&Feed::default());
+    /// ```
+    pub fn new(feed: &Feed) -> Self {
+        FeedWriter {
+            head: feed.clone_without_entries(),
+            config: WriteConfig::default(),
+        }
+    }
+
+    /// Set the configuration the feed is written with and return the writer.
+    ///
+    /// The entry order of the configuration does not apply, since entries are written as they
+    /// are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FeedWriter, WriteConfig};
+    ///
+    /// let config = WriteConfig::new().declaration(true);
+    /// let writer = FeedWriter::new(&Feed::default()).with_config(config);
+    /// ```
+    pub fn with_config(mut self, config: WriteConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Write the start of the feed to the given writer and return a writer for its entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FeedWriter};
+    ///
+    /// let writer = FeedWriter::new(&Feed::default()).into_writer(Vec::new()).unwrap();
+    /// let xml = writer.finish().unwrap();
+    /// ```
+    pub fn into_writer<W: Write>(self, writer: W) -> Result<EntryWriter<W>, Error> {
+        let mut writer = match self.config.get_indent() {
+            Some((indent_char, size)) => Writer::new_with_indent(writer, indent_char, size),
+            None => Writer::new(writer),
+        };
+        let (ctx, renames) = self.write_open(&mut writer)?;
+
+        Ok(EntryWriter {
+            writer,
+            ctx,
+            renames,
+        })
+    }
+
+    /// Write the start of the feed to the given asynchronous writer and return a writer for its
+    /// entries.
+    ///
+    /// Each part of the feed is written to the asynchronous writer as soon as it has been
+    /// serialized, so a large feed can be streamed into a response body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, FeedWriter};
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut writer = FeedWriter::new(&Feed::default())
+    ///     .into_async(Vec::new())
+    ///     .await
+    ///     .unwrap();
+    /// writer.write_entry(&Entry::default()).await.unwrap();
+    ///
+    /// let xml = writer.finish().await.unwrap();
+    /// assert!(xml.ends_with(b"</entry></feed>"));
+    /// # });
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn into_async<W>(self, writer: W) -> Result<AsyncEntryWriter<W>, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut xml_writer = match self.config.get_indent() {
+            Some((indent_char, size)) => Writer::new_with_indent(Vec::new(), indent_char, size),
+            None => Writer::new(Vec::new()),
+        };
+        let (ctx, renames) = self.write_open(&mut xml_writer)?;
+
+        let mut writer = AsyncEntryWriter {
+            writer: xml_writer,
+            sink: writer,
+            ctx,
+            renames,
+        };
+        writer.flush_buffer().await?;
+        Ok(writer)
+    }
+
+    fn write_open<W: Write>(
+        mut self,
+        writer: &mut Writer<W>,
+    ) -> Result<(WriteContext, HashMap<String, String>), Error> {
+        if self.config.is_declaration() && !self.config.is_fragment() {
+            let decl = BytesDecl::new(b"1.0", Some(b"utf-8"), None);
+            writer.write_event(Event::Decl(decl))?;
+        }
+
+        let renames = self.head.apply_namespaces(self.config.namespaces());
+        let ctx = WriteContext::new(self.config);
+        self.head.write_open(writer, false, &ctx)?;
+
+        Ok((ctx, renames))
+    }
+}
+
+/// Writes the entries of a feed started by a `FeedWriter`.
+///
+/// Call `finish` once all entries have been written to close the feed.
+pub struct EntryWriter<W: Write> {
+    writer: Writer<W>,
+    ctx: WriteContext,
+    renames: HashMap<String, String>,
+}
+
+impl<W: Write> EntryWriter<W> {
+    /// Write an entry of the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, FeedWriter};
+    ///
+    /// let mut writer = FeedWriter::new(&Feed::default()).into_writer(Vec::new()).unwrap();
+    /// writer.write_entry(&Entry::default()).unwrap();
+    /// ```
+    pub fn write_entry(&mut self, entry: &Entry) -> Result<(), Error> {
+        write_entry(&mut self.writer, entry, &self.renames, &self.ctx)?;
+        Ok(())
+    }
+
+    /// Close the feed, flush the underlying writer and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FeedWriter};
+    ///
+    /// let writer = FeedWriter::new(&Feed::default()).into_writer(Vec::new()).unwrap();
+    /// let xml = writer.finish().unwrap();
+    /// assert!(xml.ends_with(b"</feed>"));
+    /// ```
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.writer
+            .write_event(Event::End(BytesEnd::borrowed(b"feed")))?;
+        let mut writer = self.writer.into_inner();
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+/// Writes the entries of a feed started by a `FeedWriter` to an asynchronous writer.
+///
+/// Call `finish` once all entries have been written to close the feed.
+#[cfg(feature = "tokio")]
+pub struct AsyncEntryWriter<W> {
+    writer: Writer<Vec<u8>>,
+    sink: W,
+    ctx: WriteContext,
+    renames: HashMap<String, String>,
+}
+
+#[cfg(feature = "tokio")]
+impl<W: AsyncWrite + Unpin> AsyncEntryWriter<W> {
+    /// Write an entry of the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, FeedWriter};
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut writer = FeedWriter::new(&Feed::default())
+    ///     .into_async(Vec::new())
+    ///     .await
+    ///     .unwrap();
+    /// writer.write_entry(&Entry::default()).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn write_entry(&mut self, entry: &Entry) -> Result<(), Error> {
+        write_entry(&mut self.writer, entry, &self.renames, &self.ctx)?;
+        self.flush_buffer().await
+    }
+
+    /// Close the feed, flush the underlying writer and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FeedWriter};
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let writer = FeedWriter::new(&Feed::default())
+    ///     .into_async(Vec::new())
+    ///     .await
+    ///     .unwrap();
+    /// let xml = writer.finish().await.unwrap();
+    /// assert!(xml.ends_with(b"</feed>"));
+    /// # });
+    /// ```
+    pub async fn finish(mut self) -> Result<W, Error> {
+        self.writer
+            .write_event(Event::End(BytesEnd::borrowed(b"feed")))?;
+        self.flush_buffer().await?;
+        self.sink.flush().await?;
+        Ok(self.sink)
+    }
+
+    /// Move everything serialized so far to the underlying writer.
+    async fn flush_buffer(&mut self) -> Result<(), Error> {
+        let chunk = mem::take(self.writer.inner());
+        self.sink.write_all(&chunk).await?;
+        Ok(())
+    }
+}
+
+/// A writer that counts the bytes written to the writer it wraps.
+pub(crate) struct CountingWriter<W> {
+    inner: W,
+    count: usize,
+}
+
+impl<W: Write> CountingWriter<W> {
+    /// Wrap a writer in a buffer that counts the bytes passed through to it.
+    pub(crate) fn buffered(inner: W) -> BufWriter<Self> {
+        BufWriter::new(CountingWriter { inner, count: 0 })
+    }
+
+    /// Flush the buffer, returning the wrapped writer and the number of bytes written to it.
+    pub(crate) fn finish(writer: BufWriter<Self>) -> Result<(W, usize), Error> {
+        let mut counter = writer.into_inner().map_err(|err| err.into_error())?;
+        counter.inner.flush()?;
+        Ok((counter.inner, counter.count))
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.count += len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn write_entry<W: Write>(
+    writer: &mut Writer<W>,
+    entry: &Entry,
+    renames: &HashMap<String, String>,
+    ctx: &WriteContext,
+) -> Result<(), XmlError> {
+    if renames.is_empty() {
+        return entry.to_xml(writer, ctx);
+    }
+
+    let mut entry = entry.clone();
+    entry.rename_prefixes(renames);
+    entry.to_xml(writer, ctx)
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "activitystreams")]
+
+extern crate atom_syndication as atom;
+extern crate serde_json;
+
+use std::fs;
+
+use crate::atom::{Content, Entry, Feed, Person};
+use serde_json::json;
+
+#[test]
+fn activity_entry() {
+    let feed = Feed::read_from(&fs::read("tests/data/entry.xml").unwrap()[..]).unwrap();
+    let activity = feed.entries()[0].to_activity();
+
+    assert_eq!(
+        activity,
+        json!({
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "type": "Article",
+            "id": "http://example.com/article/1",
+            "name": "Entry Title",
+            "summary": "Entry summary",
+            "content": "Entry content",
+            "mediaType": "text/html",
+            "url": "http://example.com/article/",
+            "published": "2017-06-01T15:15:44-05:00",
+            "updated": "2017-06-03T15:15:44-05:00",
+            "attributedTo": [
+                { "type": "Person", "name": "John Doe" },
+                { "type": "Person", "name": "Jane Doe" },
+            ],
+            "tag": [
+                { "type": "Object", "name": "technology" },
+                { "type": "Object", "name": "podcast" },
+            ],
+            "attachment": [
+                {
+                    "type": "Document",
+                    "url": "http://example.com/audio.mp3",
+                    "mediaType": "audio/mpeg",
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn activity_collection() {
+    let mut feed = Feed::default();
+    feed.set_id("urn:feed");
+    feed.set_title("Blog");
+    feed.set_authors(vec![Person::default()
+        .with_name("John Doe")
+        .with_uri("https://example.com/john")]);
+    feed.set_entries(vec![
+        Entry::default()
+            .with_id("urn:note:2")
+            .with_content(Content::text("<3")),
+        Entry::default()
+            .with_id("urn:post:1")
+            .with_title("Post")
+            .with_content(Content::html("<p>Post</p>")),
+    ]);
+
+    let collection = feed.to_activity_collection();
+    assert_eq!(
+        collection["@context"],
+        "https://www.w3.org/ns/activitystreams"
+    );
+    assert_eq!(collection["id"], "urn:feed");
+    assert_eq!(collection["name"], "Blog");
+    assert_eq!(collection["totalItems"], 2);
+
+    let items = collection["orderedItems"].as_array().unwrap();
+    assert_eq!(items[0]["type"], "Note");
+    assert_eq!(items[0]["id"], "urn:note:2");
+    assert_eq!(items[0]["content"], "&lt;3");
+    assert!(items[0].get("@context").is_none());
+    assert!(items[0].get("name").is_none());
+    assert_eq!(
+        items[0]["attributedTo"],
+        json!([{ "type": "Person", "name": "John Doe", "url": "https://example.com/john" }])
+    );
+    assert_eq!(items[1]["type"], "Article");
+    assert_eq!(items[1]["content"], "<p>Post</p>");
+}
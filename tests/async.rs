@@ -0,0 +1,112 @@
+#![cfg(feature = "tokio")]
+
+extern crate atom_syndication as atom;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::StreamExt;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::atom::{DetectedFormat, Entry, Error, Feed, FeedWriter};
+
+/// Yields the bytes of a slice a few at a time.
+struct Trickle<'a>(&'a [u8], usize);
+
+impl<'a> AsyncRead for Trickle<'a> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let len = self.1.min(self.0.len()).min(buf.remaining());
+        buf.put_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn async_write_streaming() {
+    let feed = Feed::read_from_file("tests/data/feed.xml").unwrap();
+    let entries = (0..3)
+        .map(|i| Entry::default().with_id(format!("urn:entry:{}", i)))
+        .collect::<Vec<_>>();
+
+    let mut expected = feed.clone();
+    expected.set_entries(entries.clone());
+
+    let mut writer = FeedWriter::new(&feed).into_async(Vec::new()).await.unwrap();
+    for entry in &entries {
+        writer.write_entry(entry).await.unwrap();
+    }
+    let xml = writer.finish().await.unwrap();
+    assert_eq!(String::from_utf8(xml).unwrap(), expected.to_string());
+}
+
+#[tokio::test]
+async fn read_entry_stream() {
+    for file in &[
+        "tests/data/feed.xml",
+        "tests/data/prefixed.xml",
+        "tests/data/entry.xml",
+    ] {
+        let xml = std::fs::read(file).unwrap();
+        let feed = Feed::read_from(&xml[..]).unwrap();
+
+        for &size in &[1, 7, 8192] {
+            let entries = Feed::entry_stream(Trickle(&xml, size))
+                .map(Result::unwrap)
+                .collect::<Vec<_>>()
+                .await;
+            assert_eq!(entries, feed.entries());
+        }
+    }
+}
+
+#[tokio::test]
+async fn read_entry_stream_markup() {
+    let xml = r#"<?xml version="1.0"?>
+<!DOCTYPE feed [ <!ENTITY x "y"> ]>
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <!-- <entry><id>comment</id></entry> -->
+    <title type="html"><![CDATA[<entry>]]></title>
+    <entry xml:lang="a>b"><id>urn:entry:1</id><title> Entry </title><content><![CDATA[</entry>]]></content></entry>
+    <entry/>
+</feed>"#;
+
+    let entries = Feed::entry_stream(Trickle(xml.as_bytes(), 3))
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].id(), "urn:entry:1");
+    assert_eq!(entries[0].title(), " Entry ");
+    assert_eq!(entries[0].content().unwrap().value(), Some("</entry>"));
+    assert_eq!(entries[1], Entry::default());
+}
+
+#[tokio::test]
+async fn read_entry_stream_errors() {
+    let results = Feed::entry_stream(&b"<rss><channel></channel></rss>"[..])
+        .collect::<Vec<_>>()
+        .await;
+    assert!(matches!(
+        results[..],
+        [Err(Error::WrongFormat {
+            detected: DetectedFormat::Rss2
+        })]
+    ));
+
+    let xml = b"<feed><entry><id>urn:entry:1</id></entry><entry><id>urn:entry:2";
+    let mut results = Feed::entry_stream(&xml[..]).collect::<Vec<_>>().await;
+    let err = results.pop().unwrap().unwrap_err();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].as_ref().unwrap().id(), "urn:entry:1");
+    assert_eq!(err.path(), Some("/feed"));
+    assert!(matches!(err.without_context(), Error::Eof));
+
+    let results = Feed::entry_stream(&b""[..]).collect::<Vec<_>>().await;
+    assert!(matches!(results[..], [Err(Error::MissingElement { .. })]));
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "atompub")]
+
+extern crate atom_syndication as atom;
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+
+use crate::atom::atompub::{Client, Credentials, Method, Request, Response, Transport};
+use crate::atom::{Entry, Error};
+
+/// Records the requests it is sent and answers them with scripted responses.
+#[derive(Default)]
+struct Script {
+    requests: Vec<Request>,
+    responses: VecDeque<Response>,
+}
+
+impl Transport for Script {
+    fn send(&mut self, request: Request) -> io::Result<Response> {
+        self.requests.push(request);
+        Ok(self.responses.pop_front().unwrap())
+    }
+}
+
+fn client(responses: Vec<Response>) -> Client<Script> {
+    let script = Script {
+        requests: Vec::new(),
+        responses: responses.into(),
+    };
+    Client::new(script, "http://example.org/app/service").with_credentials(Credentials::Basic {
+        username: "user".to_string(),
+        password: "secret".to_string(),
+    })
+}
+
+#[test]
+fn atompub_collections() {
+    let service = fs::read("tests/data/service.xml").unwrap();
+    let mut client = client(vec![Response::new(200).body(service)]);
+
+    let collections = client.collections().unwrap();
+    let summary = collections
+        .iter()
+        .map(|c| (c.workspace(), c.title(), c.href(), c.accepts_entries()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "Main Site",
+                "My Blog Entries",
+                "http://example.org/blog/main",
+                true
+            ),
+            ("Main Site", "Pictures", "http://example.org/app/pic", false),
+            (
+                "Sidebar Blog",
+                "Remaindered Links",
+                "http://example.org/sidebar/list",
+                true
+            ),
+        ]
+    );
+    assert_eq!(collections[1].accept(), ["image/png", "image/jpeg"]);
+
+    let requests = client.into_transport().requests;
+    assert_eq!(requests[0].method(), Method::Get);
+    assert_eq!(requests[0].url(), "http://example.org/app/service");
+    assert_eq!(
+        requests[0].get_header("authorization"),
+        Some("Basic dXNlcjpzZWNyZXQ=")
+    );
+}
+
+#[test]
+fn atompub_members() {
+    let service = fs::read("tests/data/service.xml").unwrap();
+    let created = r#"<entry xmlns="http://www.w3.org/2005/Atom">
+        <title>Atom-Powered Robots Run Amok</title>
+        <id>urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a</id>
+        <updated>2003-12-13T18:30:02Z</updated>
+        <link rel="edit" href="http://example.org/edit/first-post.atom"/>
+    </entry>"#;
+
+    let mut client = client(vec![
+        Response::new(200).body(service),
+        Response::new(201)
+            .header("Location", "/edit/first-post.atom")
+            .header("ETag", "\"c180de84f991g8\"")
+            .body(created),
+        Response::new(200).header("ETag", "\"c181bb840673b5\""),
+        Response::new(412),
+        Response::new(204),
+    ]);
+
+    let collections = client.collections().unwrap();
+    let mut entry = Entry::default();
+    entry.set_title("Atom-Powered Robots Run Amok");
+
+    let mut member = client
+        .create(&collections[0], &entry, Some("Robots 100% Amok™"))
+        .unwrap();
+    assert_eq!(member.location(), "http://example.org/edit/first-post.atom");
+    assert_eq!(member.etag(), Some("\"c180de84f991g8\""));
+    assert_eq!(
+        member.entry().id(),
+        "urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a"
+    );
+
+    member.entry_mut().set_title("Updated");
+    let updated = client.update(&member).unwrap();
+    assert_eq!(updated.etag(), Some("\"c181bb840673b5\""));
+    assert_eq!(updated.entry().title(), "Updated");
+
+    let err = client.delete(&member).unwrap_err();
+    assert!(matches!(err, Error::HttpStatus { status: 412 }));
+    client.delete(&updated).unwrap();
+
+    let requests = client.into_transport().requests;
+
+    let post = &requests[1];
+    assert_eq!(post.method(), Method::Post);
+    assert_eq!(post.url(), "http://example.org/blog/main");
+    assert_eq!(
+        post.get_header("Content-Type"),
+        Some("application/atom+xml;type=entry")
+    );
+    assert_eq!(post.get_header("Slug"), Some("Robots 100%25 Amok%E2%84%A2"));
+    assert!(String::from_utf8_lossy(post.body()).contains("Atom-Powered Robots Run Amok"));
+
+    let put = &requests[2];
+    assert_eq!(put.method(), Method::Put);
+    assert_eq!(put.url(), "http://example.org/edit/first-post.atom");
+    assert_eq!(put.get_header("If-Match"), Some("\"c180de84f991g8\""));
+    assert!(String::from_utf8_lossy(put.body()).contains("<title>Updated</title>"));
+
+    assert_eq!(requests[3].method(), Method::Delete);
+    assert_eq!(
+        requests[3].get_header("If-Match"),
+        Some("\"c180de84f991g8\"")
+    );
+    assert_eq!(
+        requests[4].get_header("If-Match"),
+        Some("\"c181bb840673b5\"")
+    );
+}
@@ -0,0 +1,133 @@
+extern crate atom_syndication as atom;
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use crate::atom::cache::{FeedCache, Fetched, MemoryStorage, Refresh};
+
+const URL: &str = "http://example.com/feed.xml";
+
+fn modified(entries: &[(&str, &str)], etag: &str) -> Fetched {
+    let entries = entries
+        .iter()
+        .map(|(id, title)| format!("<entry><id>{}</id><title>{}</title></entry>", id, title))
+        .collect::<String>();
+    Fetched::Modified {
+        body: format!(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom">{}</feed>"#,
+            entries
+        )
+        .into_bytes(),
+        etag: Some(etag.to_string()),
+        last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+    }
+}
+
+#[test]
+fn cache_refresh() {
+    let mut cache = FeedCache::new(MemoryStorage::new()).max_age(Duration::from_secs(0));
+
+    let refresh = cache
+        .refresh(URL, |validators| {
+            assert_eq!(validators.etag(), None);
+            assert_eq!(validators.last_modified(), None);
+            Ok(modified(&[("urn:1", "One"), ("urn:2", "Two")], "\"v1\""))
+        })
+        .unwrap();
+    let delta = match refresh {
+        Refresh::Updated(delta) => delta,
+        refresh => panic!("unexpected refresh {:?}", refresh),
+    };
+    assert_eq!(delta.added().len(), 2);
+    assert!(delta.updated().is_empty());
+    assert!(delta.removed().is_empty());
+
+    let refresh = cache
+        .refresh(URL, |validators| {
+            assert_eq!(validators.etag(), Some("\"v1\""));
+            assert_eq!(
+                validators.last_modified(),
+                Some("Wed, 21 Oct 2015 07:28:00 GMT")
+            );
+            Ok(Fetched::NotModified)
+        })
+        .unwrap();
+    assert_eq!(refresh, Refresh::NotModified);
+
+    let refresh = cache
+        .refresh(URL, |_| {
+            Ok(modified(
+                &[("urn:2", "Two, edited"), ("urn:3", "Three")],
+                "\"v2\"",
+            ))
+        })
+        .unwrap();
+    let delta = match refresh {
+        Refresh::Updated(delta) => delta,
+        refresh => panic!("unexpected refresh {:?}", refresh),
+    };
+    assert_eq!(delta.added()[0].id(), "urn:3");
+    assert_eq!(delta.updated()[0].title(), "Two, edited");
+    assert_eq!(delta.removed(), ["urn:1"]);
+
+    let cached = cache.get(URL).unwrap();
+    assert_eq!(cached.feed().entries().len(), 2);
+    assert_eq!(cached.validators().etag(), Some("\"v2\""));
+
+    let refresh = cache
+        .refresh(URL, |_| {
+            Ok(modified(
+                &[("urn:2", "Two, edited"), ("urn:3", "Three")],
+                "\"v3\"",
+            ))
+        })
+        .unwrap();
+    match refresh {
+        Refresh::Updated(delta) => assert!(delta.is_empty()),
+        refresh => panic!("unexpected refresh {:?}", refresh),
+    }
+}
+
+#[test]
+fn cache_fresh() {
+    let mut cache = FeedCache::new(MemoryStorage::new()).max_age(Duration::from_secs(3600));
+    let requests = Cell::new(0);
+
+    for _ in 0..3 {
+        cache
+            .refresh(URL, |_| {
+                requests.set(requests.get() + 1);
+                Ok(modified(&[("urn:1", "One")], "\"v1\""))
+            })
+            .unwrap();
+    }
+    assert_eq!(requests.get(), 1);
+
+    let refresh = cache.refresh(URL, |_| unreachable!()).unwrap();
+    assert_eq!(refresh, Refresh::Fresh);
+
+    cache.invalidate(URL);
+    assert!(cache.get(URL).is_none());
+}
+
+#[test]
+fn cache_errors() {
+    let mut cache = FeedCache::new(MemoryStorage::new()).max_age(Duration::from_secs(0));
+    assert!(cache.refresh(URL, |_| Ok(Fetched::NotModified)).is_err());
+
+    cache
+        .refresh(URL, |_| Ok(modified(&[("urn:1", "One")], "\"v1\"")))
+        .unwrap();
+    let result = cache.refresh(URL, |_| {
+        Ok(Fetched::Modified {
+            body: b"<rss version=\"2.0\"></rss>".to_vec(),
+            etag: None,
+            last_modified: None,
+        })
+    });
+    assert!(result.is_err());
+
+    let cached = cache.get(URL).unwrap();
+    assert_eq!(cached.validators().etag(), Some("\"v1\""));
+    assert_eq!(cached.feed().entries()[0].id(), "urn:1");
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn validate(args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_atom-validate"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut input = child.stdin.take().unwrap();
+    input.write_all(stdin.unwrap_or("").as_bytes()).unwrap();
+    drop(input);
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn cli_valid() {
+    let output = validate(&["tests/data/feed.xml"], None);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).ends_with("tests/data/feed.xml: 0 errors, 0 warnings\n"));
+}
+
+#[test]
+fn cli_invalid() {
+    let output = validate(&["tests/data/invalid.xml"], None);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("tests/data/invalid.xml: error: entry[1]: missing author\n"));
+
+    let output = validate(
+        &["--json"],
+        Some("<feed><title>Feed \"Title\"</title></feed>"),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let json = stdout(&output);
+    assert!(json.starts_with(r#"{"input":"-","valid":false,"issues":[{"severity":"error","location":"feed","message":"missing id"}"#));
+}
+
+#[test]
+fn cli_strict() {
+    let feed = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+        <title>Feed Title</title>
+        <id>urn:feed</id>
+        <updated>2017-06-03T15:15:44-05:00</updated>
+    </feed>"#;
+
+    let output = validate(&["-"], Some(feed));
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("-: warning: feed: missing self link\n"));
+
+    let output = validate(&["--strict", "-"], Some(feed));
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn cli_invalid_dates() {
+    let feed = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+        <title>Feed Title</title>
+        <id>urn:feed</id>
+        <updated>2017-06-03T15:15:44-05:00</updated>
+        <link rel="self" href="http://example.com/feed"/>
+        <entry>
+            <title>Entry Title</title>
+            <id>urn:entry</id>
+            <updated>2017-06-03T15:15:44-05:00</updated>
+            <published>June 3rd</published>
+            <author><name>John Doe</name></author>
+            <content>Entry content</content>
+        </entry>
+    </feed>"#;
+
+    let output = validate(&["-"], Some(feed));
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains(
+        "-: warning: /feed/entry[0]/published: published date is not formatted as described by RFC 3339: June 3rd\n"
+    ));
+
+    let output = validate(&["--strict", "-"], Some(feed));
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn cli_errors() {
+    let output = validate(&["--json"], Some("<rss version=\"2.0\"></rss>"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout(&output).contains(r#""error":"expected an Atom feed but found an RSS 2.0 feed""#)
+    );
+
+    let output = validate(&["tests/data/missing.xml"], None);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = validate(&["--verbose"], None);
+    assert_eq!(output.status.code(), Some(64));
+}
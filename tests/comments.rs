@@ -0,0 +1,100 @@
+#![cfg(feature = "comments")]
+
+extern crate atom_syndication as atom;
+
+use std::fs;
+
+use crate::atom::{Error, Feed};
+
+fn read() -> Feed {
+    Feed::read_from(&fs::read("tests/data/comments.xml").unwrap()[..]).unwrap()
+}
+
+#[test]
+fn comments_read() {
+    let feed = read();
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.comment_count().unwrap(), Some(42));
+    assert_eq!(
+        entry.comment_feed(),
+        Some("http://example.com/post/2/comments.xml")
+    );
+
+    let entry = &feed.entries()[1];
+    assert!(
+        matches!(entry.comment_count(), Err(Error::InvalidExtension(ref value)) if value == "none")
+    );
+    assert_eq!(entry.comment_feed(), None);
+}
+
+#[test]
+fn comments_write() {
+    let mut feed = read();
+    {
+        let entry = &mut feed.entries_mut()[1];
+        entry.set_comment_count(Some(3));
+        entry.set_comment_feed("http://example.com/post/1/comments.xml");
+    }
+    {
+        let entry = &mut feed.entries_mut()[0];
+        entry.set_comment_count(None);
+        entry.set_comment_feed(None);
+        assert!(!entry.extensions().contains_key("slash"));
+        assert!(!entry.extensions().contains_key("wfw"));
+    }
+
+    let feed = feed.to_string().parse::<Feed>().unwrap();
+    assert_eq!(feed.entries()[0].comment_count().unwrap(), None);
+    assert_eq!(feed.entries()[1].comment_count().unwrap(), Some(3));
+    assert_eq!(
+        feed.entries()[1].comment_feed(),
+        Some("http://example.com/post/1/comments.xml")
+    );
+}
+
+#[test]
+fn comments_pingback_trackback() {
+    let mut feed = read();
+    assert_eq!(feed.pingback(), Some("http://example.com/xmlrpc.php"));
+
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.pingback(),
+        Some("http://example.com/xmlrpc.php?post=2")
+    );
+    assert_eq!(
+        entry.trackback_ping(),
+        Some("http://example.com/trackback/2")
+    );
+    let entry = &feed.entries()[1];
+    assert_eq!(entry.pingback(), None);
+    assert_eq!(
+        entry.trackback_ping(),
+        Some("http://example.com/trackback/1")
+    );
+
+    feed.set_pingback(None);
+    {
+        let entry = &mut feed.entries_mut()[0];
+        entry.set_pingback("http://example.com/pingback");
+        entry.set_trackback_ping(None);
+    }
+    {
+        let entry = &mut feed.entries_mut()[1];
+        entry.set_trackback_ping("http://example.com/trackback/1?type=post");
+    }
+
+    let feed = feed.to_string().parse::<Feed>().unwrap();
+    assert_eq!(feed.pingback(), None);
+    assert_eq!(
+        feed.entries()[0].pingback(),
+        Some("http://example.com/pingback")
+    );
+    assert_eq!(feed.entries()[0].links().len(), 1);
+    assert_eq!(feed.entries()[0].trackback_ping(), None);
+    assert_eq!(
+        feed.entries()[1].trackback_ping(),
+        Some("http://example.com/trackback/1?type=post")
+    );
+}
@@ -0,0 +1,52 @@
+#![cfg(any(feature = "gzip", feature = "zstd"))]
+
+extern crate atom_syndication as atom;
+
+use std::io::BufReader;
+
+use crate::atom::Feed;
+#[cfg(feature = "gzip")]
+use crate::atom::{Error, ReadConfig};
+
+fn feed() -> Feed {
+    Feed::read_from_file("tests/data/feed.xml").unwrap()
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn compress_gzip() {
+    let feed = feed();
+    let compressed = feed.write_to_gz(Vec::new()).unwrap();
+    assert!(compressed.starts_with(&[0x1F, 0x8B]));
+
+    assert_eq!(Feed::read_from_slice(&compressed).unwrap(), feed);
+
+    let config = ReadConfig::new().fidelity(true);
+    let read = Feed::read_with_config(BufReader::with_capacity(1, &compressed[..]), &config);
+    assert_eq!(read.unwrap().title(), feed.title());
+
+    let mut large = Feed::default();
+    large.set_title("a".repeat(100_000));
+    let compressed = large.write_to_gz(Vec::new()).unwrap();
+    assert!(compressed.len() < 10_000);
+
+    let config = ReadConfig::new().max_bytes(10_000);
+    match Feed::read_with_config(&compressed[..], &config).map_err(Error::without_context) {
+        Err(Error::TooLarge { limit }) => assert_eq!(limit, 10_000),
+        other => panic!("expected too large error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn compress_zstd() {
+    let feed = feed();
+    let compressed = feed.write_to_zstd(Vec::new(), 3).unwrap();
+    assert!(compressed.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]));
+
+    assert_eq!(Feed::read_from_slice(&compressed).unwrap(), feed);
+    assert_eq!(
+        Feed::read_from(BufReader::new(&compressed[..])).unwrap(),
+        feed
+    );
+}
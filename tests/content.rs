@@ -2,8 +2,9 @@ extern crate atom_syndication as atom;
 
 use std::fs::File;
 use std::io::BufReader;
+use std::time::Duration;
 
-use crate::atom::Feed;
+use crate::atom::{Content, Entry, Error, Feed, FeedParser, ReadConfig, WriteConfig};
 
 macro_rules! feed {
     ($f:expr) => {{
@@ -82,3 +83,241 @@ fn content_text_xhtml() {
     );
     assert_eq!(content.content_type(), Some("xhtml"));
 }
+
+#[test]
+fn content_xhtml_wrap() {
+    let feed = feed!("tests/data/content_text_xhtml.xml");
+    let content = feed.entries()[0].content().unwrap();
+    assert!(!content.clone().wrap_xhtml());
+
+    let unwrapped = [
+        "<p>One</p><p>Two</p>",
+        "Entry content",
+        "<div>One</div><div>Two</div>",
+        "<div>One</div> trailing text",
+        "<span>One</span>",
+        "",
+    ];
+    for value in &unwrapped {
+        let mut content = Content::default();
+        content.set_content_type("xhtml");
+        content.set_value(*value);
+        let mut entry = feed.entries()[0].clone();
+        entry.set_content(content);
+        let mut feed = feed.clone();
+        feed.set_entries(vec![entry]);
+
+        assert!(
+            feed.validate()
+                .errors()
+                .any(|issue| issue.message() == "xhtml content is not wrapped in a single div"),
+            "{}",
+            value
+        );
+
+        let mut xml = Vec::new();
+        feed.write_with_config(&mut xml, &WriteConfig::new().wrap_xhtml(true))
+            .unwrap();
+        let written = String::from_utf8(xml).unwrap().parse::<Feed>().unwrap();
+        assert_eq!(
+            written.entries()[0].content().unwrap().value(),
+            Some(
+                format!(
+                    r#"<div xmlns="http://www.w3.org/1999/xhtml">{}</div>"#,
+                    value
+                )
+                .as_str()
+            )
+        );
+    }
+
+    let wrapped = [
+        r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>One</p><p>Two</p></div>"#,
+        r#"  <xhtml:div xmlns:xhtml="http://www.w3.org/1999/xhtml">One</xhtml:div> "#,
+        r#"<!-- comment --><div xmlns="http://www.w3.org/1999/xhtml"/>"#,
+    ];
+    for value in &wrapped {
+        let mut content = Content::default();
+        content.set_content_type("xhtml");
+        content.set_value(*value);
+        assert!(!content.wrap_xhtml(), "{}", value);
+    }
+}
+
+#[test]
+fn content_constructors() {
+    let contents = vec![
+        Content::text("Entry content"),
+        Content::html("<p>Entry content</p>"),
+        Content::xhtml("<p>Entry content</p>"),
+        Content::out_of_line("http://example.com/image.png", "image/png"),
+    ];
+
+    for content in contents {
+        let mut entry = Entry::default();
+        entry.set_content(content.clone());
+        let mut feed = Feed::default();
+        feed.push_entry(entry);
+
+        let written = feed.to_string().parse::<Feed>().unwrap();
+        assert_eq!(written.entries()[0].content(), Some(&content));
+    }
+}
+
+#[test]
+fn content_body_text() {
+    let body_text = |path: &str| {
+        let feed = feed!(path);
+        feed.entries()[0].body_text().map(|body| body.into_owned())
+    };
+
+    assert_eq!(
+        body_text("tests/data/content_src.xml"),
+        Some("http://example.com/image.png".to_string())
+    );
+    assert_eq!(
+        body_text("tests/data/content_text_plain_escaped.xml"),
+        Some("<p>Entry content</p>".to_string())
+    );
+    assert_eq!(
+        body_text("tests/data/content_text_html.xml"),
+        Some("Entry content".to_string())
+    );
+    assert_eq!(
+        body_text("tests/data/content_text_xhtml.xml"),
+        Some("Entry content with a link inside.".to_string())
+    );
+    assert_eq!(body_text("tests/data/content_text_other.xml"), None);
+    assert_eq!(
+        body_text("tests/data/entry.xml"),
+        Some("Entry content".to_string())
+    );
+}
+
+#[test]
+fn content_word_count() {
+    let word_count = |path: &str| feed!(path).entries()[0].word_count();
+
+    assert_eq!(word_count("tests/data/content_text_html.xml"), 2);
+    assert_eq!(word_count("tests/data/content_text_xhtml.xml"), 6);
+    assert_eq!(word_count("tests/data/content_src.xml"), 0);
+    assert_eq!(word_count("tests/data/content_text_other.xml"), 0);
+
+    let mut entry = Entry::default();
+    entry.set_content(Content::text(
+        "Hello, world! -- 1 2 3 ... 東京タワー is tall",
+    ));
+    assert_eq!(entry.word_count(), 12);
+    assert_eq!(entry.reading_time(240), Duration::from_secs(3));
+    assert_eq!(entry.reading_time(0), Duration::from_secs(720));
+
+    entry.set_content(Content::default().with_src("http://example.com/post"));
+    entry.set_summary("A short summary".to_string());
+    assert_eq!(entry.word_count(), 3);
+}
+
+#[test]
+fn content_absolutize_urls() {
+    let mut entry = Entry::default();
+    entry.set_content(Content::xhtml(concat!(
+        r#"<p>See <a class="more" HREF = 'notes/1?a=1&amp;b=2#top'>the notes</a>, "#,
+        r#"<img src=/img/a.png alt="a > b"> and <a href="mailto:jane@example.com">Jane</a>."#,
+        r#"<!-- <a href="draft"> --></p>"#,
+    )));
+    entry
+        .attrs_mut()
+        .insert("xml:base".to_string(), "2020/".to_string());
+    entry.absolutize_content_urls("http://example.com/blog/");
+
+    assert_eq!(
+        entry.content().unwrap().value(),
+        Some(concat!(
+            r#"<div xmlns="http://www.w3.org/1999/xhtml">"#,
+            r#"<p>See <a class="more" HREF = 'http://example.com/blog/2020/notes/1?a=1&amp;b=2#top'>"#,
+            r#"the notes</a>, <img src=http://example.com/img/a.png alt="a > b"> and "#,
+            r#"<a href="mailto:jane@example.com">Jane</a>.<!-- <a href="draft"> --></p></div>"#,
+        ))
+    );
+
+    let mut entry = Entry::default();
+    entry.set_content(Content::text(r#"<a href="relative">"#));
+    entry.absolutize_content_urls("http://example.com/");
+    assert_eq!(
+        entry.content().unwrap().value(),
+        Some(r#"<a href="relative">"#)
+    );
+}
+
+#[test]
+fn content_decoded_bytes() {
+    let feed = feed!("tests/data/content_text_other.xml");
+    let content = feed.entries()[0].content().unwrap();
+    let bytes = content.decoded_bytes().unwrap();
+    assert_eq!(bytes, b"Entry content".to_vec());
+
+    let mut encoded = Content::default();
+    encoded.set_decoded_bytes(content.content_type().unwrap(), &bytes);
+    assert_eq!(encoded.decoded_bytes().unwrap(), bytes);
+
+    let mut text = Content::default();
+    text.set_decoded_bytes("text/plain", "Entry content");
+    assert_eq!(text.value(), Some("Entry content"));
+    assert_eq!(text.decoded_bytes().unwrap(), b"Entry content".to_vec());
+
+    let mut invalid = Content::default();
+    invalid.set_content_type("image/png");
+    invalid.set_value("not base64!");
+    assert!(invalid.decoded_bytes().is_err());
+}
+
+#[test]
+fn content_lazy() {
+    let config = ReadConfig::new().lazy_content(true);
+
+    for file in &[
+        "tests/data/content_src.xml",
+        "tests/data/content_text_cdata.xml",
+        "tests/data/content_text_cdata_escaped.xml",
+        "tests/data/content_text_html.xml",
+        "tests/data/content_text_other.xml",
+        "tests/data/content_text_plain.xml",
+        "tests/data/content_text_plain_escaped.xml",
+        "tests/data/content_text_xhtml.xml",
+    ] {
+        let xml = std::fs::read(file).unwrap();
+        let expected_feed = Feed::read_from(&xml[..]).unwrap();
+        let expected = expected_feed.entries()[0].content().unwrap();
+
+        let mut feed = Feed::read_with_config(&xml[..], &config).unwrap();
+        if expected.src().is_none() {
+            assert!(matches!(
+                feed.write_to(Vec::new()),
+                Err(Error::ContentNotLoaded)
+            ));
+        }
+
+        let content = feed.entries_mut()[0].content_mut().as_mut().unwrap();
+        assert_eq!(content.content_type(), expected.content_type());
+        assert_eq!(content.byte_range().is_some(), expected.src().is_none());
+        content.load(&xml, &config).unwrap();
+        assert_eq!(content, expected);
+        assert_eq!(feed.to_string(), expected_feed.to_string());
+
+        let mut parser = FeedParser::with_config(&config);
+        let mut entries = parser.feed_bytes(&xml).unwrap();
+        parser.finish().unwrap();
+        let content = entries[0].content_mut().as_mut().unwrap();
+        content.load(&xml, &config).unwrap();
+        assert_eq!(content, expected);
+
+        let bom = [
+            &b"\xef\xbb\xbf<?xml version=\"1.0\" encoding=\"utf-8\"?>\n"[..],
+            &xml,
+        ]
+        .concat();
+        let mut feed = Feed::read_with_config(&bom[..], &config).unwrap();
+        let content = feed.entries_mut()[0].content_mut().as_mut().unwrap();
+        content.load(&bom, &config).unwrap();
+        assert_eq!(content, expected);
+    }
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "dsig")]
+
+extern crate atom_syndication as atom;
+
+use std::fs;
+
+use crate::atom::dsig::{self, HmacSha256, HMAC_SHA256};
+use crate::atom::{Entry, Error, Feed};
+
+fn signed_feed(key: &HmacSha256) -> String {
+    let feed = Feed::read_from(&fs::read("tests/data/entry.xml").unwrap()[..]).unwrap();
+    String::from_utf8(feed.write_signed(Vec::new(), key).unwrap()).unwrap()
+}
+
+fn message(err: Error) -> String {
+    match err {
+        Error::InvalidSignature(message) => message,
+        err => panic!("unexpected error {:?}", err),
+    }
+}
+
+#[test]
+fn dsig_feed() {
+    let key = HmacSha256::new("secret").with_key_name("advisories");
+    let xml = signed_feed(&key);
+    assert!(xml.contains("<ds:KeyInfo><ds:KeyName>advisories</ds:KeyName></ds:KeyInfo>"));
+
+    let (feed, signature) = Feed::read_verified(xml.as_bytes(), &key).unwrap();
+    assert_eq!(signature.algorithm(), HMAC_SHA256);
+    assert_eq!(signature.key_name(), Some("advisories"));
+    assert_eq!(feed.entries()[0].title(), "Entry Title");
+    assert!(!feed.extensions().contains_key("ds"));
+}
+
+#[test]
+fn dsig_entry() {
+    let key = HmacSha256::new("secret");
+    let mut entry = Entry::default();
+    entry.set_id("urn:advisory:1");
+    entry.set_title("Advisory");
+
+    let xml = entry.write_signed(Vec::new(), &key).unwrap();
+    let signature = dsig::verify(&xml, &key).unwrap();
+    assert_eq!(signature.key_name(), None);
+}
+
+#[test]
+fn dsig_canonical() {
+    let key = HmacSha256::new("secret");
+    let document = concat!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n",
+        "<!-- comment -->\r\n",
+        "<feed xmlns=\"http://www.w3.org/2005/Atom\" b='2' a=\"1\">",
+        "<x:e xmlns:x=\"http://example.com/x\" xmlns:y=\"http://example.com/y\"/>",
+        "<title>a &amp; b &#x41;<![CDATA[ <c> ]]></title>\r\n",
+        "</feed>"
+    );
+
+    let xml = String::from_utf8(dsig::sign(document.as_bytes(), &key).unwrap()).unwrap();
+    assert!(xml.starts_with(concat!(
+        "<feed xmlns=\"http://www.w3.org/2005/Atom\" a=\"1\" b=\"2\">",
+        "<x:e xmlns:x=\"http://example.com/x\"></x:e>",
+        "<title>a &amp; b A &lt;c&gt; </title>\n",
+        "<ds:Signature xmlns:ds=\"http://www.w3.org/2000/09/xmldsig#\">"
+    )));
+    assert!(xml.ends_with("</ds:Signature></feed>"));
+
+    // formatting that canonicalization removes does not invalidate the signature
+    let reformatted = xml
+        .replacen(
+            "<feed",
+            "<?xml version=\"1.0\"?>\n<!-- signed -->\n<feed",
+            1,
+        )
+        .replace(
+            "<x:e xmlns:x=\"http://example.com/x\"></x:e>",
+            "<x:e xmlns:x='http://example.com/x' />",
+        )
+        .replace("a=\"1\" b=\"2\"", "b=\"2\"  a=\"1\"")
+        .replace("b A", "b &#65;");
+    assert!(dsig::verify(reformatted.as_bytes(), &key).is_ok());
+
+    // signing a signed document replaces its signature
+    let resigned = dsig::sign(reformatted.as_bytes(), &key).unwrap();
+    assert_eq!(resigned, xml.as_bytes());
+}
+
+#[test]
+fn dsig_invalid() {
+    let key = HmacSha256::new("secret").with_key_name("advisories");
+    let xml = signed_feed(&key);
+
+    let tampered = xml.replace("Entry Title", "Other Title");
+    let err = Feed::read_verified(tampered.as_bytes(), &key).unwrap_err();
+    assert_eq!(message(err), "digest does not match the document");
+
+    let err = Feed::read_verified(xml.as_bytes(), &HmacSha256::new("other")).unwrap_err();
+    assert_eq!(message(err), "signature value does not match");
+
+    let other = HmacSha256::new("secret").with_key_name("releases");
+    let err = Feed::read_verified(xml.as_bytes(), &other).unwrap_err();
+    assert_eq!(message(err), "signature value does not match");
+
+    let unsigned = fs::read("tests/data/entry.xml").unwrap();
+    let err = Feed::read_verified(&unsigned, &key).unwrap_err();
+    assert_eq!(message(err), "missing signature");
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "feed-rs")]
+
+extern crate atom_syndication as atom;
+extern crate feed_rs;
+
+use std::fs;
+
+use crate::atom::{Entry, Feed};
+use feed_rs::model;
+
+fn read(path: &str) -> Feed {
+    Feed::read_from(&fs::read(path).unwrap()[..]).unwrap()
+}
+
+#[test]
+fn feedrs_round_trip() {
+    for path in &["tests/data/feed.xml", "tests/data/entry.xml"] {
+        let feed = read(path);
+        let converted = Feed::from(model::Feed::from(&feed));
+
+        assert_eq!(converted.title(), feed.title());
+        assert_eq!(converted.subtitle(), feed.subtitle());
+        assert_eq!(converted.id(), feed.id());
+        assert_eq!(converted.updated(), feed.updated());
+        assert_eq!(converted.icon(), feed.icon());
+        assert_eq!(converted.logo(), feed.logo());
+        assert_eq!(converted.rights(), feed.rights());
+        assert_eq!(converted.authors(), feed.authors());
+        assert_eq!(converted.categories(), feed.categories());
+        assert_eq!(converted.contributors(), feed.contributors());
+        assert_eq!(converted.generator(), feed.generator());
+        assert_eq!(converted.links(), feed.links());
+        assert_eq!(converted.entries(), feed.entries());
+    }
+}
+
+#[test]
+fn feedrs_from_parsed() {
+    let xml = fs::read("tests/data/entry.xml").unwrap();
+    let parsed = feed_rs::parser::parse(&xml[..]).unwrap();
+
+    let feed = Feed::from(parsed);
+    let entry: &Entry = &feed.entries()[0];
+    assert_eq!(entry.title(), "Entry Title");
+    assert_eq!(entry.id(), "http://example.com/article/1");
+    assert_eq!(entry.updated().to_rfc3339(), "2017-06-03T20:15:44+00:00");
+    assert_eq!(entry.authors()[1].name(), "Jane Doe");
+    assert_eq!(entry.links()[1].rel(), "enclosure");
+    assert_eq!(entry.links()[1].mime_type(), Some("audio/mpeg"));
+    assert_eq!(entry.links()[1].length(), Some(1000));
+    assert_eq!(entry.summary(), Some("Entry summary"));
+    assert_eq!(entry.content().unwrap().value(), Some("Entry content"));
+    assert_eq!(entry.content().unwrap().content_type(), None);
+
+    let rss = r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"><channel>
+        <title>Channel Title</title>
+        <link>http://example.com/</link>
+        <description>Channel description</description>
+        <language>en-us</language>
+        <item>
+            <guid>urn:item:1</guid>
+            <title>Item Title</title>
+            <description>&lt;p&gt;Item description&lt;/p&gt;</description>
+            <pubDate>Sat, 03 Jun 2017 15:15:44 -0500</pubDate>
+        </item>
+    </channel></rss>"#;
+    let feed = Feed::from(feed_rs::parser::parse(rss.as_bytes()).unwrap());
+    assert_eq!(feed.title(), "Channel Title");
+    assert_eq!(feed.lang(), Some("en-us"));
+    assert_eq!(feed.alternate_link(), Some("http://example.com/"));
+
+    let xml = feed.to_string();
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.id(), "urn:item:1");
+    assert_eq!(entry.summary(), Some("<p>Item description</p>"));
+    assert_eq!(
+        entry.published().unwrap().to_rfc3339(),
+        "2017-06-03T20:15:44+00:00"
+    );
+}
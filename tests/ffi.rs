@@ -0,0 +1,71 @@
+#![cfg(feature = "ffi")]
+
+extern crate atom_syndication as atom;
+
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::atom::ffi::*;
+
+unsafe fn take(s: *mut c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+
+    let value = CStr::from_ptr(s).to_str().unwrap().to_string();
+    atom_string_free(s);
+    Some(value)
+}
+
+#[test]
+fn ffi_read_feed() {
+    let xml = CString::new(fs::read("tests/data/entry.xml").unwrap()).unwrap();
+
+    unsafe {
+        let feed = atom_feed_parse(xml.as_ptr(), ptr::null_mut());
+        assert!(!feed.is_null());
+        assert_eq!(atom_feed_entry_count(feed), 1);
+        assert!(atom_feed_entry(feed, 1).is_null());
+
+        let entry = atom_feed_entry(feed, 0);
+        assert_eq!(take(atom_entry_title(entry)), Some("Entry Title".into()));
+        assert_eq!(
+            take(atom_entry_id(entry)),
+            Some("http://example.com/article/1".into())
+        );
+        assert_eq!(
+            take(atom_entry_updated(entry)),
+            Some("2017-06-03T15:15:44-05:00".into())
+        );
+        assert_eq!(
+            take(atom_entry_summary(entry)),
+            Some("Entry summary".into())
+        );
+        assert_eq!(
+            take(atom_entry_content(entry)),
+            Some("Entry content".into())
+        );
+
+        let written = take(atom_feed_to_string(feed)).unwrap();
+        assert!(written.contains("<title>Entry Title</title>"));
+
+        atom_feed_free(feed);
+    }
+}
+
+#[test]
+fn ffi_read_error() {
+    let xml = CString::new("<rss></rss>").unwrap();
+
+    unsafe {
+        let mut error = ptr::null_mut();
+        let feed = atom_feed_parse(xml.as_ptr(), &mut error);
+        assert!(feed.is_null());
+        assert_eq!(
+            take(error),
+            Some("expected an Atom feed but found an RSS 2.0 feed".into())
+        );
+    }
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "fuzz")]
+
+extern crate arbitrary;
+extern crate atom_syndication as atom;
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::atom::Feed;
+
+/// Fill a buffer with pseudo-random bytes derived from `seed`.
+fn bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn arbitrary_round_trip() {
+    for seed in 0..256 {
+        let data = bytes(seed, 4096);
+        let feed = Feed::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        let xml = feed.to_string();
+        let read = xml.parse::<Feed>().unwrap();
+        assert_eq!(read, feed, "seed {}: {}", seed, xml);
+    }
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "http")]
+
+extern crate atom_syndication as atom;
+
+use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+
+use crate::atom::{Error, Feed, Severity};
+
+fn headers(content_type: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers
+}
+
+#[test]
+fn http_charset() {
+    let latin1 =
+        b"<?xml version=\"1.0\" encoding=\"iso-8859-1\"?><feed><title>Caf\xe9</title></feed>";
+
+    // the declaration is used without a charset
+    let (feed, stats) = Feed::from_http_parts(&headers("application/atom+xml"), latin1).unwrap();
+    assert_eq!(feed.title(), "Café");
+    assert!(stats.issues().is_empty());
+
+    let (feed, stats) = Feed::from_http_parts(&HeaderMap::new(), latin1).unwrap();
+    assert_eq!(feed.title(), "Café");
+    assert!(stats.issues().is_empty());
+
+    // a matching charset
+    let (feed, stats) =
+        Feed::from_http_parts(&headers("application/atom+xml; charset=latin1"), latin1).unwrap();
+    assert_eq!(feed.title(), "Café");
+    assert!(stats.issues().is_empty());
+
+    // the charset takes precedence over the declaration
+    let utf8 = "<?xml version=\"1.0\" encoding=\"iso-8859-1\"?><feed><title>Café</title></feed>";
+    let (feed, stats) =
+        Feed::from_http_parts(&headers("text/xml; charset=\"UTF-8\""), utf8.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Café");
+    assert_eq!(stats.issues().len(), 1);
+    assert_eq!(stats.issues()[0].severity(), Severity::Warning);
+    assert_eq!(
+        stats.issues()[0].message(),
+        "the Content-Type charset UTF-8 does not match the encoding iso-8859-1 declared by the \
+         document, so the charset is used"
+    );
+
+    // the byte order mark takes precedence over the charset
+    let bom = [
+        &b"\xef\xbb\xbf"[..],
+        "<feed><title>Café</title></feed>".as_bytes(),
+    ]
+    .concat();
+    let (feed, stats) =
+        Feed::from_http_parts(&headers("application/atom+xml; charset=iso-8859-1"), &bom).unwrap();
+    assert_eq!(feed.title(), "Café");
+    assert!(stats.issues().is_empty());
+
+    let (feed, stats) =
+        Feed::from_http_parts(&headers("application/atom+xml; charset=klingon"), latin1).unwrap();
+    assert_eq!(feed.title(), "Café");
+    assert_eq!(
+        stats.issues()[0].message(),
+        "unknown charset klingon in the Content-Type header"
+    );
+}
+
+#[test]
+fn http_content_type() {
+    let xml = b"<feed><title>Feed Title</title></feed>";
+    let (feed, stats) = Feed::from_http_parts(&headers("text/html"), xml).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(
+        stats.issues()[0].message(),
+        "the Content-Type text/html is not an XML media type"
+    );
+
+    let entry = br#"<entry xmlns="http://www.w3.org/2005/Atom">
+        <title>Entry Title</title>
+        <updated>2017-06-03T15:15:44-05:00</updated>
+    </entry>"#;
+    let (feed, stats) =
+        Feed::from_http_parts(&headers("application/atom+xml;type=entry"), entry).unwrap();
+    assert_eq!(feed.entries().len(), 1);
+    assert_eq!(feed.entries()[0].title(), "Entry Title");
+    assert_eq!(feed.updated(), feed.entries()[0].updated());
+    assert_eq!(stats.entries(), 1);
+
+    match Feed::from_http_parts(&headers("application/atom+xml;type=entry"), xml) {
+        Err(Error::WrongFormat { .. }) => {}
+        other => panic!("expected wrong format error, got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "generate-ids")]
+
+extern crate atom_syndication as atom;
+
+use crate::atom::Feed;
+
+#[test]
+fn ids_fill_missing() {
+    let xml = r#"<feed>
+        <entry><id>urn:entry:1</id><link href="http://example.com/1"/></entry>
+        <entry><id> </id><link rel="enclosure" href="http://example.com/a.mp3"/><link href="http://example.com/2"/></entry>
+        <entry><title>Untitled</title><published>2017-06-03T15:15:44-05:00</published></entry>
+        <entry><title>Untitled</title><published>2017-06-03T20:15:44Z</published></entry>
+        <entry><title>Untitled</title><published>2017-06-04T20:15:44Z</published></entry>
+        <entry><content>Nothing to go on</content></entry>
+    </feed>"#;
+
+    let mut feed = xml.parse::<Feed>().unwrap();
+    feed.index_entries();
+    assert_eq!(feed.fill_missing_ids(), 4);
+
+    let ids = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids[0], "urn:entry:1");
+    assert_eq!(ids[1], "urn:uuid:b01a4032-be65-5203-933d-66e573f230b4");
+    assert!(ids[2].starts_with("urn:uuid:"));
+    assert_eq!(ids[2], ids[3]);
+    assert_ne!(ids[2], ids[4]);
+    assert_eq!(ids[5], "");
+    assert!(feed.entry(ids[1]).is_some());
+
+    let mut again = xml.parse::<Feed>().unwrap();
+    again.fill_missing_ids();
+    assert_eq!(again, feed);
+    assert_eq!(feed.fill_missing_ids(), 0);
+}
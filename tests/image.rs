@@ -0,0 +1,63 @@
+#![cfg(feature = "lead-image")]
+
+extern crate atom_syndication as atom;
+
+use crate::atom::{Content, Entry, Feed};
+
+fn entry(content: &str) -> Entry {
+    Entry::default().with_content(Content::html(content))
+}
+
+#[test]
+fn lead_image_content() {
+    assert_eq!(entry("<p>No images</p>").lead_image_url(), None);
+    assert_eq!(
+        entry(r#"<IMG SRC="http://example.com/a.png"><img src="http://example.com/b.png">"#)
+            .lead_image_url()
+            .as_deref(),
+        Some("http://example.com/a.png")
+    );
+    assert_eq!(
+        entry(r#"<imgur/><img width='1' src='http://example.com/pixel.gif'/><img data-x="a>b" src=http://example.com/c.png?a=1&amp;b=2>"#)
+            .lead_image_url()
+            .as_deref(),
+        Some("http://example.com/c.png?a=1&b=2")
+    );
+    assert_eq!(entry(r#"<img alt="empty" src="">"#).lead_image_url(), None);
+
+    // text content is not scanned, but an HTML summary is
+    let mut entry = Entry::default().with_content(Content::text("<img src=\"a.png\">"));
+    assert_eq!(entry.lead_image_url(), None);
+    entry.set_summary(r#"<img src="http://example.com/summary.png">"#.to_string());
+    assert_eq!(
+        entry.lead_image_url().as_deref(),
+        Some("http://example.com/summary.png")
+    );
+}
+
+#[test]
+fn lead_image_thumbnail() {
+    let xml = r#"<feed xmlns:media="http://search.yahoo.com/mrss/" xml:base="http://example.com/">
+        <entry xml:base="http://example.com/posts/">
+            <content type="html">&lt;img src="inline.png"&gt;</content>
+            <media:group>
+                <media:content url="http://example.com/video.mp4">
+                    <media:thumbnail url="thumb.jpg" />
+                </media:content>
+            </media:group>
+        </entry>
+        <entry xml:base="http://example.com/posts/">
+            <content type="html">&lt;img src="inline.png"&gt;</content>
+        </entry>
+    </feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+
+    assert_eq!(
+        feed.entries()[0].lead_image_url().as_deref(),
+        Some("http://example.com/posts/thumb.jpg")
+    );
+    assert_eq!(
+        feed.entries()[1].lead_image_url().as_deref(),
+        Some("http://example.com/posts/inline.png")
+    );
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "license")]
+
+extern crate atom_syndication as atom;
+
+use std::fs;
+
+use crate::atom::Feed;
+
+fn read() -> Feed {
+    Feed::read_from(&fs::read("tests/data/license.xml").unwrap()[..]).unwrap()
+}
+
+#[test]
+fn license_read() {
+    let feed = read();
+    assert_eq!(
+        feed.licenses(),
+        vec!["http://creativecommons.org/licenses/by/4.0/"]
+    );
+
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.license(),
+        Some("http://creativecommons.org/licenses/by-nc/4.0/")
+    );
+
+    let entry = &feed.entries()[1];
+    assert_eq!(
+        entry.licenses(),
+        vec!["http://creativecommons.org/licenses/by-sa/4.0/"]
+    );
+}
+
+#[test]
+fn license_write() {
+    let mut feed = read();
+    feed.set_license(None);
+    feed.entries_mut()[0].set_licenses(vec![
+        "http://creativecommons.org/licenses/by/4.0/",
+        "http://www.gnu.org/licenses/gpl-3.0.html",
+    ]);
+
+    let feed = feed.to_string().parse::<Feed>().unwrap();
+    assert_eq!(feed.license(), None);
+
+    let entry = &feed.entries()[0];
+    assert!(entry.extensions().get("cc").is_none());
+    assert_eq!(
+        entry.licenses(),
+        vec![
+            "http://creativecommons.org/licenses/by/4.0/",
+            "http://www.gnu.org/licenses/gpl-3.0.html",
+        ]
+    );
+    assert!(entry.links().iter().all(|link| link.rel() == "license"));
+}
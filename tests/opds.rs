@@ -0,0 +1,52 @@
+#![cfg(feature = "opds")]
+
+extern crate atom_syndication as atom;
+
+use std::fs;
+
+use crate::atom::opds::{self, CatalogKind, Price};
+use crate::atom::Feed;
+
+fn read() -> Feed {
+    Feed::read_from(&fs::read("tests/data/opds.xml").unwrap()[..]).unwrap()
+}
+
+#[test]
+fn opds_catalog() {
+    let feed = read();
+    assert_eq!(feed.catalog_kind(), Some(CatalogKind::Acquisition));
+    assert_eq!(
+        feed.links()[1].catalog_kind(),
+        Some(CatalogKind::Navigation)
+    );
+
+    let facets = feed.facets().collect::<Vec<_>>();
+    assert_eq!(facets.len(), 2);
+    assert_eq!(facets[0].facet_group(), Some("Language"));
+    assert!(facets[0].is_active_facet());
+    assert_eq!(facets[0].facet_count(), Some(12));
+    assert!(!facets[1].is_active_facet());
+    assert_eq!(facets[1].facet_count(), Some(3));
+
+    let entry = &feed.entries()[0];
+    let links = entry.acquisition_links().collect::<Vec<_>>();
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].rel(), opds::ACQUISITION_BUY);
+    assert_eq!(
+        links[0].prices(),
+        vec![Price::new("9.99", "USD"), Price::new("8.99", "EUR")]
+    );
+    assert!(links[1].prices().is_empty());
+}
+
+#[test]
+fn opds_write() {
+    let feed = read();
+    let xml = feed.to_string();
+    assert!(xml.contains(r#"<opds:price currencycode="USD">9.99</opds:price>"#));
+    assert!(xml.contains(r#"opds:facetGroup="Language""#));
+
+    let written = xml.parse::<Feed>().unwrap();
+    assert_eq!(written.entries()[0].links(), feed.entries()[0].links());
+    assert_eq!(written.links(), feed.links());
+}
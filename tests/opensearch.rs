@@ -0,0 +1,79 @@
+#![cfg(feature = "opensearch")]
+
+extern crate atom_syndication as atom;
+
+use crate::atom::opensearch::{Query, NAMESPACE};
+use crate::atom::{Error, Feed};
+
+#[test]
+fn opensearch_read() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:os="http://a9.com/-/spec/opensearch/1.1/">
+        <title>Example.com Search: New York history</title>
+        <os:totalResults>4230000</os:totalResults>
+        <os:startIndex>21</os:startIndex>
+        <os:itemsPerPage>10</os:itemsPerPage>
+        <os:Query role="request" searchTerms="New York History" startPage="3" />
+        <os:Query role="related" searchTerms="New York City" totalResults="many" title="Related" />
+    </feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+
+    assert_eq!(feed.total_results().unwrap(), Some(4230000));
+    assert_eq!(feed.start_index().unwrap(), Some(21));
+    assert_eq!(feed.items_per_page().unwrap(), Some(10));
+
+    let queries = feed.queries();
+    assert_eq!(queries.len(), 2);
+    assert_eq!(
+        queries[0],
+        Query::new("request")
+            .with_search_terms("New York History")
+            .with_start_page(3)
+    );
+    assert_eq!(queries[1].role(), "related");
+    assert_eq!(queries[1].title(), Some("Related"));
+    assert_eq!(queries[1].total_results(), None);
+
+    let feed = "<feed><opensearch:totalResults>lots</opensearch:totalResults></feed>"
+        .parse::<Feed>()
+        .unwrap();
+    assert_eq!(feed.total_results().unwrap(), None);
+
+    let xml = r#"<feed xmlns:opensearch="http://a9.com/-/spec/opensearch/1.1/">
+        <opensearch:totalResults>lots</opensearch:totalResults>
+    </feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+    assert!(
+        matches!(feed.total_results(), Err(Error::InvalidExtension(ref value)) if value == "lots")
+    );
+}
+
+#[test]
+fn opensearch_write() {
+    let mut feed = Feed::default();
+    feed.set_total_results(Some(42));
+    feed.set_items_per_page(Some(10));
+    feed.set_start_index(Some(1));
+    feed.set_queries(vec![Query::new("request")
+        .with_search_terms("cats & dogs")
+        .with_count(10)]);
+    assert_eq!(
+        feed.namespaces().get("opensearch").map(String::as_str),
+        Some(NAMESPACE)
+    );
+
+    let xml = feed.to_string();
+    assert!(xml.contains("<opensearch:totalResults>42</opensearch:totalResults>"));
+
+    let mut feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(feed.total_results().unwrap(), Some(42));
+    assert_eq!(feed.items_per_page().unwrap(), Some(10));
+    assert_eq!(feed.start_index().unwrap(), Some(1));
+    assert_eq!(feed.queries()[0].search_terms(), Some("cats & dogs"));
+    assert_eq!(feed.queries()[0].count(), Some(10));
+
+    feed.set_total_results(None);
+    feed.set_queries(Vec::new());
+    assert_eq!(feed.total_results().unwrap(), None);
+    assert!(feed.queries().is_empty());
+    assert!(!feed.to_string().contains("totalResults"));
+}
@@ -1,10 +1,15 @@
 extern crate atom_syndication as atom;
 
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::fs::File;
 use std::io::BufReader;
 
-use crate::atom::extension::ExtensionMap;
-use crate::atom::Feed;
+use crate::atom::extension::{EntryExt, Extension, ExtensionMap, ExtensionValue};
+use crate::atom::{
+    DetectedFormat, Entry, Error, Feed, FeedParser, LanguageTag, LimitedReader, LinkPolicy,
+    ReadConfig, Severity,
+};
 
 macro_rules! feed {
     ($f:expr) => {{
@@ -31,6 +36,37 @@ fn read_feed() {
     assert_eq!(feed.links().len(), 2);
 }
 
+#[test]
+fn read_metadata() {
+    let mut xml = String::from(
+        r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/ext">
+            <title>Feed Title</title>
+            <updated>2017-06-03T15:15:44-05:00</updated>
+            <link rel="self" href="http://example.com/feed"/>
+            <ext:rank>1</ext:rank>"#,
+    );
+    xml.push_str(&"<entry><title>Entry</title></entry>".repeat(1000));
+    xml.push_str("<entry><updated>yesterday</updated></entry><subtitle>After</subtitle></feed>");
+
+    assert!(xml.parse::<Feed>().is_err());
+
+    let feed = Feed::read_metadata(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    assert_eq!(feed.links()[0].href(), "http://example.com/feed");
+    assert_eq!(feed.namespaces()["ext"], "http://example.com/ext");
+    assert_eq!(feed.extensions()["ext"]["rank"][0].value(), Some("1"));
+    assert!(feed.entries().is_empty());
+    assert_eq!(feed.subtitle(), None);
+
+    let config = ReadConfig::new().metadata_only(true);
+    let reader = BufReader::with_capacity(64, xml.as_bytes());
+    let (feed, stats) = Feed::read_with_stats(reader, &config).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(stats.entries(), 0);
+    assert!(stats.bytes() < 1000);
+}
+
 #[test]
 fn read_entry() {
     let feed = feed!("tests/data/entry.xml");
@@ -42,6 +78,10 @@ fn read_entry() {
     assert_eq!(entry.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
     assert_eq!(entry.authors().len(), 2);
     assert_eq!(entry.categories().len(), 2);
+    assert_eq!(
+        entry.category_terms().collect::<Vec<_>>(),
+        vec!["technology", "podcast"]
+    );
     assert_eq!(entry.contributors().len(), 2);
     assert_eq!(entry.links().len(), 2);
     assert_eq!(
@@ -79,10 +119,10 @@ fn read_link() {
     let link = feed.links().first().unwrap();
     assert_eq!(link.rel(), "enclosure");
     assert_eq!(link.href(), "http://example.com/audio.mp3");
-    assert_eq!(link.hreflang(), Some("en"));
+    assert_eq!(link.hreflang().map(LanguageTag::as_str), Some("en"));
     assert_eq!(link.mime_type(), Some("audio/mpeg"));
     assert_eq!(link.title(), Some("audio"));
-    assert_eq!(link.length(), Some("1000"));
+    assert_eq!(link.length(), Some(1000));
 }
 
 #[test]
@@ -113,6 +153,39 @@ fn read_source() {
     assert_eq!(source.categories().len(), 2);
     assert_eq!(source.contributors().len(), 2);
     assert!(source.generator().is_some());
+    assert_eq!(source.links().len(), 2);
+    assert_eq!(
+        source
+            .extensions()
+            .get("ext")
+            .and_then(|map| map.get("title"))
+            .and_then(|items| items.first())
+            .and_then(|ext| ext.value()),
+        Some("Source Extension")
+    );
+}
+
+#[test]
+fn read_prefixed() {
+    let feed = feed!("tests/data/prefixed.xml");
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.id(), "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    assert_eq!(feed.authors()[0].name(), "John Doe");
+    assert!(feed.extensions()["ext"].contains_key("title"));
+    assert!(!feed.extensions().contains_key("atom"));
+
+    let entry = feed.entries().first().unwrap();
+    assert_eq!(entry.title(), "Entry Title");
+    assert_eq!(entry.id(), "http://example.com/article/1");
+    assert_eq!(
+        entry.source().map(|source| source.title()),
+        Some("Source Title")
+    );
+    assert_eq!(
+        entry.content().and_then(|content| content.value()),
+        Some("<p>Entry content</p>")
+    );
 }
 
 #[test]
@@ -145,3 +218,1097 @@ fn read_extension() {
     check_extensions(feed.extensions());
     check_extensions(entry.extensions());
 }
+
+#[test]
+fn read_extension_typed() {
+    struct Parent {
+        child: String,
+    }
+
+    impl ExtensionValue for Parent {
+        fn from_extension(extension: &Extension) -> Result<Self, Error> {
+            let child = extension
+                .children()
+                .get("child")
+                .and_then(|children| children.first())
+                .ok_or_else(|| Error::InvalidExtension("missing child".to_string()))?;
+            Ok(Parent {
+                child: String::from_extension(child)?,
+            })
+        }
+
+        fn to_extension(&self) -> Extension {
+            let mut child = self.child.to_extension();
+            child.set_name("ext:child");
+
+            let mut extension = Extension::default();
+            extension
+                .children_mut()
+                .insert("child".to_string(), vec![child]);
+            extension
+        }
+    }
+
+    let feed = feed!("tests/data/extension.xml");
+    let mut entry = feed.entries().first().unwrap().clone();
+
+    let title = entry.extension::<String>("ext", "title").unwrap();
+    assert_eq!(title.as_deref(), Some("Title"));
+    let parent = entry.extension::<Parent>("ext", "parent").unwrap().unwrap();
+    assert_eq!(parent.child, "Child");
+    assert!(entry.extension::<u32>("ext", "missing").unwrap().is_none());
+    assert!(entry.extension::<u32>("ext", "title").is_err());
+
+    entry.set_extension(
+        "ext",
+        "parent",
+        &Parent {
+            child: "Updated".to_string(),
+        },
+    );
+    entry.push_extension("ext", "count", &1u32);
+    entry.push_extension("ext", "count", &2u32);
+
+    let parent = entry.extension::<Parent>("ext", "parent").unwrap().unwrap();
+    assert_eq!(parent.child, "Updated");
+    assert_eq!(
+        entry.extension_values::<u32>("ext", "count").unwrap(),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn read_enclosures() {
+    let feed = feed!("tests/data/entry.xml");
+    let entry = feed.entries().first().unwrap();
+
+    let enclosures = entry.enclosures().collect::<Vec<_>>();
+    assert_eq!(enclosures.len(), 1);
+    assert_eq!(enclosures[0].href(), "http://example.com/audio.mp3");
+    assert_eq!(enclosures[0].mime_type(), Some("audio/mpeg"));
+    assert_eq!(enclosures[0].length(), Some(1000));
+}
+
+#[test]
+fn read_feed_links() {
+    let feed = feed!("tests/data/feed.xml");
+    assert_eq!(feed.self_link(), Some("http://example.com/feed"));
+    assert_eq!(feed.alternate_link(), Some("http://example.com"));
+}
+
+#[test]
+fn read_attributes() {
+    let feed = feed!("tests/data/attributes.xml");
+    let attr = |attrs: &BTreeMap<String, String>, key: &str| attrs.get(key).cloned();
+
+    assert_eq!(feed.attrs().len(), 2);
+    assert_eq!(attr(feed.attrs(), "xml:lang"), Some("en".to_string()));
+    assert_eq!(
+        attr(feed.attrs(), "xml:base"),
+        Some("http://example.com/".to_string())
+    );
+    assert_eq!(
+        attr(feed.authors()[0].attrs(), "ext:role"),
+        Some("editor".to_string())
+    );
+    assert_eq!(
+        attr(feed.categories()[0].attrs(), "ext:weight"),
+        Some("2".to_string())
+    );
+    assert_eq!(
+        attr(feed.generator().unwrap().attrs(), "ext:build"),
+        Some("42".to_string())
+    );
+    assert_eq!(
+        attr(feed.links()[0].attrs(), "xml:id"),
+        Some("home".to_string())
+    );
+
+    let entry = feed.entries().first().unwrap();
+    assert_eq!(attr(entry.attrs(), "xml:id"), Some("entry-1".to_string()));
+    assert_eq!(attr(entry.attrs(), "ext:pinned"), Some("true".to_string()));
+    assert_eq!(
+        attr(entry.source().unwrap().attrs(), "ext:mirror"),
+        Some("yes".to_string())
+    );
+    assert_eq!(
+        attr(entry.content().unwrap().attrs(), "xml:lang"),
+        Some("fr".to_string())
+    );
+}
+
+#[test]
+fn read_fidelity() {
+    let feed = feed!("tests/data/fidelity.xml");
+    assert!(feed.unknown_elements().is_empty());
+    assert!(feed.entries()[0].unknown_elements().is_empty());
+
+    let file = File::open("tests/data/fidelity.xml").unwrap();
+    let config = ReadConfig::new().fidelity(true);
+    let feed = Feed::read_with_config(BufReader::new(file), &config).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.extensions()["ext"]["rating"][0].value(), Some("5"));
+
+    let rank = &feed.unknown_elements()[0];
+    assert_eq!(rank.name(), "rank");
+    assert_eq!(rank.value(), Some("1"));
+    assert_eq!(
+        rank.attrs().get("scheme").map(String::as_str),
+        Some("popularity")
+    );
+
+    let mood = &feed.entries()[0].unknown_elements()[0];
+    assert_eq!(mood.name(), "mood");
+    assert_eq!(mood.children()["value"][0].value(), Some("happy"));
+}
+
+#[test]
+fn read_link_invalid() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><link href="http://example.com" length="big" /></feed>"#;
+    match xml.parse::<Feed>().map_err(Error::without_context) {
+        Err(atom::Error::InvalidAttribute { name, value }) => {
+            assert_eq!(name, "length");
+            assert_eq!(value, "big");
+        }
+        other => panic!("expected invalid length, got {:?}", other),
+    }
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+        <link href="http://example.com/en" hreflang="en_US" length="" />
+        <link href="http://example.com/any" hreflang="" />
+    </feed>"#;
+    let (feed, stats) = Feed::read_with_stats(xml.as_bytes(), &ReadConfig::new()).unwrap();
+    assert!(feed.links().iter().all(|link| link.hreflang().is_none()));
+    assert_eq!(feed.links()[0].length(), None);
+
+    let issues = stats
+        .issues()
+        .iter()
+        .map(|issue| (issue.severity(), issue.message()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        vec![
+            (
+                Severity::Warning,
+                "hreflang is not a valid language tag: en_US"
+            ),
+            (Severity::Warning, "hreflang is not a valid language tag: "),
+        ]
+    );
+}
+
+#[test]
+fn read_from_file() {
+    let feed = Feed::read_from_file("tests/data/feed.xml").unwrap();
+    assert_eq!(feed, feed!("tests/data/feed.xml"));
+
+    match Feed::read_from_file("tests/data/missing.xml") {
+        Err(atom::Error::Io(_)) => {}
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn read_from_mmap() {
+    // Safety: the test data is not modified while the tests run.
+    let feed = unsafe { Feed::read_from_mmap("tests/data/feed.xml") }.unwrap();
+    assert_eq!(feed, feed!("tests/data/feed.xml"));
+
+    match unsafe { Feed::read_from_mmap("tests/data/missing.xml") } {
+        Err(atom::Error::Io(_)) => {}
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+}
+
+#[test]
+fn read_from_slice_encodings() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-16"?><feed><title>Feed Title</title></feed>"#;
+
+    let mut utf8 = vec![0xEF, 0xBB, 0xBF];
+    utf8.extend_from_slice(xml.as_bytes());
+    assert_eq!(Feed::read_from_slice(&utf8).unwrap().title(), "Feed Title");
+
+    let mut utf16be = vec![0xFE, 0xFF];
+    let mut utf16le = vec![0xFF, 0xFE];
+    for unit in xml.encode_utf16() {
+        utf16be.extend_from_slice(&unit.to_be_bytes());
+        utf16le.extend_from_slice(&unit.to_le_bytes());
+    }
+    assert_eq!(
+        Feed::read_from_slice(&utf16be).unwrap().title(),
+        "Feed Title"
+    );
+    assert_eq!(
+        Feed::read_from_slice(&utf16le).unwrap().title(),
+        "Feed Title"
+    );
+
+    let feed = Feed::read_from_str("\u{feff}<feed><title>Feed Title</title></feed>").unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+
+    for bytes in &[utf8, utf16be, utf16le] {
+        let feed = Feed::read_from(BufReader::new(bytes.as_slice())).unwrap();
+        assert_eq!(feed.title(), "Feed Title");
+    }
+}
+
+#[test]
+fn read_too_deep() {
+    let depth = 100_000;
+    let mut xml = String::from(r#"<feed xmlns="http://www.w3.org/2005/Atom"><ext:a>"#);
+    xml.push_str(&"<ext:a>".repeat(depth));
+    xml.push_str(&"</ext:a>".repeat(depth));
+    xml.push_str("</ext:a></feed>");
+
+    match xml.parse::<Feed>().map_err(Error::without_context) {
+        Err(atom::Error::TooDeep) => {}
+        other => panic!("expected too deep error, got {:?}", other.map(|_| ())),
+    }
+
+    let config = ReadConfig::new().max_depth(2);
+    let xml = r#"<feed><ext:a><ext:b><ext:c/></ext:b></ext:a></feed>"#;
+    assert!(Feed::read_with_config(xml.as_bytes(), &config).is_ok());
+    let xml = r#"<feed><ext:a><ext:b><ext:c><ext:d/></ext:c></ext:b></ext:a></feed>"#;
+    match Feed::read_with_config(xml.as_bytes(), &config).map_err(Error::without_context) {
+        Err(atom::Error::TooDeep) => {}
+        other => panic!("expected too deep error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn read_too_large() {
+    let xml = r#"<feed><title>Feed Title</title><entry><id>urn:entry:1</id></entry></feed>"#;
+    let len = xml.len() as u64;
+
+    let config = ReadConfig::new().max_bytes(len);
+    assert!(Feed::read_with_config(xml.as_bytes(), &config).is_ok());
+    let reader = BufReader::with_capacity(1, LimitedReader::new(xml.as_bytes(), len));
+    assert!(Feed::read_from(reader).is_ok());
+
+    let config = ReadConfig::new().max_bytes(len - 1);
+    match Feed::read_with_config(xml.as_bytes(), &config).map_err(Error::without_context) {
+        Err(atom::Error::TooLarge { limit }) => assert_eq!(limit, len - 1),
+        other => panic!("expected too large error, got {:?}", other.map(|_| ())),
+    }
+
+    let reader = BufReader::new(LimitedReader::new(xml.as_bytes(), 10));
+    match Feed::read_from(reader).map_err(Error::without_context) {
+        Err(atom::Error::TooLarge { limit }) => assert_eq!(limit, 10),
+        other => panic!("expected too large error, got {:?}", other.map(|_| ())),
+    }
+
+    let mut parser = FeedParser::with_config(&ReadConfig::new().max_bytes(40));
+    assert!(parser.feed_bytes(&xml.as_bytes()[..40]).is_ok());
+    match parser.feed_bytes(&xml.as_bytes()[40..]) {
+        Err(atom::Error::TooLarge { limit }) => assert_eq!(limit, 40),
+        other => panic!("expected too large error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn read_link_policy() {
+    let xml = r#"<feed>
+        <link href="javascript:alert(1)"/>
+        <link href="https://example.com/"/>
+        <entry>
+            <link href=" JaVa&#9;Script:alert(1)"/>
+            <link href="relative/page"/>
+            <link href="ftp://example.com/file"/>
+            <source><link href="data:text/html,hi"/><link href="mailto:a@example.com"/></source>
+            <content src="vbscript:msgbox(1)"/>
+        </entry>
+    </feed>"#;
+
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(
+        feed.link_policy_violations(&LinkPolicy::default()),
+        vec![
+            "javascript:alert(1)",
+            " JaVa\tScript:alert(1)",
+            "ftp://example.com/file",
+            "data:text/html,hi",
+            "vbscript:msgbox(1)",
+        ]
+    );
+
+    let config = ReadConfig::new().link_policy(LinkPolicy::new().allow_scheme("FTP"));
+    let feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    assert_eq!(feed.links().len(), 1);
+    let entry = &feed.entries()[0];
+    let hrefs = entry
+        .links()
+        .iter()
+        .map(|link| link.href())
+        .collect::<Vec<_>>();
+    assert_eq!(hrefs, vec!["relative/page", "ftp://example.com/file"]);
+    assert_eq!(
+        entry.source().unwrap().links()[0].href(),
+        "mailto:a@example.com"
+    );
+    assert_eq!(entry.content().unwrap().src(), None);
+    assert_eq!(
+        feed.link_policy_violations(&LinkPolicy::relative_only()),
+        vec![
+            "https://example.com/",
+            "ftp://example.com/file",
+            "mailto:a@example.com",
+        ]
+    );
+}
+#[test]
+fn read_entry_index() {
+    let mut feed = Feed::default();
+    for i in 0..4 {
+        feed.push_entry(Entry::default().with_id(format!("urn:uuid:{}", i)));
+    }
+
+    feed.index_entries();
+    assert_eq!(feed.entry("urn:uuid:2").map(Entry::id), Some("urn:uuid:2"));
+
+    feed.push_entry(Entry::default().with_id("urn:uuid:4"));
+    assert!(feed.entry("urn:uuid:4").is_some());
+
+    feed.entry_mut("urn:uuid:1").unwrap().set_id("urn:uuid:5");
+    assert!(feed.entry("urn:uuid:1").is_none());
+    assert_eq!(feed.entry("urn:uuid:5").map(Entry::id), Some("urn:uuid:5"));
+
+    feed.entry_mut("urn:uuid:5").unwrap().set_id("urn:uuid:0");
+    assert!(feed.entry("urn:uuid:5").is_none());
+    assert!(std::ptr::eq(
+        feed.entry("urn:uuid:0").unwrap(),
+        &feed.entries()[0]
+    ));
+
+    assert!(feed.remove_entry("urn:uuid:0").is_some());
+    assert_eq!(feed.entries().len(), 4);
+    assert!(std::ptr::eq(
+        feed.entry("urn:uuid:0").unwrap(),
+        &feed.entries()[0]
+    ));
+    assert_eq!(feed.entry("urn:uuid:3").map(Entry::id), Some("urn:uuid:3"));
+
+    feed.entries_mut().reverse();
+    assert!(std::ptr::eq(
+        feed.entry("urn:uuid:3").unwrap(),
+        &feed.entries()[1]
+    ));
+    assert!(feed.entry_mut("urn:uuid:4").is_some());
+
+    feed.extend(vec![
+        Entry::default().with_id("urn:uuid:6"),
+        Entry::default().with_id("urn:uuid:7"),
+    ]);
+    assert_eq!(feed.entry("urn:uuid:6").map(Entry::id), Some("urn:uuid:6"));
+    assert!(feed.entry_mut("urn:uuid:7").is_some());
+    assert!(feed.remove_entry("urn:uuid:7").is_some());
+    assert!(feed.entry("urn:uuid:7").is_none());
+}
+
+#[test]
+fn read_entry_offsets() {
+    let xml = concat!(
+        "<?xml version=\"1.0\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+        "  <title>Feed</title>\n",
+        "  <entry><id>urn:entry:1</id><title>Caf\u{e9}</title></entry>\n",
+        "  <!-- comment --><entry xml:lang=\"en\"/>",
+        "<entry><id>urn:entry:3</id></entry>\n",
+        "</feed>\n"
+    );
+    let expected = vec![
+        "<entry><id>urn:entry:1</id><title>Caf\u{e9}</title></entry>",
+        "<entry xml:lang=\"en\"/>",
+        "<entry><id>urn:entry:3</id></entry>",
+    ];
+
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(feed.entries()[0].byte_range(), None);
+
+    let config = ReadConfig::new().entry_offsets(true);
+    let feed =
+        Feed::read_with_config(BufReader::with_capacity(7, xml.as_bytes()), &config).unwrap();
+    let ranges = feed
+        .entries()
+        .iter()
+        .map(|entry| &xml[entry.byte_range().unwrap()])
+        .collect::<Vec<_>>();
+    assert_eq!(ranges, expected);
+
+    let mut parser = FeedParser::with_config(&config);
+    let mut entries = Vec::new();
+    for chunk in xml.as_bytes().chunks(5) {
+        entries.extend(parser.feed_bytes(chunk).unwrap());
+    }
+    let ranges = entries
+        .iter()
+        .map(|entry| &xml[entry.byte_range().unwrap()])
+        .collect::<Vec<_>>();
+    assert_eq!(ranges, expected);
+
+    let bom = [
+        &b"\xef\xbb\xbf"[..],
+        xml.replace("?>", " encoding=\"utf-8\"?>").as_bytes(),
+    ]
+    .concat();
+    let feed = Feed::read_with_config(&bom[..], &config).unwrap();
+    let ranges = feed
+        .entries()
+        .iter()
+        .map(|entry| &bom[entry.byte_range().unwrap()])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ranges,
+        expected.iter().map(|e| e.as_bytes()).collect::<Vec<_>>()
+    );
+
+    let mut utf16 = vec![0xFF, 0xFE];
+    utf16.extend(xml.encode_utf16().flat_map(u16::to_le_bytes));
+    assert!(matches!(
+        Feed::read_with_config(&utf16[..], &config),
+        Err(Error::OffsetsUnsupported)
+    ));
+    assert!(Feed::read_from(&utf16[..]).is_ok());
+}
+
+#[test]
+fn read_interned() {
+    let xml = concat!(
+        "<feed xmlns=\"http://www.w3.org/2005/Atom\">",
+        "<entry><author><name>John Doe</name></author><category term=\"rust\"/></entry>",
+        "<entry><author><name>John Doe</name></author><category term=\"rust\"/></entry>",
+        "</feed>"
+    );
+    let names = |feed: &Feed| {
+        feed.entries()
+            .iter()
+            .map(|entry| entry.authors()[0].name().as_ptr())
+            .collect::<Vec<_>>()
+    };
+
+    let feed = xml.parse::<Feed>().unwrap();
+    let eager = names(&feed);
+    assert_ne!(eager[0], eager[1]);
+
+    let config = ReadConfig::new().intern(true);
+    let mut feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    let shared = names(&feed);
+    assert_eq!(shared[0], shared[1]);
+    let terms = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.categories()[0].term().as_ptr())
+        .collect::<Vec<_>>();
+    assert_eq!(terms[0], terms[1]);
+    assert_eq!(feed, xml.parse::<Feed>().unwrap());
+
+    feed.normalize();
+    assert_eq!(names(&feed), shared);
+
+    feed.entries_mut()[0].authors_mut()[0]
+        .name_mut()
+        .push_str(" Jr.");
+    assert_eq!(feed.entries()[0].authors()[0].name(), "John Doe Jr.");
+    assert_eq!(feed.entries()[1].authors()[0].name(), "John Doe");
+}
+
+#[test]
+fn read_fingerprint() {
+    let entry = |updated: &str, content: &str| {
+        let xml = format!(
+            "<feed><entry><id>urn:entry:1</id><title>Title</title><updated>{}</updated>{}</entry></feed>",
+            updated, content
+        );
+        xml.parse::<Feed>().unwrap().entries()[0].clone()
+    };
+
+    let original = entry("2017-06-03T15:15:44-05:00", "<content>Hello</content>");
+    assert_eq!(original.fingerprint(), 18025321347919838245);
+    assert_eq!(
+        original.fingerprint(),
+        entry(
+            "2017-06-03T20:15:44Z",
+            "<content type=\"text\">Hello</content>"
+        )
+        .fingerprint()
+    );
+    assert_ne!(
+        original.fingerprint(),
+        entry("2017-06-03T15:15:45-05:00", "<content>Hello</content>").fingerprint()
+    );
+    assert_ne!(
+        original.fingerprint(),
+        entry("2017-06-03T15:15:44-05:00", "<content>Hello!</content>").fingerprint()
+    );
+    assert_ne!(
+        original.fingerprint(),
+        entry("2017-06-03T15:15:44-05:00", "").fingerprint()
+    );
+    assert_eq!(
+        original.fingerprint(),
+        original
+            .clone()
+            .with_summary("Summary".to_string())
+            .fingerprint()
+    );
+}
+
+#[test]
+fn read_fill_missing_updated() {
+    let xml = r#"<feed>
+        <entry><updated>2017-06-01T00:00:00Z</updated><published>2017-05-01T00:00:00Z</published></entry>
+        <entry><published>2017-06-02T00:00:00Z</published></entry>
+        <entry></entry>
+        <updated>2017-06-03T00:00:00Z</updated>
+    </feed>"#;
+    let updated = |feed: &Feed| {
+        feed.entries()
+            .iter()
+            .map(|entry| entry.updated().to_rfc3339())
+            .collect::<Vec<_>>()
+    };
+
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(
+        updated(&feed),
+        vec![
+            "2017-06-01T00:00:00+00:00",
+            "1970-01-01T00:00:00+00:00",
+            "1970-01-01T00:00:00+00:00",
+        ]
+    );
+
+    let config = ReadConfig::new().fill_missing_updated(true);
+    let mut feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    assert_eq!(
+        updated(&feed),
+        vec![
+            "2017-06-01T00:00:00+00:00",
+            "2017-06-02T00:00:00+00:00",
+            "2017-06-03T00:00:00+00:00",
+        ]
+    );
+    assert_eq!(feed.fill_missing_updated(), 0);
+
+    let mut feed = "<feed><entry></entry></feed>".parse::<Feed>().unwrap();
+    assert_eq!(feed.fill_missing_updated(), 0);
+}
+
+#[test]
+fn read_wrong_format() {
+    let detect = |xml: &str| match xml.parse::<Feed>() {
+        Err(Error::WrongFormat { detected }) => detected,
+        other => panic!("expected wrong format error, got {:?}", other.map(|_| ())),
+    };
+
+    assert_eq!(
+        detect(r#"<?xml version="1.0"?><rss version="2.0"><channel></channel></rss>"#),
+        DetectedFormat::Rss2
+    );
+    assert_eq!(
+        detect(r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"></rdf:RDF>"#),
+        DetectedFormat::Rss1
+    );
+    assert_eq!(
+        detect("<!doctype html>\n<html><head><meta charset=\"utf-8\"></head></html>"),
+        DetectedFormat::Html
+    );
+    assert_eq!(detect("<HTML><body></body></HTML>"), DetectedFormat::Html);
+    assert_eq!(detect("<opml></opml>"), DetectedFormat::Unknown);
+
+    let err = "<rss></rss>".parse::<Feed>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected an Atom feed but found an RSS 2.0 feed"
+    );
+}
+
+#[test]
+fn read_custom_element() {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    use crate::atom::fromxml::{FromXml, ReadContext};
+
+    let mut reader = Reader::from_str(r#"<ext:rating scheme="urn:simple">adult</ext:rating>"#);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    let mut ctx = ReadContext::new(ReadConfig::default());
+
+    let extension = match reader.read_event(&mut buf).unwrap() {
+        Event::Start(element) => {
+            Extension::from_xml(&mut reader, element.attributes(), &mut ctx).unwrap()
+        }
+        _ => panic!("expected a start tag"),
+    };
+
+    assert_eq!(extension.name(), "ext:rating");
+    assert_eq!(extension.value(), Some("adult"));
+    assert_eq!(
+        extension.attrs().get("scheme").map(String::as_str),
+        Some("urn:simple")
+    );
+}
+
+#[test]
+fn read_text_markup() {
+    let feed = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+        <title type="html">Fish &amp;amp; &lt;em&gt;Chips&lt;/em&gt;</title>
+        <subtitle><![CDATA[Salt & <b>vinegar</b>]]></subtitle>
+        <entry>
+            <title>An <b>improperly</b> typed <i>title</i></title>
+            <summary type="html">a &amp;nbsp; b</summary>
+        </entry>
+    </feed>"#
+        .parse::<Feed>()
+        .unwrap();
+
+    assert_eq!(feed.title(), "Fish &amp; <em>Chips</em>");
+    assert_eq!(feed.subtitle(), Some("Salt & <b>vinegar</b>"));
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.title(), "An <b>improperly</b> typed <i>title</i>");
+    assert_eq!(entry.summary(), Some("a &nbsp; b"));
+}
+
+#[test]
+fn read_errors() {
+    match r#"<?xml version="1.0"?>"#.parse::<Feed>() {
+        Err(Error::MissingElement { name, path }) => {
+            assert_eq!(name, "feed");
+            assert_eq!(path, "/");
+        }
+        other => panic!("expected missing element, got {:?}", other.map(|_| ())),
+    }
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><updated>yesterday</updated></feed>"#;
+    match xml.parse::<Feed>().map_err(Error::without_context) {
+        Err(Error::InvalidDate { value }) => assert_eq!(value, "yesterday"),
+        other => panic!("expected invalid date, got {:?}", other.map(|_| ())),
+    }
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Truncated"#;
+    match xml.parse::<Feed>().map_err(Error::without_context) {
+        Err(Error::Eof) => {}
+        other => panic!("expected end of input, got {:?}", other.map(|_| ())),
+    }
+
+    match "<feed><title></feed>"
+        .parse::<Feed>()
+        .map_err(Error::without_context)
+    {
+        Err(Error::Xml(_)) => {}
+        other => panic!("expected XML error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn read_error_context() {
+    use std::error::Error as _;
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+        <entry><id>1</id></entry>
+        <entry><id>2</id><updated>yesterday</updated></entry>
+    </feed>"#;
+    let err = xml.parse::<Feed>().unwrap_err();
+    assert_eq!(err.path(), Some("/feed/entry[1]/updated"));
+    assert_eq!(err.entry_index(), Some(1));
+    assert_eq!(
+        err.to_string(),
+        "dates must be formatted as described by RFC 3339, rather than yesterday at /feed/entry[1]/updated"
+    );
+    assert!(err.source().is_some());
+
+    let err = "<feed><entry><title></entry></feed>"
+        .parse::<Feed>()
+        .unwrap_err();
+    assert_eq!(err.path(), Some("/feed/entry[0]/title"));
+    let source = err.source().unwrap();
+    assert!(source.source().unwrap().is::<quick_xml::Error>());
+}
+
+#[test]
+fn read_inherited_lang_base() {
+    let xml = r##"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="en" xml:base="http://a/b/c/d;p?q">
+        <entry></entry>
+        <entry xml:lang="fr" xml:base="g"></entry>
+        <entry xml:base="../g?y#s"></entry>
+        <entry xml:base="/./g"></entry>
+        <entry xml:base="//g"></entry>
+        <entry xml:base="#s"></entry>
+        <entry xml:base="https://example.com/"></entry>
+    </feed>"##;
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(feed.lang(), Some("en"));
+    assert_eq!(feed.base(), Some("http://a/b/c/d;p?q"));
+
+    let langs = feed
+        .entries()
+        .iter()
+        .map(|entry| feed.entry_lang(entry))
+        .collect::<Vec<_>>();
+    assert_eq!(langs[..2], [Some("en"), Some("fr")]);
+
+    let bases = feed
+        .entries()
+        .iter()
+        .map(|entry| feed.entry_base(entry).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bases,
+        vec![
+            "http://a/b/c/d;p?q",
+            "http://a/b/c/g",
+            "http://a/b/g?y#s",
+            "http://a/g",
+            "http://g",
+            "http://a/b/c/d;p?q#s",
+            "https://example.com/",
+        ]
+    );
+
+    let written = feed.to_string();
+    assert!(written.contains(r#" xml:base="http://a/b/c/d;p?q" xml:lang="en">"#));
+}
+
+#[test]
+fn read_feed_parser() {
+    for file in &[
+        "tests/data/feed.xml",
+        "tests/data/prefixed.xml",
+        "tests/data/entry.xml",
+    ] {
+        let xml = std::fs::read(file).unwrap();
+        let feed = Feed::read_from(&xml[..]).unwrap();
+
+        for &size in &[1, 5, xml.len()] {
+            let mut parser = FeedParser::new();
+            let mut entries = Vec::new();
+            for chunk in xml.chunks(size) {
+                entries.extend(parser.feed_bytes(chunk).unwrap());
+            }
+            parser.finish().unwrap();
+            assert_eq!(entries, feed.entries());
+        }
+    }
+
+    let mut parser = FeedParser::new();
+    let first = parser
+        .feed_bytes(b"<feed><entry><id>urn:entry:1</id></entry><entry><id>urn")
+        .unwrap();
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].id(), "urn:entry:1");
+    let err = parser
+        .feed_bytes(b":entry:2</id><updated>now</updated></entry>")
+        .unwrap_err();
+    assert_eq!(err.path(), Some("/feed/entry[1]/updated"));
+    assert_eq!(err.entry_index(), Some(1));
+
+    let mut parser = FeedParser::new();
+    let err = parser.feed_bytes(b"<html><body>").unwrap_err();
+    assert!(matches!(
+        err,
+        Error::WrongFormat {
+            detected: DetectedFormat::Html
+        }
+    ));
+}
+
+#[test]
+fn read_stats() {
+    let text = std::fs::read_to_string("tests/data/fidelity.xml").unwrap();
+    let xml = text.as_bytes();
+
+    let (feed, stats) = Feed::read_with_stats(xml, &ReadConfig::default()).unwrap();
+    assert_eq!(feed, Feed::read_from(xml).unwrap());
+    // reading stops at the end of the feed element
+    assert_eq!(stats.bytes(), text.trim_end().len());
+    assert_eq!(stats.entries(), 1);
+    assert_eq!(
+        stats.skipped_elements().iter().collect::<Vec<_>>(),
+        vec![(&"mood".to_string(), &1), (&"rank".to_string(), &1)]
+    );
+
+    let config = ReadConfig::new().fidelity(true);
+    let (_, stats) = Feed::read_with_stats(xml, &config).unwrap();
+    assert!(stats.skipped_elements().is_empty());
+}
+
+#[test]
+fn read_lenient_dates() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+        <updated>2017-06-03T15:15:44-05:00</updated>
+        <entry>
+            <updated>last week</updated>
+            <published>2017-06-03 15:15:44</published>
+        </entry>
+        <entry>
+            <updated>2017-06-03T15:15:44-05:00</updated>
+            <source><updated>never</updated></source>
+        </entry>
+    </feed>"#;
+
+    match xml.parse::<Feed>().map_err(Error::without_context) {
+        Err(Error::InvalidDate { value }) => assert_eq!(value, "last week"),
+        other => panic!("expected invalid date, got {:?}", other.map(|_| ())),
+    }
+
+    let config = ReadConfig::new().lenient_dates(true);
+    let (feed, stats) = Feed::read_with_stats(xml.as_bytes(), &config).unwrap();
+    assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    assert_eq!(feed.entries()[0].updated().timestamp(), 0);
+    assert_eq!(feed.entries()[0].published(), None);
+    assert_eq!(
+        feed.entries()[1].updated().to_rfc3339(),
+        "2017-06-03T15:15:44-05:00"
+    );
+    assert_eq!(feed.entries()[1].source().unwrap().updated().timestamp(), 0);
+
+    let issues = stats
+        .issues()
+        .iter()
+        .map(|issue| (issue.severity(), issue.location(), issue.message()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        vec![
+            (
+                Severity::Warning,
+                "/feed/entry[0]/updated",
+                "updated date is not formatted as described by RFC 3339: last week"
+            ),
+            (
+                Severity::Warning,
+                "/feed/entry[0]/published",
+                "published date is not formatted as described by RFC 3339: 2017-06-03 15:15:44"
+            ),
+            (
+                Severity::Warning,
+                "/feed/entry[1]/source",
+                "updated date is not formatted as described by RFC 3339: never"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn read_skip_broken_entries() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+        <entry><id>urn:entry:1</id></entry>
+        <entry><id>urn:entry:2</id><updated>yesterday</updated><title>Title</title></entry>
+        <entry><id>urn:entry:3</id><title>Title</titel><summary>Summary</summary></entry>
+        <entry><id>urn:entry:4</id></entry>
+        <title>Feed Title</title>
+    </feed>"#;
+
+    match xml.parse::<Feed>() {
+        Err(err) => assert_eq!(err.entry_index(), Some(1)),
+        other => panic!("expected invalid date, got {:?}", other.map(|_| ())),
+    }
+
+    let config = ReadConfig::new().skip_broken_entries(true);
+    match Feed::read_with_config(xml.as_bytes(), &config) {
+        Err(err) => assert_eq!(err.path(), Some("/feed/entry[2]/title")),
+        other => panic!("expected malformed XML, got {:?}", other.map(|_| ())),
+    }
+
+    let valid_xml = xml.replace("</titel>", "</title>");
+    let (feed, stats) = Feed::read_with_stats(valid_xml.as_bytes(), &config).unwrap();
+    let ids = feed.entries().iter().map(Entry::id).collect::<Vec<_>>();
+    assert_eq!(ids, ["urn:entry:1", "urn:entry:3", "urn:entry:4"]);
+    assert_eq!(feed.title(), "Feed Title");
+
+    let issues = stats
+        .issues()
+        .iter()
+        .map(|issue| (issue.severity(), issue.location(), issue.message()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        [(
+            Severity::Error,
+            "/feed/entry[1]/updated",
+            "dates must be formatted as described by RFC 3339, rather than yesterday"
+        )]
+    );
+
+    let mut parser = FeedParser::with_config(&config);
+    let entries = parser.feed_bytes(xml.as_bytes()).unwrap();
+    parser.finish().unwrap();
+    let ids = entries.iter().map(Entry::id).collect::<Vec<_>>();
+    assert_eq!(ids, ["urn:entry:1", "urn:entry:4"]);
+
+    let locations = parser
+        .issues()
+        .iter()
+        .map(|issue| issue.location())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        locations,
+        ["/feed/entry[1]/updated", "/feed/entry[2]/title"]
+    );
+}
+
+#[test]
+fn read_invalid_utf8() {
+    let xml = b"<feed><title>Feed \xff Title</title><entry><id>urn:\xc3(</id></entry></feed>";
+
+    // a small buffer splits multi-byte sequences across reads
+    let reader = BufReader::with_capacity(4, &xml[..]);
+    let (feed, stats) = Feed::read_with_stats(reader, &ReadConfig::default()).unwrap();
+    assert_eq!(feed.title(), "Feed \u{fffd} Title");
+    assert_eq!(feed.entries()[0].id(), "urn:\u{fffd}(");
+
+    let issues = stats
+        .issues()
+        .iter()
+        .map(|issue| (issue.severity(), issue.location(), issue.message()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        [(
+            Severity::Warning,
+            "/",
+            "invalid UTF-8 replaced with U+FFFD in 2 places, first at byte 18"
+        )]
+    );
+
+    let valid = "<feed><title>Feed \u{e9} Title</title></feed>";
+    let reader = BufReader::with_capacity(1, valid.as_bytes());
+    let (_, stats) = Feed::read_with_stats(reader, &ReadConfig::default()).unwrap();
+    assert!(stats.issues().is_empty());
+
+    let latin1 = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><feed><title>\xe9</title></feed>";
+    let (feed, stats) = Feed::read_with_stats(&latin1[..], &ReadConfig::default()).unwrap();
+    assert_eq!(feed.title(), "\u{e9}");
+    assert!(stats.issues().is_empty());
+
+    let mut parser = FeedParser::new();
+    let entries = parser.feed_bytes(&xml[..]).unwrap();
+    assert_eq!(entries[0].id(), "urn:\u{fffd}(");
+    assert_eq!(parser.issues()[0].location(), "/feed/entry[0]");
+    assert_eq!(
+        parser.issues()[0].message(),
+        "invalid UTF-8 replaced with U+FFFD in 1 place, first at byte 48"
+    );
+}
+
+#[test]
+fn read_html_entities() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+        <title>Fish&nbsp;&amp;&nbsp;Chips</title>
+        <rights>&copy; 2017 John Doe &Omega;&omega;</rights>
+        <entry>
+            <title type="html">&lt;b&gt;&Omega;&lt;/b&gt; &amp;omega; &omega; &unknown;</title>
+            <summary>&hellip; &#8230; &amp;hellip;</summary>
+            <author><name>Jos&eacute;</name></author>
+        </entry>
+    </feed>"#;
+
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(feed.title(), "Fish&nbsp;&amp;&nbsp;Chips");
+
+    let config = ReadConfig::new().html_entities(true);
+    let feed = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    assert_eq!(feed.title(), "Fish\u{a0}&\u{a0}Chips");
+    assert_eq!(feed.rights(), Some("\u{a9} 2017 John Doe \u{3a9}\u{3c9}"));
+
+    let entry = &feed.entries()[0];
+    // text with a reference that is not an HTML entity is still kept as it was
+    assert_eq!(
+        entry.title(),
+        "&lt;b&gt;&Omega;&lt;/b&gt; &amp;omega; &omega; &unknown;"
+    );
+    assert_eq!(entry.summary(), Some("\u{2026} \u{2026} &hellip;"));
+    assert_eq!(entry.authors()[0].name(), "Jos\u{e9}");
+
+    let config = ReadConfig::new().lenient(true);
+    assert!(config.is_html_entities());
+    assert!(config.is_lenient_dates());
+}
+
+#[test]
+fn read_try_from() {
+    let xml = "<feed><title>Feed Title</title></feed>";
+    assert_eq!(Feed::try_from(xml).unwrap().title(), "Feed Title");
+    assert_eq!(
+        Feed::try_from(xml.as_bytes()).unwrap().title(),
+        "Feed Title"
+    );
+    assert!(Feed::try_from("<rss></rss>").is_err());
+
+    let xml = r#"<entry xmlns="http://www.w3.org/2005/Atom"><title>Entry Title</title></entry>"#;
+    assert_eq!(Entry::try_from(xml).unwrap().title(), "Entry Title");
+    let bytes = [&b"\xef\xbb\xbf"[..], xml.as_bytes()].concat();
+    assert_eq!(Entry::try_from(&bytes[..]).unwrap().title(), "Entry Title");
+    assert!(Entry::try_from("<feed></feed>").is_err());
+}
+
+#[test]
+fn read_feed_stats() {
+    let feed = feed!("tests/data/entry.xml");
+    let stats = feed.stats();
+    assert_eq!(stats.entries(), 1);
+    assert_eq!(
+        stats.oldest_published().map(|date| date.to_rfc3339()),
+        Some("2017-06-01T15:15:44-05:00".to_string())
+    );
+    assert_eq!(stats.oldest_updated(), stats.newest_updated());
+    assert_eq!(stats.authors(), 2);
+    assert_eq!(stats.categories()["technology"], 1);
+    assert_eq!(stats.categories()["podcast"], 1);
+    assert_eq!(stats.content_bytes(), "Entry content".len());
+
+    let stats = feed!("tests/data/feed.xml").stats();
+    assert_eq!(stats.entries(), 0);
+    assert_eq!(stats.newest_published(), None);
+    assert_eq!(stats.newest_updated(), None);
+    assert!(stats.categories().is_empty());
+}
+
+#[test]
+fn read_entries_by_category() {
+    let feed = feed!("tests/data/entry.xml");
+    let groups = feed.entries_by_category();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups["technology"][0].id(), "http://example.com/article/1");
+    assert_eq!(groups["podcast"].len(), 1);
+
+    let groups = feed.entries_by_scheme_and_category();
+    assert_eq!(groups[&(None, "technology")].len(), 1);
+}
+
+#[test]
+fn read_entries_by_author() {
+    let feed = feed!("tests/data/entry.xml");
+    let authors = feed.entry_authors();
+    assert_eq!(authors.len(), 2);
+    assert_eq!(authors[0].name(), "John Doe");
+    assert_eq!(authors[1].name(), "Jane Doe");
+
+    assert_eq!(feed.entries_by_author("Jane Doe").len(), 1);
+    assert!(feed.entries_by_author("Richard Roe").is_empty());
+}
+
+#[test]
+fn read_entries_between() {
+    let feed = feed!("tests/data/entry.xml");
+    let published = *feed.entries()[0].published().unwrap();
+    let day = chrono::Duration::days(1);
+
+    assert_eq!(feed.entries_between(published, published + day).len(), 1);
+    assert!(feed.entries_between(published - day, published).is_empty());
+    assert!(feed
+        .entries_between(published.with_timezone(&chrono::Utc) + day, published + day)
+        .is_empty());
+}
+
+#[test]
+fn read_paginate() {
+    let feed = feed!("tests/data/entry.xml");
+    let pages = feed.paginate(10).collect::<Vec<_>>();
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0], feed);
+
+    assert_eq!(feed!("tests/data/feed.xml").paginate(10).count(), 0);
+}
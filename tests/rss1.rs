@@ -0,0 +1,58 @@
+#![cfg(feature = "rss1")]
+
+extern crate atom_syndication as atom;
+
+use std::fs;
+
+use crate::atom::{Error, Feed};
+
+#[test]
+fn rss1_read() {
+    let feed = Feed::read_rss1(&fs::read("tests/data/rss1.xml").unwrap()[..]).unwrap();
+
+    assert_eq!(feed.id(), "http://example.com/index.rdf");
+    assert_eq!(feed.title(), "Channel Title");
+    assert_eq!(feed.subtitle(), Some("Channel description"));
+    assert_eq!(feed.alternate_link(), Some("http://example.com/"));
+    assert_eq!(feed.lang(), Some("en-us"));
+    assert_eq!(feed.rights(), Some("Copyright 2017 Example"));
+    assert_eq!(feed.authors()[0].name(), "Example Media");
+    assert_eq!(feed.categories()[0].term(), "Technology");
+    assert_eq!(feed.logo(), Some("http://example.com/logo.png"));
+    assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:00-05:00");
+    assert_eq!(feed.entries().len(), 2);
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.id(), "http://example.com/story/2");
+    assert_eq!(entry.title(), "Second Story");
+    assert_eq!(entry.links()[0].href(), "http://example.com/story/2");
+    assert_eq!(entry.summary(), Some("Second <b>story</b>"));
+    assert_eq!(entry.content().unwrap().content_type(), Some("html"));
+    assert_eq!(
+        entry.content().unwrap().value(),
+        Some("<p>Second <b>story</b></p>")
+    );
+    assert_eq!(entry.authors()[0].name(), "John Doe");
+    assert_eq!(entry.categories()[0].term(), "rust");
+
+    let entry = &feed.entries()[1];
+    assert_eq!(entry.updated().to_rfc3339(), "2017-06-01T00:00:00+00:00");
+    assert!(entry.authors().is_empty());
+
+    // the result is an ordinary Atom feed
+    let feed = feed.to_string().parse::<Feed>().unwrap();
+    assert_eq!(feed.entries()[0].title(), "Second Story");
+}
+
+#[test]
+fn rss1_errors() {
+    let err = Feed::read_rss1(&fs::read("tests/data/feed.xml").unwrap()[..]).unwrap_err();
+    assert!(matches!(err, Error::MissingElement { ref name, .. } if name == "rdf:RDF"));
+
+    let rdf = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+        xmlns="http://purl.org/rss/1.0/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <item rdf:about="urn:item"><dc:date>yesterday</dc:date></item>
+    </rdf:RDF>"#;
+    let err = Feed::read_rss1(rdf.as_bytes()).unwrap_err();
+    assert!(matches!(err, Error::InvalidDate { ref value } if value == "yesterday"));
+}
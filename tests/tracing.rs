@@ -0,0 +1,84 @@
+#![cfg(feature = "tracing")]
+
+extern crate atom_syndication as atom;
+extern crate tracing;
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use crate::atom::Feed;
+
+/// Records the names of spans and the messages of events.
+struct Recorder {
+    log: Arc<Mutex<Vec<String>>>,
+    next_id: AtomicU64,
+}
+
+struct Message<'a>(&'a mut String);
+
+impl<'a> Visit for Message<'a> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            *self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("span {}", span.metadata().name()));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = String::new();
+        event.record(&mut Message(&mut message));
+        self.log.lock().unwrap().push(format!("event {}", message));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn trace_read_write() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let recorder = Recorder {
+        log: log.clone(),
+        next_id: AtomicU64::new(0),
+    };
+
+    tracing::subscriber::with_default(recorder, || {
+        let feed = Feed::read_from_file("tests/data/entry.xml").unwrap();
+        feed.to_string();
+    });
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "span read_feed",
+            "span read_entry",
+            "event read entry",
+            "event read feed",
+            "span write_feed",
+            "event wrote feed",
+        ]
+    );
+}
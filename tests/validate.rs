@@ -0,0 +1,234 @@
+extern crate atom_syndication as atom;
+
+use std::fs::File;
+use std::io::BufReader;
+
+use crate::atom::{Error, Feed, Link, Person, Severity};
+
+macro_rules! feed {
+    ($f:expr) => {{
+        let file = File::open($f).unwrap();
+        let reader = BufReader::new(file);
+        Feed::read_from(reader).unwrap()
+    }};
+}
+
+fn person(email: Option<&str>, uri: Option<&str>) -> Person {
+    let mut person = Person::default();
+    person.set_name("John Doe");
+    person.set_email(email.map(String::from));
+    person.set_uri(uri.map(String::from));
+    person
+}
+
+#[test]
+fn validate_person() {
+    assert!(person(None, None).validate().is_ok());
+    assert!(person(Some("john.doe@example.com"), None)
+        .validate()
+        .is_ok());
+    assert!(person(None, Some("http://example.com/~john"))
+        .validate()
+        .is_ok());
+    assert!(
+        person(None, Some("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6"))
+            .validate()
+            .is_ok()
+    );
+    assert!(person(None, Some("http://例え.jp/")).validate().is_ok());
+}
+
+#[test]
+fn validate_person_email() {
+    for email in &[
+        "johndoe",
+        "@example.com",
+        "john@",
+        "john doe@example.com",
+        "a@b@c",
+        "a@.com",
+    ] {
+        match person(Some(email), None).validate() {
+            Err(Error::InvalidEmail(ref e)) => assert_eq!(e, email),
+            other => panic!("expected invalid email for {}, got {:?}", email, other),
+        }
+    }
+}
+
+#[test]
+fn validate_person_uri() {
+    for uri in &[
+        "example.com",
+        "/relative/path",
+        "1http://example.com",
+        "http://exa mple.com",
+    ] {
+        match person(None, Some(uri)).validate() {
+            Err(Error::InvalidUri(ref u)) => assert_eq!(u, uri),
+            other => panic!("expected invalid uri for {}, got {:?}", uri, other),
+        }
+    }
+}
+
+#[test]
+fn validate_feed() {
+    let feed = feed!("tests/data/feed.xml");
+    let report = feed.validate();
+    assert!(report.is_valid(), "{}", report);
+}
+
+#[test]
+fn validate_feed_invalid() {
+    let feed = feed!("tests/data/invalid.xml");
+    let report = feed.validate();
+    let issues = report
+        .issues()
+        .iter()
+        .map(|issue| (issue.severity(), issue.location(), issue.message()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        issues,
+        vec![
+            (
+                Severity::Error,
+                "entry[0]",
+                "id article-1 is not an absolute IRI"
+            ),
+            (
+                Severity::Warning,
+                "entry[0]",
+                "author has an invalid email address: johndoe"
+            ),
+            (
+                Severity::Error,
+                "entry[1]",
+                "id article-1 is not an absolute IRI"
+            ),
+            (
+                Severity::Warning,
+                "entry[1]",
+                "published date is later than updated date"
+            ),
+            (Severity::Error, "entry[1]", "missing author"),
+            (
+                Severity::Warning,
+                "entry[1]",
+                "duplicate id article-1 also used by entry[0]"
+            ),
+            (Severity::Error, "entry[2]", "missing title"),
+            (Severity::Error, "entry[2]", "missing updated date"),
+            (
+                Severity::Error,
+                "entry[2]",
+                "missing content and alternate link"
+            ),
+        ]
+    );
+    assert!(!report.is_valid());
+    assert_eq!(report.warnings().count(), 3);
+}
+
+#[test]
+fn validate_duplicate_alternates() {
+    let mut feed = feed!("tests/data/feed.xml");
+    let links = vec![
+        Link::default()
+            .with_href("http://example.com/1")
+            .with_mime_type("text/html"),
+        Link::default()
+            .with_href("http://example.com/1.json")
+            .with_mime_type("application/json"),
+        Link::default()
+            .with_href("http://example.com/2")
+            .with_mime_type("TEXT/HTML"),
+        Link::default()
+            .with_href("http://example.com/3")
+            .with_rel("related"),
+        Link::default()
+            .with_href("http://example.com/4")
+            .with_mime_type("text/html"),
+    ];
+    let mut entry = feed!("tests/data/entry.xml").entries()[0].clone();
+    entry.set_links(links);
+    feed.set_entries(vec![entry]);
+
+    let report = feed.validate();
+    let messages = report
+        .errors()
+        .map(|issue| (issue.location(), issue.message()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            (
+                "entry[0]",
+                "alternate link http://example.com/2 has the same type and hreflang as http://example.com/1"
+            ),
+            (
+                "entry[0]",
+                "alternate link http://example.com/4 has the same type and hreflang as http://example.com/1"
+            ),
+        ]
+    );
+
+    assert_eq!(feed.remove_duplicate_alternates(), 2);
+    let hrefs = feed.entries()[0]
+        .links()
+        .iter()
+        .map(Link::href)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        hrefs,
+        vec![
+            "http://example.com/1",
+            "http://example.com/1.json",
+            "http://example.com/3"
+        ]
+    );
+    assert!(feed.validate().is_valid());
+}
+
+#[test]
+fn validate_effective_authors() {
+    let mut feed = feed!("tests/data/feed.xml");
+    let source = feed!("tests/data/source.xml").entries()[0].clone();
+    let mut entry = feed!("tests/data/entry.xml").entries()[0].clone();
+
+    let names = |authors: &[Person]| {
+        authors
+            .iter()
+            .map(|person| person.name().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        names(entry.effective_authors(&feed)),
+        ["John Doe", "Jane Doe"]
+    );
+
+    entry.set_authors(Vec::new());
+    entry.set_source(source.source().cloned());
+    entry
+        .source_mut()
+        .as_mut()
+        .unwrap()
+        .authors_mut()
+        .truncate(1);
+    assert_eq!(names(entry.effective_authors(&feed)), ["John Doe"]);
+
+    entry.source_mut().as_mut().unwrap().set_authors(Vec::new());
+    feed.authors_mut().reverse();
+    assert_eq!(
+        names(entry.effective_authors(&feed)),
+        ["Jane Doe", "John Doe"]
+    );
+
+    feed.set_authors(Vec::new());
+    feed.set_entries(vec![entry]);
+    assert!(feed.entries()[0].effective_authors(&feed).is_empty());
+
+    let report = feed.validate();
+    assert!(report
+        .errors()
+        .any(|issue| issue.location() == "entry[0]" && issue.message() == "missing author"));
+}
@@ -1,9 +1,14 @@
 extern crate atom_syndication as atom;
 
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{self, BufReader, Write};
 
-use crate::atom::Feed;
+use crate::atom::extension::{Extension, ExtensionMap};
+use crate::atom::{
+    Category, CdataPolicy, Content, Entry, EntryOrder, Error, Feed, FeedWriter, FixedDateTime,
+    Generator, IntoEntry, Link, Person, ReadConfig, WriteConfig,
+};
 
 macro_rules! feed {
     ($f:expr) => {{
@@ -60,3 +65,747 @@ fn write_extension() {
     let feed = feed!("tests/data/extension.xml");
     assert_eq!(feed.to_string().parse::<Feed>().unwrap(), feed);
 }
+
+#[test]
+fn write_fluent() {
+    let entry = Entry::default()
+        .with_title("Entry Title")
+        .with_id("http://example.com/article/1")
+        .with_links(vec![
+            Link::default().with_href("http://example.com/article/")
+        ])
+        .with_content(Content::default().with_value("Entry content".to_string()));
+    let feed = Feed::default()
+        .with_title("Feed Title")
+        .with_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6")
+        .with_authors(vec![Person::default().with_name("John Doe")])
+        .with_categories(vec![Category::default().with_term("technology")])
+        .with_entries(vec![entry]);
+    assert_eq!(feed.to_string().parse::<Feed>().unwrap(), feed);
+}
+
+#[test]
+fn write_attributes() {
+    let feed = feed!("tests/data/attributes.xml");
+    assert_eq!(feed.to_string().parse::<Feed>().unwrap(), feed);
+}
+
+#[test]
+fn write_fidelity() {
+    let file = File::open("tests/data/fidelity.xml").unwrap();
+    let config = ReadConfig::new().fidelity(true);
+    let feed = Feed::read_with_config(BufReader::new(file), &config).unwrap();
+
+    let xml = feed.to_string();
+    let position = |s: &str| xml.find(s).unwrap();
+    assert!(position("<id>urn") < position("<ext:rating"));
+    assert!(position("<ext:rating") < position("<title>Feed"));
+    assert!(position("<title>Feed") < position("<rank"));
+    assert!(position("<rank") < position("<updated>"));
+    assert!(position("<content") < position("<id>http"));
+    assert!(position("<title>Entry") < position("<mood>"));
+
+    let written = Feed::read_with_config(xml.as_bytes(), &config).unwrap();
+    assert_eq!(written, feed);
+}
+
+#[test]
+fn write_namespace_prefixes() {
+    let feed = feed!("tests/data/extension.xml");
+    let config = WriteConfig::new().namespace("example", "http://example.com");
+    let xml = feed.write_with_config(Vec::new(), &config).unwrap();
+    let written = Feed::read_from(xml.as_slice()).unwrap();
+
+    assert_eq!(
+        written.namespaces().get("example").map(String::as_str),
+        Some("http://example.com")
+    );
+    assert!(!written.namespaces().contains_key("ext"));
+
+    let parent = &written.extensions()["example"]["parent"][0];
+    assert_eq!(parent.name(), "example:parent");
+    assert_eq!(parent.children()["child"][0].name(), "example:child");
+    assert_eq!(
+        written.entries()[0].extensions()["example"]["title"][0].value(),
+        Some("Title")
+    );
+}
+
+#[test]
+fn write_namespace_conflicts() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:ex="http://example.com/other"
+        xmlns:x="http://example.com/ext" xmlns:y="http://example.com/ext">
+        <ex:foo>other</ex:foo>
+        <entry><ex:foo>entry</ex:foo><x:title>Title</x:title><y:title>Title</y:title></entry>
+    </feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+    let config = WriteConfig::new().namespace("ex", "http://example.com/ext");
+    let xml = feed.write_with_config(Vec::new(), &config).unwrap();
+    let written = Feed::read_from(xml.as_slice()).unwrap();
+
+    let mut namespaces = written
+        .namespaces()
+        .iter()
+        .map(|(prefix, uri)| (prefix.as_str(), uri.as_str()))
+        .collect::<Vec<_>>();
+    namespaces.sort();
+    assert_eq!(
+        namespaces,
+        vec![
+            ("ex", "http://example.com/ext"),
+            ("ex1", "http://example.com/other"),
+        ]
+    );
+
+    assert_eq!(written.extensions()["ex1"]["foo"][0].value(), Some("other"));
+    let extensions = written.entries()[0].extensions();
+    assert_eq!(extensions["ex1"]["foo"][0].value(), Some("entry"));
+    assert_eq!(extensions["ex"]["title"].len(), 2);
+    assert_eq!(extensions["ex"]["title"][0].value(), Some("Title"));
+    assert!(!extensions["ex"].contains_key("foo"));
+}
+
+#[test]
+fn write_config() {
+    let mut content = Content::default();
+    content.set_content_type("html");
+    content.set_value("<p>Entry ]]> content</p>");
+
+    let feed = Feed::default().with_title("Feed Title").with_entries(vec![
+        Entry::default()
+            .with_id("urn:uuid:1")
+            .with_updated(FixedDateTime::parse_from_rfc3339("2017-06-01T15:15:44-05:00").unwrap()),
+        Entry::default()
+            .with_id("urn:uuid:2")
+            .with_updated(FixedDateTime::parse_from_rfc3339("2017-06-03T15:15:44-05:00").unwrap())
+            .with_content(content),
+    ]);
+
+    let config = WriteConfig::new()
+        .declaration(true)
+        .indent(b' ', 2)
+        .cdata(CdataPolicy::WhenNeeded)
+        .entry_order(EntryOrder::NewestFirst);
+    let xml = feed.write_with_config(Vec::new(), &config).unwrap();
+    let xml = String::from_utf8(xml).unwrap();
+
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed"));
+    assert!(xml.contains("\n  <title>Feed Title</title>"));
+    assert!(xml.contains("<![CDATA[<p>Entry ]]]]><![CDATA[> content</p>]]>"));
+
+    let written = xml.parse::<Feed>().unwrap();
+    let ids = written.entries().iter().map(Entry::id).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:uuid:2", "urn:uuid:1"]);
+    assert_eq!(
+        written.entries()[0].content().and_then(Content::value),
+        Some("<p>Entry ]]> content</p>")
+    );
+
+    let config = WriteConfig::new().entry_order(EntryOrder::Custom(|a, b| {
+        b.content().is_some().cmp(&a.content().is_some())
+    }));
+    let written = feed
+        .write_with_config(Vec::new(), &config)
+        .map(|xml| String::from_utf8(xml).unwrap().parse::<Feed>().unwrap())
+        .unwrap();
+    let ids = written.entries().iter().map(Entry::id).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:uuid:2", "urn:uuid:1"]);
+}
+
+#[test]
+fn write_generator_escaped() {
+    let mut generator = Generator::default();
+    generator.set_value("Feeds & Co");
+    generator.set_uri("http://example.com/?a=1&b=2".to_string());
+    generator.set_version("1.0".to_string());
+
+    let mut feed = Feed::default();
+    feed.set_generator(generator);
+
+    let xml = feed.to_string();
+    assert!(xml.contains("Feeds &amp; Co"));
+    assert_eq!(xml.parse::<Feed>().unwrap(), feed);
+}
+
+#[test]
+fn write_to_file() {
+    let feed = feed!("tests/data/feed.xml");
+    let dir = std::env::temp_dir().join(format!("atom-write-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("feed.xml");
+    std::fs::write(&path, "stale").unwrap();
+    feed.write_to_file(&path).unwrap();
+
+    assert_eq!(Feed::read_from_file(&path).unwrap(), feed);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    let feeds = (0..8)
+        .map(|i| feed.clone().with_title(format!("Feed {}", i).repeat(1000)))
+        .collect::<Vec<_>>();
+    std::thread::scope(|scope| {
+        for feed in &feeds {
+            let path = &path;
+            scope.spawn(move || {
+                for _ in 0..5 {
+                    feed.write_to_file(path).unwrap();
+                }
+            });
+        }
+    });
+    assert!(feeds.contains(&Feed::read_from_file(&path).unwrap()));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn write_fragment() {
+    let mut feed = Feed::default();
+    feed.set_title("Feed Title");
+    feed.set_entries(vec![Entry::default().with_title("Entry Title")]);
+    feed.namespaces_mut().insert(
+        "dc".to_string(),
+        "http://purl.org/dc/elements/1.1/".to_string(),
+    );
+
+    let config = WriteConfig::new().declaration(true).fragment(true);
+    let xml = String::from_utf8(feed.write_with_config(Vec::new(), &config).unwrap()).unwrap();
+    assert!(xml.starts_with("<feed><title>Feed Title</title>"));
+    assert!(!xml.contains("xmlns"));
+
+    let entry = &feed.entries()[0];
+    let xml = String::from_utf8(entry.write_with_config(Vec::new(), &config).unwrap()).unwrap();
+    assert!(xml.starts_with("<entry><title>Entry Title</title>"));
+
+    let config = WriteConfig::new()
+        .declaration(true)
+        .namespace("dc", "http://purl.org/dc/elements/1.1/");
+    let xml = String::from_utf8(entry.write_with_config(Vec::new(), &config).unwrap()).unwrap();
+    assert!(xml.starts_with(concat!(
+        r#"<?xml version="1.0" encoding="utf-8"?>"#,
+        r#"<entry xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">"#
+    )));
+}
+
+#[test]
+fn write_archive() {
+    let entries = (1..=5)
+        .map(|day| {
+            let updated = format!("2020-01-0{}T00:00:00Z", day);
+            Entry::default()
+                .with_id(format!("urn:entry:{}", day))
+                .with_updated(FixedDateTime::parse_from_rfc3339(&updated).unwrap())
+        })
+        .rev()
+        .collect::<Vec<_>>();
+
+    let mut feed = Feed::default();
+    feed.set_id("urn:feed");
+    feed.set_self_link("http://example.com/feed.xml");
+    feed.set_entries(entries);
+
+    let archive = feed.archive(2, "http://example.com/archive/{page}.xml");
+    let ids = |feed: &Feed| {
+        feed.entries()
+            .iter()
+            .map(|entry| entry.id().to_string())
+            .collect::<Vec<_>>()
+    };
+    let link = |feed: &Feed, rel: &str| {
+        feed.links()
+            .iter()
+            .find(|link| link.rel() == rel)
+            .map(|link| link.href().to_string())
+    };
+
+    let current = archive.current();
+    assert_eq!(ids(current), vec!["urn:entry:5"]);
+    assert_eq!(
+        link(current, "prev-archive").as_deref(),
+        Some("http://example.com/archive/2.xml")
+    );
+    assert!(!current.to_string().contains("fh:archive"));
+
+    let archives = archive.archives();
+    assert_eq!(archives.len(), 2);
+
+    let (url, oldest) = &archives[0];
+    assert_eq!(url, "http://example.com/archive/1.xml");
+    assert_eq!(ids(oldest), vec!["urn:entry:2", "urn:entry:1"]);
+    assert_eq!(oldest.updated().to_rfc3339(), "2020-01-02T00:00:00+00:00");
+    assert_eq!(link(oldest, "self").as_deref(), Some(url.as_str()));
+    assert_eq!(
+        link(oldest, "current").as_deref(),
+        Some("http://example.com/feed.xml")
+    );
+    assert_eq!(link(oldest, "prev-archive"), None);
+    assert_eq!(
+        link(oldest, "next-archive").as_deref(),
+        Some("http://example.com/archive/2.xml")
+    );
+
+    let (_, newest) = &archives[1];
+    assert_eq!(ids(newest), vec!["urn:entry:4", "urn:entry:3"]);
+    assert_eq!(
+        link(newest, "prev-archive").as_deref(),
+        Some("http://example.com/archive/1.xml")
+    );
+    assert_eq!(link(newest, "next-archive"), None);
+
+    let xml = newest.to_string();
+    assert!(xml.contains(r#"xmlns:fh="http://purl.org/syndication/history/1.0""#));
+    assert!(xml.contains("<fh:archive></fh:archive>"));
+}
+
+#[test]
+fn write_aggregate() {
+    let feed = |id: &str, days: &[u32]| {
+        let entries = days
+            .iter()
+            .map(|day| {
+                let updated = format!("2020-01-0{}T00:00:00Z", day);
+                Entry::default()
+                    .with_id(format!("{}:{}", id, day))
+                    .with_updated(FixedDateTime::parse_from_rfc3339(&updated).unwrap())
+            })
+            .collect::<Vec<_>>();
+        let mut feed = Feed::default();
+        feed.set_id(id);
+        feed.set_title(id);
+        feed.set_entries(entries);
+        feed
+    };
+
+    let first = feed("urn:first", &[5, 1]);
+    let mut second = feed("urn:second", &[4, 2]);
+    second
+        .namespaces_mut()
+        .insert("ext".to_string(), "http://example.com/ext".to_string());
+    let mut source = atom::Source::default();
+    source.set_id("urn:original");
+    second.entries_mut()[1].set_source(source);
+
+    let planet = Feed::aggregate(&[first, second]);
+    let ids = planet
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec!["urn:first:5", "urn:second:4", "urn:second:2", "urn:first:1"]
+    );
+
+    let sources = planet
+        .entries()
+        .iter()
+        .map(|entry| entry.source().unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sources,
+        vec!["urn:first", "urn:second", "urn:original", "urn:first"]
+    );
+    assert_eq!(planet.updated().to_rfc3339(), "2020-01-05T00:00:00+00:00");
+    assert_eq!(
+        planet.namespaces().get("ext").map(String::as_str),
+        Some("http://example.com/ext")
+    );
+
+    let xml = planet.to_string();
+    assert!(xml.contains("<source><title>urn:first</title><id>urn:first</id>"));
+}
+
+#[test]
+fn write_aggregate_namespaces() {
+    let first = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/a">
+        <entry><id>urn:first:1</id><ext:value>a</ext:value></entry>
+    </feed>"#;
+    let second = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/b"
+        xmlns:other="http://example.com/a">
+        <entry><id>urn:second:1</id><ext:value>b</ext:value><other:value>c</other:value></entry>
+    </feed>"#;
+    let feeds = [
+        first.parse::<Feed>().unwrap(),
+        second.parse::<Feed>().unwrap(),
+    ];
+
+    let planet = Feed::aggregate(&feeds);
+    let mut namespaces = planet
+        .namespaces()
+        .iter()
+        .map(|(prefix, uri)| (prefix.as_str(), uri.as_str()))
+        .collect::<Vec<_>>();
+    namespaces.sort();
+    assert_eq!(
+        namespaces,
+        vec![
+            ("ext", "http://example.com/a"),
+            ("ext1", "http://example.com/b")
+        ]
+    );
+
+    let value = |entry: &Entry, prefix: &str| {
+        entry.extensions()[prefix]["value"]
+            .iter()
+            .map(|extension| extension.value().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    let entry = &planet.entries()[1];
+    assert_eq!(entry.id(), "urn:second:1");
+    assert_eq!(value(entry, "ext"), vec!["c"]);
+    assert_eq!(value(entry, "ext1"), vec!["b"]);
+    assert!(!entry.extensions().contains_key("other"));
+
+    let xml = planet.to_string();
+    assert!(xml.contains("<ext1:value>b</ext1:value>"));
+    assert_eq!(xml.parse::<Feed>().unwrap(), planet);
+}
+
+struct Post {
+    slug: &'static str,
+    day: u32,
+    author: Option<&'static str>,
+}
+
+impl IntoEntry for Post {
+    fn permalink(&self) -> String {
+        format!("http://example.com/{}", self.slug)
+    }
+
+    fn title(&self) -> String {
+        self.slug.to_uppercase()
+    }
+
+    fn updated(&self) -> FixedDateTime {
+        FixedDateTime::parse_from_rfc3339(&format!("2020-01-0{}T00:00:00Z", self.day)).unwrap()
+    }
+
+    fn authors(&self) -> Vec<Person> {
+        self.author
+            .map(|name| Person::default().with_name(name))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[test]
+fn write_from_items() {
+    let posts = vec![
+        Post {
+            slug: "first",
+            day: 2,
+            author: Some("John Doe"),
+        },
+        Post {
+            slug: "second",
+            day: 5,
+            author: None,
+        },
+    ];
+
+    let mut feed = Feed::from_items(&posts);
+    feed.set_id("http://example.com/");
+    feed.set_title("Posts");
+    assert_eq!(feed.updated(), &posts[1].updated());
+
+    let feed = feed.to_string().parse::<Feed>().unwrap();
+    let entries = feed.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].id(), "http://example.com/first");
+    assert_eq!(entries[0].title(), "FIRST");
+    assert_eq!(entries[0].authors()[0].name(), "John Doe");
+    assert_eq!(entries[0].links()[0].rel(), "alternate");
+    assert_eq!(entries[0].links()[0].href(), "http://example.com/first");
+    assert!(entries[1].authors().is_empty());
+    assert!(entries[1].summary().is_none());
+    assert!(entries[1].content().is_none());
+}
+
+#[test]
+fn write_collect() {
+    let entries = (1..=3)
+        .map(|i| {
+            Entry::default()
+                .with_id(format!("urn:entry:{}", i))
+                .with_updated(
+                    format!("2017-06-0{}T00:00:00Z", i)
+                        .parse::<FixedDateTime>()
+                        .unwrap(),
+                )
+        })
+        .collect::<Vec<_>>();
+
+    let mut feed = entries
+        .clone()
+        .into_iter()
+        .filter(|entry| entry.id() != "urn:entry:3")
+        .collect::<Feed>();
+    assert_eq!(feed.entries(), &entries[..2]);
+    assert_eq!(feed.updated(), entries[1].updated());
+    assert_eq!(feed.title(), "");
+
+    feed.extend(entries[2..].iter().cloned());
+    assert_eq!(feed.entries(), &entries[..]);
+    assert_eq!((&feed).into_iter().count(), 3);
+
+    let ids = feed
+        .into_iter()
+        .map(|entry| entry.id().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["urn:entry:1", "urn:entry:2", "urn:entry:3"]);
+}
+
+#[test]
+fn write_normalize_dates_utc() {
+    let xml = r#"<feed>
+        <updated>2017-06-03T15:15:44-05:00</updated>
+        <entry>
+            <updated>2017-06-04T01:30:00.250+05:30</updated>
+            <published>2017-06-03T20:15:44Z</published>
+            <source><updated>2017-06-02T23:00:00-01:00</updated></source>
+        </entry>
+    </feed>"#;
+    let mut feed = xml.parse::<Feed>().unwrap();
+    let original = feed.clone();
+    feed.normalize_dates_utc();
+
+    assert_eq!(feed.updated(), original.updated());
+    assert_eq!(feed.entries()[0].updated(), original.entries()[0].updated());
+
+    let xml = feed.to_string();
+    assert!(xml.contains("<updated>2017-06-03T20:15:44+00:00</updated>"));
+    assert!(xml.contains("<updated>2017-06-03T20:00:00.250+00:00</updated>"));
+    assert!(xml.contains("<published>2017-06-03T20:15:44+00:00</published>"));
+    assert!(xml.contains("<updated>2017-06-03T00:00:00+00:00</updated>"));
+}
+
+#[test]
+fn write_normalize() {
+    let xml = r#"<feed>
+        <title>
+            Feed Title
+        </title>
+        <id> urn:feed </id>
+        <updated>2017-06-03T15:15:44-05:00</updated>
+        <author><name> John Doe </name><email> </email></author>
+        <category term="rust"/>
+        <category term=" rust " label="Rust"/>
+        <category term="rust" scheme="http://example.com/tags"/>
+        <link href=" http://example.com/ "/>
+        <entry>
+            <id>urn:entry:1</id>
+            <updated>2017-06-01T00:00:00Z</updated>
+            <summary>  Summary  </summary>
+        </entry>
+        <entry>
+            <id>urn:entry:3</id>
+            <updated>2017-06-02T00:00:00+02:00</updated>
+            <rights>   </rights>
+            <content>  Content  </content>
+        </entry>
+        <entry>
+            <id>urn:entry:2</id>
+            <updated>2017-06-01T22:00:00Z</updated>
+        </entry>
+    </feed>"#;
+
+    let mut feed = xml.parse::<Feed>().unwrap();
+    feed.normalize();
+
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.id(), "urn:feed");
+    assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T20:15:44+00:00");
+    assert_eq!(feed.authors()[0].name(), "John Doe");
+    assert_eq!(feed.authors()[0].email(), None);
+    let categories = feed
+        .categories()
+        .iter()
+        .map(|category| (category.term(), category.scheme(), category.label()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        categories,
+        vec![
+            ("rust", None, Some("Rust")),
+            ("rust", Some("http://example.com/tags"), None),
+        ]
+    );
+    assert_eq!(feed.links()[0].href(), "http://example.com/");
+
+    let ids = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:entry:2", "urn:entry:3", "urn:entry:1"]);
+    assert_eq!(feed.entries()[1].rights(), None);
+    assert_eq!(
+        feed.entries()[1].content().unwrap().value(),
+        Some("  Content  ")
+    );
+    assert_eq!(feed.entries()[2].summary(), Some("Summary"));
+
+    let mut again = feed.to_string().parse::<Feed>().unwrap();
+    again.normalize();
+    assert_eq!(again.to_string(), feed.to_string());
+}
+
+#[test]
+fn write_streaming() {
+    let feed = feed!("tests/data/feed.xml");
+    let mut expected = feed.clone();
+    let entries = (0..3)
+        .map(|i| Entry::default().with_id(format!("urn:entry:{}", i)))
+        .collect::<Vec<_>>();
+    expected.set_entries(entries.clone());
+
+    let mut writer = FeedWriter::new(&feed).into_writer(Vec::new()).unwrap();
+    for entry in &entries {
+        writer.write_entry(entry).unwrap();
+    }
+    let xml = String::from_utf8(writer.finish().unwrap()).unwrap();
+    assert_eq!(xml, expected.to_string());
+
+    let config = WriteConfig::new().declaration(true).indent(b' ', 2);
+    let mut writer = FeedWriter::new(&feed)
+        .with_config(config.clone())
+        .into_writer(Vec::new())
+        .unwrap();
+    for entry in &entries {
+        writer.write_entry(entry).unwrap();
+    }
+    assert_eq!(
+        writer.finish().unwrap(),
+        expected.write_with_config(Vec::new(), &config).unwrap()
+    );
+}
+
+fn reproducible_feed() -> Feed {
+    let names = ["e", "d", "c", "b", "a"];
+
+    let mut child = Extension::default();
+    child.set_name("ext:child");
+    let mut extension = Extension::default();
+    extension.set_name("ext:parent");
+    extension.set_attrs(
+        names
+            .iter()
+            .map(|name| (name.to_string(), name.to_string()))
+            .collect::<HashMap<_, _>>(),
+    );
+    extension.set_children(
+        names
+            .iter()
+            .map(|name| (name.to_string(), vec![child.clone()]))
+            .collect::<HashMap<_, _>>(),
+    );
+
+    let mut extensions = ExtensionMap::new();
+    let map = extensions
+        .entry("ext".to_string())
+        .or_insert_with(HashMap::new);
+    for name in &names {
+        map.insert(name.to_string(), vec![extension.clone()]);
+    }
+
+    Feed::default()
+        .with_namespaces(
+            names
+                .iter()
+                .map(|name| (name.to_string(), format!("http://example.com/{}", name)))
+                .collect::<HashMap<_, _>>(),
+        )
+        .with_extensions(extensions.clone())
+        .with_entries(vec![Entry::default().with_extensions(extensions)])
+}
+
+#[test]
+fn write_reproducible() {
+    let xml = reproducible_feed().to_string();
+    for _ in 0..20 {
+        assert_eq!(reproducible_feed().to_string(), xml);
+    }
+
+    assert!(xml.starts_with(
+        "<feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:a=\"http://example.com/a\" \
+         xmlns:b=\"http://example.com/b\" xmlns:c=\"http://example.com/c\""
+    ));
+    assert!(xml.contains("<ext:parent a=\"a\" b=\"b\" c=\"c\" d=\"d\" e=\"e\">"));
+}
+
+/// A writer that records each call made to it.
+#[derive(Default)]
+struct RecordingWriter {
+    bytes: Vec<u8>,
+    writes: usize,
+    flushed: bool,
+}
+
+impl Write for RecordingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+        self.flushed = false;
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushed = true;
+        Ok(())
+    }
+}
+
+#[test]
+fn write_buffered() {
+    let feed = feed!("tests/data/feed.xml");
+    let xml = feed.to_string();
+
+    let writer = feed.write_to(RecordingWriter::default()).unwrap();
+    assert_eq!(String::from_utf8(writer.bytes).unwrap(), xml);
+    assert_eq!(writer.writes, 1);
+    assert!(writer.flushed);
+
+    let (writer, len) = feed
+        .write_counted(RecordingWriter::default(), &WriteConfig::default())
+        .unwrap();
+    assert_eq!(len, xml.len());
+    assert_eq!(writer.bytes.len(), len);
+
+    let entry = feed!("tests/data/entry.xml").entries()[0].clone();
+    let writer = entry.write_to(RecordingWriter::default()).unwrap();
+    assert_eq!(writer.writes, 1);
+    assert!(writer.flushed);
+
+    let mut writer = FeedWriter::new(&feed)
+        .into_writer(RecordingWriter::default())
+        .unwrap();
+    writer.write_entry(&entry).unwrap();
+    assert!(writer.finish().unwrap().flushed);
+}
+
+#[test]
+fn write_etag() {
+    let feed = feed!("tests/data/feed.xml");
+    let etag = feed.etag().unwrap();
+    assert_eq!(etag.len(), 18);
+    assert_eq!(
+        feed.to_string().parse::<Feed>().unwrap().etag().unwrap(),
+        etag
+    );
+
+    let mut changed = feed.clone();
+    changed.entries_mut().push(Entry::default());
+    assert_ne!(changed.etag().unwrap(), etag);
+
+    // the tag does not depend on the order the namespaces happen to be stored in
+    assert_eq!(
+        reproducible_feed().etag().unwrap(),
+        reproducible_feed().etag().unwrap()
+    );
+
+    // a feed that cannot be written has no tag
+    let config = ReadConfig::new().lazy_content(true);
+    let file = BufReader::new(File::open("tests/data/content_text_html.xml").unwrap());
+    let lazy = Feed::read_with_config(file, &config).unwrap();
+    assert!(matches!(lazy.etag(), Err(Error::ContentNotLoaded)));
+}